@@ -16,10 +16,11 @@
 
 use beefy_primitives::crypto::AuthorityId as BeefyId;
 use bp_millau::derive_account_from_rialto_id;
+use bp_polkadot_core::parachains::ParaId;
 use millau_runtime::{
 	AccountId, AuraConfig, BalancesConfig, BeefyConfig, BridgeRialtoMessagesConfig,
-	BridgeWestendGrandpaConfig, GenesisConfig, GrandpaConfig, SessionConfig, SessionKeys,
-	Signature, SudoConfig, SystemConfig, WASM_BINARY,
+	BridgeRialtoParachainsConfig, BridgeWestendGrandpaConfig, GenesisConfig, GrandpaConfig,
+	SessionConfig, SessionKeys, Signature, SudoConfig, SystemConfig, WASM_BINARY,
 };
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{sr25519, Pair, Public};
@@ -215,6 +216,12 @@ fn testnet_genesis(
 		},
 		bridge_rialto_messages: BridgeRialtoMessagesConfig {
 			owner: Some(get_account_id_from_seed::<sr25519::Public>("RialtoMessagesOwner")),
+			opened_lanes: vec![[0, 0, 0, 0], [0, 0, 0, 1]],
+			..Default::default()
+		},
+		bridge_rialto_parachains: BridgeRialtoParachainsConfig {
+			owner: Some(get_account_id_from_seed::<sr25519::Public>("RialtoParachainsOwner")),
+			parachains: vec![ParaId(bp_rialto_parachain::RIALTO_PARACHAIN_ID)],
 			..Default::default()
 		},
 		xcm_pallet: Default::default(),
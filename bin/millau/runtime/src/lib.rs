@@ -35,7 +35,8 @@ use crate::rialto_messages::{ToRialtoMessagePayload, WithRialtoMessageBridge};
 
 use beefy_primitives::{crypto::AuthorityId as BeefyId, mmr::MmrLeafVersion, ValidatorSet};
 use bridge_runtime_common::messages::{
-	source::estimate_message_dispatch_and_delivery_fee, MessageBridge,
+	source::{apply_congestion_fee, estimate_message_dispatch_and_delivery_fee},
+	MessageBridge,
 };
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
@@ -73,6 +74,7 @@ pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_bridge_grandpa::Call as BridgeGrandpaCall;
 pub use pallet_bridge_messages::Call as MessagesCall;
+pub use pallet_bridge_parachains::Call as BridgeParachainsCall;
 pub use pallet_sudo::Call as SudoCall;
 pub use pallet_timestamp::Call as TimestampCall;
 
@@ -375,6 +377,25 @@ parameter_types! {
 	// Note that once this is hit the pallet will essentially throttle incoming requests down to one
 	// call per block.
 	pub const MaxRequests: u32 = 50;
+
+	// Maximal number of headers that may be imported in a single transaction. Every header
+	// counts as a separate request.
+	pub const MaxBatchSize: u32 = 16;
+
+	// Non-mandatory headers are free if they improve the best finalized header by at least 16
+	// blocks. Relayers are not rewarded on our testnets, so we don't limit the number of free
+	// headers much.
+	pub const FreeHeadersInterval: Option<u32> = Some(16);
+	pub const MaxRewardsPerPeriod: u32 = 64;
+	pub const RewardsPeriod: bp_millau::BlockNumber = bp_millau::HOURS;
+
+	// Number of GRANDPA authority sets to keep. It is enough to verify finality of headers,
+	// finalized by recent authority sets, on our testnets.
+	pub const AuthoritySetsToKeep: u32 = 128;
+
+	// Number of historical headers to keep. Historical headers are only imported on demand, so we
+	// don't need to keep many of them.
+	pub const HistoricalHeadersToKeep: u32 = 1024;
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -397,26 +418,74 @@ parameter_types! {
 	pub const HeadersToKeep: u32 = 7 * bp_rialto::DAYS as u32;
 }
 
+parameter_types! {
+	/// We keep the fixed number of the most recent headers.
+	pub const HeadersRetention: bp_header_chain::HeadersRetentionPolicy =
+		bp_header_chain::HeadersRetentionPolicy::Count;
+}
+
 pub type RialtoGrandpaInstance = ();
 impl pallet_bridge_grandpa::Config for Runtime {
+	type Event = Event;
 	type BridgedChain = bp_rialto::Rialto;
 	type MaxRequests = MaxRequests;
+	type MaxBatchSize = MaxBatchSize;
+	type FreeHeadersInterval = FreeHeadersInterval;
+	type MaxRewardsPerPeriod = MaxRewardsPerPeriod;
+	type RewardsPeriod = RewardsPeriod;
+	type RelayerRewardPayment = ();
 	type HeadersToKeep = HeadersToKeep;
+	type HeadersRetention = HeadersRetention;
+	type AuthoritySetsToKeep = AuthoritySetsToKeep;
+	type HistoricalHeadersToKeep = HistoricalHeadersToKeep;
 
 	type WeightInfo = pallet_bridge_grandpa::weights::MillauWeight<Runtime>;
 }
 
 pub type WestendGrandpaInstance = pallet_bridge_grandpa::Instance1;
 impl pallet_bridge_grandpa::Config<WestendGrandpaInstance> for Runtime {
+	type Event = Event;
 	type BridgedChain = bp_westend::Westend;
 	type MaxRequests = MaxRequests;
+	type MaxBatchSize = MaxBatchSize;
+	type FreeHeadersInterval = FreeHeadersInterval;
+	type MaxRewardsPerPeriod = MaxRewardsPerPeriod;
+	type RewardsPeriod = RewardsPeriod;
+	type RelayerRewardPayment = ();
 	type HeadersToKeep = HeadersToKeep;
+	type HeadersRetention = HeadersRetention;
+	type AuthoritySetsToKeep = AuthoritySetsToKeep;
+	type HistoricalHeadersToKeep = HistoricalHeadersToKeep;
 
 	type WeightInfo = pallet_bridge_grandpa::weights::MillauWeight<Runtime>;
 }
 
 impl pallet_shift_session_manager::Config for Runtime {}
 
+parameter_types! {
+	/// Number of parachain heads to keep.
+	///
+	/// Assuming the worst case of every relay header updating the head, we will keep heads at least
+	/// for a day.
+	pub const ParachainHeadsToKeep: u32 = bp_rialto::DAYS as u32;
+	/// Id of the Rialto parachain, whose heads are tracked by the pallet.
+	pub const RialtoParachainId: bp_polkadot_core::parachains::ParaId =
+		bp_polkadot_core::parachains::ParaId(bp_rialto_parachain::RIALTO_PARACHAIN_ID);
+}
+
+/// Instance of the parachains pallet used to track heads of Rialto parachains.
+pub type WithRialtoParachainsInstance = ();
+impl pallet_bridge_parachains::Config<WithRialtoParachainsInstance> for Runtime {
+	type Event = Event;
+	type WeightInfo = pallet_bridge_parachains::weights::MillauWeight<Runtime>;
+	type BridgesGrandpaPalletInstance = RialtoGrandpaInstance;
+	type HeadsToKeep = ParachainHeadsToKeep;
+	type HeadDecoder = pallet_bridge_parachains::SingleParaHeadDecoder<
+		bp_rialto_parachain::Header,
+		RialtoParachainId,
+	>;
+}
+
 parameter_types! {
 	pub const MaxMessagesToPruneAtOnce: bp_messages::MessageNonce = 8;
 	pub const MaxUnrewardedRelayerEntriesAtInboundLane: bp_messages::MessageNonce =
@@ -427,6 +496,13 @@ parameter_types! {
 	pub const GetDeliveryConfirmationTransactionFee: Balance =
 		bp_millau::MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT as _;
 	pub const RootAccountForPayments: Option<AccountId> = None;
+	// retrying failed messages dispatch is disabled
+	pub const FailedMessagesRetryPeriod: BlockNumber = 0;
+	pub const MaxDispatchRetries: u32 = 0;
+	// relayers are rewarded right when message delivery is confirmed
+	pub const ClaimableRelayersRewards: bool = false;
+	// inbound messages expiration is checked against the best finalized Rialto header
+	pub BestFinalizedRialtoBlockNumber: u64 = BridgeRialtoGrandpa::best_finalized().number.into();
 	pub const RialtoChainId: bp_runtime::ChainId = bp_runtime::RIALTO_CHAIN_ID;
 }
 
@@ -447,17 +523,21 @@ impl pallet_bridge_messages::Config<WithRialtoMessagesInstance> for Runtime {
 	type InboundPayload = crate::rialto_messages::FromRialtoMessagePayload;
 	type InboundMessageFee = bp_rialto::Balance;
 	type InboundRelayer = bp_rialto::AccountId;
+	type FailedMessagesRetryPeriod = FailedMessagesRetryPeriod;
+	type MaxDispatchRetries = MaxDispatchRetries;
 
 	type AccountIdConverter = bp_millau::AccountIdConverter;
 
 	type TargetHeaderChain = crate::rialto_messages::Rialto;
 	type LaneMessageVerifier = crate::rialto_messages::ToRialtoMessageVerifier;
 	type MessageDeliveryAndDispatchPayment = ();
+	type ClaimableRelayersRewards = ClaimableRelayersRewards;
 	type OnMessageAccepted = ();
 	type OnDeliveryConfirmed = ();
 
 	type SourceHeaderChain = crate::rialto_messages::Rialto;
 	type MessageDispatch = crate::rialto_messages::FromRialtoMessageDispatch;
+	type BridgedChainBestFinalizedBlockNumber = BestFinalizedRialtoBlockNumber;
 	type BridgedChainId = RialtoChainId;
 }
 
@@ -489,11 +569,14 @@ construct_runtime!(
 		MmrLeaf: pallet_beefy_mmr::{Pallet, Storage},
 
 		// Rialto bridge modules.
-		BridgeRialtoGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage},
+		BridgeRialtoGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage, Event<T>},
 		BridgeRialtoMessages: pallet_bridge_messages::{Pallet, Call, Storage, Event<T>, Config<T>},
 
+		// Rialto parachains bridge modules.
+		BridgeRialtoParachains: pallet_bridge_parachains::{Pallet, Call, Storage, Event<T>, Config<T>},
+
 		// Westend bridge modules.
-		BridgeWestendGrandpa: pallet_bridge_grandpa::<Instance1>::{Pallet, Call, Config<T>, Storage},
+		BridgeWestendGrandpa: pallet_bridge_grandpa::<Instance1>::{Pallet, Call, Config<T>, Storage, Event<T>},
 
 		// Pallet for sending XCM.
 		XcmPallet: pallet_xcm::{Pallet, Call, Storage, Event<T>, Origin, Config} = 99,
@@ -534,6 +617,15 @@ pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
+/// Storage migrations of the runtime pallets.
+pub type Migrations = (
+	pallet_bridge_messages::migrations::v1::Migration<Runtime, WithRialtoMessagesInstance>,
+	pallet_bridge_parachains::migrations::v1::Migration<Runtime, WithRialtoParachainsInstance>,
+	pallet_bridge_grandpa::migrations::v1::Migration<Runtime, RialtoGrandpaInstance>,
+	pallet_bridge_grandpa::migrations::v1::Migration<Runtime, WestendGrandpaInstance>,
+	pallet_bridge_grandpa::migrations::v2::Migration<Runtime, RialtoGrandpaInstance>,
+	pallet_bridge_grandpa::migrations::v2::Migration<Runtime, WestendGrandpaInstance>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -541,6 +633,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 impl_runtime_apis! {
@@ -724,6 +817,20 @@ impl_runtime_apis! {
 			let header = BridgeRialtoGrandpa::best_finalized();
 			(header.number, header.hash())
 		}
+
+		fn authority_set_at(
+			number: bp_rialto::BlockNumber,
+		) -> Option<bp_header_chain::AuthoritySet> {
+			BridgeRialtoGrandpa::authority_set_at(number)
+		}
+	}
+
+	impl bp_rialto::RialtoParachainsApi<Block> for Runtime {
+		fn best_parachain_head(
+			parachain: bp_polkadot_core::parachains::ParaId,
+		) -> Option<bp_polkadot_core::parachains::BestParaHeadInfo> {
+			BridgeRialtoParachains::best_parachain_head_info(parachain)
+		}
 	}
 
 	impl bp_westend::WestendFinalityApi<Block> for Runtime {
@@ -731,11 +838,19 @@ impl_runtime_apis! {
 			let header = BridgeWestendGrandpa::best_finalized();
 			(header.number, header.hash())
 		}
+
+		fn authority_set_at(
+			number: bp_westend::BlockNumber,
+		) -> Option<bp_header_chain::AuthoritySet> {
+			BridgeWestendGrandpa::authority_set_at(number)
+		}
 	}
 
-	impl bp_rialto::ToRialtoOutboundLaneApi<Block, Balance, ToRialtoMessagePayload> for Runtime {
+	impl bp_rialto::ToRialtoOutboundLaneApi<Block, Balance, ToRialtoMessagePayload, AccountId>
+		for Runtime
+	{
 		fn estimate_message_delivery_and_dispatch_fee(
-			_lane_id: bp_messages::LaneId,
+			lane_id: bp_messages::LaneId,
 			payload: ToRialtoMessagePayload,
 			rialto_to_this_conversion_rate: Option<FixedU128>,
 		) -> Option<Balance> {
@@ -743,7 +858,14 @@ impl_runtime_apis! {
 				&payload,
 				WithRialtoMessageBridge::RELAYER_FEE_PERCENT,
 				rialto_to_this_conversion_rate,
-			).ok()
+			).and_then(|fee| apply_congestion_fee::<WithRialtoMessageBridge>(
+				fee,
+				WithRialtoMessageBridge::CONGESTION_FEE_PERCENT,
+				bridge_runtime_common::messages_api::outbound_lane_pending_messages::<
+					Runtime,
+					WithRialtoMessagesInstance,
+				>(lane_id),
+			)).ok()
 		}
 
 		fn message_details(
@@ -758,6 +880,10 @@ impl_runtime_apis! {
 				xcm_config::OutboundXcmWeigher,
 			>(lane, begin, end)
 		}
+
+		fn relayer_reward(relayer: AccountId, lane: bp_messages::LaneId) -> Balance {
+			BridgeRialtoMessages::relayer_reward(relayer, lane)
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -770,11 +896,13 @@ impl_runtime_apis! {
 			use frame_support::traits::StorageInfoTrait;
 
 			use pallet_bridge_messages::benchmarking::Pallet as MessagesBench;
+			use pallet_bridge_parachains::benchmarking::Pallet as ParachainsBench;
 
 			let mut list = Vec::<BenchmarkList>::new();
 
 			list_benchmark!(list, extra, pallet_bridge_messages, MessagesBench::<Runtime, WithRialtoMessagesInstance>);
 			list_benchmark!(list, extra, pallet_bridge_grandpa, BridgeRialtoGrandpa);
+			list_benchmark!(list, extra, pallet_bridge_parachains, ParachainsBench::<Runtime, WithRialtoParachainsInstance>);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -811,6 +939,10 @@ impl_runtime_apis! {
 				MessageParams,
 				MessageProofParams,
 			};
+			use pallet_bridge_parachains::benchmarking::{
+				Pallet as ParachainsBench,
+				Config as ParachainsConfig,
+			};
 			use rialto_messages::WithRialtoMessageBridge;
 
 			impl MessagesConfig<WithRialtoMessagesInstance> for Runtime {
@@ -860,6 +992,28 @@ impl_runtime_apis! {
 				}
 			}
 
+			impl ParachainsConfig<WithRialtoParachainsInstance> for Runtime {
+				fn parachains() -> Vec<bp_polkadot_core::parachains::ParaId> {
+					vec![bp_polkadot_core::parachains::ParaId(bp_rialto_parachain::RIALTO_PARACHAIN_ID)]
+				}
+
+				fn prepare_parachain_heads_proof(
+					parachains: &[bp_polkadot_core::parachains::ParaId],
+					parachain_head_size: u32,
+					proof_size: bp_runtime::StorageProofSize,
+				) -> (
+					pallet_bridge_parachains::RelayBlockHash,
+					bp_polkadot_core::parachains::ParachainHeadsProof,
+					Vec<(bp_polkadot_core::parachains::ParaId, bp_polkadot_core::parachains::ParaHash)>,
+				) {
+					bridge_runtime_common::parachains_benchmarking::prepare_parachain_heads_proof::<Runtime, WithRialtoParachainsInstance>(
+						parachains,
+						parachain_head_size,
+						proof_size,
+					)
+				}
+			}
+
 
 			add_benchmark!(
 				params,
@@ -868,6 +1022,12 @@ impl_runtime_apis! {
 				MessagesBench::<Runtime, WithRialtoMessagesInstance>
 			);
 			add_benchmark!(params, batches, pallet_bridge_grandpa, BridgeRialtoGrandpa);
+			add_benchmark!(
+				params,
+				batches,
+				pallet_bridge_parachains,
+				ParachainsBench::<Runtime, WithRialtoParachainsInstance>
+			);
 
 			Ok(batches)
 		}
@@ -18,12 +18,15 @@
 
 use super::{
 	rialto_messages::WithRialtoMessageBridge, AccountId, AllPalletsWithSystem, Balances,
-	BridgeRialtoMessages, Call, Event, Origin, Runtime, XcmPallet,
+	BridgeRialtoMessages, Call, Event, Origin, Runtime, WithRialtoMessagesInstance, XcmPallet,
 };
-use bp_messages::source_chain::MessagesBridge;
+use bp_messages::{source_chain::MessagesBridge, LaneId};
 use bp_millau::{Balance, WeightToFee};
 use bridge_runtime_common::messages::{
-	source::{estimate_message_dispatch_and_delivery_fee, FromThisChainMessagePayload},
+	source::{
+		apply_congestion_fee, estimate_message_dispatch_and_delivery_fee,
+		FromThisChainMessagePayload,
+	},
 	MessageBridge,
 };
 use codec::Encode;
@@ -191,6 +194,9 @@ impl pallet_xcm::Config for Runtime {
 	type MaxLockers = frame_support::traits::ConstU32<8>;
 }
 
+/// Lane that is used to send XCM messages to Rialto.
+const XCM_LANE: LaneId = [0, 0, 0, 0];
+
 /// With-rialto bridge.
 pub struct ToRialtoBridge<MB>(PhantomData<MB>);
 
@@ -220,6 +226,16 @@ impl<MB: MessagesBridge<Origin, AccountId, Balance, FromThisChainMessagePayload>
 			WithRialtoMessageBridge::RELAYER_FEE_PERCENT,
 			None,
 		)
+		.and_then(|fee| {
+			apply_congestion_fee::<WithRialtoMessageBridge>(
+				fee,
+				WithRialtoMessageBridge::CONGESTION_FEE_PERCENT,
+				bridge_runtime_common::messages_api::outbound_lane_pending_messages::<
+					Runtime,
+					WithRialtoMessagesInstance,
+				>(XCM_LANE),
+			)
+		})
 		.map_err(SendError::Transport)?;
 		let fee_assets = MultiAssets::from((Here, fee));
 
@@ -227,7 +243,7 @@ impl<MB: MessagesBridge<Origin, AccountId, Balance, FromThisChainMessagePayload>
 	}
 
 	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
-		let lane = [0, 0, 0, 0];
+		let lane = XCM_LANE;
 		let (fee, msg) = ticket;
 		let result = MB::send_message(
 			pallet_xcm::Origin::from(MultiLocation::from(UniversalLocation::get())).into(),
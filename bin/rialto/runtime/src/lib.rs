@@ -36,7 +36,8 @@ use crate::millau_messages::{ToMillauMessagePayload, WithMillauMessageBridge};
 
 use beefy_primitives::{crypto::AuthorityId as BeefyId, mmr::MmrLeafVersion, ValidatorSet};
 use bridge_runtime_common::messages::{
-	source::estimate_message_dispatch_and_delivery_fee, MessageBridge,
+	source::{apply_congestion_fee, estimate_message_dispatch_and_delivery_fee},
+	MessageBridge,
 };
 use pallet_grandpa::{
 	fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList,
@@ -398,18 +399,50 @@ parameter_types! {
 	/// call per block.
 	pub const MaxRequests: u32 = 50;
 
+	/// Maximal number of headers that may be imported in a single transaction. Every header
+	/// counts as a separate request.
+	pub const MaxBatchSize: u32 = 16;
+
+	/// Non-mandatory headers are free if they improve the best finalized header by at least 16
+	/// blocks. Relayers are not rewarded on our testnets, so we don't limit the number of free
+	/// headers much.
+	pub const FreeHeadersInterval: Option<u32> = Some(16);
+	pub const MaxRewardsPerPeriod: u32 = 64;
+	pub const RewardsPeriod: bp_rialto::BlockNumber = bp_rialto::HOURS;
+
 	/// Number of headers to keep.
 	///
 	/// Assuming the worst case of every header being finalized, we will keep headers at least for a
 	/// week.
 	pub const HeadersToKeep: u32 = 7 * bp_rialto::DAYS as u32;
+
+	/// We keep the fixed number of the most recent headers.
+	pub const HeadersRetention: bp_header_chain::HeadersRetentionPolicy =
+		bp_header_chain::HeadersRetentionPolicy::Count;
+
+	/// Number of GRANDPA authority sets to keep. It is enough to verify finality of headers,
+	/// finalized by recent authority sets, on our testnets.
+	pub const AuthoritySetsToKeep: u32 = 128;
+
+	/// Number of historical headers to keep. Historical headers are only imported on demand, so we
+	/// don't need to keep many of them.
+	pub const HistoricalHeadersToKeep: u32 = 1024;
 }
 
 pub type MillauGrandpaInstance = ();
 impl pallet_bridge_grandpa::Config for Runtime {
+	type Event = Event;
 	type BridgedChain = bp_millau::Millau;
 	type MaxRequests = MaxRequests;
+	type MaxBatchSize = MaxBatchSize;
+	type FreeHeadersInterval = FreeHeadersInterval;
+	type MaxRewardsPerPeriod = MaxRewardsPerPeriod;
+	type RewardsPeriod = RewardsPeriod;
+	type RelayerRewardPayment = ();
 	type HeadersToKeep = HeadersToKeep;
+	type HeadersRetention = HeadersRetention;
+	type AuthoritySetsToKeep = AuthoritySetsToKeep;
+	type HistoricalHeadersToKeep = HistoricalHeadersToKeep;
 	type WeightInfo = pallet_bridge_grandpa::weights::MillauWeight<Runtime>;
 }
 
@@ -425,6 +458,13 @@ parameter_types! {
 	pub const GetDeliveryConfirmationTransactionFee: Balance =
 		bp_rialto::MAX_SINGLE_MESSAGE_DELIVERY_CONFIRMATION_TX_WEIGHT as _;
 	pub const RootAccountForPayments: Option<AccountId> = None;
+	// retrying failed messages dispatch is disabled
+	pub const FailedMessagesRetryPeriod: BlockNumber = 0;
+	pub const MaxDispatchRetries: u32 = 0;
+	// relayers are rewarded right when message delivery is confirmed
+	pub const ClaimableRelayersRewards: bool = false;
+	// inbound messages expiration is checked against the best finalized Millau header
+	pub BestFinalizedMillauBlockNumber: u64 = BridgeMillauGrandpa::best_finalized().number.into();
   pub const BridgedChainId: bp_runtime::ChainId = bp_runtime::MILLAU_CHAIN_ID;
 }
 
@@ -445,17 +485,21 @@ impl pallet_bridge_messages::Config<WithMillauMessagesInstance> for Runtime {
 	type InboundPayload = crate::millau_messages::FromMillauMessagePayload;
 	type InboundMessageFee = bp_millau::Balance;
 	type InboundRelayer = bp_millau::AccountId;
+	type FailedMessagesRetryPeriod = FailedMessagesRetryPeriod;
+	type MaxDispatchRetries = MaxDispatchRetries;
 
 	type AccountIdConverter = bp_rialto::AccountIdConverter;
 
 	type TargetHeaderChain = crate::millau_messages::Millau;
 	type LaneMessageVerifier = crate::millau_messages::ToMillauMessageVerifier;
 	type MessageDeliveryAndDispatchPayment = ();
+	type ClaimableRelayersRewards = ClaimableRelayersRewards;
 	type OnMessageAccepted = ();
 	type OnDeliveryConfirmed = ();
 
 	type SourceHeaderChain = crate::millau_messages::Millau;
 	type MessageDispatch = crate::millau_messages::FromMillauMessageDispatch;
+	type BridgedChainBestFinalizedBlockNumber = BestFinalizedMillauBlockNumber;
 	type BridgedChainId = BridgedChainId;
 }
 
@@ -487,7 +531,7 @@ construct_runtime!(
 		MmrLeaf: pallet_beefy_mmr::{Pallet, Storage},
 
 		// Millau bridge modules.
-		BridgeMillauGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage},
+		BridgeMillauGrandpa: pallet_bridge_grandpa::{Pallet, Call, Storage, Event<T>},
 		BridgeMillauMessages: pallet_bridge_messages::{Pallet, Call, Storage, Event<T>, Config<T>},
 
 		// Parachain modules.
@@ -541,6 +585,12 @@ pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
+/// Storage migrations of the runtime pallets.
+pub type Migrations = (
+	pallet_bridge_messages::migrations::v1::Migration<Runtime, WithMillauMessagesInstance>,
+	pallet_bridge_grandpa::migrations::v1::Migration<Runtime, MillauGrandpaInstance>,
+	pallet_bridge_grandpa::migrations::v2::Migration<Runtime, MillauGrandpaInstance>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -548,6 +598,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 impl_runtime_apis! {
@@ -646,6 +697,12 @@ impl_runtime_apis! {
 			let header = BridgeMillauGrandpa::best_finalized();
 			(header.number, header.hash())
 		}
+
+		fn authority_set_at(
+			number: bp_millau::BlockNumber,
+		) -> Option<bp_header_chain::AuthoritySet> {
+			BridgeMillauGrandpa::authority_set_at(number)
+		}
 	}
 
 	impl sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block> for Runtime {
@@ -878,9 +935,11 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl bp_millau::ToMillauOutboundLaneApi<Block, Balance, ToMillauMessagePayload> for Runtime {
+	impl bp_millau::ToMillauOutboundLaneApi<Block, Balance, ToMillauMessagePayload, AccountId>
+		for Runtime
+	{
 		fn estimate_message_delivery_and_dispatch_fee(
-			_lane_id: bp_messages::LaneId,
+			lane_id: bp_messages::LaneId,
 			payload: ToMillauMessagePayload,
 			millau_to_this_conversion_rate: Option<FixedU128>,
 		) -> Option<Balance> {
@@ -888,7 +947,14 @@ impl_runtime_apis! {
 				&payload,
 				WithMillauMessageBridge::RELAYER_FEE_PERCENT,
 				millau_to_this_conversion_rate,
-			).ok()
+			).and_then(|fee| apply_congestion_fee::<WithMillauMessageBridge>(
+				fee,
+				WithMillauMessageBridge::CONGESTION_FEE_PERCENT,
+				bridge_runtime_common::messages_api::outbound_lane_pending_messages::<
+					Runtime,
+					WithMillauMessagesInstance,
+				>(lane_id),
+			)).ok()
 		}
 
 		fn message_details(
@@ -903,6 +969,10 @@ impl_runtime_apis! {
 				xcm_config::OutboundXcmWeigher,
 			>(lane, begin, end)
 		}
+
+		fn relayer_reward(relayer: AccountId, lane: bp_messages::LaneId) -> Balance {
+			BridgeMillauMessages::relayer_reward(relayer, lane)
+		}
 	}
 }
 
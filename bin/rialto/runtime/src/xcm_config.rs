@@ -18,12 +18,15 @@
 
 use super::{
 	millau_messages::WithMillauMessageBridge, AccountId, AllPalletsWithSystem, Balances,
-	BridgeMillauMessages, Call, Event, Origin, Runtime, XcmPallet,
+	BridgeMillauMessages, Call, Event, Origin, Runtime, WithMillauMessagesInstance, XcmPallet,
 };
-use bp_messages::source_chain::MessagesBridge;
+use bp_messages::{source_chain::MessagesBridge, LaneId};
 use bp_rialto::{Balance, WeightToFee};
 use bridge_runtime_common::messages::{
-	source::{estimate_message_dispatch_and_delivery_fee, FromThisChainMessagePayload},
+	source::{
+		apply_congestion_fee, estimate_message_dispatch_and_delivery_fee,
+		FromThisChainMessagePayload,
+	},
 	MessageBridge,
 };
 use codec::Encode;
@@ -191,6 +194,9 @@ impl pallet_xcm::Config for Runtime {
 	type MaxLockers = frame_support::traits::ConstU32<8>;
 }
 
+/// Lane that is used to send XCM messages to Millau.
+const XCM_LANE: LaneId = [0, 0, 0, 0];
+
 /// With-rialto bridge.
 pub struct ToMillauBridge<MB>(PhantomData<MB>);
 
@@ -220,6 +226,16 @@ impl<MB: MessagesBridge<Origin, AccountId, Balance, FromThisChainMessagePayload>
 			WithMillauMessageBridge::RELAYER_FEE_PERCENT,
 			None,
 		)
+		.and_then(|fee| {
+			apply_congestion_fee::<WithMillauMessageBridge>(
+				fee,
+				WithMillauMessageBridge::CONGESTION_FEE_PERCENT,
+				bridge_runtime_common::messages_api::outbound_lane_pending_messages::<
+					Runtime,
+					WithMillauMessagesInstance,
+				>(XCM_LANE),
+			)
+		})
 		.map_err(SendError::Transport)?;
 		let fee_assets = MultiAssets::from((Here, fee));
 
@@ -227,7 +243,7 @@ impl<MB: MessagesBridge<Origin, AccountId, Balance, FromThisChainMessagePayload>
 	}
 
 	fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
-		let lane = [0, 0, 0, 0];
+		let lane = XCM_LANE;
 		let (fee, msg) = ticket;
 		let result = MB::send_message(
 			pallet_xcm::Origin::from(MultiLocation::from(UniversalLocation::get())).into(),
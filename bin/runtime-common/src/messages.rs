@@ -21,17 +21,21 @@
 //! of to elements - message lane id and message nonce.
 
 use bp_messages::{
-	source_chain::LaneMessageVerifier,
-	target_chain::{DispatchMessage, MessageDispatch, ProvedLaneMessages, ProvedMessages},
-	InboundLaneData, LaneId, Message, MessageData, MessageKey, MessageNonce, OutboundLaneData,
+	source_chain::{LaneMessageVerifier, TargetHeaderChain},
+	target_chain::{
+		DispatchMessage, MessageDispatch, ProvedLaneMessages, ProvedMessages, SourceHeaderChain,
+	},
+	ExpiredMessages, InboundLaneData, LaneId, Message, MessageData, MessageKey, MessageNonce,
+	OutboundLaneData,
 };
+use bp_polkadot_core::parachains::{ParaHash, ParaId};
 use bp_runtime::{messages::MessageDispatchResult, ChainId, Size, StorageProofChecker};
 use codec::{Decode, DecodeLimit, Encode};
-use frame_support::{traits::Get, weights::Weight, RuntimeDebug};
+use frame_support::{traits::Get, weights::Weight, Parameter, RuntimeDebug};
 use hash_db::Hasher;
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedDiv, CheckedMul},
+	traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedDiv, CheckedMul, Header as HeaderT},
 	FixedPointNumber, FixedPointOperand, FixedU128,
 };
 use sp_std::{cmp::PartialOrd, convert::TryFrom, fmt::Debug, marker::PhantomData, vec::Vec};
@@ -41,6 +45,9 @@ use sp_trie::StorageProof;
 pub trait MessageBridge {
 	/// Relayer interest (in percents).
 	const RELAYER_FEE_PERCENT: u32;
+	/// Additional fee (in percents of the minimal fee) that is charged for every message that
+	/// is already queued (not yet delivered) at the outbound lane.
+	const CONGESTION_FEE_PERCENT: u32;
 
 	/// Identifier of this chain.
 	const THIS_CHAIN_ID: ChainId;
@@ -192,6 +199,34 @@ pub fn transaction_payment<Balance: AtLeast32BitUnsigned + FixedPointOperand>(
 	base_fee.saturating_add(len_fee).saturating_add(adjusted_weight_fee)
 }
 
+/// Parse storage proof, generated at the bridged parachain header that is known to the
+/// `pallet-bridge-parachains` at This chain.
+///
+/// The parachain head must be decoded by the pallet (see `HeadDecoder` of the pallet
+/// configuration), because the state root of the decoded header is used to verify the proof.
+pub fn parse_finalized_parachain_storage_proof<
+	BridgedHeader: HeaderT<Hash = ParaHash>,
+	ThisRuntime,
+	ParachainsInstance: 'static,
+	R,
+>(
+	parachain: ParaId,
+	parachain_head_hash: ParaHash,
+	storage_proof: RawStorageProof,
+	parse: impl FnOnce(StorageProofChecker<BridgedHeader::Hashing>) -> R,
+) -> Result<R, &'static str>
+where
+	ThisRuntime: pallet_bridge_parachains::Config<ParachainsInstance>,
+{
+	pallet_bridge_parachains::Pallet::<ThisRuntime, ParachainsInstance>::parse_finalized_storage_proof(
+		parachain,
+		parachain_head_hash,
+		StorageProof::new(storage_proof),
+		parse,
+	)
+	.map_err(<&'static str>::from)
+}
+
 /// Sub-module that is declaring types required for processing This -> Bridged chain messages.
 pub mod source {
 	use super::*;
@@ -228,9 +263,10 @@ pub mod source {
 		}
 	}
 
-	/// 'Parsed' message delivery proof - inbound lane id and its state.
+	/// 'Parsed' message delivery proof - inbound lane id, its state and nonces of expired
+	/// messages.
 	pub type ParsedMessagesDeliveryProofFromBridgedChain<B> =
-		(LaneId, InboundLaneData<AccountIdOf<ThisChain<B>>>);
+		(LaneId, InboundLaneData<AccountIdOf<ThisChain<B>>>, ExpiredMessages);
 
 	/// Message verifier that is doing all basic checks.
 	///
@@ -245,7 +281,8 @@ pub mod source {
 	///   lane;
 	/// - check that the sender has rights to dispatch the call on target chain using provided
 	///   dispatch origin;
-	/// - check that the sender has paid enough funds for both message delivery and dispatch.
+	/// - check that the sender has paid enough funds for both message delivery and dispatch. The
+	///   minimal fee grows with the number of messages that are queued at the outbound lane.
 	#[derive(RuntimeDebug)]
 	pub struct FromThisChainMessageVerifier<B>(PhantomData<B>);
 
@@ -302,6 +339,11 @@ pub mod source {
 				B::RELAYER_FEE_PERCENT,
 				None,
 			)?;
+			let minimal_fee_in_this_tokens = apply_congestion_fee::<B>(
+				minimal_fee_in_this_tokens,
+				B::CONGESTION_FEE_PERCENT,
+				pending_messages,
+			)?;
 
 			// compare with actual fee paid
 			if *delivery_and_dispatch_fee < minimal_fee_in_this_tokens {
@@ -389,6 +431,27 @@ pub mod source {
 			.ok_or("Overflow when computing minimal required message delivery and dispatch fee")
 	}
 
+	/// Increase the minimal message fee according to the number of messages that are already
+	/// queued at the outbound lane.
+	///
+	/// Every queued message adds `congestion_fee_percent` percents of the `fee` to the result.
+	pub fn apply_congestion_fee<B: MessageBridge>(
+		fee: BalanceOf<ThisChain<B>>,
+		congestion_fee_percent: u32,
+		pending_messages: MessageNonce,
+	) -> Result<BalanceOf<ThisChain<B>>, &'static str> {
+		u32::try_from(pending_messages)
+			.ok()
+			.and_then(|pending_messages| pending_messages.checked_mul(congestion_fee_percent))
+			.and_then(|congestion_percent| {
+				// multiplication first, see `estimate_message_dispatch_and_delivery_fee`
+				fee.checked_mul(&congestion_percent.into())
+					.and_then(|congestion_fee| congestion_fee.checked_div(&100u32.into()))
+					.and_then(|congestion_fee| fee.checked_add(&congestion_fee))
+			})
+			.ok_or("Overflow when computing message fee at the congested lane")
+	}
+
 	/// Verify proof of This -> Bridged chain messages delivery.
 	pub fn verify_messages_delivery_proof<B: MessageBridge, ThisRuntime, GrandpaInstance: 'static>(
 		proof: FromBridgedChainMessagesDeliveryProof<HashOf<BridgedChain<B>>>,
@@ -406,23 +469,126 @@ pub mod source {
 		pallet_bridge_grandpa::Pallet::<ThisRuntime, GrandpaInstance>::parse_finalized_storage_proof(
 			bridged_header_hash.into(),
 			StorageProof::new(storage_proof),
-			|storage| {
-				// Messages delivery proof is just proof of single storage key read => any error
-				// is fatal.
-				let storage_inbound_lane_data_key =
-					bp_messages::storage_keys::inbound_lane_data_key(B::BRIDGED_MESSAGES_PALLET_NAME, &lane);
-				let raw_inbound_lane_data = storage
-					.read_value(storage_inbound_lane_data_key.0.as_ref())
-					.map_err(|_| "Failed to read inbound lane state from storage proof")?
-					.ok_or("Inbound lane state is missing from the messages proof")?;
-				let inbound_lane_data = InboundLaneData::decode(&mut &raw_inbound_lane_data[..])
-					.map_err(|_| "Failed to decode inbound lane state from the proof")?;
-
-				Ok((lane, inbound_lane_data))
-			},
+			|storage| read_inbound_lane_data::<B, _>(&storage, lane),
 		)
 		.map_err(<&'static str>::from)?
 	}
+
+	/// Verify proof of This -> Bridged parachain messages delivery.
+	///
+	/// The proof must be generated at the bridged parachain header, which is known to the
+	/// `pallet-bridge-parachains` at This chain.
+	pub fn verify_messages_delivery_proof_from_parachain<
+		B: MessageBridge,
+		BridgedHeader: HeaderT<Hash = ParaHash>,
+		ThisRuntime,
+		ParachainsInstance: 'static,
+	>(
+		bridged_parachain: ParaId,
+		proof: FromBridgedChainMessagesDeliveryProof<HashOf<BridgedChain<B>>>,
+	) -> Result<ParsedMessagesDeliveryProofFromBridgedChain<B>, &'static str>
+	where
+		ThisRuntime: pallet_bridge_parachains::Config<ParachainsInstance>,
+		HashOf<BridgedChain<B>>: Into<ParaHash>,
+	{
+		let FromBridgedChainMessagesDeliveryProof { bridged_header_hash, storage_proof, lane } =
+			proof;
+		parse_finalized_parachain_storage_proof::<BridgedHeader, ThisRuntime, ParachainsInstance, _>(
+			bridged_parachain,
+			bridged_header_hash.into(),
+			storage_proof,
+			|storage| read_inbound_lane_data::<B, _>(&storage, lane),
+		)?
+	}
+
+	/// Read inbound lane state and nonces of expired messages from the storage proof of the
+	/// Bridged chain.
+	fn read_inbound_lane_data<B: MessageBridge, H: Hasher>(
+		storage: &StorageProofChecker<H>,
+		lane: LaneId,
+	) -> Result<ParsedMessagesDeliveryProofFromBridgedChain<B>, &'static str> {
+		// Messages delivery proof is just proof of two storage keys read => any error
+		// is fatal.
+		let storage_inbound_lane_data_key = bp_messages::storage_keys::inbound_lane_data_key(
+			B::BRIDGED_MESSAGES_PALLET_NAME,
+			&lane,
+		);
+		let raw_inbound_lane_data = storage
+			.read_value(storage_inbound_lane_data_key.0.as_ref())
+			.map_err(|_| "Failed to read inbound lane state from storage proof")?
+			.ok_or("Inbound lane state is missing from the messages proof")?;
+		let inbound_lane_data = InboundLaneData::decode(&mut &raw_inbound_lane_data[..])
+			.map_err(|_| "Failed to decode inbound lane state from the proof")?;
+
+		// expired messages are stored separately, so the inbound lane state encoding is the same
+		// at both sides of the bridge. The bridged chain, that doesn't track expired messages yet,
+		// has no such value in its storage, but the proof must still prove its absence - otherwise
+		// the relayer would be able to avoid refunds of expired messages
+		let storage_expired_messages_key = bp_messages::storage_keys::expired_inbound_messages_key(
+			B::BRIDGED_MESSAGES_PALLET_NAME,
+			&lane,
+		);
+		let expired_messages = storage
+			.read_value(storage_expired_messages_key.0.as_ref())
+			.map_err(|_| "Failed to read expired messages from storage proof")?
+			.map(|raw_expired_messages| {
+				ExpiredMessages::decode(&mut &raw_expired_messages[..])
+					.map_err(|_| "Failed to decode expired messages from the proof")
+			})
+			.transpose()?
+			.unwrap_or_default();
+
+		Ok((lane, inbound_lane_data, expired_messages))
+	}
+
+	/// Target header chain adapter for the bridge with parachain, whose heads are tracked by the
+	/// `pallet-bridge-parachains` at This chain.
+	///
+	/// `BridgedParachainId` is the id of the bridged parachain at its relay chain.
+	#[derive(RuntimeDebug)]
+	pub struct ParachainTargetHeaderChain<
+		B,
+		BridgedHeader,
+		ThisRuntime,
+		ParachainsInstance,
+		BridgedParachainId,
+	>(PhantomData<(B, BridgedHeader, ThisRuntime, ParachainsInstance, BridgedParachainId)>);
+
+	impl<B, BridgedHeader, ThisRuntime, ParachainsInstance, BridgedParachainId>
+		TargetHeaderChain<FromThisChainMessagePayload, AccountIdOf<ThisChain<B>>>
+		for ParachainTargetHeaderChain<
+			B,
+			BridgedHeader,
+			ThisRuntime,
+			ParachainsInstance,
+			BridgedParachainId,
+		>
+	where
+		B: MessageBridge,
+		BridgedHeader: HeaderT<Hash = ParaHash>,
+		ThisRuntime: pallet_bridge_parachains::Config<ParachainsInstance>,
+		ParachainsInstance: 'static,
+		BridgedParachainId: Get<ParaId>,
+		HashOf<BridgedChain<B>>: Parameter + Into<ParaHash> + 'static,
+	{
+		type Error = &'static str;
+		type MessagesDeliveryProof = FromBridgedChainMessagesDeliveryProof<HashOf<BridgedChain<B>>>;
+
+		fn verify_message(payload: &FromThisChainMessagePayload) -> Result<(), Self::Error> {
+			verify_chain_message::<B>(payload)
+		}
+
+		fn verify_messages_delivery_proof(
+			proof: Self::MessagesDeliveryProof,
+		) -> Result<ParsedMessagesDeliveryProofFromBridgedChain<B>, Self::Error> {
+			verify_messages_delivery_proof_from_parachain::<
+				B,
+				BridgedHeader,
+				ThisRuntime,
+				ParachainsInstance,
+			>(BridgedParachainId::get(), proof)
+		}
+	}
 }
 
 /// Sub-module that is declaring types required for processing Bridged -> This chain messages.
@@ -538,6 +704,30 @@ pub mod target {
 		fn dispatch(
 			_relayer_account: &AccountIdOf<ThisChain<B>>,
 			message: DispatchMessage<Self::DispatchPayload, BalanceOf<BridgedChain<B>>>,
+		) -> MessageDispatchResult {
+			// dispatch fee is never paid at this chain, so we don't need relayer account
+			Self::dispatch_xcm(message)
+		}
+
+		fn redispatch(
+			message: DispatchMessage<Self::DispatchPayload, BalanceOf<BridgedChain<B>>>,
+		) -> MessageDispatchResult {
+			Self::dispatch_xcm(message)
+		}
+	}
+
+	impl<B: MessageBridge, XcmExecutor, XcmWeigher, WeightCredit>
+		FromBridgedChainMessageDispatch<B, XcmExecutor, XcmWeigher, WeightCredit>
+	where
+		XcmExecutor: xcm::v3::ExecuteXcm<CallOf<ThisChain<B>>>,
+		WeightCredit: Get<Weight>,
+	{
+		/// Execute XCM program from the inbound message.
+		fn dispatch_xcm(
+			message: DispatchMessage<
+				FromBridgedChainMessagePayload<CallOf<ThisChain<B>>>,
+				BalanceOf<BridgedChain<B>>,
+			>,
 		) -> MessageDispatchResult {
 			use xcm::latest::*;
 
@@ -624,6 +814,98 @@ pub mod target {
 		.map_err(Into::into)
 	}
 
+	/// Verify proof of Bridged parachain -> This chain messages.
+	///
+	/// The proof must be generated at the bridged parachain header, which is known to the
+	/// `pallet-bridge-parachains` at This chain.
+	///
+	/// The `messages_count` argument verification (sane limits) is supposed to be made
+	/// outside of this function. This function only verifies that the proof declares exactly
+	/// `messages_count` messages.
+	pub fn verify_messages_proof_from_parachain<
+		B: MessageBridge,
+		BridgedHeader: HeaderT<Hash = ParaHash>,
+		ThisRuntime,
+		ParachainsInstance: 'static,
+	>(
+		bridged_parachain: ParaId,
+		proof: FromBridgedChainMessagesProof<HashOf<BridgedChain<B>>>,
+		messages_count: u32,
+	) -> Result<ProvedMessages<Message<BalanceOf<BridgedChain<B>>>>, &'static str>
+	where
+		ThisRuntime: pallet_bridge_parachains::Config<ParachainsInstance>,
+		HashOf<BridgedChain<B>>: Into<ParaHash>,
+	{
+		verify_messages_proof_with_parser::<B, _, _>(
+			proof,
+			messages_count,
+			|bridged_header_hash, bridged_storage_proof| {
+				parse_finalized_parachain_storage_proof::<
+					BridgedHeader,
+					ThisRuntime,
+					ParachainsInstance,
+					_,
+				>(
+					bridged_parachain,
+					bridged_header_hash.into(),
+					bridged_storage_proof,
+					|storage_adapter| storage_adapter,
+				)
+				.map(|storage| StorageProofCheckerAdapter::<_, B> {
+					storage,
+					_dummy: Default::default(),
+				})
+				.map_err(MessageProofError::Custom)
+			},
+		)
+		.map_err(Into::into)
+	}
+
+	/// Source header chain adapter for the bridge with parachain, whose heads are tracked by the
+	/// `pallet-bridge-parachains` at This chain.
+	///
+	/// `BridgedParachainId` is the id of the bridged parachain at its relay chain.
+	#[derive(RuntimeDebug)]
+	pub struct ParachainSourceHeaderChain<
+		B,
+		BridgedHeader,
+		ThisRuntime,
+		ParachainsInstance,
+		BridgedParachainId,
+	>(PhantomData<(B, BridgedHeader, ThisRuntime, ParachainsInstance, BridgedParachainId)>);
+
+	impl<B, BridgedHeader, ThisRuntime, ParachainsInstance, BridgedParachainId>
+		SourceHeaderChain<BalanceOf<BridgedChain<B>>>
+		for ParachainSourceHeaderChain<
+			B,
+			BridgedHeader,
+			ThisRuntime,
+			ParachainsInstance,
+			BridgedParachainId,
+		>
+	where
+		B: MessageBridge,
+		BridgedHeader: HeaderT<Hash = ParaHash>,
+		ThisRuntime: pallet_bridge_parachains::Config<ParachainsInstance>,
+		ParachainsInstance: 'static,
+		BridgedParachainId: Get<ParaId>,
+		HashOf<BridgedChain<B>>: Parameter + Into<ParaHash> + 'static,
+	{
+		type Error = &'static str;
+		type MessagesProof = FromBridgedChainMessagesProof<HashOf<BridgedChain<B>>>;
+
+		fn verify_messages_proof(
+			proof: Self::MessagesProof,
+			messages_count: u32,
+		) -> Result<ProvedMessages<Message<BalanceOf<BridgedChain<B>>>>, Self::Error> {
+			verify_messages_proof_from_parachain::<B, BridgedHeader, ThisRuntime, ParachainsInstance>(
+				BridgedParachainId::get(),
+				proof,
+				messages_count,
+			)
+		}
+	}
+
 	#[derive(Debug, PartialEq)]
 	pub(crate) enum MessageProofError {
 		Empty,
@@ -868,6 +1150,7 @@ pub mod xcm_copy {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::mock::{run_test, BridgedParachainHeader, BridgedParachainId, TestRuntime};
 	use codec::{Decode, Encode};
 	use frame_support::weights::Weight;
 	use std::ops::RangeInclusive;
@@ -888,6 +1171,7 @@ mod tests {
 
 	impl MessageBridge for OnThisChainBridge {
 		const RELAYER_FEE_PERCENT: u32 = 10;
+		const CONGESTION_FEE_PERCENT: u32 = 5;
 		const THIS_CHAIN_ID: ChainId = *b"this";
 		const BRIDGED_CHAIN_ID: ChainId = *b"brdg";
 		const BRIDGED_MESSAGES_PALLET_NAME: &'static str = "";
@@ -913,6 +1197,7 @@ mod tests {
 
 	impl MessageBridge for OnBridgedChainBridge {
 		const RELAYER_FEE_PERCENT: u32 = 20;
+		const CONGESTION_FEE_PERCENT: u32 = 5;
 		const THIS_CHAIN_ID: ChainId = *b"brdg";
 		const BRIDGED_CHAIN_ID: ChainId = *b"this";
 		const BRIDGED_MESSAGES_PALLET_NAME: &'static str = "";
@@ -1211,6 +1496,55 @@ mod tests {
 		.is_ok(),);
 	}
 
+	#[test]
+	fn message_fee_grows_with_outbound_lane_congestion() {
+		const EXPECTED_MINIMAL_FEE: u32 = 2860;
+		// 10 pending messages * 5% = 50% of the minimal fee
+		const EXPECTED_CONGESTED_LANE_FEE: u32 = EXPECTED_MINIMAL_FEE + EXPECTED_MINIMAL_FEE / 2;
+
+		assert_eq!(
+			source::apply_congestion_fee::<OnThisChainBridge>(
+				ThisChainBalance(EXPECTED_MINIMAL_FEE),
+				OnThisChainBridge::CONGESTION_FEE_PERCENT,
+				0,
+			),
+			Ok(ThisChainBalance(EXPECTED_MINIMAL_FEE)),
+		);
+		assert_eq!(
+			source::apply_congestion_fee::<OnThisChainBridge>(
+				ThisChainBalance(EXPECTED_MINIMAL_FEE),
+				OnThisChainBridge::CONGESTION_FEE_PERCENT,
+				10,
+			),
+			Ok(ThisChainBalance(EXPECTED_CONGESTED_LANE_FEE)),
+		);
+
+		// fee that is enough for the empty lane, is not enough for the congested lane
+		let congested_lane_outbound_data = OutboundLaneData {
+			latest_received_nonce: 100,
+			latest_generated_nonce: 110,
+			..Default::default()
+		};
+		assert_eq!(
+			source::FromThisChainMessageVerifier::<OnThisChainBridge>::verify_message(
+				&ThisChainOrigin(Ok(frame_system::RawOrigin::Root)),
+				&ThisChainBalance(EXPECTED_MINIMAL_FEE),
+				TEST_LANE_ID,
+				&congested_lane_outbound_data,
+				&regular_outbound_message_payload(),
+			),
+			Err(source::TOO_LOW_FEE)
+		);
+		assert!(source::FromThisChainMessageVerifier::<OnThisChainBridge>::verify_message(
+			&ThisChainOrigin(Ok(frame_system::RawOrigin::Root)),
+			&ThisChainBalance(EXPECTED_CONGESTED_LANE_FEE),
+			TEST_LANE_ID,
+			&congested_lane_outbound_data,
+			&regular_outbound_message_payload(),
+		)
+		.is_ok());
+	}
+
 	#[test]
 	fn message_is_rejected_when_sent_using_disabled_lane() {
 		assert_eq!(
@@ -1495,7 +1829,11 @@ mod tests {
 					}),
 					messages: vec![Message {
 						key: MessageKey { lane_id: Default::default(), nonce: 1 },
-						data: MessageData { payload: 1u64.encode(), fee: BridgedChainBalance(0) },
+						data: MessageData {
+							payload: 1u64.encode(),
+							fee: BridgedChainBalance(0),
+							expires_at: None,
+						},
 					}],
 				},
 			)]
@@ -1572,4 +1910,469 @@ mod tests {
 
 		assert!(regular_fee < overrided_fee);
 	}
+
+	/// Bridge that is deployed on ThisChain and allows sending/receiving messages to/from
+	/// BridgedParachain;
+	#[derive(Debug, PartialEq, Eq)]
+	struct OnThisChainParachainBridge;
+
+	impl MessageBridge for OnThisChainParachainBridge {
+		const RELAYER_FEE_PERCENT: u32 = 10;
+		const CONGESTION_FEE_PERCENT: u32 = 5;
+		const THIS_CHAIN_ID: ChainId = *b"this";
+		const BRIDGED_CHAIN_ID: ChainId = *b"brpa";
+		const BRIDGED_MESSAGES_PALLET_NAME: &'static str = "BridgedMessages";
+
+		type ThisChain = ThisChain;
+		type BridgedChain = BridgedParachain;
+
+		fn bridged_balance_to_this_balance(
+			_bridged_balance: BridgedChainBalance,
+			_bridged_to_this_conversion_rate_override: Option<FixedU128>,
+		) -> ThisChainBalance {
+			unreachable!()
+		}
+	}
+
+	struct BridgedParachain;
+
+	impl ChainWithMessages for BridgedParachain {
+		type Hash = ParaHash;
+		type AccountId = BridgedChainAccountId;
+		type Signer = BridgedChainSigner;
+		type Signature = BridgedChainSignature;
+		type Weight = frame_support::weights::Weight;
+		type Balance = BridgedChainBalance;
+	}
+
+	impl BridgedChainWithMessages for BridgedParachain {
+		fn maximal_extrinsic_size() -> u32 {
+			<BridgedChain as BridgedChainWithMessages>::maximal_extrinsic_size()
+		}
+
+		fn verify_dispatch_weight(message_payload: &[u8]) -> bool {
+			<BridgedChain as BridgedChainWithMessages>::verify_dispatch_weight(message_payload)
+		}
+
+		fn estimate_delivery_transaction(
+			message_payload: &[u8],
+			include_pay_dispatch_fee_cost: bool,
+			message_dispatch_weight: WeightOf<Self>,
+		) -> MessageTransaction<WeightOf<Self>> {
+			<BridgedChain as BridgedChainWithMessages>::estimate_delivery_transaction(
+				message_payload,
+				include_pay_dispatch_fee_cost,
+				message_dispatch_weight,
+			)
+		}
+
+		fn transaction_payment(transaction: MessageTransaction<WeightOf<Self>>) -> BalanceOf<Self> {
+			<BridgedChain as BridgedChainWithMessages>::transaction_payment(transaction)
+		}
+	}
+
+	frame_support::parameter_types! {
+		const UnknownParachainId: ParaId = ParaId(42);
+	}
+
+	type TestParachainTargetHeaderChain<ParachainId> = source::ParachainTargetHeaderChain<
+		OnThisChainParachainBridge,
+		BridgedParachainHeader,
+		TestRuntime,
+		(),
+		ParachainId,
+	>;
+	type TestParachainSourceHeaderChain<ParachainId> = target::ParachainSourceHeaderChain<
+		OnThisChainParachainBridge,
+		BridgedParachainHeader,
+		TestRuntime,
+		(),
+		ParachainId,
+	>;
+
+	/// Prepare storage proof of given key-value pairs, generated at the bridged parachain header.
+	fn prepare_parachain_storage_proof(
+		entries: Vec<(Vec<u8>, Vec<u8>)>,
+	) -> (ParaHash, RawStorageProof) {
+		use sp_runtime::traits::BlakeTwo256;
+		use sp_trie::{
+			record_all_keys, trie_types::TrieDBMutV1, LayoutV1, MemoryDB, Recorder, TrieMut,
+		};
+
+		let mut root = Default::default();
+		let mut mdb = MemoryDB::default();
+		{
+			let mut trie = TrieDBMutV1::<BlakeTwo256>::new(&mut mdb, &mut root);
+			for (key, value) in entries {
+				trie.insert(&key, &value).expect("TrieMut::insert should not fail in tests");
+			}
+		}
+
+		let mut proof_recorder = Recorder::<ParaHash>::new();
+		record_all_keys::<LayoutV1<BlakeTwo256>, _>(&mdb, &root, &mut proof_recorder)
+			.expect("record_all_keys should not fail in tests");
+		let storage_proof = proof_recorder.drain().into_iter().map(|n| n.data.to_vec()).collect();
+
+		(root, storage_proof)
+	}
+
+	/// Insert header of given parachain into the parachains pallet storage and return its hash.
+	fn insert_parachain_header(parachain: ParaId, state_root: ParaHash) -> ParaHash {
+		let header = BridgedParachainHeader::new(
+			0,
+			Default::default(),
+			state_root,
+			Default::default(),
+			Default::default(),
+		);
+		let hash = header.hash();
+		pallet_bridge_parachains::ImportedParaHeaders::<TestRuntime>::insert(
+			parachain,
+			hash,
+			pallet_bridge_parachains::ParaStoredHeaderData { number: 0, hash, state_root },
+		);
+		hash
+	}
+
+	fn parachain_inbound_lane_data() -> InboundLaneData<ThisChainAccountId> {
+		InboundLaneData { relayers: Default::default(), last_confirmed_nonce: 10 }
+	}
+
+	/// Prepare messages delivery proof, generated at the bridged parachain header. The header
+	/// is not inserted into the parachains pallet storage.
+	fn parachain_messages_delivery_proof(
+	) -> (ParaHash, source::FromBridgedChainMessagesDeliveryProof<ParaHash>) {
+		parachain_messages_delivery_proof_with_expired_messages(None)
+	}
+
+	/// Prepare messages delivery proof with given expired messages, generated at the bridged
+	/// parachain header. The header is not inserted into the parachains pallet storage.
+	fn parachain_messages_delivery_proof_with_expired_messages(
+		expired_messages: Option<ExpiredMessages>,
+	) -> (ParaHash, source::FromBridgedChainMessagesDeliveryProof<ParaHash>) {
+		let mut entries = vec![(
+			bp_messages::storage_keys::inbound_lane_data_key(
+				OnThisChainParachainBridge::BRIDGED_MESSAGES_PALLET_NAME,
+				TEST_LANE_ID,
+			)
+			.0,
+			parachain_inbound_lane_data().encode(),
+		)];
+		if let Some(expired_messages) = expired_messages {
+			entries.push((
+				bp_messages::storage_keys::expired_inbound_messages_key(
+					OnThisChainParachainBridge::BRIDGED_MESSAGES_PALLET_NAME,
+					TEST_LANE_ID,
+				)
+				.0,
+				expired_messages.encode(),
+			));
+		}
+		let (state_root, storage_proof) = prepare_parachain_storage_proof(entries);
+		let bridged_header_hash = BridgedParachainHeader::new(
+			0,
+			Default::default(),
+			state_root,
+			Default::default(),
+			Default::default(),
+		)
+		.hash();
+		(
+			state_root,
+			source::FromBridgedChainMessagesDeliveryProof {
+				bridged_header_hash,
+				storage_proof,
+				lane: *TEST_LANE_ID,
+			},
+		)
+	}
+
+	fn parachain_message_data() -> MessageData<BridgedChainBalance> {
+		MessageData { payload: 1u64.encode(), fee: BridgedChainBalance(0), expires_at: None }
+	}
+
+	/// Prepare proof of the single message, generated at the bridged parachain header. The
+	/// header is not inserted into the parachains pallet storage.
+	fn parachain_messages_proof() -> (ParaHash, target::FromBridgedChainMessagesProof<ParaHash>) {
+		let (state_root, storage_proof) = prepare_parachain_storage_proof(vec![(
+			bp_messages::storage_keys::message_key(
+				OnThisChainParachainBridge::BRIDGED_MESSAGES_PALLET_NAME,
+				TEST_LANE_ID,
+				1,
+			)
+			.0,
+			parachain_message_data().encode(),
+		)]);
+		let bridged_header_hash = BridgedParachainHeader::new(
+			0,
+			Default::default(),
+			state_root,
+			Default::default(),
+			Default::default(),
+		)
+		.hash();
+		(
+			state_root,
+			target::FromBridgedChainMessagesProof {
+				bridged_header_hash,
+				storage_proof,
+				lane: *TEST_LANE_ID,
+				nonces_start: 1,
+				nonces_end: 1,
+			},
+		)
+	}
+
+	fn parachain_proved_messages() -> ProvedMessages<Message<BridgedChainBalance>> {
+		vec![(
+			*TEST_LANE_ID,
+			ProvedLaneMessages {
+				lane_state: None,
+				messages: vec![Message {
+					key: MessageKey { lane_id: *TEST_LANE_ID, nonce: 1 },
+					data: parachain_message_data(),
+				}],
+			},
+		)]
+		.into_iter()
+		.collect()
+	}
+
+	#[test]
+	fn parse_finalized_parachain_storage_proof_works() {
+		run_test(|| {
+			let (state_root, storage_proof) =
+				prepare_parachain_storage_proof(vec![(b"key".to_vec(), b"value".to_vec())]);
+			let head_hash = insert_parachain_header(BridgedParachainId::get(), state_root);
+
+			assert_eq!(
+				parse_finalized_parachain_storage_proof::<BridgedParachainHeader, TestRuntime, (), _>(
+					BridgedParachainId::get(),
+					head_hash,
+					storage_proof,
+					|storage| storage.read_value(b"key").ok().flatten()
+				),
+				Ok(Some(b"value".to_vec())),
+			);
+		});
+	}
+
+	#[test]
+	fn parse_finalized_parachain_storage_proof_rejects_unknown_parachain() {
+		run_test(|| {
+			let (state_root, storage_proof) =
+				prepare_parachain_storage_proof(vec![(b"key".to_vec(), b"value".to_vec())]);
+			let head_hash = insert_parachain_header(BridgedParachainId::get(), state_root);
+
+			assert_eq!(
+				parse_finalized_parachain_storage_proof::<BridgedParachainHeader, TestRuntime, (), _>(
+					UnknownParachainId::get(),
+					head_hash,
+					storage_proof,
+					|_| ()
+				),
+				Err("UnknownParaHead"),
+			);
+		});
+	}
+
+	#[test]
+	fn parse_finalized_parachain_storage_proof_rejects_missing_head() {
+		run_test(|| {
+			let (state_root, storage_proof) =
+				prepare_parachain_storage_proof(vec![(b"key".to_vec(), b"value".to_vec())]);
+			insert_parachain_header(BridgedParachainId::get(), state_root);
+
+			assert_eq!(
+				parse_finalized_parachain_storage_proof::<BridgedParachainHeader, TestRuntime, (), _>(
+					BridgedParachainId::get(),
+					Default::default(),
+					storage_proof,
+					|_| ()
+				),
+				Err("UnknownParaHead"),
+			);
+		});
+	}
+
+	#[test]
+	fn parse_finalized_parachain_storage_proof_rejects_proof_with_wrong_root() {
+		run_test(|| {
+			let (state_root, _) =
+				prepare_parachain_storage_proof(vec![(b"key".to_vec(), b"value".to_vec())]);
+			let (_, storage_proof) =
+				prepare_parachain_storage_proof(vec![(b"key".to_vec(), b"other".to_vec())]);
+			let head_hash = insert_parachain_header(BridgedParachainId::get(), state_root);
+
+			assert_eq!(
+				parse_finalized_parachain_storage_proof::<BridgedParachainHeader, TestRuntime, (), _>(
+					BridgedParachainId::get(),
+					head_hash,
+					storage_proof,
+					|_| ()
+				),
+				Err("StorageRootMismatch"),
+			);
+		});
+	}
+
+	#[test]
+	fn messages_delivery_proof_from_parachain_is_accepted() {
+		run_test(|| {
+			let (state_root, proof) = parachain_messages_delivery_proof();
+			insert_parachain_header(BridgedParachainId::get(), state_root);
+
+			assert_eq!(
+				source::verify_messages_delivery_proof_from_parachain::<
+					OnThisChainParachainBridge,
+					BridgedParachainHeader,
+					TestRuntime,
+					(),
+				>(BridgedParachainId::get(), proof.clone()),
+				Ok((*TEST_LANE_ID, parachain_inbound_lane_data(), ExpiredMessages::new())),
+			);
+			assert_eq!(
+				TestParachainTargetHeaderChain::<BridgedParachainId>::verify_messages_delivery_proof(
+					proof
+				),
+				Ok((*TEST_LANE_ID, parachain_inbound_lane_data(), ExpiredMessages::new())),
+			);
+		});
+	}
+
+	#[test]
+	fn messages_delivery_proof_from_parachain_with_expired_messages_is_accepted() {
+		run_test(|| {
+			let expired_messages: ExpiredMessages = vec![5, 7].into_iter().collect();
+			let (state_root, proof) = parachain_messages_delivery_proof_with_expired_messages(
+				Some(expired_messages.clone()),
+			);
+			insert_parachain_header(BridgedParachainId::get(), state_root);
+
+			assert_eq!(
+				TestParachainTargetHeaderChain::<BridgedParachainId>::verify_messages_delivery_proof(
+					proof
+				),
+				Ok((*TEST_LANE_ID, parachain_inbound_lane_data(), expired_messages)),
+			);
+		});
+	}
+
+	#[test]
+	fn messages_delivery_proof_from_unknown_parachain_is_rejected() {
+		run_test(|| {
+			let (state_root, proof) = parachain_messages_delivery_proof();
+			insert_parachain_header(BridgedParachainId::get(), state_root);
+
+			assert_eq!(
+				source::verify_messages_delivery_proof_from_parachain::<
+					OnThisChainParachainBridge,
+					BridgedParachainHeader,
+					TestRuntime,
+					(),
+				>(UnknownParachainId::get(), proof.clone()),
+				Err("UnknownParaHead"),
+			);
+			assert_eq!(
+				TestParachainTargetHeaderChain::<UnknownParachainId>::verify_messages_delivery_proof(
+					proof
+				),
+				Err("UnknownParaHead"),
+			);
+		});
+	}
+
+	#[test]
+	fn messages_delivery_proof_from_parachain_is_rejected_if_head_is_missing() {
+		run_test(|| {
+			let (_, proof) = parachain_messages_delivery_proof();
+
+			assert_eq!(
+				source::verify_messages_delivery_proof_from_parachain::<
+					OnThisChainParachainBridge,
+					BridgedParachainHeader,
+					TestRuntime,
+					(),
+				>(BridgedParachainId::get(), proof.clone()),
+				Err("UnknownParaHead"),
+			);
+			assert_eq!(
+				TestParachainTargetHeaderChain::<BridgedParachainId>::verify_messages_delivery_proof(
+					proof
+				),
+				Err("UnknownParaHead"),
+			);
+		});
+	}
+
+	#[test]
+	fn messages_proof_from_parachain_is_accepted() {
+		run_test(|| {
+			let (state_root, proof) = parachain_messages_proof();
+			insert_parachain_header(BridgedParachainId::get(), state_root);
+
+			assert_eq!(
+				target::verify_messages_proof_from_parachain::<
+					OnThisChainParachainBridge,
+					BridgedParachainHeader,
+					TestRuntime,
+					(),
+				>(BridgedParachainId::get(), proof.clone(), 1),
+				Ok(parachain_proved_messages()),
+			);
+			assert_eq!(
+				TestParachainSourceHeaderChain::<BridgedParachainId>::verify_messages_proof(
+					proof, 1
+				),
+				Ok(parachain_proved_messages()),
+			);
+		});
+	}
+
+	#[test]
+	fn messages_proof_from_unknown_parachain_is_rejected() {
+		run_test(|| {
+			let (state_root, proof) = parachain_messages_proof();
+			insert_parachain_header(BridgedParachainId::get(), state_root);
+
+			assert_eq!(
+				target::verify_messages_proof_from_parachain::<
+					OnThisChainParachainBridge,
+					BridgedParachainHeader,
+					TestRuntime,
+					(),
+				>(UnknownParachainId::get(), proof.clone(), 1),
+				Err("UnknownParaHead"),
+			);
+			assert_eq!(
+				TestParachainSourceHeaderChain::<UnknownParachainId>::verify_messages_proof(
+					proof, 1
+				),
+				Err("UnknownParaHead"),
+			);
+		});
+	}
+
+	#[test]
+	fn messages_proof_from_parachain_is_rejected_if_head_is_missing() {
+		run_test(|| {
+			let (_, proof) = parachain_messages_proof();
+
+			assert_eq!(
+				target::verify_messages_proof_from_parachain::<
+					OnThisChainParachainBridge,
+					BridgedParachainHeader,
+					TestRuntime,
+					(),
+				>(BridgedParachainId::get(), proof.clone(), 1),
+				Err("UnknownParaHead"),
+			);
+			assert_eq!(
+				TestParachainSourceHeaderChain::<BridgedParachainId>::verify_messages_proof(
+					proof, 1
+				),
+				Err("UnknownParaHead"),
+			);
+		});
+	}
 }
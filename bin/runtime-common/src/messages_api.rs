@@ -23,7 +23,22 @@ use codec::Decode;
 use frame_support::weights::Weight;
 use sp_std::vec::Vec;
 
+/// Returns number of messages that are queued (not yet delivered) at the outbound lane.
+pub fn outbound_lane_pending_messages<Runtime, MessagesPalletInstance>(lane: LaneId) -> MessageNonce
+where
+	Runtime: pallet_bridge_messages::Config<MessagesPalletInstance>,
+	MessagesPalletInstance: 'static,
+{
+	let lane_data =
+		pallet_bridge_messages::OutboundLanes::<Runtime, MessagesPalletInstance>::get(lane);
+	lane_data.latest_generated_nonce.saturating_sub(lane_data.latest_received_nonce)
+}
+
 /// Implementation of the `To*OutboundLaneApi::message_details`.
+///
+/// The message deadline is reported as is. Only the target chain knows whether the message has
+/// expired (according to its view of this chain), so it is up to relayer to decide whether it
+/// needs to pay for the message dispatch.
 pub fn outbound_message_details<Runtime, MessagesPalletInstance, BridgeConfig, XcmWeigher>(
 	lane: LaneId,
 	begin: MessageNonce,
@@ -50,6 +65,7 @@ where
 				size: message_data.payload.len() as _,
 				delivery_and_dispatch_fee: message_data.fee,
 				dispatch_fee_payment: bp_runtime::messages::DispatchFeePayment::AtTargetChain,
+				expires_at: message_data.expires_at,
 			})
 		})
 		.collect()
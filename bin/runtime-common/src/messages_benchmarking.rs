@@ -167,6 +167,7 @@ where
 			let message_data = MessageData {
 				fee: BalanceOf::<BridgedChain<B>>::from(0),
 				payload: message_payload.clone(),
+				expires_at: None,
 			};
 			let storage_key = storage_keys::message_key(
 				B::BRIDGED_MESSAGES_PALLET_NAME,
@@ -228,7 +229,11 @@ where
 }
 
 /// Populate trie with dummy keys+values until trie has at least given size.
-fn grow_trie<H: Hasher>(mut root: H::Out, mdb: &mut MemoryDB<H>, trie_size: ProofSize) -> H::Out {
+pub(crate) fn grow_trie<H: Hasher>(
+	mut root: H::Out,
+	mdb: &mut MemoryDB<H>,
+	trie_size: ProofSize,
+) -> H::Out {
 	let (iterations, leaf_size, minimal_trie_size) = match trie_size {
 		ProofSize::Minimal(_) => return root,
 		ProofSize::HasLargeLeaf(size) => (1, size, size),
@@ -0,0 +1,150 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A mock runtime with bridge GRANDPA and parachains pallets, used to test helpers that are
+//! verifying proofs of bridged parachains.
+
+use bp_polkadot_core::parachains::ParaId;
+use bp_runtime::Chain;
+use frame_support::{
+	construct_runtime, parameter_types,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_runtime::{
+	testing::{Header, H256},
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+pub type AccountId = u64;
+pub type TestNumber = u64;
+
+pub type BridgedRelayHeader =
+	sp_runtime::generic::Header<bp_polkadot_core::BlockNumber, bp_polkadot_core::Hasher>;
+pub type BridgedParachainHeader =
+	sp_runtime::generic::Header<bp_polkadot_core::BlockNumber, BlakeTwo256>;
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+
+construct_runtime! {
+	pub enum TestRuntime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		BridgeGrandpa: pallet_bridge_grandpa::{Pallet, Event<T>},
+		BridgeParachains: pallet_bridge_parachains::{Pallet, Event<T>},
+	}
+}
+
+parameter_types! {
+	pub const BlockHashCount: TestNumber = 250;
+}
+
+impl frame_system::Config for TestRuntime {
+	type Origin = Origin;
+	type Index = u64;
+	type Call = Call;
+	type BlockNumber = TestNumber;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type SystemWeightInfo = ();
+	type DbWeight = RocksDbWeight;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+	pub const MaxRequests: u32 = 2;
+	pub const FreeHeadersInterval: Option<u32> = None;
+	pub const HeadersToKeep: u32 = 5;
+	pub const HeadersRetention: bp_header_chain::HeadersRetentionPolicy =
+		bp_header_chain::HeadersRetentionPolicy::Count;
+	pub const AuthoritySetsToKeep: u32 = 3;
+	pub const HistoricalHeadersToKeep: u32 = 2;
+}
+
+impl pallet_bridge_grandpa::Config for TestRuntime {
+	type Event = Event;
+	type BridgedChain = BridgedRelayChain;
+	type MaxRequests = MaxRequests;
+	type MaxBatchSize = MaxRequests;
+	type FreeHeadersInterval = FreeHeadersInterval;
+	type MaxRewardsPerPeriod = frame_support::traits::ConstU32<0>;
+	type RewardsPeriod = frame_support::traits::ConstU64<1>;
+	type RelayerRewardPayment = ();
+	type HeadersToKeep = HeadersToKeep;
+	type HeadersRetention = HeadersRetention;
+	type AuthoritySetsToKeep = AuthoritySetsToKeep;
+	type HistoricalHeadersToKeep = HistoricalHeadersToKeep;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const HeadsToKeep: u32 = 4;
+	pub const BridgedParachainId: ParaId = ParaId(2000);
+}
+
+impl pallet_bridge_parachains::Config for TestRuntime {
+	type Event = Event;
+	type WeightInfo = ();
+	type BridgesGrandpaPalletInstance = ();
+	type HeadsToKeep = HeadsToKeep;
+	type HeadDecoder =
+		pallet_bridge_parachains::SingleParaHeadDecoder<BridgedParachainHeader, BridgedParachainId>;
+}
+
+#[derive(Debug)]
+pub struct BridgedRelayChain;
+
+impl Chain for BridgedRelayChain {
+	type BlockNumber = bp_polkadot_core::BlockNumber;
+	type Hash = bp_polkadot_core::Hash;
+	type Hasher = bp_polkadot_core::Hasher;
+	type Header = BridgedRelayHeader;
+
+	type AccountId = AccountId;
+	type Balance = u32;
+	type Index = u32;
+	type Signature = sp_runtime::testing::TestSignature;
+
+	fn max_extrinsic_size() -> u32 {
+		unreachable!()
+	}
+
+	fn max_extrinsic_weight() -> Weight {
+		unreachable!()
+	}
+}
+
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	sp_io::TestExternalities::new(Default::default()).execute_with(test)
+}
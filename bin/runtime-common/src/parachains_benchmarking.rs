@@ -0,0 +1,98 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Everything required to run benchmarks of parachains finality module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::messages_benchmarking::grow_trie;
+
+use bp_polkadot_core::parachains::{ParaHash, ParaHead, ParaId, ParachainHeadsProof};
+use bp_runtime::StorageProofSize;
+use codec::Encode;
+use pallet_bridge_parachains::{
+	storage_keys::parachain_head_key, ParachainOperatingMode, RelayBlockHash, RelayBlockHasher,
+	RelayBlockNumber, TrackedParachains,
+};
+use sp_runtime::traits::{Header as HeaderT, Zero};
+use sp_std::prelude::*;
+use sp_trie::{record_all_keys, trie_types::TrieDBMutV1, LayoutV1, MemoryDB, Recorder, TrieMut};
+
+/// Prepare proof of parachain heads for the `submit_parachain_heads` call.
+///
+/// In addition to returning valid parachain heads proof, environment is prepared to verify this
+/// proof: the relay chain header with the proof state root is inserted into the GRANDPA pallet
+/// and all given parachains are added to the set of tracked parachains.
+pub fn prepare_parachain_heads_proof<R, PI>(
+	parachains: &[ParaId],
+	parachain_head_size: u32,
+	size: StorageProofSize,
+) -> (RelayBlockHash, ParachainHeadsProof, Vec<(ParaId, ParaHash)>)
+where
+	R: pallet_bridge_parachains::Config<PI>
+		+ pallet_bridge_grandpa::Config<R::BridgesGrandpaPalletInstance>,
+	PI: 'static,
+	<R as pallet_bridge_grandpa::Config<R::BridgesGrandpaPalletInstance>>::BridgedChain:
+		bp_runtime::Chain<BlockNumber = RelayBlockNumber, Hash = RelayBlockHash>,
+{
+	// zero-filled head is decoded as the default Substrate header (trailing bytes are ignored), so
+	// the decoded header data is also written to the storage when benchmarks are running
+	let parachain_head = ParaHead(vec![0u8; parachain_head_size as usize]);
+
+	// insert all heads to the trie
+	let mut parachain_heads = Vec::with_capacity(parachains.len());
+	let mut state_root = Default::default();
+	let mut mdb = MemoryDB::default();
+	{
+		let mut trie = TrieDBMutV1::<RelayBlockHasher>::new(&mut mdb, &mut state_root);
+
+		// insert parachain heads
+		for parachain in parachains {
+			let storage_key = parachain_head_key(*parachain);
+			trie.insert(&storage_key.0, &parachain_head.encode())
+				.map_err(|_| "TrieMut::insert has failed")
+				.expect("TrieMut::insert should not fail in benchmarks");
+			parachain_heads.push((*parachain, parachain_head.hash()));
+
+			// heads of untracked parachains are rejected by the pallet
+			TrackedParachains::<R, PI>::insert(parachain, ParachainOperatingMode::Normal);
+		}
+	}
+	state_root = grow_trie(state_root, &mut mdb, size);
+
+	// generate heads storage proof
+	let mut proof_recorder = Recorder::<RelayBlockHash>::new();
+	record_all_keys::<LayoutV1<RelayBlockHasher>, _>(&mdb, &state_root, &mut proof_recorder)
+		.map_err(|_| "record_all_keys has failed")
+		.expect("record_all_keys should not fail in benchmarks");
+	let proof = proof_recorder.drain().into_iter().map(|n| n.data.to_vec()).collect();
+
+	// finally insert header with given state root to our storage
+	let relay_block =
+		pallet_bridge_grandpa::BridgedHeader::<R, R::BridgesGrandpaPalletInstance>::new(
+			Zero::zero(),
+			Default::default(),
+			state_root,
+			Default::default(),
+			Default::default(),
+		);
+	let relay_block_hash = relay_block.hash();
+	pallet_bridge_grandpa::initialize_for_benchmarks::<R, R::BridgesGrandpaPalletInstance>(
+		relay_block,
+	);
+
+	(relay_block_hash, proof, parachain_heads)
+}
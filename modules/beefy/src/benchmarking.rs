@@ -0,0 +1,132 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the BEEFY Pallet.
+//!
+//! The only dispatchable that needs to be benchmarked is `submit_commitment`. There are two main
+//! factors which affect commitment verification:
+//!
+//! 1. The number of validators in the validator set. Every validator is a leaf of the validator
+//!    set merkle tree and every validator signature is verified;
+//! 2. The number of items in the MMR leaf proof.
+//!
+//! The commitment that is imported in benchmarks is signed by all validators of the next validator
+//! set and it prunes the oldest imported commitment. This is the worst case in terms of storage
+//! accesses.
+
+use crate::*;
+
+use bp_beefy::{BeefyMmrProof, MmrLeafVersion};
+use bp_test_utils::{
+	beefy_validator_ids, beefy_validator_pairs, make_beefy_commitment, sign_beefy_commitment,
+	TEST_BEEFY_VALIDATOR_SET_ID,
+};
+use codec::{Decode, Encode};
+use frame_benchmarking::{benchmarks_instance_pallet, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Hash, One, TrailingZeroInput, Zero};
+use sp_std::vec::Vec;
+
+// The maximal number of items in the MMR leaf proof, used in benchmarks. The proof of the latest
+// leaf of the MMR with single peak has the same number of items as the height of this peak, so
+// the number of leaves in such MMR must fit into `u64`.
+const MAX_MMR_PROOF_SIZE: u32 = 63;
+
+/// Prepare finality proof to submit using `submit_commitment`.
+///
+/// The proof is signed by `validators` validators of the next validator set and has
+/// `proof_size` items in the MMR leaf proof.
+fn prepare_benchmark_data<T: Config<I>, I: 'static>(
+	validators: u32,
+	proof_size: u32,
+) -> bp_beefy::BeefyFinalityProofOf<BridgedChain<T, I>> {
+	let validator_pairs = beefy_validator_pairs(validators);
+	let validator_set = beefy_validator_ids(&validator_pairs);
+	let current_set_id = TEST_BEEFY_VALIDATOR_SET_ID;
+	let next_set_id = current_set_id + 1;
+
+	initialize_bridge::<T, I>(bp_beefy::InitializationData {
+		is_halted: false,
+		best_block_number: Zero::zero(),
+		current_authority_set: bp_beefy::beefy_authority_set_info::<BridgedChain<T, I>>(
+			current_set_id,
+			&validator_set,
+		),
+		next_authority_set: bp_beefy::beefy_authority_set_info::<BridgedChain<T, I>>(
+			next_set_id,
+			&validator_set,
+		),
+	});
+	// the commitment that occupies the ring buffer slot is pruned when the new one is imported
+	<ImportedBlockNumbers<T, I>>::insert(0, BridgedBlockNumber::<T, I>::zero());
+
+	// the leaf is the latest (and the rightmost) leaf of the MMR with single peak, so every proof
+	// item is the left sibling of the node on the path from the leaf to the peak
+	let mmr_leaf = BridgedBeefyMmrLeaf::<T, I> {
+		version: MmrLeafVersion::new(0, 0),
+		parent_number_and_hash: (Zero::zero(), Default::default()),
+		beefy_next_authority_set: bp_beefy::beefy_authority_set_info::<BridgedChain<T, I>>(
+			next_set_id + 1,
+			&validator_set,
+		),
+		leaf_extra: Decode::decode(&mut TrailingZeroInput::zeroes())
+			.expect("infinite zero input is enough to decode any leaf extra; qed"),
+	};
+	let items = (0..proof_size)
+		.map(|index| BridgedMmrHashing::<T, I>::hash(&index.encode()))
+		.collect::<Vec<_>>();
+	let mut mmr_root = BridgedMmrHashing::<T, I>::hash(&mmr_leaf.encode());
+	for item in &items {
+		mmr_root = BridgedMmrHashing::<T, I>::hash(&[item.as_ref(), mmr_root.as_ref()].concat());
+	}
+	let leaf_count = 1_u64 << proof_size;
+	let mmr_proof = BeefyMmrProof { leaf_index: leaf_count - 1, leaf_count, items };
+
+	let commitment = sign_beefy_commitment(
+		make_beefy_commitment(One::one(), next_set_id, mmr_root),
+		&validator_pairs,
+		validator_pairs.len(),
+	);
+
+	bp_beefy::BeefyFinalityProofOf::<BridgedChain<T, I>> {
+		commitment,
+		validator_set,
+		mmr_leaf,
+		mmr_proof,
+	}
+}
+
+benchmarks_instance_pallet! {
+	submit_commitment {
+		let v in 1..T::MaxValidators::get();
+		let p in 1..T::MaxMmrProofSize::get().min(MAX_MMR_PROOF_SIZE);
+		let caller: T::AccountId = whitelisted_caller();
+		let proof = prepare_benchmark_data::<T, I>(v, p);
+	}: submit_commitment(
+		RawOrigin::Signed(caller),
+		proof.commitment,
+		proof.validator_set,
+		Box::new(proof.mmr_leaf),
+		proof.mmr_proof
+	)
+	verify {
+		let expected_number: BridgedBlockNumber<T, I> = One::one();
+
+		assert_eq!(<BestBlockNumber<T, I>>::get(), Some(expected_number));
+		assert!(<ImportedCommitments<T, I>>::contains_key(expected_number));
+		assert_eq!(<CurrentAuthoritySetInfo<T, I>>::get().id, 1);
+	}
+}
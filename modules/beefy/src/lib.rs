@@ -0,0 +1,935 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Substrate BEEFY Pallet
+//!
+//! This pallet is an on-chain BEEFY light client for Substrate based chains that are using BEEFY
+//! finality together with the MMR pallet.
+//!
+//! Verifying GRANDPA justifications may be too expensive for some chains. BEEFY validators are
+//! signing commitments to the MMR root of the bridged chain, using ECDSA keys. Every MMR leaf
+//! contains the number and hash of its parent block and the merkle root of the next validator
+//! set. So instead of importing headers, the pallet imports signed commitments, verifying that:
+//!
+//! - the commitment is signed by more than 2/3 of validators of the validator set we know of;
+//!
+//! - the provided validator set matches the validator set merkle root, known to the pallet;
+//!
+//! - the provided MMR leaf is the latest leaf of the MMR with the committed root.
+//!
+//! The pallet is responsible for tracking BEEFY validator set hand-offs. The commitment may be
+//! signed either by the current, or by the next validator set, that is known to the pallet. In the
+//! latter case, the next set becomes the current one. Then the next validator set is updated using
+//! the latest MMR leaf of the imported commitment. So the pallet can't skip validator sets - at
+//! least one commitment, signed by every validator set must be imported.
+//!
+//! Imported MMR roots may then be used to verify MMR leaves (and hence block hashes) of any bridged
+//! chain block, that is older than the committed block.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// Runtime-generated enums
+#![allow(clippy::large_enum_variant)]
+
+use bp_beefy::{
+	BeefyAuthoritySetInfoOf, BeefyMmrLeafOf, BeefyMmrProofOf, BeefySignedCommitmentOf,
+	ChainWithBeefy, EcdsaValidatorId, ImportedCommitmentOf, InitializationDataOf, MmrHashOf,
+};
+use bp_runtime::{BlockNumberOf, HashOf};
+use frame_support::{ensure, fail};
+use frame_system::{ensure_signed, RawOrigin};
+use sp_runtime::traits::BadOrigin;
+use sp_std::{boxed::Box, vec::Vec};
+
+mod utils;
+
+#[cfg(test)]
+mod mock;
+
+/// Module, containing weights for this pallet.
+pub mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
+// Re-export in crate namespace for `construct_runtime!`
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// The target that will be used when publishing logs related to this pallet.
+const LOG_TARGET: &str = "runtime::bridge-beefy";
+
+/// The chain we are bridging to here.
+pub type BridgedChain<T, I> = <T as Config<I>>::BridgedChain;
+/// Block number of the bridged chain.
+pub type BridgedBlockNumber<T, I> = BlockNumberOf<BridgedChain<T, I>>;
+/// Block hash of the bridged chain.
+pub type BridgedBlockHash<T, I> = HashOf<BridgedChain<T, I>>;
+/// MMR hashing of the bridged chain.
+pub type BridgedMmrHashing<T, I> = <BridgedChain<T, I> as ChainWithBeefy>::MmrHashing;
+/// MMR hash of the bridged chain.
+pub type BridgedMmrHash<T, I> = MmrHashOf<BridgedChain<T, I>>;
+/// BEEFY commitment, signed by the bridged chain validators.
+pub type BridgedBeefySignedCommitment<T, I> = BeefySignedCommitmentOf<BridgedChain<T, I>>;
+/// Information about the BEEFY validator set of the bridged chain.
+pub type BridgedBeefyAuthoritySetInfo<T, I> = BeefyAuthoritySetInfoOf<BridgedChain<T, I>>;
+/// BEEFY MMR leaf of the bridged chain.
+pub type BridgedBeefyMmrLeaf<T, I> = BeefyMmrLeafOf<BridgedChain<T, I>>;
+/// MMR leaf proof of the bridged chain.
+pub type BridgedBeefyMmrProof<T, I> = BeefyMmrProofOf<BridgedChain<T, I>>;
+/// BEEFY commitment of the bridged chain, imported by the pallet.
+pub type BridgedImportedCommitment<T, I> = ImportedCommitmentOf<BridgedChain<T, I>>;
+/// Initialization data of the pallet.
+pub type InitializationData<T, I> = InitializationDataOf<BridgedChain<T, I>>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The chain we are bridging to here.
+		type BridgedChain: ChainWithBeefy;
+
+		/// The upper bound on the number of requests allowed by the pallet.
+		///
+		/// A request refers to an action which writes a commitment to storage.
+		///
+		/// Once this bound is reached the pallet will not allow any dispatchables to be called
+		/// until the request count has decreased.
+		#[pallet::constant]
+		type MaxRequests: Get<u32>;
+
+		/// Maximal number of imported commitments to keep in the storage.
+		///
+		/// The setting is there to prevent growing the on-chain state indefinitely. Note
+		/// the setting does not relate to block numbers - we will simply keep as much items
+		/// in the storage, so it doesn't guarantee any fixed timeframe for imported commitments.
+		#[pallet::constant]
+		type CommitmentsToKeep: Get<u32>;
+
+		/// Maximal number of validators in the BEEFY validator set of the bridged chain.
+		///
+		/// Commitments with larger validator sets or with more signatures are rejected before
+		/// any signature is verified.
+		#[pallet::constant]
+		type MaxValidators: Get<u32>;
+
+		/// Maximal number of items in the MMR leaf proof.
+		#[pallet::constant]
+		type MaxMmrProofSize: Get<u32>;
+
+		/// Weights gathered through benchmarking.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_initialize(_n: T::BlockNumber) -> frame_support::weights::Weight {
+			<RequestCount<T, I>>::mutate(|count| *count = count.saturating_sub(1));
+
+			(0_u64)
+				.saturating_add(T::DbWeight::get().reads(1))
+				.saturating_add(T::DbWeight::get().writes(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: T::BlockNumber) -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Verify BEEFY commitment, signed by the bridged chain validators, and import the MMR
+		/// root from its payload.
+		///
+		/// The `validator_set` must contain all validators of the set that has signed the
+		/// commitment. The `mmr_leaf` must be the latest leaf of the MMR at the committed block.
+		/// The next validator set from this leaf is used to track validator set hand-offs.
+		#[pallet::weight(T::WeightInfo::submit_commitment(
+			validator_set.len().try_into().unwrap_or(u32::MAX),
+			mmr_proof.items.len().try_into().unwrap_or(u32::MAX),
+		))]
+		pub fn submit_commitment(
+			origin: OriginFor<T>,
+			commitment: BridgedBeefySignedCommitment<T, I>,
+			validator_set: Vec<EcdsaValidatorId>,
+			mmr_leaf: Box<BridgedBeefyMmrLeaf<T, I>>,
+			mmr_proof: BridgedBeefyMmrProof<T, I>,
+		) -> DispatchResult {
+			ensure_operational::<T, I>()?;
+			let _ = ensure_signed(origin)?;
+
+			ensure!(Self::request_count() < T::MaxRequests::get(), <Error<T, I>>::TooManyRequests);
+
+			import_commitment::<T, I>(commitment, validator_set, *mmr_leaf, mmr_proof)?;
+			<RequestCount<T, I>>::mutate(|count| *count += 1);
+
+			Ok(())
+		}
+
+		/// Bootstrap the bridge pallet with an initial block number and validator sets from which
+		/// to sync.
+		///
+		/// This function is only allowed to be called from a trusted origin and writes to storage
+		/// with practically no checks in terms of the validity of the data. It is important that
+		/// you ensure that valid data is being passed in.
+		#[pallet::weight((T::DbWeight::get().reads_writes(2, 4), DispatchClass::Operational))]
+		pub fn initialize(
+			origin: OriginFor<T>,
+			init_data: super::InitializationData<T, I>,
+		) -> DispatchResult {
+			ensure_owner_or_root::<T, I>(origin)?;
+
+			let init_allowed = !<BestBlockNumber<T, I>>::exists();
+			ensure!(init_allowed, <Error<T, I>>::AlreadyInitialized);
+			initialize_bridge::<T, I>(init_data.clone());
+
+			log::info!(
+				target: LOG_TARGET,
+				"Pallet has been initialized with the following parameters: {:?}",
+				init_data
+			);
+
+			Ok(())
+		}
+
+		/// Change `PalletOwner`.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::DbWeight::get().reads_writes(1, 1), DispatchClass::Operational))]
+		pub fn set_owner(origin: OriginFor<T>, new_owner: Option<T::AccountId>) -> DispatchResult {
+			ensure_owner_or_root::<T, I>(origin)?;
+			match new_owner {
+				Some(new_owner) => {
+					PalletOwner::<T, I>::put(&new_owner);
+					log::info!(target: LOG_TARGET, "Setting pallet Owner to: {:?}", new_owner);
+				},
+				None => {
+					PalletOwner::<T, I>::kill();
+					log::info!(target: LOG_TARGET, "Removed Owner of pallet.");
+				},
+			}
+
+			Ok(())
+		}
+
+		/// Halt or resume all pallet operations.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::DbWeight::get().reads_writes(1, 1), DispatchClass::Operational))]
+		pub fn set_operational(origin: OriginFor<T>, operational: bool) -> DispatchResult {
+			ensure_owner_or_root::<T, I>(origin)?;
+			<IsHalted<T, I>>::put(!operational);
+
+			if operational {
+				log::info!(target: LOG_TARGET, "Resuming pallet operations.");
+			} else {
+				log::warn!(target: LOG_TARGET, "Stopping pallet operations.");
+			}
+
+			Ok(())
+		}
+	}
+
+	/// The current number of requests which have written to storage.
+	///
+	/// If the `RequestCount` hits `MaxRequests`, no more calls will be allowed to the pallet until
+	/// the request capacity is increased.
+	///
+	/// The `RequestCount` is decreased by one at the beginning of every block. This is to ensure
+	/// that the pallet can always make progress.
+	#[pallet::storage]
+	#[pallet::getter(fn request_count)]
+	pub(super) type RequestCount<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+	/// Number of the best block, finalized by the imported commitment.
+	///
+	/// It is missing from the storage until the pallet is initialized.
+	#[pallet::storage]
+	pub type BestBlockNumber<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BridgedBlockNumber<T, I>, OptionQuery>;
+
+	/// A ring buffer of numbers of blocks with imported commitments. Ordered by the insertion
+	/// time.
+	#[pallet::storage]
+	pub(super) type ImportedBlockNumbers<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, u32, BridgedBlockNumber<T, I>>;
+
+	/// Current ring buffer position.
+	#[pallet::storage]
+	pub(super) type ImportedBlockNumbersPointer<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u32, ValueQuery>;
+
+	/// Commitments which have been imported into the pallet, mapped by the committed block number.
+	#[pallet::storage]
+	pub type ImportedCommitments<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, BridgedBlockNumber<T, I>, BridgedImportedCommitment<T, I>>;
+
+	/// The current BEEFY validator set info.
+	#[pallet::storage]
+	pub(super) type CurrentAuthoritySetInfo<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BridgedBeefyAuthoritySetInfo<T, I>, ValueQuery>;
+
+	/// The next BEEFY validator set info.
+	#[pallet::storage]
+	pub(super) type NextAuthoritySetInfo<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BridgedBeefyAuthoritySetInfo<T, I>, ValueQuery>;
+
+	/// Optional pallet owner.
+	///
+	/// Pallet owner has a right to halt all pallet operations and then resume it. If it is
+	/// `None`, then there are no direct ways to halt/resume pallet operations, but other
+	/// runtime methods may still be used to do that (i.e. democracy::referendum to update halt
+	/// flag directly or call the `halt_operations`).
+	#[pallet::storage]
+	pub type PalletOwner<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// If true, all pallet transactions are failed immediately.
+	#[pallet::storage]
+	pub(super) type IsHalted<T: Config<I>, I: 'static = ()> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// There are too many requests for the current window to handle.
+		TooManyRequests,
+		/// The pallet is not yet initialized.
+		NotInitialized,
+		/// The pallet has already been initialized.
+		AlreadyInitialized,
+		/// All pallet operations are halted.
+		Halted,
+		/// The commitment is not newer than the best commitment known to the pallet.
+		OldCommitment,
+		/// The commitment is signed by the unknown validator set.
+		InvalidValidatorSetId,
+		/// The number of validators in the provided validator set is invalid.
+		InvalidValidatorSetLen,
+		/// The provided validator set doesn't match the validator set root known to the pallet.
+		InvalidValidatorSetRoot,
+		/// The number of signatures doesn't match the number of validators.
+		InvalidSignaturesLength,
+		/// The commitment contains invalid signature.
+		InvalidSignature,
+		/// The commitment is not signed by enough validators.
+		NotEnoughCorrectSignatures,
+		/// The commitment payload doesn't contain the MMR root.
+		MmrRootMissingFromCommitment,
+		/// The MMR leaf is not the latest leaf at the committed block.
+		InvalidMmrLeaf,
+		/// The MMR proof is invalid for given leaf and committed MMR root.
+		InvalidMmrProof,
+		/// The next validator set id from the MMR leaf doesn't follow the id of the validator set
+		/// that has signed the commitment.
+		InvalidNextAuthoritySetId,
+		/// The validator set is larger than `MaxValidators`.
+		TooManyValidators,
+		/// The commitment has more than `MaxValidators` signatures.
+		TooManySignatures,
+		/// The MMR proof has more than `MaxMmrProofSize` items.
+		TooLargeMmrProof,
+	}
+
+	/// Verify the signed commitment and import it.
+	pub(crate) fn import_commitment<T: Config<I>, I: 'static>(
+		commitment: BridgedBeefySignedCommitment<T, I>,
+		validator_set: Vec<EcdsaValidatorId>,
+		mmr_leaf: BridgedBeefyMmrLeaf<T, I>,
+		mmr_proof: BridgedBeefyMmrProof<T, I>,
+	) -> Result<(), sp_runtime::DispatchError> {
+		let block_number = commitment.commitment.block_number;
+		log::trace!(target: LOG_TARGET, "Going to try and import commitment of block {:?}", block_number);
+
+		// the call weight depends on these values, so we need to bound them before verification
+		let max_validators = T::MaxValidators::get() as usize;
+		ensure!(validator_set.len() <= max_validators, <Error<T, I>>::TooManyValidators);
+		ensure!(commitment.signatures.len() <= max_validators, <Error<T, I>>::TooManySignatures);
+		ensure!(
+			mmr_proof.items.len() <= T::MaxMmrProofSize::get() as usize,
+			<Error<T, I>>::TooLargeMmrProof
+		);
+
+		let best_block_number = match <BestBlockNumber<T, I>>::get() {
+			Some(best_block_number) => best_block_number,
+			None => {
+				log::error!(
+					target: LOG_TARGET,
+					"Cannot import commitment of block {:?} because pallet is not yet initialized",
+					block_number,
+				);
+				fail!(<Error<T, I>>::NotInitialized);
+			},
+		};
+		ensure!(best_block_number < block_number, <Error<T, I>>::OldCommitment);
+
+		// the commitment may be signed either by the current, or by the next validator set
+		let current_authority_set = <CurrentAuthoritySetInfo<T, I>>::get();
+		let next_authority_set = <NextAuthoritySetInfo<T, I>>::get();
+		let validator_set_id = commitment.commitment.validator_set_id;
+		let is_handoff = validator_set_id != current_authority_set.id;
+		let authority_set = if !is_handoff {
+			current_authority_set
+		} else if validator_set_id == next_authority_set.id {
+			next_authority_set
+		} else {
+			fail!(<Error<T, I>>::InvalidValidatorSetId);
+		};
+
+		utils::verify_validator_set::<T, I>(&authority_set, &validator_set)?;
+		utils::verify_commitment_signatures::<T, I>(&commitment, &validator_set)?;
+		let mmr_root = utils::extract_mmr_root::<T, I>(&commitment.commitment)?;
+		utils::verify_mmr_leaf::<T, I>(block_number, mmr_root, &mmr_leaf, mmr_proof)?;
+
+		let next_authority_set = mmr_leaf.beefy_next_authority_set;
+		ensure!(
+			next_authority_set.id == validator_set_id + 1,
+			<Error<T, I>>::InvalidNextAuthoritySetId
+		);
+		if is_handoff {
+			log::info!(
+				target: LOG_TARGET,
+				"Transitioned to validator set {}! New validator set is: {:?}",
+				validator_set_id,
+				authority_set,
+			);
+			<CurrentAuthoritySetInfo<T, I>>::put(authority_set);
+		}
+		<NextAuthoritySetInfo<T, I>>::put(next_authority_set);
+
+		insert_commitment::<T, I>(
+			block_number,
+			BridgedImportedCommitment::<T, I> {
+				parent_number_and_hash: mmr_leaf.parent_number_and_hash,
+				mmr_root,
+			},
+		);
+		log::info!(target: LOG_TARGET, "Successfully imported commitment of block {:?}!", block_number);
+
+		Ok(())
+	}
+
+	/// Import a previously verified commitment to the storage.
+	///
+	/// Note this function solely takes care of updating the storage and pruning old entries,
+	/// but does not verify the validity of such import.
+	pub(crate) fn insert_commitment<T: Config<I>, I: 'static>(
+		block_number: BridgedBlockNumber<T, I>,
+		commitment: BridgedImportedCommitment<T, I>,
+	) {
+		let index = <ImportedBlockNumbersPointer<T, I>>::get();
+		let pruning = <ImportedBlockNumbers<T, I>>::try_get(index);
+		<BestBlockNumber<T, I>>::put(block_number);
+		<ImportedCommitments<T, I>>::insert(block_number, commitment);
+		<ImportedBlockNumbers<T, I>>::insert(index, block_number);
+
+		// Update ring buffer pointer and remove old commitment.
+		<ImportedBlockNumbersPointer<T, I>>::put((index + 1) % T::CommitmentsToKeep::get());
+		if let Ok(block_number) = pruning {
+			log::debug!(target: LOG_TARGET, "Pruning old commitment of block: {:?}.", block_number);
+			<ImportedCommitments<T, I>>::remove(block_number);
+		}
+	}
+
+	/// Since this writes to storage with no real checks this should only be used in functions that
+	/// were called by a trusted origin.
+	pub(crate) fn initialize_bridge<T: Config<I>, I: 'static>(
+		init_params: super::InitializationData<T, I>,
+	) {
+		let bp_beefy::InitializationData {
+			is_halted,
+			best_block_number,
+			current_authority_set,
+			next_authority_set,
+		} = init_params;
+
+		<BestBlockNumber<T, I>>::put(best_block_number);
+		<ImportedBlockNumbersPointer<T, I>>::put(0);
+		<CurrentAuthoritySetInfo<T, I>>::put(current_authority_set);
+		<NextAuthoritySetInfo<T, I>>::put(next_authority_set);
+		<IsHalted<T, I>>::put(is_halted);
+	}
+
+	/// Ensure that the origin is either root, or `PalletOwner`.
+	fn ensure_owner_or_root<T: Config<I>, I: 'static>(origin: T::Origin) -> Result<(), BadOrigin> {
+		match origin.into() {
+			Ok(RawOrigin::Root) => Ok(()),
+			Ok(RawOrigin::Signed(ref signer))
+				if Some(signer) == <PalletOwner<T, I>>::get().as_ref() =>
+				Ok(()),
+			_ => Err(BadOrigin),
+		}
+	}
+
+	/// Ensure that the pallet is in operational mode (not halted).
+	fn ensure_operational<T: Config<I>, I: 'static>() -> Result<(), Error<T, I>> {
+		if <IsHalted<T, I>>::get() {
+			Err(<Error<T, I>>::Halted)
+		} else {
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Returns commitment of given block, if it has been imported by the pallet.
+	pub fn imported_commitment(
+		block_number: BridgedBlockNumber<T, I>,
+	) -> Option<BridgedImportedCommitment<T, I>> {
+		<ImportedCommitments<T, I>>::get(block_number)
+	}
+
+	/// Ensure that the pallet storage is consistent.
+	///
+	/// Returns an error if any of the storage invariants is broken.
+	#[cfg(any(feature = "try-runtime", test))]
+	pub fn do_try_state() -> Result<(), &'static str> {
+		let commitments_to_keep = T::CommitmentsToKeep::get();
+		let pointer = <ImportedBlockNumbersPointer<T, I>>::get();
+		ensure!(
+			pointer < commitments_to_keep,
+			"Imported block numbers pointer is outside of the ring buffer"
+		);
+
+		// every block in the ring buffer has imported commitment and every imported commitment is
+		// in the buffer
+		let mut imported_block_numbers = 0;
+		for (index, block_number) in <ImportedBlockNumbers<T, I>>::iter() {
+			ensure!(
+				index < commitments_to_keep,
+				"Imported block number is outside of the ring buffer"
+			);
+			ensure!(
+				<ImportedCommitments<T, I>>::contains_key(block_number),
+				"Commitment of the block from the ring buffer is not imported"
+			);
+			imported_block_numbers += 1;
+		}
+		ensure!(
+			<ImportedCommitments<T, I>>::iter_keys().count() == imported_block_numbers,
+			"Imported commitment is missing from the ring buffer"
+		);
+
+		// if there are imported commitments, the best block is the latest imported block
+		if imported_block_numbers != 0 {
+			let best_block_index = pointer.checked_sub(1).unwrap_or(commitments_to_keep - 1);
+			ensure!(
+				<ImportedBlockNumbers<T, I>>::get(best_block_index) ==
+					<BestBlockNumber<T, I>>::get(),
+				"Best block is not the latest imported block"
+			);
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{
+		authority_set_info, finality_proof, finality_proof_with_leaf, mmr_leaf, run_test,
+		validator_pairs, Origin, TestFinalityProof, TestRuntime, VALIDATORS,
+	};
+	use bp_test_utils::{beefy_validator_ids, beefy_validator_pair, sign_beefy_commitment};
+	use frame_support::{assert_noop, assert_ok, storage::generator::StorageValue};
+	use sp_runtime::DispatchError;
+
+	fn init_data(set_id: u64) -> InitializationData<TestRuntime, ()> {
+		bp_beefy::InitializationData {
+			is_halted: false,
+			best_block_number: 0,
+			current_authority_set: authority_set_info(set_id),
+			next_authority_set: authority_set_info(set_id + 1),
+		}
+	}
+
+	fn init_with_origin(
+		origin: Origin,
+	) -> Result<InitializationData<TestRuntime, ()>, DispatchError> {
+		let init_data = init_data(0);
+		Pallet::<TestRuntime>::initialize(origin, init_data.clone()).map(|_| init_data)
+	}
+
+	fn initialize_substrate_bridge() {
+		assert_ok!(init_with_origin(Origin::root()));
+	}
+
+	fn submit_commitment(proof: TestFinalityProof) -> DispatchResult {
+		Pallet::<TestRuntime>::submit_commitment(
+			Origin::signed(1),
+			proof.commitment,
+			proof.validator_set,
+			Box::new(proof.mmr_leaf),
+			proof.mmr_proof,
+		)
+	}
+
+	fn next_block() {
+		use frame_support::traits::OnInitialize;
+
+		let current_number = frame_system::Pallet::<TestRuntime>::block_number();
+		frame_system::Pallet::<TestRuntime>::set_block_number(current_number + 1);
+		let _ = Pallet::<TestRuntime>::on_initialize(current_number);
+	}
+
+	#[test]
+	fn init_root_or_owner_origin_can_initialize_pallet() {
+		run_test(|| {
+			assert_noop!(init_with_origin(Origin::signed(1)), DispatchError::BadOrigin);
+			assert_ok!(init_with_origin(Origin::root()));
+
+			// Reset storage so we can initialize the pallet again
+			BestBlockNumber::<TestRuntime>::kill();
+			PalletOwner::<TestRuntime>::put(2);
+			assert_ok!(init_with_origin(Origin::signed(2)));
+		})
+	}
+
+	#[test]
+	fn init_storage_entries_are_correctly_initialized() {
+		run_test(|| {
+			let init_data = init_with_origin(Origin::root()).unwrap();
+
+			assert_eq!(BestBlockNumber::<TestRuntime>::get(), Some(init_data.best_block_number));
+			assert_eq!(
+				CurrentAuthoritySetInfo::<TestRuntime>::get(),
+				init_data.current_authority_set
+			);
+			assert_eq!(NextAuthoritySetInfo::<TestRuntime>::get(), init_data.next_authority_set);
+			assert!(!IsHalted::<TestRuntime>::get());
+		})
+	}
+
+	#[test]
+	fn init_can_only_initialize_pallet_once() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_noop!(
+				init_with_origin(Origin::root()),
+				<Error<TestRuntime>>::AlreadyInitialized
+			);
+		})
+	}
+
+	#[test]
+	fn pallet_owner_may_change_owner() {
+		run_test(|| {
+			PalletOwner::<TestRuntime>::put(2);
+
+			assert_ok!(Pallet::<TestRuntime>::set_owner(Origin::root(), Some(1)));
+			assert_noop!(
+				Pallet::<TestRuntime>::set_operational(Origin::signed(2), false),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(Pallet::<TestRuntime>::set_operational(Origin::root(), false));
+
+			assert_ok!(Pallet::<TestRuntime>::set_owner(Origin::signed(1), None));
+			assert_noop!(
+				Pallet::<TestRuntime>::set_operational(Origin::signed(1), true),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(Pallet::<TestRuntime>::set_operational(Origin::root(), true));
+		});
+	}
+
+	#[test]
+	fn pallet_rejects_transactions_if_halted() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_ok!(Pallet::<TestRuntime>::set_operational(Origin::root(), false));
+			assert_noop!(submit_commitment(finality_proof(1, 0, 4)), Error::<TestRuntime>::Halted);
+
+			assert_ok!(Pallet::<TestRuntime>::set_operational(Origin::root(), true));
+			assert_ok!(submit_commitment(finality_proof(1, 0, 4)));
+		})
+	}
+
+	#[test]
+	fn pallet_rejects_commitment_if_not_initialized_yet() {
+		run_test(|| {
+			assert_noop!(
+				submit_commitment(finality_proof(1, 0, 4)),
+				Error::<TestRuntime>::NotInitialized
+			);
+		});
+	}
+
+	#[test]
+	fn succesfully_imports_commitment_signed_by_current_validator_set() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let proof = finality_proof(1, 0, 4);
+			let mmr_root =
+				utils::extract_mmr_root::<TestRuntime, ()>(&proof.commitment.commitment).unwrap();
+			assert_ok!(submit_commitment(proof.clone()));
+
+			assert_eq!(BestBlockNumber::<TestRuntime>::get(), Some(1));
+			assert_eq!(
+				Pallet::<TestRuntime>::imported_commitment(1),
+				Some(BridgedImportedCommitment::<TestRuntime, ()> {
+					parent_number_and_hash: proof.mmr_leaf.parent_number_and_hash,
+					mmr_root,
+				}),
+			);
+			assert_eq!(CurrentAuthoritySetInfo::<TestRuntime>::get(), authority_set_info(0));
+			assert_eq!(NextAuthoritySetInfo::<TestRuntime>::get(), authority_set_info(1));
+			assert_ok!(Pallet::<TestRuntime>::do_try_state());
+		})
+	}
+
+	#[test]
+	fn succesfully_imports_commitment_signed_by_next_validator_set() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_ok!(submit_commitment(finality_proof(1, 1, 4)));
+
+			assert_eq!(BestBlockNumber::<TestRuntime>::get(), Some(1));
+			assert_eq!(CurrentAuthoritySetInfo::<TestRuntime>::get(), authority_set_info(1));
+			assert_eq!(NextAuthoritySetInfo::<TestRuntime>::get(), authority_set_info(2));
+		})
+	}
+
+	#[test]
+	fn rejects_old_commitment() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_ok!(submit_commitment(finality_proof(2, 0, 4)));
+			assert_noop!(
+				submit_commitment(finality_proof(2, 0, 4)),
+				Error::<TestRuntime>::OldCommitment
+			);
+			assert_noop!(
+				submit_commitment(finality_proof(1, 0, 4)),
+				Error::<TestRuntime>::OldCommitment
+			);
+		})
+	}
+
+	#[test]
+	fn rejects_commitment_signed_by_unknown_validator_set() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_noop!(
+				submit_commitment(finality_proof(1, 2, 4)),
+				Error::<TestRuntime>::InvalidValidatorSetId
+			);
+		})
+	}
+
+	#[test]
+	fn rejects_commitment_with_invalid_validator_set() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut proof = finality_proof(1, 0, 4);
+			proof.validator_set.pop();
+			assert_noop!(submit_commitment(proof), Error::<TestRuntime>::InvalidValidatorSetLen);
+
+			let mut proof = finality_proof(1, 0, 4);
+			proof.validator_set[0] = beefy_validator_ids(&[beefy_validator_pair(1000)])[0].clone();
+			assert_noop!(submit_commitment(proof), Error::<TestRuntime>::InvalidValidatorSetRoot);
+		})
+	}
+
+	#[test]
+	fn rejects_commitment_with_invalid_signatures() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut proof = finality_proof(1, 0, 4);
+			proof.commitment.signatures.pop();
+			assert_noop!(submit_commitment(proof), Error::<TestRuntime>::InvalidSignaturesLength);
+
+			// signature of the first validator is generated by the second validator
+			let mut proof = finality_proof(1, 0, 4);
+			proof.commitment.signatures[0] = proof.commitment.signatures[1].clone();
+			assert_noop!(submit_commitment(proof), Error::<TestRuntime>::InvalidSignature);
+
+			// the commitment is signed by the validator set, but then modified
+			let mut proof = finality_proof(1, 0, 4);
+			proof.commitment.commitment.block_number = 2;
+			assert_noop!(submit_commitment(proof), Error::<TestRuntime>::InvalidSignature);
+		})
+	}
+
+	#[test]
+	fn rejects_commitment_with_too_large_proof() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let extra_validator = beefy_validator_ids(&[beefy_validator_pair(1000)])[0].clone();
+			let mut proof = finality_proof(1, 0, 4);
+			proof.validator_set.push(extra_validator);
+			assert_noop!(submit_commitment(proof), Error::<TestRuntime>::TooManyValidators);
+
+			let mut proof = finality_proof(1, 0, 4);
+			proof.commitment.signatures.push(None);
+			assert_noop!(submit_commitment(proof), Error::<TestRuntime>::TooManySignatures);
+
+			let mut proof = finality_proof(1, 0, 4);
+			proof.mmr_proof.items.extend(vec![Default::default(); 2]);
+			assert_noop!(submit_commitment(proof), Error::<TestRuntime>::TooLargeMmrProof);
+		})
+	}
+
+	#[test]
+	fn rejects_commitment_with_not_enough_signatures() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// 4 of 5 signatures are required
+			assert_eq!(VALIDATORS, 5);
+			assert_noop!(
+				submit_commitment(finality_proof(1, 0, 3)),
+				Error::<TestRuntime>::NotEnoughCorrectSignatures
+			);
+		})
+	}
+
+	#[test]
+	fn rejects_commitment_without_mmr_root() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut proof = finality_proof(1, 0, 4);
+			let mut commitment = proof.commitment.commitment;
+			commitment.payload = bp_beefy::BeefyPayload::new(*b"xx", vec![42]);
+			proof.commitment = sign_beefy_commitment(commitment, &validator_pairs(0), 4);
+			assert_noop!(
+				submit_commitment(proof),
+				Error::<TestRuntime>::MmrRootMissingFromCommitment
+			);
+		})
+	}
+
+	#[test]
+	fn rejects_commitment_with_invalid_mmr_leaf() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// the leaf is not the latest leaf at the committed block
+			let mut leaf = mmr_leaf(2, 1);
+			leaf.parent_number_and_hash.0 = 0;
+			let mut proof = finality_proof_with_leaf(leaf, 0, 4);
+			proof.commitment.commitment.block_number = 2;
+			proof.commitment =
+				sign_beefy_commitment(proof.commitment.commitment, &validator_pairs(0), 4);
+			assert_noop!(submit_commitment(proof), Error::<TestRuntime>::InvalidMmrLeaf);
+
+			// the leaf is not a part of the committed MMR
+			let mut proof = finality_proof(1, 0, 4);
+			proof.mmr_leaf.parent_number_and_hash.1 = Default::default();
+			assert_noop!(submit_commitment(proof), Error::<TestRuntime>::InvalidMmrProof);
+		})
+	}
+
+	#[test]
+	fn rejects_commitment_with_unexpected_next_validator_set() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_noop!(
+				submit_commitment(finality_proof_with_leaf(mmr_leaf(1, 2), 0, 4)),
+				Error::<TestRuntime>::InvalidNextAuthoritySetId
+			);
+		})
+	}
+
+	#[test]
+	fn rejects_commitments_if_too_many_requests() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_ok!(submit_commitment(finality_proof(1, 0, 4)));
+			assert_ok!(submit_commitment(finality_proof(2, 0, 4)));
+			assert_noop!(
+				submit_commitment(finality_proof(3, 0, 4)),
+				Error::<TestRuntime>::TooManyRequests
+			);
+
+			next_block();
+			assert_ok!(submit_commitment(finality_proof(3, 0, 4)));
+		})
+	}
+
+	#[test]
+	fn old_commitments_are_pruned() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			for block_number in 1..=4 {
+				next_block();
+				assert_ok!(submit_commitment(finality_proof(block_number, 0, 4)));
+			}
+
+			// `CommitmentsToKeep` is 3
+			assert!(Pallet::<TestRuntime>::imported_commitment(1).is_none());
+			assert!(Pallet::<TestRuntime>::imported_commitment(2).is_some());
+			assert!(Pallet::<TestRuntime>::imported_commitment(3).is_some());
+			assert!(Pallet::<TestRuntime>::imported_commitment(4).is_some());
+			assert_ok!(Pallet::<TestRuntime>::do_try_state());
+		})
+	}
+
+	#[test]
+	fn try_state_detects_outdated_best_block() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_ok!(submit_commitment(finality_proof(1, 0, 4)));
+
+			BestBlockNumber::<TestRuntime>::put(5);
+			assert_eq!(
+				Pallet::<TestRuntime>::do_try_state(),
+				Err("Best block is not the latest imported block"),
+			);
+		})
+	}
+
+	#[test]
+	fn storage_keys_computed_properly() {
+		assert_eq!(
+			IsHalted::<TestRuntime>::storage_value_final_key().to_vec(),
+			bp_beefy::storage_keys::is_halted_key("Beefy").0,
+		);
+
+		assert_eq!(
+			BestBlockNumber::<TestRuntime>::storage_value_final_key().to_vec(),
+			bp_beefy::storage_keys::best_block_number_key("Beefy").0,
+		);
+
+		assert_eq!(
+			CurrentAuthoritySetInfo::<TestRuntime>::storage_value_final_key().to_vec(),
+			bp_beefy::storage_keys::current_authority_set_info_key("Beefy").0,
+		);
+	}
+}
@@ -0,0 +1,215 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+// From construct_runtime macro
+#![allow(clippy::from_over_into)]
+
+use bp_beefy::{
+	BeefyAuthoritySetInfoOf, BeefyFinalityProofOf, BeefyMmrLeafOf, BeefyMmrProof, ChainWithBeefy,
+	MmrLeafVersion, ValidatorSetId,
+};
+use bp_runtime::Chain;
+use bp_test_utils::{
+	beefy_validator_ids, beefy_validator_pair, make_beefy_commitment, sign_beefy_commitment,
+};
+use codec::Encode;
+use frame_support::{construct_runtime, parameter_types, weights::Weight};
+use sp_core::{ecdsa, sr25519::Signature};
+use sp_runtime::{
+	testing::{Header, H256},
+	traits::{BlakeTwo256, Hash, IdentityLookup, Keccak256},
+	Perbill,
+};
+
+pub type AccountId = u64;
+pub type TestNumber = crate::BridgedBlockNumber<TestRuntime, ()>;
+pub type TestMmrHash = crate::BridgedMmrHash<TestRuntime, ()>;
+pub type TestMmrLeaf = BeefyMmrLeafOf<TestBridgedChain>;
+pub type TestFinalityProof = BeefyFinalityProofOf<TestBridgedChain>;
+
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+
+use crate as beefy;
+
+construct_runtime! {
+	pub enum TestRuntime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Beefy: beefy::{Pallet, Call},
+	}
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Config for TestRuntime {
+	type Origin = Origin;
+	type Index = u64;
+	type Call = Call;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = ();
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type BaseCallFilter = frame_support::traits::Everything;
+	type SystemWeightInfo = ();
+	type DbWeight = ();
+	type BlockWeights = ();
+	type BlockLength = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+	pub const MaxRequests: u32 = 2;
+	pub const CommitmentsToKeep: u32 = 3;
+	pub const MaxValidators: u32 = VALIDATORS;
+	pub const MaxMmrProofSize: u32 = 2;
+}
+
+impl beefy::Config for TestRuntime {
+	type BridgedChain = TestBridgedChain;
+	type MaxRequests = MaxRequests;
+	type CommitmentsToKeep = CommitmentsToKeep;
+	type MaxValidators = MaxValidators;
+	type MaxMmrProofSize = MaxMmrProofSize;
+	type WeightInfo = ();
+}
+
+#[derive(Debug)]
+pub struct TestBridgedChain;
+
+impl Chain for TestBridgedChain {
+	type BlockNumber = <TestRuntime as frame_system::Config>::BlockNumber;
+	type Hash = <TestRuntime as frame_system::Config>::Hash;
+	type Hasher = <TestRuntime as frame_system::Config>::Hashing;
+	type Header = <TestRuntime as frame_system::Config>::Header;
+
+	type AccountId = AccountId;
+	type Balance = u64;
+	type Index = u64;
+	type Signature = Signature;
+
+	fn max_extrinsic_size() -> u32 {
+		unreachable!()
+	}
+	fn max_extrinsic_weight() -> Weight {
+		unreachable!()
+	}
+}
+
+impl ChainWithBeefy for TestBridgedChain {
+	type MmrHashing = Keccak256;
+	type MmrHash = H256;
+	type BeefyMmrLeafExtra = ();
+	type AuthorityIdToMerkleLeaf = pallet_beefy_mmr::BeefyEcdsaToEthereum;
+}
+
+/// Number of validators in every test validator set.
+pub const VALIDATORS: u32 = 5;
+
+pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
+	sp_io::TestExternalities::new(Default::default()).execute_with(test)
+}
+
+/// Get key pairs of validators of the validator set with given id.
+///
+/// Every validator set has its own validators.
+pub fn validator_pairs(set_id: ValidatorSetId) -> Vec<ecdsa::Pair> {
+	let first_validator = set_id as u32 * VALIDATORS;
+	(first_validator..first_validator + VALIDATORS)
+		.map(beefy_validator_pair)
+		.collect()
+}
+
+/// Get information about the validator set with given id.
+pub fn authority_set_info(set_id: ValidatorSetId) -> BeefyAuthoritySetInfoOf<TestBridgedChain> {
+	bp_beefy::beefy_authority_set_info::<TestBridgedChain>(
+		set_id,
+		&beefy_validator_ids(&validator_pairs(set_id)),
+	)
+}
+
+/// Build MMR with two leaves, where the given leaf is the latest one.
+///
+/// Returns the MMR root and the proof of the given leaf. The first leaf is only represented by
+/// its hash.
+pub fn mmr_root_and_proof(leaf: &TestMmrLeaf) -> (TestMmrHash, BeefyMmrProof<TestMmrHash>) {
+	let first_leaf_hash = TestMmrHash::repeat_byte(42);
+	let leaf_hash = Keccak256::hash(&leaf.encode());
+	let root = Keccak256::hash(&[first_leaf_hash.as_bytes(), leaf_hash.as_bytes()].concat());
+	(root, BeefyMmrProof { leaf_index: 1, leaf_count: 2, items: vec![first_leaf_hash] })
+}
+
+/// Build the latest MMR leaf at the given block.
+pub fn mmr_leaf(block_number: TestNumber, next_set_id: ValidatorSetId) -> TestMmrLeaf {
+	TestMmrLeaf {
+		version: MmrLeafVersion::new(0, 0),
+		parent_number_and_hash: (block_number - 1, H256::repeat_byte(block_number as u8)),
+		beefy_next_authority_set: authority_set_info(next_set_id),
+		leaf_extra: (),
+	}
+}
+
+/// Build finality proof of the given block, signed by first `signers` validators of the given
+/// validator set.
+pub fn finality_proof(
+	block_number: TestNumber,
+	set_id: ValidatorSetId,
+	signers: usize,
+) -> TestFinalityProof {
+	finality_proof_with_leaf(mmr_leaf(block_number, set_id + 1), set_id, signers)
+}
+
+/// Build finality proof of the block, which latest MMR leaf is given.
+pub fn finality_proof_with_leaf(
+	mmr_leaf: TestMmrLeaf,
+	set_id: ValidatorSetId,
+	signers: usize,
+) -> TestFinalityProof {
+	let validators = validator_pairs(set_id);
+	let block_number = mmr_leaf.parent_number_and_hash.0 + 1;
+	let (mmr_root, mmr_proof) = mmr_root_and_proof(&mmr_leaf);
+	let commitment = sign_beefy_commitment(
+		make_beefy_commitment(block_number, set_id, mmr_root),
+		&validators,
+		signers,
+	);
+
+	TestFinalityProof {
+		commitment,
+		validator_set: beefy_validator_ids(&validators),
+		mmr_leaf,
+		mmr_proof,
+	}
+}
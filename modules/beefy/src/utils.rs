@@ -0,0 +1,139 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Verification of BEEFY commitments and MMR leaves.
+
+use crate::{
+	BridgedBeefyAuthoritySetInfo, BridgedBeefyMmrLeaf, BridgedBeefyMmrProof,
+	BridgedBeefySignedCommitment, BridgedBlockNumber, BridgedChain, BridgedMmrHash,
+	BridgedMmrHashing, Config, Error,
+};
+
+use bp_beefy::{
+	beefy_authority_set_root, signatures_required, Commitment, EcdsaValidatorId,
+	EcdsaValidatorSignature, MMR_ROOT_PAYLOAD_ID,
+};
+use codec::{Decode, Encode};
+use frame_support::ensure;
+use sp_mmr_primitives::DataOrHash;
+use sp_runtime::traits::{One, Saturating};
+use sp_std::convert::TryInto;
+
+/// Verify that the validator set matches the validator set info, known to the pallet.
+pub(crate) fn verify_validator_set<T: Config<I>, I: 'static>(
+	authority_set_info: &BridgedBeefyAuthoritySetInfo<T, I>,
+	validators: &[EcdsaValidatorId],
+) -> Result<(), Error<T, I>> {
+	ensure!(
+		!validators.is_empty() && validators.len() == authority_set_info.len as usize,
+		Error::<T, I>::InvalidValidatorSetLen
+	);
+	ensure!(
+		beefy_authority_set_root::<BridgedChain<T, I>>(validators) == authority_set_info.root,
+		Error::<T, I>::InvalidValidatorSetRoot
+	);
+
+	Ok(())
+}
+
+/// Verify that the commitment is signed by more than 2/3 of the given validators.
+///
+/// Signatures must be ordered the same way as validators. Every provided signature must be valid.
+pub(crate) fn verify_commitment_signatures<T: Config<I>, I: 'static>(
+	commitment: &BridgedBeefySignedCommitment<T, I>,
+	validators: &[EcdsaValidatorId],
+) -> Result<(), Error<T, I>> {
+	ensure!(
+		commitment.signatures.len() == validators.len(),
+		Error::<T, I>::InvalidSignaturesLength
+	);
+
+	let commitment_hash = sp_io::hashing::keccak_256(&commitment.commitment.encode());
+	let mut correct_signatures = 0;
+	for (validator, signature) in validators.iter().zip(commitment.signatures.iter()) {
+		if let Some(signature) = signature {
+			ensure!(
+				verify_signature(&commitment_hash, validator, signature),
+				Error::<T, I>::InvalidSignature
+			);
+			correct_signatures += 1;
+		}
+	}
+	ensure!(
+		correct_signatures >= signatures_required(validators.len()),
+		Error::<T, I>::NotEnoughCorrectSignatures
+	);
+
+	Ok(())
+}
+
+/// Extract MMR root from the commitment payload.
+pub(crate) fn extract_mmr_root<T: Config<I>, I: 'static>(
+	commitment: &Commitment<BridgedBlockNumber<T, I>>,
+) -> Result<BridgedMmrHash<T, I>, Error<T, I>> {
+	commitment
+		.payload
+		.get_raw(&MMR_ROOT_PAYLOAD_ID)
+		.and_then(|raw_mmr_root| Decode::decode(&mut &raw_mmr_root[..]).ok())
+		.ok_or(Error::<T, I>::MmrRootMissingFromCommitment)
+}
+
+/// Verify that the MMR leaf is the latest leaf of the MMR with given root at the committed block.
+pub(crate) fn verify_mmr_leaf<T: Config<I>, I: 'static>(
+	block_number: BridgedBlockNumber<T, I>,
+	mmr_root: BridgedMmrHash<T, I>,
+	mmr_leaf: &BridgedBeefyMmrLeaf<T, I>,
+	mmr_proof: BridgedBeefyMmrProof<T, I>,
+) -> Result<(), Error<T, I>> {
+	// the leaf that is appended to the MMR at the committed block, contains its parent number
+	ensure!(
+		mmr_leaf.parent_number_and_hash.0.saturating_add(One::one()) == block_number,
+		Error::<T, I>::InvalidMmrLeaf
+	);
+
+	pallet_mmr::verify_leaf_proof::<BridgedMmrHashing<T, I>, _>(
+		mmr_root,
+		DataOrHash::Data(mmr_leaf.clone()),
+		mmr_proof.into(),
+	)
+	.map_err(|e| {
+		log::error!(
+			target: "runtime::bridge-beefy",
+			"Received invalid MMR proof of the leaf at block {:?}: {:?}",
+			block_number,
+			e,
+		);
+		Error::<T, I>::InvalidMmrProof
+	})
+}
+
+/// Returns true if the signature of the given message hash has been generated by the validator.
+fn verify_signature(
+	message_hash: &[u8; 32],
+	validator: &EcdsaValidatorId,
+	signature: &EcdsaValidatorSignature,
+) -> bool {
+	let raw_signature: &[u8] = signature.as_ref();
+	let raw_signature: [u8; 65] = match raw_signature.try_into() {
+		Ok(raw_signature) => raw_signature,
+		Err(_) => return false,
+	};
+	let raw_validator: &[u8] = validator.as_ref();
+
+	sp_io::crypto::secp256k1_ecdsa_recover_compressed(&raw_signature, message_hash)
+		.map(|recovered_validator| recovered_validator[..] == *raw_validator)
+		.unwrap_or(false)
+}
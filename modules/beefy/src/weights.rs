@@ -0,0 +1,59 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_bridge_beefy`
+//!
+//! The pallet is not a part of any bridge node runtime yet, so the weights below are not generated
+//! by the benchmark CLI. Once the pallet is added to the runtime, they should be regenerated
+//! using benchmarks from `benchmarking.rs` and the `millau-weight-template.hbs` template.
+
+#![allow(clippy::all)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for `pallet_bridge_beefy`.
+pub trait WeightInfo {
+	fn submit_commitment(v: u32, p: u32) -> Weight;
+}
+
+/// Weights for `pallet_bridge_beefy` using the Millau node and recommended hardware.
+pub struct MillauWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for MillauWeight<T> {
+	fn submit_commitment(v: u32, p: u32) -> Weight {
+		(72_816_000 as Weight)
+			.saturating_add((64_127_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((1_983_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn submit_commitment(v: u32, p: u32) -> Weight {
+		(72_816_000 as Weight)
+			.saturating_add((64_127_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((1_983_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+}
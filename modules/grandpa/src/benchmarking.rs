@@ -38,6 +38,9 @@
 //!
 //! Note that the worst case scenario here would be a justification where each validator has it's
 //! own fork which is `SESSION_LENGTH` blocks long.
+//!
+//! The header that is imported in benchmarks enacts the pending authority set change and schedules
+//! the next delayed change. This is the worst case in terms of storage accesses.
 
 use crate::*;
 
@@ -70,6 +73,20 @@ fn header_number<T: Config<I>, I: 'static, N: From<u32>>() -> N {
 	(T::HeadersToKeep::get() + 1).into()
 }
 
+/// Returns digest item that schedules authority set change with one block delay.
+fn scheduled_change_digest<T: Config<I>, I: 'static>(
+	next_authorities: AuthorityList,
+) -> sp_runtime::DigestItem {
+	let change = sp_finality_grandpa::ScheduledChange::<BridgedBlockNumber<T, I>> {
+		next_authorities,
+		delay: One::one(),
+	};
+	sp_runtime::DigestItem::Consensus(
+		GRANDPA_ENGINE_ID,
+		ConsensusLog::ScheduledChange(change).encode(),
+	)
+}
+
 /// Prepare header and its justification to submit using `submit_finality_proof`.
 fn prepare_benchmark_data<T: Config<I>, I: 'static>(
 	precommits: u32,
@@ -82,14 +99,21 @@ fn prepare_benchmark_data<T: Config<I>, I: 'static>(
 
 	let init_data = InitializationData {
 		header: Box::new(bp_test_utils::test_header(Zero::zero())),
-		authority_list,
+		authority_list: authority_list.clone(),
 		set_id: TEST_GRANDPA_SET_ID,
 		is_halted: false,
 	};
 
 	bootstrap_bridge::<T, I>(init_data);
 
-	let header: BridgedHeader<T, I> = bp_test_utils::test_header(header_number::<T, I, _>());
+	// the header enacts pending change and schedules the next change. Since the change is enacted
+	// by the header itself, it is still finalized by the current authority set
+	<PendingChange<T, I>>::put(StoredPendingChange {
+		enact_at: header_number::<T, I, _>(),
+		next_authorities: authority_list.clone(),
+	});
+	let mut header: BridgedHeader<T, I> = bp_test_utils::test_header(header_number::<T, I, _>());
+	header.digest_mut().push(scheduled_change_digest::<T, I>(authority_list));
 	let params = JustificationGeneratorParams {
 		header: header.clone(),
 		round: TEST_GRANDPA_ROUND,
@@ -112,10 +136,14 @@ benchmarks_instance_pallet! {
 		let (header, justification) = prepare_benchmark_data::<T, I>(p, v);
 	}: submit_finality_proof(RawOrigin::Signed(caller), Box::new(header), justification)
 	verify {
-		let header: BridgedHeader<T, I> = bp_test_utils::test_header(header_number::<T, I, _>());
-		let expected_hash = header.hash();
+		let expected_number: BridgedBlockNumber<T, I> = header_number::<T, I, _>();
+		let best_finalized = <ImportedHeaders<T, I>>::get(<BestFinalized<T, I>>::get()).unwrap();
 
-		assert_eq!(<BestFinalized<T, I>>::get(), expected_hash);
-		assert!(<ImportedHeaders<T, I>>::contains_key(expected_hash));
+		assert_eq!(*best_finalized.number(), expected_number);
+		assert_eq!(<CurrentAuthoritySet<T, I>>::get().set_id, TEST_GRANDPA_SET_ID + 1);
+		assert_eq!(
+			<PendingChange<T, I>>::get().map(|change| change.enact_at),
+			Some(expected_number + One::one()),
+		);
 	}
 }
@@ -24,31 +24,73 @@
 //!
 //! The pallet is responsible for tracking GRANDPA validator set hand-offs. We only import headers
 //! with justifications signed by the current validator set we know of. The header is inspected for
-//! a `ScheduledChanges` digest item, which is then used to update to next validator set.
+//! a `ScheduledChanges` digest item, which is then used to update to next validator set. If the
+//! change is delayed, it is kept as pending until the pallet sees the header that enacts it, or any
+//! of its descendants, which finality is proved by the next validator set.
+//!
+//! The pallet keeps a bounded history of GRANDPA authority sets (see `AuthoritySetsToKeep`). It
+//! is used to verify finality of headers that are older than the best finalized header and have
+//! been finalized by past authority sets. Such headers may be imported using the
+//! `submit_historical_finality_proof` call. They don't change the best finalized header and
+//! are kept in a separate ring buffer (see `HistoricalHeadersToKeep`), so importing them never
+//! causes pruning of recent headers.
+//!
+//! Imported headers are pruned according to the `HeadersRetention` policy. Either the fixed
+//! number of most recent headers is kept, or headers are kept for the fixed number of bridged
+//! chain blocks. In the latter case, mandatory headers may be kept longer than regular headers.
+//!
+//! Forced authority set changes, which are used by GRANDPA to recover from stalls, can't be
+//! verified by the pallet in a regular way. They may only be enacted by the pallet owner (or root)
+//! using the `enact_forced_change` call, which requires a proof of finality, generated by the new
+//! authority set.
+//!
+//! Relayers are not paying fee for importing mandatory headers and headers that improve the best
+//! finalized header by at least `FreeHeadersInterval` blocks. They may also be rewarded for
+//! importing such headers from the relayers fund account (see `relayer_fund_account_id`). At most
+//! `MaxRewardsPerPeriod` rewards are paid in every `RewardsPeriod`.
 //!
 //! Since this pallet only tracks finalized headers it does not deal with forks. Forks can only
 //! occur if the GRANDPA validator set on the bridged chain is either colluding or there is a severe
-//! bug causing resulting in an equivocation. Such events are outside the scope of this pallet.
-//! Shall the fork occur on the bridged chain governance intervention will be required to
+//! bug causing resulting in an equivocation. Anyone may report such fork to the pallet, using the
+//! `report_conflicting_finality` call with two conflicting headers, finalized by the current
+//! validator set, and the ancestry of the higher header, proving that it doesn't descend from the
+//! lower header. The pallet is halted then. Governance intervention will be required to
 //! re-initialize the bridge and track the right fork.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 // Runtime-generated enums
 #![allow(clippy::large_enum_variant)]
 
-use bp_header_chain::{justification::GrandpaJustification, InitializationData};
+use bp_header_chain::{
+	justification::GrandpaJustification, FinalityProofRewardPayment, HeadersRetentionPolicy,
+	InitializationData,
+};
 use bp_runtime::{BlockNumberOf, Chain, HashOf, HasherOf, HeaderOf};
+use codec::{Decode, Encode};
 use finality_grandpa::voter_set::VoterSet;
-use frame_support::{ensure, fail};
+use frame_support::{
+	ensure, fail,
+	traits::{Currency, ExistenceRequirement, Get, PalletInfoAccess},
+	transactional,
+	weights::{Pays, Weight},
+	RuntimeDebug,
+};
 use frame_system::{ensure_signed, RawOrigin};
-use sp_finality_grandpa::{ConsensusLog, GRANDPA_ENGINE_ID};
-use sp_runtime::traits::{BadOrigin, Header as HeaderT, Zero};
-use sp_std::{boxed::Box, convert::TryInto};
+use scale_info::TypeInfo;
+use sp_finality_grandpa::{AuthorityList, ConsensusLog, GRANDPA_ENGINE_ID};
+use sp_runtime::traits::{
+	BadOrigin, BlakeTwo256, Hash as HashT, Header as HeaderT, One, Saturating, TrailingZeroInput,
+	Zero,
+};
+use sp_std::{boxed::Box, convert::TryInto, marker::PhantomData};
 
 mod extension;
 #[cfg(test)]
 mod mock;
 
+/// Module, containing storage migrations of this pallet.
+pub mod migrations;
+
 /// Module, containing weights for this pallet.
 pub mod weights;
 
@@ -68,6 +110,30 @@ pub type BridgedBlockHasher<T, I> = HasherOf<<T as Config<I>>::BridgedChain>;
 /// Header of the bridged chain.
 pub type BridgedHeader<T, I> = HeaderOf<<T as Config<I>>::BridgedChain>;
 
+/// Maximal number of expired headers that are pruned when a new header is imported.
+///
+/// It is larger than one, so that pruning catches up with the import of new headers.
+pub const MAX_EXPIRED_HEADERS_TO_PRUNE: u32 = 2;
+
+/// GRANDPA authority set change that has been scheduled by the imported header, but is not yet
+/// enacted.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct StoredPendingChange<Number> {
+	/// Number of the header that enacts the change once finalized.
+	pub enact_at: Number,
+	/// Authorities that will be active after the change is enacted.
+	pub next_authorities: AuthorityList,
+}
+
+/// GRANDPA authority set that has been enacted at the bridged chain.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct StoredAuthoritySet<Number> {
+	/// Authorities of the set.
+	pub authorities: AuthorityList,
+	/// Number of the first header that is finalized by this authority set.
+	pub first_block: Number,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -76,6 +142,9 @@ pub mod pallet {
 
 	#[pallet::config]
 	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
+
 		/// The chain we are bridging to here.
 		type BridgedChain: Chain;
 
@@ -88,19 +157,87 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxRequests: Get<u32>;
 
+		/// Maximal number of finality proofs that may be submitted in a single
+		/// `submit_finality_proofs` call.
+		///
+		/// Every header of the batch counts as a separate request against `MaxRequests`, so it
+		/// must not be larger than `MaxRequests`.
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+
+		/// Minimal number of blocks by which a non-mandatory header must improve the best
+		/// finalized header to be imported for free.
+		///
+		/// Relayer doesn't pay fee for importing mandatory headers and headers that are at least
+		/// `FreeHeadersInterval` blocks ahead of the best finalized header. Set it to `None` if
+		/// relayers must pay for all non-mandatory headers.
+		#[pallet::constant]
+		type FreeHeadersInterval: Get<Option<u32>>;
+
+		/// Maximal number of free headers that relayers are rewarded for in every
+		/// `RewardsPeriod`.
+		///
+		/// Once the limit is reached, non-mandatory headers are no longer free and relayers are
+		/// not rewarded until the next period starts. Mandatory headers are always free.
+		#[pallet::constant]
+		type MaxRewardsPerPeriod: Get<u32>;
+
+		/// Length of the rewards period in blocks of this chain (see `MaxRewardsPerPeriod`).
+		#[pallet::constant]
+		type RewardsPeriod: Get<Self::BlockNumber>;
+
+		/// Payment of rewards to relayers that are submitting useful (free) headers and to
+		/// reporters of conflicting finality.
+		///
+		/// Rewards are paid from the account, returned by the `relayer_fund_account_id`
+		/// function. Use `()` if relayers shall not be rewarded.
+		type RelayerRewardPayment: FinalityProofRewardPayment<Self::AccountId>;
+
 		/// Maximal number of finalized headers to keep in the storage.
 		///
 		/// The setting is there to prevent growing the on-chain state indefinitely. Note
 		/// the setting does not relate to block numbers - we will simply keep as much items
 		/// in the storage, so it doesn't guarantee any fixed timeframe for finality headers.
+		/// It is the hard limit under any `HeadersRetention` policy.
 		#[pallet::constant]
 		type HeadersToKeep: Get<u32>;
 
+		/// Policy of pruning imported headers.
+		///
+		/// Under the `HeadersRetentionPolicy::BlockNumber` policy, at most
+		/// `MAX_EXPIRED_HEADERS_TO_PRUNE` expired headers are pruned when a new header is imported.
+		/// The oldest header is pruned first and pruning stops at the header that is not expired
+		/// yet, so regular headers that are imported after a mandatory header may be kept as long
+		/// as the mandatory header is kept. `HeadersToKeep` must be large enough to fit all
+		/// headers that are imported within the retention window, otherwise headers may be
+		/// pruned before they expire.
+		#[pallet::constant]
+		type HeadersRetention: Get<HeadersRetentionPolicy>;
+
+		/// Maximal number of GRANDPA authority sets to keep in the storage.
+		///
+		/// Past authority sets are used to verify finality of historical headers (see
+		/// `submit_historical_finality_proof`). The current authority set is always kept.
+		#[pallet::constant]
+		type AuthoritySetsToKeep: Get<u32>;
+
+		/// Maximal number of historical headers to keep in the storage.
+		///
+		/// Historical headers (see `submit_historical_finality_proof`) are not subject to the
+		/// `HeadersRetention` policy. When there are more than `HistoricalHeadersToKeep`
+		/// historical headers, the oldest imported historical header is pruned.
+		#[pallet::constant]
+		type HistoricalHeadersToKeep: Get<u32>;
+
 		/// Weights gathered through benchmarking.
 		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
@@ -113,6 +250,27 @@ pub mod pallet {
 				.saturating_add(T::DbWeight::get().reads(1))
 				.saturating_add(T::DbWeight::get().writes(1))
 		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: T::BlockNumber) -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+
+		fn integrity_test() {
+			if let HeadersRetentionPolicy::BlockNumber { blocks, mandatory_blocks } =
+				T::HeadersRetention::get()
+			{
+				assert!(
+					blocks <= mandatory_blocks,
+					"Mandatory headers must be kept at least as long as regular headers",
+				);
+			}
+		}
 	}
 
 	#[pallet::call]
@@ -127,54 +285,30 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::submit_finality_proof(
 			justification.commit.precommits.len().try_into().unwrap_or(u32::MAX),
 			justification.votes_ancestries.len().try_into().unwrap_or(u32::MAX),
-		))]
+		)
+		.saturating_add(header_import_db_weight::<T, I>())
+		.saturating_add(relayer_reward_db_weight::<T, I>()))]
 		pub fn submit_finality_proof(
 			origin: OriginFor<T>,
 			finality_target: Box<BridgedHeader<T, I>>,
 			justification: GrandpaJustification<BridgedHeader<T, I>>,
 		) -> DispatchResultWithPostInfo {
 			ensure_operational::<T, I>()?;
-			let _ = ensure_signed(origin)?;
+			let relayer = ensure_signed(origin)?;
 
 			ensure!(Self::request_count() < T::MaxRequests::get(), <Error<T, I>>::TooManyRequests);
 
-			let (hash, number) = (finality_target.hash(), finality_target.number());
-			log::trace!(target: "runtime::bridge-grandpa", "Going to try and finalize header {:?}", finality_target);
-
-			let best_finalized = match <ImportedHeaders<T, I>>::get(<BestFinalized<T, I>>::get()) {
-				Some(best_finalized) => best_finalized,
-				None => {
-					log::error!(
-						target: "runtime::bridge-grandpa",
-						"Cannot finalize header {:?} because pallet is not yet initialized",
-						finality_target,
-					);
-					fail!(<Error<T, I>>::NotInitialized);
-				},
-			};
-
-			// We do a quick check here to ensure that our header chain is making progress and isn't
-			// "travelling back in time" (which could be indicative of something bad, e.g a
-			// hard-fork).
-			ensure!(best_finalized.number() < number, <Error<T, I>>::OldHeader);
-
-			let authority_set = <CurrentAuthoritySet<T, I>>::get();
-			let set_id = authority_set.set_id;
-			verify_justification::<T, I>(&justification, hash, *number, authority_set)?;
-
-			let is_authorities_change_enacted =
-				try_enact_authority_change::<T, I>(&finality_target, set_id)?;
+			let (is_mandatory_header, improved_by) =
+				import_finality_proof::<T, I>(*finality_target, justification)?;
 			<RequestCount<T, I>>::mutate(|count| *count += 1);
-			insert_header::<T, I>(*finality_target, hash);
-			log::info!(target: "runtime::bridge-grandpa", "Successfully imported finalized header with hash {:?}!", hash);
 
-			// mandatory header is a header that changes authorities set. The pallet can't go
-			// further without importing this header. So every bridge MUST import mandatory headers.
+			// mandatory header is a header that changes (or schedules change of) authorities set.
+			// The pallet can't go further without importing this header. So every bridge MUST
+			// import mandatory headers.
 			//
 			// We don't want to charge extra costs for mandatory operations. So relayer is not
 			// paying fee for mandatory headers import transactions.
-			let is_mandatory_header = is_authorities_change_enacted;
-			let pays_fee = if is_mandatory_header { Pays::No } else { Pays::Yes };
+			let pays_fee = reward_relayer::<T, I>(&relayer, is_mandatory_header, improved_by);
 
 			Ok(pays_fee.into())
 		}
@@ -188,7 +322,7 @@ pub mod pallet {
 		/// This function is only allowed to be called from a trusted origin and writes to storage
 		/// with practically no checks in terms of the validity of the data. It is important that
 		/// you ensure that valid data is being passed in.
-		#[pallet::weight((T::DbWeight::get().reads_writes(2, 5), DispatchClass::Operational))]
+		#[pallet::weight((T::DbWeight::get().reads_writes(2, 7), DispatchClass::Operational))]
 		pub fn initialize(
 			origin: OriginFor<T>,
 			init_data: super::InitializationData<BridgedHeader<T, I>>,
@@ -250,6 +384,268 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Enact forced GRANDPA authority set change, signalled by the `forced_change_header`.
+		///
+		/// Forced changes are used by GRANDPA to recover from stalls, so they can't be verified
+		/// the regular way. This function is only allowed to be called by the `PalletOwner` or
+		/// root. The header with the forced change signal is trusted, but the finality of the
+		/// `finality_target` must be proved by the new authority set. The `finality_target` must
+		/// be the `forced_change_header` itself, or its descendant that is not older than the
+		/// block where the forced change is enacted.
+		///
+		/// The `forced_change_ancestry` must contain all headers between the
+		/// `forced_change_header` and the `finality_target` (both exclusive), ordered by their
+		/// numbers. It is used to prove that the `finality_target` descends from the
+		/// `forced_change_header`.
+		///
+		/// The pallet doesn't need to be operational to enact the forced change.
+		#[pallet::weight((
+			T::WeightInfo::submit_finality_proof(
+				justification.commit.precommits.len().try_into().unwrap_or(u32::MAX),
+				justification
+					.votes_ancestries
+					.len()
+					.saturating_add(forced_change_ancestry.len())
+					.try_into()
+					.unwrap_or(u32::MAX),
+			)
+			.saturating_add(header_import_db_weight::<T, I>())
+			// forced change is inserted into the `AuthoritySetHistory` and the oldest set is pruned
+			.saturating_add(T::DbWeight::get().writes(2)),
+			DispatchClass::Operational,
+		))]
+		pub fn enact_forced_change(
+			origin: OriginFor<T>,
+			forced_change_header: Box<BridgedHeader<T, I>>,
+			forced_change_ancestry: sp_std::vec::Vec<BridgedHeader<T, I>>,
+			finality_target: Box<BridgedHeader<T, I>>,
+			justification: GrandpaJustification<BridgedHeader<T, I>>,
+		) -> DispatchResultWithPostInfo {
+			ensure_owner_or_root::<T, I>(origin)?;
+
+			let best_finalized = <ImportedHeaders<T, I>>::get(<BestFinalized<T, I>>::get())
+				.ok_or(<Error<T, I>>::NotInitialized)?;
+			ensure!(
+				best_finalized.number() < forced_change_header.number(),
+				<Error<T, I>>::OldHeader
+			);
+
+			let (_, change) = super::find_forced_change(&*forced_change_header)
+				.ok_or(<Error<T, I>>::MissingForcedChange)?;
+			let enacted_at = *forced_change_header.number() + change.delay;
+			ensure!(*finality_target.number() >= enacted_at, <Error<T, I>>::ForcedChangeNotEnacted);
+			ensure!(
+				super::is_descendant_of(
+					&finality_target,
+					&forced_change_ancestry,
+					forced_change_header.hash(),
+				),
+				<Error<T, I>>::InvalidForcedChangeAncestry
+			);
+
+			// finality of the target header must be proved by the new authority set
+			let current_set_id = <CurrentAuthoritySet<T, I>>::get().set_id;
+			let next_authorities = bp_header_chain::AuthoritySet {
+				authorities: change.next_authorities,
+				set_id: current_set_id + 1,
+			};
+			let (hash, number) = (finality_target.hash(), *finality_target.number());
+			verify_justification::<T, I>(&justification, hash, number, next_authorities.clone())?;
+
+			// forced change overrides any pending regular change
+			insert_authority_set::<T, I>(&next_authorities, enacted_at);
+			<PendingChange<T, I>>::kill();
+			log::warn!(
+				target: "runtime::bridge-grandpa",
+				"Forced transition from authority set {} to {} at {:?}! New authorities are: {:?}",
+				current_set_id,
+				next_authorities.set_id,
+				hash,
+				next_authorities,
+			);
+
+			// the target header may also schedule the regular change
+			if hash != forced_change_header.hash() {
+				try_enact_authority_change::<T, I>(&finality_target, next_authorities.set_id)?;
+			}
+			insert_header::<T, I>(*finality_target, hash, true);
+
+			Ok(().into())
+		}
+
+		/// Verify and import multiple headers, each finalized according to its finality proof.
+		///
+		/// Headers are verified and imported in the given order, so the batch may cover multiple
+		/// authority set changes. The batch may contain at most `MaxBatchSize` headers and every
+		/// header counts as a separate request against `MaxRequests`. If any of the headers is
+		/// rejected, the whole batch is rejected.
+		///
+		/// The transaction is free only if all headers in the batch are free (see
+		/// `FreeHeadersInterval`).
+		#[pallet::weight(finality_proofs.iter().fold(0, |weight: Weight, (_, justification)| {
+			weight
+				.saturating_add(T::WeightInfo::submit_finality_proof(
+					justification.commit.precommits.len().try_into().unwrap_or(u32::MAX),
+					justification.votes_ancestries.len().try_into().unwrap_or(u32::MAX),
+				))
+				.saturating_add(header_import_db_weight::<T, I>())
+				.saturating_add(relayer_reward_db_weight::<T, I>())
+		}))]
+		#[transactional]
+		pub fn submit_finality_proofs(
+			origin: OriginFor<T>,
+			finality_proofs: sp_std::vec::Vec<(
+				Box<BridgedHeader<T, I>>,
+				GrandpaJustification<BridgedHeader<T, I>>,
+			)>,
+		) -> DispatchResultWithPostInfo {
+			ensure_operational::<T, I>()?;
+			let relayer = ensure_signed(origin)?;
+
+			ensure!(!finality_proofs.is_empty(), <Error<T, I>>::EmptyBatch);
+			let batch_size: u32 = finality_proofs.len().try_into().unwrap_or(u32::MAX);
+			ensure!(batch_size <= T::MaxBatchSize::get(), <Error<T, I>>::TooLargeBatch);
+			ensure!(
+				Self::request_count().saturating_add(batch_size) <= T::MaxRequests::get(),
+				<Error<T, I>>::TooManyRequests
+			);
+
+			let mut pays_fee = Pays::No;
+			for (finality_target, justification) in finality_proofs {
+				let (is_mandatory_header, improved_by) =
+					import_finality_proof::<T, I>(*finality_target, justification)?;
+				if reward_relayer::<T, I>(&relayer, is_mandatory_header, improved_by) == Pays::Yes {
+					pays_fee = Pays::Yes;
+				}
+			}
+			<RequestCount<T, I>>::mutate(|count| *count += batch_size);
+
+			Ok(pays_fee.into())
+		}
+
+		/// Report that the current authority set has finalized two conflicting headers.
+		///
+		/// Headers are conflicting if they can't belong to the same chain: they are either
+		/// different headers at the same height, or the higher header doesn't descend from the
+		/// lower header. In the latter case, `higher_header_ancestry` must contain ancestors of
+		/// the higher header, down to the height of the lower header (or to the next height),
+		/// ordered by their numbers. It may be empty if the higher header is at the next height.
+		/// Both justifications must be generated by the current authority set, known to the
+		/// pallet.
+		///
+		/// If the report is valid, all pallet operations are halted and the reporter is rewarded
+		/// (see `RelayerRewardPayment`). The transaction is free then.
+		#[pallet::weight(T::WeightInfo::submit_finality_proof(
+			first_justification.commit.precommits.len().try_into().unwrap_or(u32::MAX),
+			first_justification.votes_ancestries.len().try_into().unwrap_or(u32::MAX),
+		).saturating_add(T::WeightInfo::submit_finality_proof(
+			second_justification.commit.precommits.len().try_into().unwrap_or(u32::MAX),
+			// verifying the higher header ancestry is similar to verifying votes ancestries
+			second_justification
+				.votes_ancestries
+				.len()
+				.saturating_add(higher_header_ancestry.len())
+				.try_into()
+				.unwrap_or(u32::MAX),
+		)))]
+		pub fn report_conflicting_finality(
+			origin: OriginFor<T>,
+			first_header: Box<BridgedHeader<T, I>>,
+			first_justification: GrandpaJustification<BridgedHeader<T, I>>,
+			second_header: Box<BridgedHeader<T, I>>,
+			second_justification: GrandpaJustification<BridgedHeader<T, I>>,
+			higher_header_ancestry: sp_std::vec::Vec<BridgedHeader<T, I>>,
+		) -> DispatchResultWithPostInfo {
+			ensure_operational::<T, I>()?;
+			let reporter = ensure_signed(origin)?;
+
+			ensure!(<BestFinalized<T, I>>::exists(), <Error<T, I>>::NotInitialized);
+			ensure!(
+				super::are_conflicting_headers(
+					&*first_header,
+					&*second_header,
+					&higher_header_ancestry,
+				),
+				<Error<T, I>>::NotConflictingFinality
+			);
+
+			// both headers must be finalized by the current authority set
+			let authority_set = <CurrentAuthoritySet<T, I>>::get();
+			let set_id = authority_set.set_id;
+			let (first_hash, second_hash) = (first_header.hash(), second_header.hash());
+			verify_justification::<T, I>(
+				&first_justification,
+				first_hash,
+				*first_header.number(),
+				authority_set.clone(),
+			)?;
+			verify_justification::<T, I>(
+				&second_justification,
+				second_hash,
+				*second_header.number(),
+				authority_set,
+			)?;
+
+			<IsHalted<T, I>>::put(true);
+			log::error!(
+				target: "runtime::bridge-grandpa",
+				"Authority set {} has finalized conflicting headers {:?} and {:?}. Stopping pallet operations.",
+				set_id,
+				first_hash,
+				second_hash,
+			);
+
+			Self::deposit_event(Event::ConflictingFinalityReported(
+				reporter.clone(),
+				set_id,
+				first_hash,
+				second_hash,
+			));
+			T::RelayerRewardPayment::pay_reward(&reporter, &relayer_fund_account_id::<T, I>());
+
+			Ok(Pays::No.into())
+		}
+
+		/// Verify and import a header that is older than the best finalized header.
+		///
+		/// Finality of the header must be proved by the authority set that has been active at
+		/// the header height, according to the `AuthoritySetHistory`. The header may then be used
+		/// to verify storage proofs, but it doesn't change the best finalized header. Authority
+		/// set changes, signalled by the header, are ignored.
+		///
+		/// Historical headers are never free and relayers are not rewarded for importing them.
+		#[pallet::weight(T::WeightInfo::submit_finality_proof(
+			justification.commit.precommits.len().try_into().unwrap_or(u32::MAX),
+			justification.votes_ancestries.len().try_into().unwrap_or(u32::MAX),
+		)
+		.saturating_add(historical_header_import_db_weight::<T, I>()))]
+		pub fn submit_historical_finality_proof(
+			origin: OriginFor<T>,
+			finality_target: Box<BridgedHeader<T, I>>,
+			justification: GrandpaJustification<BridgedHeader<T, I>>,
+		) -> DispatchResultWithPostInfo {
+			ensure_operational::<T, I>()?;
+			ensure_signed(origin)?;
+
+			ensure!(Self::request_count() < T::MaxRequests::get(), <Error<T, I>>::TooManyRequests);
+
+			let (hash, number) = (finality_target.hash(), *finality_target.number());
+			let best_finalized = <ImportedHeaders<T, I>>::get(<BestFinalized<T, I>>::get())
+				.ok_or(<Error<T, I>>::NotInitialized)?;
+			ensure!(number < *best_finalized.number(), <Error<T, I>>::NotHistoricalHeader);
+			ensure!(!<ImportedHeaders<T, I>>::contains_key(hash), <Error<T, I>>::AlreadyImported);
+
+			let authority_set =
+				Self::authority_set_at(number).ok_or(<Error<T, I>>::UnknownAuthoritySet)?;
+			verify_justification::<T, I>(&justification, hash, number, authority_set)?;
+
+			insert_historical_header::<T, I>(*finality_target, hash);
+			<RequestCount<T, I>>::mutate(|count| *count += 1);
+			log::info!(target: "runtime::bridge-grandpa", "Successfully imported historical header with hash {:?}!", hash);
+
+			Ok(().into())
+		}
 	}
 
 	/// The current number of requests which have written to storage.
@@ -283,6 +679,26 @@ pub mod pallet {
 	pub(super) type ImportedHashesPointer<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, u32, ValueQuery>;
 
+	/// Position of the oldest imported hash in the ring buffer.
+	#[pallet::storage]
+	pub(super) type OldestImportedHashPointer<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u32, ValueQuery>;
+
+	/// A ring buffer of imported historical hashes. Ordered by the insertion time.
+	#[pallet::storage]
+	pub(super) type HistoricalHashes<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, u32, BridgedBlockHash<T, I>>;
+
+	/// Current historical hashes ring buffer position.
+	#[pallet::storage]
+	pub(super) type HistoricalHashesPointer<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u32, ValueQuery>;
+
+	/// Hashes of imported mandatory headers.
+	#[pallet::storage]
+	pub(super) type MandatoryHeaders<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, BridgedBlockHash<T, I>, ()>;
+
 	/// Headers which have been imported into the pallet.
 	#[pallet::storage]
 	pub type ImportedHeaders<T: Config<I>, I: 'static = ()> =
@@ -293,6 +709,22 @@ pub mod pallet {
 	pub(super) type CurrentAuthoritySet<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, bp_header_chain::AuthoritySet, ValueQuery>;
 
+	/// Recent GRANDPA authority sets, including the current one.
+	///
+	/// The oldest set is pruned when there are more than `AuthoritySetsToKeep` sets.
+	#[pallet::storage]
+	pub(super) type AuthoritySetHistory<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Identity,
+		sp_finality_grandpa::SetId,
+		StoredAuthoritySet<BridgedBlockNumber<T, I>>,
+	>;
+
+	/// Scheduled GRANDPA authority set change that is not yet enacted.
+	#[pallet::storage]
+	pub(super) type PendingChange<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, StoredPendingChange<BridgedBlockNumber<T, I>>>;
+
 	/// Optional pallet owner.
 	///
 	/// Pallet owner has a right to halt all pallet operations and then resume it. If it is
@@ -303,6 +735,13 @@ pub mod pallet {
 	pub type PalletOwner<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, T::AccountId, OptionQuery>;
 
+	/// Index of the rewards period and the number of rewards that have been paid in this period.
+	///
+	/// The counter is reset when the first reward of the next period is paid.
+	#[pallet::storage]
+	pub(super) type PaidRewards<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, (T::BlockNumber, u32), ValueQuery>;
+
 	/// If true, all pallet transactions are failed immediately.
 	#[pallet::storage]
 	pub(super) type IsHalted<T: Config<I>, I: 'static = ()> = StorageValue<_, bool, ValueQuery>;
@@ -339,6 +778,19 @@ pub mod pallet {
 		}
 	}
 
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// Current authority set has finalized two conflicting headers. The pallet is halted.
+		/// Contains the reporter account, the authority set id and hashes of conflicting headers.
+		ConflictingFinalityReported(
+			T::AccountId,
+			sp_finality_grandpa::SetId,
+			BridgedBlockHash<T, I>,
+			BridgedBlockHash<T, I>,
+		),
+	}
+
 	#[pallet::error]
 	pub enum Error<T, I = ()> {
 		/// The given justification is invalid for the given header.
@@ -353,7 +805,8 @@ pub mod pallet {
 		UnknownHeader,
 		/// The scheduled authority set change found in the header is unsupported by the pallet.
 		///
-		/// This is the case for non-standard (e.g forced) authority set changes.
+		/// This is the case for non-standard (e.g forced) authority set changes and for changes
+		/// that are scheduled while there's another pending change.
 		UnsupportedScheduledChange,
 		/// The pallet is not yet initialized.
 		NotInitialized,
@@ -363,54 +816,171 @@ pub mod pallet {
 		Halted,
 		/// The storage proof doesn't contains storage root. So it is invalid for given header.
 		StorageRootMismatch,
+		/// The header doesn't signal forced authority set change.
+		MissingForcedChange,
+		/// The forced authority set change is not yet enacted at the finality target header.
+		ForcedChangeNotEnacted,
+		/// The batch of finality proofs is empty.
+		EmptyBatch,
+		/// The batch of finality proofs contains more than `MaxBatchSize` proofs.
+		TooLargeBatch,
+		/// The headers, reported as conflicting, may belong to the same chain.
+		NotConflictingFinality,
+		/// The header is not older than the best finalized header known to the pallet.
+		NotHistoricalHeader,
+		/// The header has already been imported.
+		AlreadyImported,
+		/// The authority set that has finalized the header is unknown to the pallet.
+		UnknownAuthoritySet,
+		/// The finality target is not proved to be the forced change header or its descendant.
+		InvalidForcedChangeAncestry,
+	}
+
+	/// Verify that the header is finalized according to the given finality proof and import it.
+	///
+	/// Returned value will indicate if the header is mandatory (i.e. it enacts or schedules
+	/// authority set change) and the number of blocks it has improved the best finalized header by.
+	pub(crate) fn import_finality_proof<T: Config<I>, I: 'static>(
+		finality_target: BridgedHeader<T, I>,
+		justification: GrandpaJustification<BridgedHeader<T, I>>,
+	) -> Result<(bool, BridgedBlockNumber<T, I>), sp_runtime::DispatchError> {
+		let (hash, number) = (finality_target.hash(), finality_target.number());
+		log::trace!(target: "runtime::bridge-grandpa", "Going to try and finalize header {:?}", finality_target);
+
+		let best_finalized = match <ImportedHeaders<T, I>>::get(<BestFinalized<T, I>>::get()) {
+			Some(best_finalized) => best_finalized,
+			None => {
+				log::error!(
+					target: "runtime::bridge-grandpa",
+					"Cannot finalize header {:?} because pallet is not yet initialized",
+					finality_target,
+				);
+				fail!(<Error<T, I>>::NotInitialized);
+			},
+		};
+
+		// We do a quick check here to ensure that our header chain is making progress and isn't
+		// "travelling back in time" (which could be indicative of something bad, e.g a
+		// hard-fork).
+		ensure!(best_finalized.number() < number, <Error<T, I>>::OldHeader);
+
+		// headers that are beyond the header which enacts pending change are finalized by the
+		// next authority set
+		let authority_set = <CurrentAuthoritySet<T, I>>::get();
+		let set_id = authority_set.set_id;
+		let authority_set = match <PendingChange<T, I>>::get() {
+			Some(pending_change) if *number > pending_change.enact_at =>
+				bp_header_chain::AuthoritySet {
+					authorities: pending_change.next_authorities,
+					set_id: set_id + 1,
+				},
+			_ => authority_set,
+		};
+		verify_justification::<T, I>(&justification, hash, *number, authority_set)?;
+
+		let improved_by = number.saturating_sub(*best_finalized.number());
+		let is_authorities_change_enacted =
+			try_enact_authority_change::<T, I>(&finality_target, set_id)?;
+		insert_header::<T, I>(finality_target, hash, is_authorities_change_enacted);
+		log::info!(target: "runtime::bridge-grandpa", "Successfully imported finalized header with hash {:?}!", hash);
+
+		Ok((is_authorities_change_enacted, improved_by))
 	}
 
 	/// Check the given header for a GRANDPA scheduled authority set change. If a change
-	/// is found it will be enacted immediately.
+	/// is found it will be enacted immediately (if it has zero delay), or stored as pending.
+	///
+	/// Pending change is enacted when the header, that is enacting it, or any of its descendants
+	/// is imported.
 	///
-	/// This function does not support forced changes, or scheduled changes with delays
-	/// since these types of changes are indicative of abnormal behavior from GRANDPA.
+	/// This function does not support forced changes, since these types of changes are
+	/// indicative of abnormal behavior from GRANDPA.
 	///
-	/// Returned value will indicate if a change was enacted or not.
+	/// Returned value will indicate if a change was enacted or scheduled by the header.
 	pub(crate) fn try_enact_authority_change<T: Config<I>, I: 'static>(
 		header: &BridgedHeader<T, I>,
 		current_set_id: sp_finality_grandpa::SetId,
 	) -> Result<bool, sp_runtime::DispatchError> {
 		let mut change_enacted = false;
+		let mut current_set_id = current_set_id;
 
-		// We don't support forced changes - at that point governance intervention is required.
+		// We don't support forced changes here - at that point governance intervention is required
+		// (see `enact_forced_change`).
 		ensure!(
 			super::find_forced_change(header).is_none(),
 			<Error<T, I>>::UnsupportedScheduledChange
 		);
 
-		if let Some(change) = super::find_scheduled_change(header) {
-			// GRANDPA only includes a `delay` for forced changes, so this isn't valid.
-			ensure!(change.delay == Zero::zero(), <Error<T, I>>::UnsupportedScheduledChange);
-
-			// TODO [#788]: Stop manually increasing the `set_id` here.
-			let next_authorities = bp_header_chain::AuthoritySet {
-				authorities: change.next_authorities,
-				set_id: current_set_id + 1,
-			};
+		if let Some(pending_change) = <PendingChange<T, I>>::get() {
+			if *header.number() >= pending_change.enact_at {
+				<PendingChange<T, I>>::kill();
+				enact_authority_set::<T, I>(
+					pending_change.next_authorities,
+					current_set_id,
+					pending_change.enact_at.saturating_add(One::one()),
+				);
+				current_set_id += 1;
+				change_enacted = true;
+			}
+		}
 
-			// Since our header schedules a change and we know the delay is 0, it must also enact
-			// the change.
-			<CurrentAuthoritySet<T, I>>::put(&next_authorities);
+		if let Some(change) = super::find_scheduled_change(header) {
+			// GRANDPA doesn't allow scheduling regular change while there's another one pending
+			ensure!(!<PendingChange<T, I>>::exists(), <Error<T, I>>::UnsupportedScheduledChange);
+
+			if change.delay.is_zero() {
+				// Since our header schedules a change and the delay is 0, it must also enact
+				// the change.
+				enact_authority_set::<T, I>(
+					change.next_authorities,
+					current_set_id,
+					(*header.number()).saturating_add(One::one()),
+				);
+			} else {
+				let enact_at = (*header.number()).saturating_add(change.delay);
+				<PendingChange<T, I>>::put(StoredPendingChange {
+					enact_at,
+					next_authorities: change.next_authorities,
+				});
+
+				log::info!(
+					target: "runtime::bridge-grandpa",
+					"Scheduled transition from authority set {} to {} at {:?}",
+					current_set_id,
+					current_set_id + 1,
+					enact_at,
+				);
+			}
 			change_enacted = true;
-
-			log::info!(
-				target: "runtime::bridge-grandpa",
-				"Transitioned from authority set {} to {}! New authorities are: {:?}",
-				current_set_id,
-				current_set_id + 1,
-				next_authorities,
-			);
 		};
 
 		Ok(change_enacted)
 	}
 
+	/// Replace current authority set with the next one.
+	///
+	/// The `first_block` is the number of the first header that is finalized by the next set.
+	fn enact_authority_set<T: Config<I>, I: 'static>(
+		next_authorities: AuthorityList,
+		current_set_id: sp_finality_grandpa::SetId,
+		first_block: BridgedBlockNumber<T, I>,
+	) {
+		// TODO [#788]: Stop manually increasing the `set_id` here.
+		let next_authorities = bp_header_chain::AuthoritySet {
+			authorities: next_authorities,
+			set_id: current_set_id + 1,
+		};
+		insert_authority_set::<T, I>(&next_authorities, first_block);
+
+		log::info!(
+			target: "runtime::bridge-grandpa",
+			"Transitioned from authority set {} to {}! New authorities are: {:?}",
+			current_set_id,
+			current_set_id + 1,
+			next_authorities,
+		);
+	}
+
 	/// Verify a GRANDPA justification (finality proof) for a given header.
 	///
 	/// Will use the GRANDPA current authorities known to the pallet.
@@ -446,6 +1016,27 @@ pub mod pallet {
 		})?)
 	}
 
+	/// Make given authority set the current one and record it in the authority sets history.
+	///
+	/// The oldest authority set is pruned from the history if there are more than
+	/// `AuthoritySetsToKeep` sets.
+	pub(crate) fn insert_authority_set<T: Config<I>, I: 'static>(
+		authority_set: &bp_header_chain::AuthoritySet,
+		first_block: BridgedBlockNumber<T, I>,
+	) {
+		<CurrentAuthoritySet<T, I>>::put(authority_set);
+		<AuthoritySetHistory<T, I>>::insert(
+			authority_set.set_id,
+			StoredAuthoritySet { authorities: authority_set.authorities.clone(), first_block },
+		);
+
+		let sets_to_keep = T::AuthoritySetsToKeep::get().max(1);
+		if let Some(pruned_set_id) = authority_set.set_id.checked_sub(sets_to_keep.into()) {
+			log::debug!(target: "runtime::bridge-grandpa", "Pruning old authority set: {}.", pruned_set_id);
+			<AuthoritySetHistory<T, I>>::remove(pruned_set_id);
+		}
+	}
+
 	/// Import a previously verified header to the storage.
 	///
 	/// Note this function solely takes care of updating the storage and pruning old entries,
@@ -453,19 +1044,102 @@ pub mod pallet {
 	pub(crate) fn insert_header<T: Config<I>, I: 'static>(
 		header: BridgedHeader<T, I>,
 		hash: BridgedBlockHash<T, I>,
+		is_mandatory_header: bool,
+	) {
+		<BestFinalized<T, I>>::put(hash);
+		if is_mandatory_header {
+			<MandatoryHeaders<T, I>>::insert(hash, ());
+		}
+		insert_imported_header::<T, I>(header, hash);
+	}
+
+	/// Import a previously verified historical header to the storage.
+	///
+	/// Unlike `insert_header`, this function doesn't change the best finalized header. The
+	/// header occupies a slot of the separate ring buffer of historical headers, so it never
+	/// causes pruning of recent headers.
+	pub(crate) fn insert_historical_header<T: Config<I>, I: 'static>(
+		header: BridgedHeader<T, I>,
+		hash: BridgedBlockHash<T, I>,
+	) {
+		let index = <HistoricalHashesPointer<T, I>>::get();
+		let pruning = <HistoricalHashes<T, I>>::try_get(index);
+		<ImportedHeaders<T, I>>::insert(hash, header);
+		<HistoricalHashes<T, I>>::insert(index, hash);
+
+		// Update ring buffer pointer and remove old historical header.
+		let next_index = (index + 1) % T::HistoricalHeadersToKeep::get().max(1);
+		<HistoricalHashesPointer<T, I>>::put(next_index);
+		if let Ok(hash) = pruning {
+			log::debug!(target: "runtime::bridge-grandpa", "Pruning old historical header: {:?}.", hash);
+			<ImportedHeaders<T, I>>::remove(hash);
+		}
+	}
+
+	/// Insert header into the ring buffer of imported headers, pruning the oldest header if the
+	/// buffer is full. Expired headers are pruned then.
+	fn insert_imported_header<T: Config<I>, I: 'static>(
+		header: BridgedHeader<T, I>,
+		hash: BridgedBlockHash<T, I>,
 	) {
 		let index = <ImportedHashesPointer<T, I>>::get();
 		let pruning = <ImportedHashes<T, I>>::try_get(index);
-		<BestFinalized<T, I>>::put(hash);
 		<ImportedHeaders<T, I>>::insert(hash, header);
 		<ImportedHashes<T, I>>::insert(index, hash);
 
 		// Update ring buffer pointer and remove old header.
-		<ImportedHashesPointer<T, I>>::put((index + 1) % T::HeadersToKeep::get());
+		let next_index = (index + 1) % T::HeadersToKeep::get();
+		<ImportedHashesPointer<T, I>>::put(next_index);
 		if let Ok(hash) = pruning {
 			log::debug!(target: "runtime::bridge-grandpa", "Pruning old header: {:?}.", hash);
 			<ImportedHeaders<T, I>>::remove(hash);
+			<MandatoryHeaders<T, I>>::remove(hash);
+			// the ring buffer is full, so the next header is the oldest one
+			<OldestImportedHashPointer<T, I>>::put(next_index);
+		}
+
+		prune_expired_headers::<T, I>();
+	}
+
+	/// Prune oldest imported headers that are out of the retention window.
+	///
+	/// Does nothing unless the `HeadersRetentionPolicy::BlockNumber` policy is used. At most
+	/// `MAX_EXPIRED_HEADERS_TO_PRUNE` headers are pruned.
+	fn prune_expired_headers<T: Config<I>, I: 'static>() {
+		let (blocks, mandatory_blocks) = match T::HeadersRetention::get() {
+			HeadersRetentionPolicy::Count => return,
+			HeadersRetentionPolicy::BlockNumber { blocks, mandatory_blocks } =>
+				(blocks, mandatory_blocks),
+		};
+		let best_finalized_number = match <ImportedHeaders<T, I>>::get(<BestFinalized<T, I>>::get())
+		{
+			Some(best_finalized) => *best_finalized.number(),
+			None => return,
+		};
+
+		let mut oldest_index = <OldestImportedHashPointer<T, I>>::get();
+		for _ in 0..MAX_EXPIRED_HEADERS_TO_PRUNE {
+			let oldest_hash = match <ImportedHashes<T, I>>::get(oldest_index) {
+				Some(oldest_hash) => oldest_hash,
+				None => break,
+			};
+			let oldest_number = match <ImportedHeaders<T, I>>::get(oldest_hash) {
+				Some(oldest_header) => *oldest_header.number(),
+				None => break,
+			};
+			let is_mandatory_header = <MandatoryHeaders<T, I>>::contains_key(oldest_hash);
+			let retention_blocks = if is_mandatory_header { mandatory_blocks } else { blocks };
+			if best_finalized_number.saturating_sub(oldest_number) <= retention_blocks.into() {
+				break
+			}
+
+			log::debug!(target: "runtime::bridge-grandpa", "Pruning expired header: {:?}.", oldest_hash);
+			<ImportedHashes<T, I>>::remove(oldest_index);
+			<ImportedHeaders<T, I>>::remove(oldest_hash);
+			<MandatoryHeaders<T, I>>::remove(oldest_hash);
+			oldest_index = (oldest_index + 1) % T::HeadersToKeep::get();
 		}
+		<OldestImportedHashPointer<T, I>>::put(oldest_index);
 	}
 
 	/// Since this writes to storage with no real checks this should only be used in functions that
@@ -475,13 +1149,14 @@ pub mod pallet {
 	) {
 		let super::InitializationData { header, authority_list, set_id, is_halted } = init_params;
 
-		let initial_hash = header.hash();
+		let (initial_hash, initial_number) = (header.hash(), *header.number());
 		<InitialHash<T, I>>::put(initial_hash);
 		<ImportedHashesPointer<T, I>>::put(0);
-		insert_header::<T, I>(*header, initial_hash);
+		<OldestImportedHashPointer<T, I>>::put(0);
+		insert_header::<T, I>(*header, initial_hash, false);
 
 		let authority_set = bp_header_chain::AuthoritySet::new(authority_list, set_id);
-		<CurrentAuthoritySet<T, I>>::put(authority_set);
+		insert_authority_set::<T, I>(&authority_set, initial_number);
 
 		<IsHalted<T, I>>::put(is_halted);
 	}
@@ -505,10 +1180,61 @@ pub mod pallet {
 				Default::default(),
 			);
 			let hash = header.hash();
-			insert_header::<T, I>(header, hash);
+			insert_header::<T, I>(header, hash, false);
+		}
+	}
+
+	/// Decide whether the relayer pays fee for importing the header and reward the relayer if it
+	/// doesn't.
+	///
+	/// Mandatory headers are always free. Headers that improve the best finalized header by at
+	/// least `FreeHeadersInterval` blocks are free while there are unused rewards in the current
+	/// rewards period.
+	pub(crate) fn reward_relayer<T: Config<I>, I: 'static>(
+		relayer: &T::AccountId,
+		is_mandatory_header: bool,
+		improved_by: BridgedBlockNumber<T, I>,
+	) -> Pays {
+		let is_useful_header = is_mandatory_header ||
+			T::FreeHeadersInterval::get()
+				.map(|interval| improved_by >= interval.into())
+				.unwrap_or(false);
+		if !is_useful_header {
+			return Pays::Yes
+		}
+
+		let is_reward_allowed = try_use_reward::<T, I>();
+		if is_reward_allowed {
+			T::RelayerRewardPayment::pay_reward(relayer, &relayer_fund_account_id::<T, I>());
+		}
+
+		if is_mandatory_header || is_reward_allowed {
+			Pays::No
+		} else {
+			Pays::Yes
 		}
 	}
 
+	/// Increase the number of rewards, paid in the current rewards period.
+	///
+	/// Returns false if `MaxRewardsPerPeriod` rewards have already been paid in this period.
+	fn try_use_reward<T: Config<I>, I: 'static>() -> bool {
+		let period = frame_system::Pallet::<T>::block_number() /
+			T::RewardsPeriod::get().max(One::one());
+		<PaidRewards<T, I>>::mutate(|(paid_period, paid_rewards)| {
+			if *paid_period != period {
+				*paid_period = period;
+				*paid_rewards = 0;
+			}
+			if *paid_rewards >= T::MaxRewardsPerPeriod::get() {
+				return false
+			}
+
+			*paid_rewards += 1;
+			true
+		})
+	}
+
 	/// Ensure that the origin is either root, or `PalletOwner`.
 	fn ensure_owner_or_root<T: Config<I>, I: 'static>(origin: T::Origin) -> Result<(), BadOrigin> {
 		match origin.into() {
@@ -530,14 +1256,64 @@ pub mod pallet {
 	}
 }
 
-impl<T: Config<I>, I: 'static> Pallet<T, I> {
-	/// Get the best finalized header the pallet knows of.
-	///
-	/// Returns a dummy header if there is no best header. This can only happen
-	/// if the pallet has not been initialized yet.
-	pub fn best_finalized() -> BridgedHeader<T, I> {
-		let hash = <BestFinalized<T, I>>::get();
-		<ImportedHeaders<T, I>>::get(hash).unwrap_or_else(|| {
+/// Returns account id of the fund that is used to pay rewards to finality relayers.
+///
+/// Every pallet instance has its own fund account.
+pub fn relayer_fund_account_id<T: Config<I>, I: 'static>() -> T::AccountId {
+	let entropy = (b"relayer-fund-account", <Pallet<T, I> as PalletInfoAccess>::name())
+		.using_encoded(BlakeTwo256::hash);
+	Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+		.expect("infinite length input; no invalid inputs for type; qed")
+}
+
+/// Reward payment that transfers fixed `Reward` from the relayers fund account to the relayer.
+pub struct InstantCurrencyRewards<AccountId, C, Reward> {
+	_phantom: PhantomData<(AccountId, C, Reward)>,
+}
+
+impl<AccountId, C, Reward> FinalityProofRewardPayment<AccountId>
+	for InstantCurrencyRewards<AccountId, C, Reward>
+where
+	AccountId: sp_std::fmt::Debug,
+	C: Currency<AccountId>,
+	Reward: Get<C::Balance>,
+{
+	fn pay_reward(relayer: &AccountId, relayer_fund_account: &AccountId) {
+		let reward = Reward::get();
+		let pay_result = C::transfer(
+			relayer_fund_account,
+			relayer,
+			reward,
+			// the relayer fund account must stay alive
+			ExistenceRequirement::KeepAlive,
+		);
+
+		match pay_result {
+			Ok(_) => log::trace!(
+				target: "runtime::bridge-grandpa",
+				"Rewarded finality relayer {:?} with {:?}",
+				relayer,
+				reward,
+			),
+			Err(error) => log::error!(
+				target: "runtime::bridge-grandpa",
+				"Failed to pay finality relayer {:?} reward {:?}: {:?}",
+				relayer,
+				reward,
+				error,
+			),
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Get the best finalized header the pallet knows of.
+	///
+	/// Returns a dummy header if there is no best header. This can only happen
+	/// if the pallet has not been initialized yet.
+	pub fn best_finalized() -> BridgedHeader<T, I> {
+		let hash = <BestFinalized<T, I>>::get();
+		<ImportedHeaders<T, I>>::get(hash).unwrap_or_else(|| {
 			<BridgedHeader<T, I>>::new(
 				Default::default(),
 				Default::default(),
@@ -553,6 +1329,35 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		<ImportedHeaders<T, I>>::contains_key(hash)
 	}
 
+	/// Get the GRANDPA authority set that has finalized the header with given number.
+	///
+	/// Returns `None` if the pallet is not initialized, or if the authority set has already been
+	/// pruned from the `AuthoritySetHistory`.
+	pub fn authority_set_at(
+		number: BridgedBlockNumber<T, I>,
+	) -> Option<bp_header_chain::AuthoritySet> {
+		// headers that are beyond the header which enacts pending change are finalized by the
+		// next authority set
+		let current_set_id = <CurrentAuthoritySet<T, I>>::get().set_id;
+		if let Some(pending_change) = <PendingChange<T, I>>::get() {
+			if number > pending_change.enact_at {
+				return Some(bp_header_chain::AuthoritySet::new(
+					pending_change.next_authorities,
+					current_set_id + 1,
+				))
+			}
+		}
+
+		let mut set_id = current_set_id;
+		loop {
+			let authority_set = <AuthoritySetHistory<T, I>>::get(set_id)?;
+			if authority_set.first_block <= number {
+				return Some(bp_header_chain::AuthoritySet::new(authority_set.authorities, set_id))
+			}
+			set_id = set_id.checked_sub(1)?;
+		}
+	}
+
 	/// Verify that the passed storage proof is valid, given it is crafted using
 	/// known finalized header. If the proof is valid, then the `parse` callback
 	/// is called and the function returns its result.
@@ -568,6 +1373,176 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		Ok(parse(storage_proof_checker))
 	}
+
+	/// Ensure that the pallet storage is consistent.
+	///
+	/// Returns an error if any of the storage invariants is broken.
+	#[cfg(any(feature = "try-runtime", test))]
+	pub fn do_try_state() -> Result<(), &'static str> {
+		let headers_to_keep = T::HeadersToKeep::get();
+		let pointer = <ImportedHashesPointer<T, I>>::get();
+		ensure!(pointer < headers_to_keep, "Imported hashes pointer is outside of the ring buffer");
+		ensure!(
+			<OldestImportedHashPointer<T, I>>::get() < headers_to_keep,
+			"Oldest imported hash pointer is outside of the ring buffer"
+		);
+
+		// every header in ring buffers is imported and every imported header is in one of buffers
+		let mut imported_hashes = 0;
+		for (index, hash) in <ImportedHashes<T, I>>::iter() {
+			ensure!(index < headers_to_keep, "Imported hash is outside of the ring buffer");
+			ensure!(
+				<ImportedHeaders<T, I>>::contains_key(hash),
+				"Header from the ring buffer is not imported"
+			);
+			imported_hashes += 1;
+		}
+		let historical_headers_to_keep = T::HistoricalHeadersToKeep::get().max(1);
+		ensure!(
+			<HistoricalHashesPointer<T, I>>::get() < historical_headers_to_keep,
+			"Historical hashes pointer is outside of the ring buffer"
+		);
+		let mut historical_hashes = 0;
+		for (index, hash) in <HistoricalHashes<T, I>>::iter() {
+			ensure!(
+				index < historical_headers_to_keep,
+				"Historical hash is outside of the ring buffer"
+			);
+			ensure!(
+				<ImportedHeaders<T, I>>::contains_key(hash),
+				"Header from the historical ring buffer is not imported"
+			);
+			historical_hashes += 1;
+		}
+		ensure!(
+			<ImportedHeaders<T, I>>::iter_keys().count() == imported_hashes + historical_hashes,
+			"Imported header is missing from the ring buffer"
+		);
+		for hash in <MandatoryHeaders<T, I>>::iter_keys() {
+			ensure!(
+				<ImportedHeaders<T, I>>::contains_key(hash),
+				"Mandatory header is not imported"
+			);
+		}
+
+		// if the pallet is initialized, the best finalized header is the latest imported header.
+		// Historical headers may be imported after it, but they are older
+		if imported_hashes != 0 {
+			let best_finalized = <ImportedHeaders<T, I>>::get(<BestFinalized<T, I>>::get())
+				.ok_or("Best finalized header is not imported")?;
+			ensure!(
+				<ImportedHeaders<T, I>>::iter_values()
+					.all(|header| header.number() <= best_finalized.number()),
+				"Best finalized header is not the latest imported header"
+			);
+
+			let current_authority_set = <CurrentAuthoritySet<T, I>>::get();
+			ensure!(
+				!current_authority_set.authorities.is_empty(),
+				"Current authority set is empty"
+			);
+			ensure!(
+				<AuthoritySetHistory<T, I>>::get(current_authority_set.set_id)
+					.map(|authority_set| authority_set.authorities) ==
+					Some(current_authority_set.authorities),
+				"Current authority set is missing from the history"
+			);
+		}
+		ensure!(
+			<AuthoritySetHistory<T, I>>::iter_keys().count() <=
+				T::AuthoritySetsToKeep::get().max(1) as usize,
+			"Too many authority sets in the history"
+		);
+
+		Ok(())
+	}
+}
+
+/// Weight of storage accesses of the header import that are not accounted by the
+/// `submit_finality_proof` benchmark.
+///
+/// When the header is imported, the pallet may:
+///
+/// - update `MandatoryHeaders` and prune the oldest header from the ring buffer, updating the
+///   `OldestImportedHashPointer`;
+///
+/// - prune up to `MAX_EXPIRED_HEADERS_TO_PRUNE` expired headers;
+///
+/// - enact or schedule authority set change, updating the `PendingChange` and the
+///   `AuthoritySetHistory`.
+pub(crate) fn header_import_db_weight<T: Config<I>, I: 'static>() -> Weight {
+	let db_weight = T::DbWeight::get();
+	// `MandatoryHeaders` insertion, `MandatoryHeaders` removal and `OldestImportedHashPointer`
+	// update when the ring buffer is full
+	let insertion = db_weight.writes(3);
+	// reads of the best finalized header and the `OldestImportedHashPointer`, update of the
+	// `OldestImportedHashPointer` and, for every expired header: reads of `ImportedHashes`,
+	// `ImportedHeaders`, `MandatoryHeaders` and removals from these maps
+	let pruning = db_weight.reads_writes(3, 1).saturating_add(
+		db_weight.reads_writes(3, 3).saturating_mul(MAX_EXPIRED_HEADERS_TO_PRUNE as Weight),
+	);
+	// `PendingChange` read and update, `AuthoritySetHistory` insertion and pruning
+	let authority_set_change = db_weight.reads_writes(1, 3);
+
+	insertion.saturating_add(pruning).saturating_add(authority_set_change)
+}
+
+/// Weight of storage accesses of the relayer reward (`PaidRewards` read and update) that are not
+/// accounted by the `submit_finality_proof` benchmark.
+pub(crate) fn relayer_reward_db_weight<T: Config<I>, I: 'static>() -> Weight {
+	T::DbWeight::get().reads_writes(1, 1)
+}
+
+/// Weight of storage accesses of the historical header import that are not accounted by the
+/// `submit_finality_proof` benchmark.
+///
+/// The authority set that has finalized the header is searched in the `AuthoritySetHistory`, so
+/// every stored authority set may be read. The oldest historical header may be pruned from the
+/// `HistoricalHashes` ring buffer.
+pub(crate) fn historical_header_import_db_weight<T: Config<I>, I: 'static>() -> Weight {
+	let db_weight = T::DbWeight::get();
+	// `PendingChange` read and reads of all stored authority sets, including the missing one
+	let authority_set_search =
+		db_weight.reads((T::AuthoritySetsToKeep::get().max(1) as Weight).saturating_add(2));
+	// `HistoricalHashesPointer` and `HistoricalHashes` reads and updates, pruned header removal
+	let historical_hashes = db_weight.reads_writes(2, 3);
+
+	authority_set_search.saturating_add(historical_hashes)
+}
+
+/// Returns true if given headers can't belong to the same chain.
+///
+/// The `higher_ancestry` must contain ancestors of the higher header, ordered by their numbers.
+/// Headers are conflicting if the ancestor of the higher header (or the higher header itself) at
+/// the height of the lower header is different from the lower header. They are also conflicting
+/// if the ancestor (or the higher header itself) at the next height isn't a child of the lower
+/// header. Nothing may be said about headers if the ancestry doesn't reach these heights, or if it
+/// isn't a valid chain of headers.
+pub(crate) fn are_conflicting_headers<H: HeaderT>(
+	first: &H,
+	second: &H,
+	higher_ancestry: &[H],
+) -> bool {
+	let (lower, higher) =
+		if first.number() <= second.number() { (first, second) } else { (second, first) };
+
+	// every header of the ancestry must be the parent of the next header
+	let mut lowest_ancestor = higher;
+	for ancestor in higher_ancestry.iter().rev() {
+		if ancestor.hash() != *lowest_ancestor.parent_hash() {
+			return false
+		}
+		lowest_ancestor = ancestor;
+	}
+
+	if lowest_ancestor.number() == lower.number() {
+		return lowest_ancestor.hash() != lower.hash()
+	}
+	if *lower.number() + One::one() == *lowest_ancestor.number() {
+		return *lowest_ancestor.parent_hash() != lower.hash()
+	}
+
+	false
 }
 
 pub(crate) fn find_scheduled_change<H: HeaderT>(
@@ -606,6 +1581,30 @@ pub(crate) fn find_forced_change<H: HeaderT>(
 	header.digest().convert_first(|l| l.try_to(id).and_then(filter_log))
 }
 
+/// Returns true if the `header` is the header with `ancestor_hash` hash or its descendant.
+///
+/// The `ancestry` must contain all headers between the ancestor and the `header` (both exclusive),
+/// ordered by their numbers.
+pub(crate) fn is_descendant_of<H: HeaderT>(
+	header: &H,
+	ancestry: &[H],
+	ancestor_hash: H::Hash,
+) -> bool {
+	if ancestry.is_empty() && header.hash() == ancestor_hash {
+		return true
+	}
+
+	let mut expected_parent_hash = *header.parent_hash();
+	for ancestor in ancestry.iter().rev() {
+		if ancestor.hash() != expected_parent_hash {
+			return false
+		}
+		expected_parent_hash = *ancestor.parent_hash();
+	}
+
+	expected_parent_hash == ancestor_hash
+}
+
 /// (Re)initialize bridge with given header for using it in `pallet-bridge-messages` benchmarks.
 #[cfg(feature = "runtime-benchmarks")]
 pub fn initialize_for_benchmarks<T: Config<I>, I: 'static>(header: BridgedHeader<T, I>) {
@@ -621,7 +1620,11 @@ pub fn initialize_for_benchmarks<T: Config<I>, I: 'static>(header: BridgedHeader
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::mock::{run_test, test_header, Origin, TestHeader, TestNumber, TestRuntime};
+	use crate::mock::{
+		run_test, test_header, Event as TestEvent, FreeHeadersInterval, HeadersRetention,
+		MaxBatchSize, MaxRewardsPerPeriod, Origin, RewardsPeriod, TestHeader, TestNumber,
+		TestRewardPayment, TestRuntime,
+	};
 	use bp_test_utils::{
 		authority_list, make_default_justification, make_justification_for_header,
 		JustificationGeneratorParams, ALICE, BOB,
@@ -631,6 +1634,7 @@ mod tests {
 		assert_err, assert_noop, assert_ok, storage::generator::StorageValue,
 		weights::PostDispatchInfo,
 	};
+	use frame_system::{EventRecord, Phase};
 	use sp_runtime::{Digest, DigestItem, DispatchError};
 
 	fn initialize_substrate_bridge() {
@@ -666,6 +1670,50 @@ mod tests {
 		)
 	}
 
+	fn fork_header(number: TestNumber) -> TestHeader {
+		let mut header = test_header(number);
+		header.state_root = [42; 32].into();
+		header
+	}
+
+	fn report_conflicting_finality(
+		first_header: TestHeader,
+		second_header: TestHeader,
+	) -> frame_support::dispatch::DispatchResultWithPostInfo {
+		report_conflicting_finality_with_ancestry(first_header, second_header, vec![])
+	}
+
+	fn report_conflicting_finality_with_ancestry(
+		first_header: TestHeader,
+		second_header: TestHeader,
+		higher_header_ancestry: Vec<TestHeader>,
+	) -> frame_support::dispatch::DispatchResultWithPostInfo {
+		let first_justification = make_default_justification(&first_header);
+		let second_justification = make_default_justification(&second_header);
+
+		Pallet::<TestRuntime>::report_conflicting_finality(
+			Origin::signed(1),
+			Box::new(first_header),
+			first_justification,
+			Box::new(second_header),
+			second_justification,
+			higher_header_ancestry,
+		)
+	}
+
+	/// Returns headers `(number, number + count]` of the chain, descending from the given header.
+	fn descendants_of(header: &TestHeader, count: TestNumber) -> Vec<TestHeader> {
+		let mut parent_hash = header.hash();
+		(header.number + 1..=header.number + count)
+			.map(|number| {
+				let mut descendant = test_header(number);
+				descendant.parent_hash = parent_hash;
+				parent_hash = descendant.hash();
+				descendant
+			})
+			.collect()
+	}
+
 	fn next_block() {
 		use frame_support::traits::OnInitialize;
 
@@ -960,21 +2008,135 @@ mod tests {
 		})
 	}
 
+	fn schedule_delayed_change() -> TestHeader {
+		// header #2 schedules authority set change that is enacted at header #4
+		let mut header = test_header(2);
+		header.digest = change_log(2);
+		let justification = make_default_justification(&header);
+		assert_ok!(
+			Pallet::<TestRuntime>::submit_finality_proof(
+				Origin::signed(1),
+				Box::new(header.clone()),
+				justification
+			),
+			PostDispatchInfo { actual_weight: None, pays_fee: frame_support::weights::Pays::No },
+		);
+		header
+	}
+
+	fn submit_finality_proof_of_next_authority_set(
+		header: u8,
+	) -> frame_support::dispatch::DispatchResultWithPostInfo {
+		let header = test_header(header.into());
+		let justification = make_justification_for_header(JustificationGeneratorParams {
+			header: header.clone(),
+			set_id: 2,
+			authorities: vec![(ALICE, 1), (BOB, 1)],
+			..Default::default()
+		});
+
+		Pallet::<TestRuntime>::submit_finality_proof(
+			Origin::signed(1),
+			Box::new(header),
+			justification,
+		)
+	}
+
+	#[test]
+	fn importing_header_with_scheduled_change_delay_schedules_pending_change() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let header = schedule_delayed_change();
+
+			// the header is imported, but authority set is not changed yet
+			assert_eq!(<BestFinalized<TestRuntime>>::get(), header.hash());
+			assert_eq!(
+				<CurrentAuthoritySet<TestRuntime>>::get(),
+				bp_header_chain::AuthoritySet::new(authority_list(), 1),
+			);
+			assert_eq!(
+				<PendingChange<TestRuntime>>::get(),
+				Some(StoredPendingChange {
+					enact_at: 4,
+					next_authorities: vec![(ALICE.into(), 1), (BOB.into(), 1)],
+				}),
+			);
+		})
+	}
+
 	#[test]
-	fn importing_header_rejects_header_with_scheduled_change_delay() {
+	fn pending_change_is_enacted_by_header_that_enacts_it() {
 		run_test(|| {
 			initialize_substrate_bridge();
+			schedule_delayed_change();
 
-			// Need to update the header digest to indicate that our header signals an authority set
-			// change. However, the change doesn't happen until the next block.
-			let mut header = test_header(2);
-			header.digest = change_log(1);
+			// header #3 is still finalized by the current authority set
+			assert_ok!(submit_finality_proof(3));
+			assert!(<PendingChange<TestRuntime>>::exists());
 
-			// Create a valid justification for the header
+			// header #4 enacts the change, but it is still finalized by the current authority set
+			assert_ok!(
+				submit_finality_proof(4),
+				PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: frame_support::weights::Pays::No,
+				},
+			);
+			assert!(!<PendingChange<TestRuntime>>::exists());
+			assert_eq!(
+				<CurrentAuthoritySet<TestRuntime>>::get(),
+				bp_header_chain::AuthoritySet::new(vec![(ALICE.into(), 1), (BOB.into(), 1)], 2),
+			);
+
+			// and the next header is finalized by the new authority set
+			assert_ok!(submit_finality_proof_of_next_authority_set(5));
+		})
+	}
+
+	#[test]
+	fn pending_change_is_enacted_by_descendant_of_header_that_enacts_it() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			schedule_delayed_change();
+
+			assert_ok!(submit_finality_proof_of_next_authority_set(5));
+			assert!(!<PendingChange<TestRuntime>>::exists());
+			assert_eq!(
+				<CurrentAuthoritySet<TestRuntime>>::get(),
+				bp_header_chain::AuthoritySet::new(vec![(ALICE.into(), 1), (BOB.into(), 1)], 2),
+			);
+		})
+	}
+
+	#[test]
+	fn importing_header_rejects_header_finalized_by_wrong_authority_set_when_change_is_pending() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			schedule_delayed_change();
+
+			// header #4 must be finalized by the current authority set
+			assert_noop!(
+				submit_finality_proof_of_next_authority_set(4),
+				<Error<TestRuntime>>::InvalidJustification
+			);
+
+			// and header #5 must be finalized by the next authority set
+			assert_noop!(submit_finality_proof(5), <Error<TestRuntime>>::InvalidJustification);
+		})
+	}
+
+	#[test]
+	fn importing_header_rejects_scheduled_change_when_another_change_is_pending() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			schedule_delayed_change();
+
+			let mut header = test_header(3);
+			header.digest = change_log(0);
 			let justification = make_default_justification(&header);
 
-			// Should not be allowed to import this header
-			assert_err!(
+			assert_noop!(
 				Pallet::<TestRuntime>::submit_finality_proof(
 					Origin::signed(1),
 					Box::new(header),
@@ -1010,117 +2172,676 @@ mod tests {
 		})
 	}
 
-	#[test]
-	fn parse_finalized_storage_proof_rejects_proof_on_unknown_header() {
-		run_test(|| {
-			assert_noop!(
-				Pallet::<TestRuntime>::parse_finalized_storage_proof(
-					Default::default(),
-					sp_trie::StorageProof::new(vec![]),
-					|_| (),
-				),
-				Error::<TestRuntime>::UnknownHeader,
-			);
+	fn enact_forced_change(
+		origin: Origin,
+		forced_change_header: TestHeader,
+		forced_change_ancestry: Vec<TestHeader>,
+		finality_target: TestHeader,
+	) -> frame_support::dispatch::DispatchResultWithPostInfo {
+		// the finality target must be finalized by the new authority set
+		let justification = make_justification_for_header(JustificationGeneratorParams {
+			header: finality_target.clone(),
+			set_id: 2,
+			authorities: vec![(ALICE, 1), (BOB, 1)],
+			..Default::default()
 		});
+
+		Pallet::<TestRuntime>::enact_forced_change(
+			origin,
+			Box::new(forced_change_header),
+			forced_change_ancestry,
+			Box::new(finality_target),
+			justification,
+		)
+	}
+
+	/// Returns `count` headers that are descendants of the given header.
+	fn descendants_of(header: &TestHeader, count: TestNumber) -> Vec<TestHeader> {
+		let mut descendants: Vec<TestHeader> = Vec::with_capacity(count as usize);
+		for number in header.number + 1..=header.number + count {
+			let mut descendant = test_header(number);
+			descendant.parent_hash = descendants.last().unwrap_or(header).hash();
+			descendants.push(descendant);
+		}
+		descendants
 	}
 
 	#[test]
-	fn parse_finalized_storage_accepts_valid_proof() {
+	fn only_owner_or_root_may_enact_forced_change() {
 		run_test(|| {
-			let (state_root, storage_proof) = bp_runtime::craft_valid_storage_proof();
+			initialize_substrate_bridge();
+			PalletOwner::<TestRuntime>::put(2);
 
 			let mut header = test_header(2);
-			header.set_state_root(state_root);
-
-			let hash = header.hash();
-			<BestFinalized<TestRuntime>>::put(hash);
-			<ImportedHeaders<TestRuntime>>::insert(hash, header);
+			header.digest = forced_change_log(0);
 
-			assert_ok!(
-				Pallet::<TestRuntime>::parse_finalized_storage_proof(hash, storage_proof, |_| (),),
-				(),
+			assert_noop!(
+				enact_forced_change(Origin::signed(1), header.clone(), vec![], header.clone()),
+				DispatchError::BadOrigin,
 			);
-		});
+			assert_ok!(enact_forced_change(Origin::signed(2), header.clone(), vec![], header));
+		})
 	}
 
 	#[test]
-	fn rate_limiter_disallows_imports_once_limit_is_hit_in_single_block() {
+	fn enacting_forced_change_works_when_pallet_is_halted() {
 		run_test(|| {
 			initialize_substrate_bridge();
+			assert_ok!(Pallet::<TestRuntime>::set_operational(Origin::root(), false));
 
-			assert_ok!(submit_finality_proof(1));
-			assert_ok!(submit_finality_proof(2));
-			assert_err!(submit_finality_proof(3), <Error<TestRuntime>>::TooManyRequests);
+			let mut header = test_header(2);
+			header.digest = forced_change_log(0);
+
+			assert_ok!(enact_forced_change(Origin::root(), header.clone(), vec![], header.clone()));
+			assert_eq!(<BestFinalized<TestRuntime>>::get(), header.hash());
 		})
 	}
 
 	#[test]
-	fn rate_limiter_invalid_requests_do_not_count_towards_request_count() {
+	fn enacting_forced_change_enacts_new_authority_set() {
 		run_test(|| {
-			let submit_invalid_request = || {
-				let header = test_header(1);
-				let mut invalid_justification = make_default_justification(&header);
-				invalid_justification.round = 42;
+			initialize_substrate_bridge();
 
-				Pallet::<TestRuntime>::submit_finality_proof(
-					Origin::signed(1),
-					Box::new(header),
-					invalid_justification,
-				)
-			};
+			// the forced change is signalled at header#2 and enacted at header#4
+			let mut forced_change_header = test_header(2);
+			forced_change_header.digest = forced_change_log(2);
+			let mut descendants = descendants_of(&forced_change_header, 2);
+			let finality_target = descendants.pop().unwrap();
 
-			initialize_substrate_bridge();
+			assert_ok!(enact_forced_change(
+				Origin::root(),
+				forced_change_header,
+				descendants,
+				finality_target.clone()
+			));
 
-			for _ in 0..<TestRuntime as Config>::MaxRequests::get() + 1 {
-				// Notice that the error here *isn't* `TooManyRequests`
-				assert_err!(submit_invalid_request(), <Error<TestRuntime>>::InvalidJustification);
-			}
+			// the finality target is the best finalized header now
+			assert_eq!(<BestFinalized<TestRuntime>>::get(), finality_target.hash());
+			assert!(<ImportedHeaders<TestRuntime>>::contains_key(finality_target.hash()));
 
-			// Can still submit `MaxRequests` requests afterwards
-			assert_ok!(submit_finality_proof(1));
-			assert_ok!(submit_finality_proof(2));
-			assert_err!(submit_finality_proof(3), <Error<TestRuntime>>::TooManyRequests);
+			// and the authority set has been changed
+			assert_eq!(
+				<CurrentAuthoritySet<TestRuntime>>::get(),
+				bp_header_chain::AuthoritySet::new(vec![(ALICE.into(), 1), (BOB.into(), 1)], 2),
+			);
 		})
 	}
 
 	#[test]
-	fn rate_limiter_allows_request_after_new_block_has_started() {
+	fn enacting_forced_change_discards_pending_change() {
 		run_test(|| {
 			initialize_substrate_bridge();
-			assert_ok!(submit_finality_proof(1));
-			assert_ok!(submit_finality_proof(2));
-
-			next_block();
-			assert_ok!(submit_finality_proof(3));
+			schedule_delayed_change();
+
+			let mut forced_change_header = test_header(3);
+			forced_change_header.digest = forced_change_log(0);
+			assert_ok!(enact_forced_change(
+				Origin::root(),
+				forced_change_header.clone(),
+				vec![],
+				forced_change_header
+			));
+
+			assert!(!<PendingChange<TestRuntime>>::exists());
+			assert_eq!(
+				<CurrentAuthoritySet<TestRuntime>>::get(),
+				bp_header_chain::AuthoritySet::new(vec![(ALICE.into(), 1), (BOB.into(), 1)], 2),
+			);
 		})
 	}
 
 	#[test]
-	fn rate_limiter_disallows_imports_once_limit_is_hit_across_different_blocks() {
+	fn enacting_forced_change_rejects_header_without_forced_change() {
 		run_test(|| {
 			initialize_substrate_bridge();
-			assert_ok!(submit_finality_proof(1));
-			assert_ok!(submit_finality_proof(2));
 
-			next_block();
-			assert_ok!(submit_finality_proof(3));
-			assert_err!(submit_finality_proof(4), <Error<TestRuntime>>::TooManyRequests);
+			let mut header = test_header(2);
+			header.digest = change_log(0);
+
+			assert_noop!(
+				enact_forced_change(Origin::root(), header.clone(), vec![], header),
+				<Error<TestRuntime>>::MissingForcedChange,
+			);
 		})
 	}
 
 	#[test]
-	fn rate_limiter_allows_max_requests_after_long_time_with_no_activity() {
+	fn enacting_forced_change_rejects_old_header() {
 		run_test(|| {
 			initialize_substrate_bridge();
-			assert_ok!(submit_finality_proof(1));
-			assert_ok!(submit_finality_proof(2));
+			assert_ok!(submit_finality_proof(3));
 
+			let mut header = test_header(2);
+			header.digest = forced_change_log(0);
+
+			assert_noop!(
+				enact_forced_change(Origin::root(), header.clone(), vec![], header),
+				<Error<TestRuntime>>::OldHeader,
+			);
+		})
+	}
+
+	#[test]
+	fn enacting_forced_change_rejects_target_before_enactment() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut forced_change_header = test_header(2);
+			forced_change_header.digest = forced_change_log(2);
+
+			let mut descendants = descendants_of(&forced_change_header, 1);
+			let finality_target = descendants.pop().unwrap();
+
+			assert_noop!(
+				enact_forced_change(
+					Origin::root(),
+					forced_change_header,
+					descendants,
+					finality_target
+				),
+				<Error<TestRuntime>>::ForcedChangeNotEnacted,
+			);
+		})
+	}
+
+	#[test]
+	fn enacting_forced_change_rejects_target_that_is_not_descendant() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut forced_change_header = test_header(2);
+			forced_change_header.digest = forced_change_log(2);
+
+			// header#4 is not a descendant of the forced change header
+			assert_noop!(
+				enact_forced_change(
+					Origin::root(),
+					forced_change_header.clone(),
+					vec![test_header(3)],
+					test_header(4)
+				),
+				<Error<TestRuntime>>::InvalidForcedChangeAncestry,
+			);
+
+			// ancestry of the descendant must be complete
+			let descendants = descendants_of(&forced_change_header, 2);
+			assert_noop!(
+				enact_forced_change(
+					Origin::root(),
+					forced_change_header.clone(),
+					vec![],
+					descendants[1].clone()
+				),
+				<Error<TestRuntime>>::InvalidForcedChangeAncestry,
+			);
+
+			// and must not include unrelated headers
+			assert_noop!(
+				enact_forced_change(
+					Origin::root(),
+					forced_change_header.clone(),
+					vec![test_header(3)],
+					descendants[1].clone()
+				),
+				<Error<TestRuntime>>::InvalidForcedChangeAncestry,
+			);
+		})
+	}
+
+	#[test]
+	fn enacting_forced_change_rejects_justification_of_old_authority_set() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut header = test_header(2);
+			header.digest = forced_change_log(0);
+			let justification = make_default_justification(&header);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::enact_forced_change(
+					Origin::root(),
+					Box::new(header.clone()),
+					vec![],
+					Box::new(header),
+					justification,
+				),
+				<Error<TestRuntime>>::InvalidJustification,
+			);
+		})
+	}
+
+	#[test]
+	fn parse_finalized_storage_proof_rejects_proof_on_unknown_header() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::parse_finalized_storage_proof(
+					Default::default(),
+					sp_trie::StorageProof::new(vec![]),
+					|_| (),
+				),
+				Error::<TestRuntime>::UnknownHeader,
+			);
+		});
+	}
+
+	#[test]
+	fn parse_finalized_storage_accepts_valid_proof() {
+		run_test(|| {
+			let (state_root, storage_proof) = bp_runtime::craft_valid_storage_proof();
+
+			let mut header = test_header(2);
+			header.set_state_root(state_root);
+
+			let hash = header.hash();
+			<BestFinalized<TestRuntime>>::put(hash);
+			<ImportedHeaders<TestRuntime>>::insert(hash, header);
+
+			assert_ok!(
+				Pallet::<TestRuntime>::parse_finalized_storage_proof(hash, storage_proof, |_| (),),
+				(),
+			);
+		});
+	}
+
+	#[test]
+	fn rate_limiter_disallows_imports_once_limit_is_hit_in_single_block() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_ok!(submit_finality_proof(1));
+			assert_ok!(submit_finality_proof(2));
+			assert_err!(submit_finality_proof(3), <Error<TestRuntime>>::TooManyRequests);
+		})
+	}
+
+	#[test]
+	fn rate_limiter_invalid_requests_do_not_count_towards_request_count() {
+		run_test(|| {
+			let submit_invalid_request = || {
+				let header = test_header(1);
+				let mut invalid_justification = make_default_justification(&header);
+				invalid_justification.round = 42;
+
+				Pallet::<TestRuntime>::submit_finality_proof(
+					Origin::signed(1),
+					Box::new(header),
+					invalid_justification,
+				)
+			};
+
+			initialize_substrate_bridge();
+
+			for _ in 0..<TestRuntime as Config>::MaxRequests::get() + 1 {
+				// Notice that the error here *isn't* `TooManyRequests`
+				assert_err!(submit_invalid_request(), <Error<TestRuntime>>::InvalidJustification);
+			}
+
+			// Can still submit `MaxRequests` requests afterwards
+			assert_ok!(submit_finality_proof(1));
+			assert_ok!(submit_finality_proof(2));
+			assert_err!(submit_finality_proof(3), <Error<TestRuntime>>::TooManyRequests);
+		})
+	}
+
+	#[test]
+	fn rate_limiter_allows_request_after_new_block_has_started() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_ok!(submit_finality_proof(1));
+			assert_ok!(submit_finality_proof(2));
+
+			next_block();
+			assert_ok!(submit_finality_proof(3));
+		})
+	}
+
+	#[test]
+	fn rate_limiter_disallows_imports_once_limit_is_hit_across_different_blocks() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_ok!(submit_finality_proof(1));
+			assert_ok!(submit_finality_proof(2));
+
+			next_block();
+			assert_ok!(submit_finality_proof(3));
+			assert_err!(submit_finality_proof(4), <Error<TestRuntime>>::TooManyRequests);
+		})
+	}
+
+	#[test]
+	fn rate_limiter_allows_max_requests_after_long_time_with_no_activity() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_ok!(submit_finality_proof(1));
+			assert_ok!(submit_finality_proof(2));
+
+			next_block();
+			next_block();
+
+			next_block();
+			assert_ok!(submit_finality_proof(5));
+			assert_ok!(submit_finality_proof(7));
+		})
+	}
+
+	fn header_and_justification(
+		header: TestHeader,
+		set_id: sp_finality_grandpa::SetId,
+	) -> (Box<TestHeader>, GrandpaJustification<TestHeader>) {
+		// the initial authority set has id 1 and the next set (with id 2) is ALICE + BOB
+		let justification = if set_id == 1 {
+			make_default_justification(&header)
+		} else {
+			make_justification_for_header(JustificationGeneratorParams {
+				header: header.clone(),
+				set_id,
+				authorities: vec![(ALICE, 1), (BOB, 1)],
+				..Default::default()
+			})
+		};
+		(Box::new(header), justification)
+	}
+
+	#[test]
+	fn submit_finality_proofs_imports_headers_across_authority_set_changes() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// header#2 enacts new authority set, header#3 is finalized by this new set
+			let mut header2 = test_header(2);
+			header2.digest = change_log(0);
+			let header3 = test_header(3);
+			assert_ok!(
+				Pallet::<TestRuntime>::submit_finality_proofs(
+					Origin::signed(1),
+					vec![
+						header_and_justification(header2.clone(), 1),
+						header_and_justification(header3.clone(), 2),
+					],
+				),
+				PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: frame_support::weights::Pays::No,
+				},
+			);
+
+			assert!(<ImportedHeaders<TestRuntime>>::contains_key(header2.hash()));
+			assert_eq!(<BestFinalized<TestRuntime>>::get(), header3.hash());
+			assert_eq!(
+				<CurrentAuthoritySet<TestRuntime>>::get(),
+				bp_header_chain::AuthoritySet::new(vec![(ALICE.into(), 1), (BOB.into(), 1)], 2),
+			);
+
+			// every header of the batch is a separate request
+			assert_eq!(<RequestCount<TestRuntime>>::get(), 2);
+		})
+	}
+
+	#[test]
+	fn submit_finality_proofs_is_free_if_all_headers_are_mandatory() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut header2 = test_header(2);
+			header2.digest = change_log(0);
+			let mut header3 = test_header(3);
+			header3.digest = change_log(0);
+			assert_ok!(
+				Pallet::<TestRuntime>::submit_finality_proofs(
+					Origin::signed(1),
+					vec![
+						header_and_justification(header2, 1),
+						header_and_justification(header3, 2)
+					],
+				),
+				PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: frame_support::weights::Pays::No,
+				},
+			);
+		})
+	}
+
+	#[test]
+	fn submit_finality_proofs_rejects_whole_batch_if_any_header_is_invalid() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// header#3 is finalized by the wrong authority set
+			let mut header2 = test_header(2);
+			header2.digest = change_log(0);
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_finality_proofs(
+					Origin::signed(1),
+					vec![
+						header_and_justification(header2, 1),
+						header_and_justification(test_header(3), 1),
+					],
+				),
+				<Error<TestRuntime>>::InvalidJustification
+			);
+		})
+	}
+
+	#[test]
+	fn submit_finality_proofs_rejects_empty_batch() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_finality_proofs(Origin::signed(1), vec![]),
+				<Error<TestRuntime>>::EmptyBatch
+			);
+		})
+	}
+
+	#[test]
+	fn submit_finality_proofs_rejects_too_large_batch() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_finality_proofs(
+					Origin::signed(1),
+					(1..=MaxBatchSize::get() as u64 + 1)
+						.map(|number| header_and_justification(test_header(number), 1))
+						.collect(),
+				),
+				<Error<TestRuntime>>::TooLargeBatch
+			);
+		})
+	}
+
+	#[test]
+	fn submit_finality_proofs_rejects_batch_exceeding_request_limit() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_ok!(submit_finality_proof(1));
+
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_finality_proofs(
+					Origin::signed(1),
+					vec![
+						header_and_justification(test_header(2), 1),
+						header_and_justification(test_header(3), 1),
+					],
+				),
+				<Error<TestRuntime>>::TooManyRequests
+			);
+		})
+	}
+
+	#[test]
+	fn submit_finality_proofs_rejects_batch_when_pallet_is_halted() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_ok!(Pallet::<TestRuntime>::set_operational(Origin::root(), false));
+
+			assert_noop!(
+				Pallet::<TestRuntime>::submit_finality_proofs(
+					Origin::signed(1),
+					vec![header_and_justification(test_header(1), 1)],
+				),
+				<Error<TestRuntime>>::Halted
+			);
+		})
+	}
+
+	#[test]
+	fn relayer_is_rewarded_for_importing_mandatory_header() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut header = test_header(2);
+			header.digest = change_log(0);
+			let justification = make_default_justification(&header);
+			assert_ok!(
+				Pallet::<TestRuntime>::submit_finality_proof(
+					Origin::signed(1),
+					Box::new(header),
+					justification
+				),
+				PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: frame_support::weights::Pays::No,
+				},
+			);
+			assert!(TestRewardPayment::is_reward_paid(1));
+			assert_eq!(<PaidRewards<TestRuntime>>::get(), (0, 1));
+		})
+	}
+
+	#[test]
+	fn relayer_is_not_rewarded_for_importing_paid_header() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_ok!(
+				submit_finality_proof(1),
+				PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: frame_support::weights::Pays::Yes,
+				},
+			);
+			assert!(!TestRewardPayment::is_reward_paid(1));
+		})
+	}
+
+	fn submit_finality_proof_by(
+		relayer: u64,
+		header: u8,
+	) -> frame_support::dispatch::DispatchResultWithPostInfo {
+		let header = test_header(header.into());
+		let justification = make_default_justification(&header);
+
+		Pallet::<TestRuntime>::submit_finality_proof(
+			Origin::signed(relayer),
+			Box::new(header),
+			justification,
+		)
+	}
+
+	#[test]
+	fn header_improving_best_finalized_by_free_headers_interval_is_free() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			FreeHeadersInterval::set(&Some(2));
+
+			assert_ok!(
+				submit_finality_proof_by(1, 1),
+				PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: frame_support::weights::Pays::Yes,
+				},
+			);
+			assert!(!TestRewardPayment::is_reward_paid(1));
+
+			assert_ok!(
+				submit_finality_proof_by(2, 3),
+				PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: frame_support::weights::Pays::No,
+				},
+			);
+			assert!(TestRewardPayment::is_reward_paid(2));
+		})
+	}
+
+	#[test]
+	fn free_headers_are_limited_in_every_rewards_period() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			FreeHeadersInterval::set(&Some(1));
+			MaxRewardsPerPeriod::set(&1);
+
+			let free_dispatch_info = PostDispatchInfo {
+				actual_weight: None,
+				pays_fee: frame_support::weights::Pays::No,
+			};
+			let paid_dispatch_info = PostDispatchInfo {
+				actual_weight: None,
+				pays_fee: frame_support::weights::Pays::Yes,
+			};
+
+			assert_ok!(submit_finality_proof_by(1, 1), free_dispatch_info);
+			assert!(TestRewardPayment::is_reward_paid(1));
+
+			// no more rewards in this period
 			next_block();
-			next_block();
+			assert_ok!(submit_finality_proof_by(2, 2), paid_dispatch_info);
+			assert!(!TestRewardPayment::is_reward_paid(2));
+
+			// mandatory headers are still free, but relayer isn't rewarded
+			let mut header = test_header(3);
+			header.digest = change_log(0);
+			let justification = make_default_justification(&header);
+			assert_ok!(
+				Pallet::<TestRuntime>::submit_finality_proof(
+					Origin::signed(3),
+					Box::new(header),
+					justification,
+				),
+				free_dispatch_info,
+			);
+			assert!(!TestRewardPayment::is_reward_paid(3));
+
+			// next rewards period has started
+			frame_system::Pallet::<TestRuntime>::set_block_number(RewardsPeriod::get());
+			next_block();
+			assert_ok!(submit_finality_proof_of_next_authority_set(4), free_dispatch_info);
+			assert_eq!(<PaidRewards<TestRuntime>>::get(), (1, 1));
+		})
+	}
+
+	#[test]
+	fn submit_finality_proofs_is_paid_if_any_header_is_paid() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_ok!(
+				Pallet::<TestRuntime>::submit_finality_proofs(
+					Origin::signed(1),
+					vec![
+						header_and_justification(test_header(1), 1),
+						header_and_justification(test_header(2), 1),
+					],
+				),
+				PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: frame_support::weights::Pays::Yes,
+				},
+			);
+			assert!(!TestRewardPayment::is_reward_paid(1));
+		})
+	}
 
-			next_block();
-			assert_ok!(submit_finality_proof(5));
-			assert_ok!(submit_finality_proof(7));
+	#[test]
+	fn relayer_fund_account_is_not_default_account() {
+		run_test(|| {
+			assert_ne!(
+				relayer_fund_account_id::<TestRuntime, ()>(),
+				<TestRuntime as frame_system::Config>::AccountId::default(),
+			);
 		})
 	}
 
@@ -1150,6 +2871,142 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn expired_headers_are_pruned_under_block_number_retention_policy() {
+		run_test(|| {
+			HeadersRetention::set(&HeadersRetentionPolicy::BlockNumber {
+				blocks: 2,
+				mandatory_blocks: 2,
+			});
+			initialize_substrate_bridge();
+
+			assert_ok!(submit_finality_proof(1));
+			next_block();
+			assert_ok!(submit_finality_proof(2));
+			next_block();
+			assert!(Pallet::<TestRuntime>::is_known_header(test_header(0).hash()));
+
+			// header #0 expires once header #3 is imported
+			assert_ok!(submit_finality_proof(3));
+			next_block();
+			assert!(!Pallet::<TestRuntime>::is_known_header(test_header(0).hash()));
+			assert!(Pallet::<TestRuntime>::is_known_header(test_header(1).hash()));
+
+			// at most two expired headers are pruned when new header is imported
+			assert_ok!(submit_finality_proof(10));
+			assert!(!Pallet::<TestRuntime>::is_known_header(test_header(1).hash()));
+			assert!(!Pallet::<TestRuntime>::is_known_header(test_header(2).hash()));
+			assert!(Pallet::<TestRuntime>::is_known_header(test_header(3).hash()));
+			assert_ok!(Pallet::<TestRuntime>::do_try_state());
+		})
+	}
+
+	#[test]
+	fn mandatory_headers_are_kept_longer_under_block_number_retention_policy() {
+		run_test(|| {
+			HeadersRetention::set(&HeadersRetentionPolicy::BlockNumber {
+				blocks: 1,
+				mandatory_blocks: 3,
+			});
+			initialize_substrate_bridge();
+
+			// header #1 enacts the authority set change, so it is mandatory
+			let mut header = test_header(1);
+			header.digest = change_log(0);
+			let justification = make_default_justification(&header);
+			assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+				Origin::signed(1),
+				Box::new(header.clone()),
+				justification,
+			));
+			next_block();
+			assert!(<MandatoryHeaders<TestRuntime>>::contains_key(header.hash()));
+
+			// header #2 is expired, but it is imported after mandatory header, so it is still kept
+			for number in 2..5 {
+				assert_ok!(submit_finality_proof_of_next_authority_set(number));
+				next_block();
+			}
+			assert!(!Pallet::<TestRuntime>::is_known_header(test_header(0).hash()));
+			assert!(Pallet::<TestRuntime>::is_known_header(header.hash()));
+			assert!(Pallet::<TestRuntime>::is_known_header(test_header(2).hash()));
+
+			// mandatory header expires once header #5 is imported
+			assert_ok!(submit_finality_proof_of_next_authority_set(5));
+			assert!(!Pallet::<TestRuntime>::is_known_header(header.hash()));
+			assert!(!Pallet::<TestRuntime>::is_known_header(test_header(2).hash()));
+			assert!(!<MandatoryHeaders<TestRuntime>>::contains_key(header.hash()));
+			assert_ok!(Pallet::<TestRuntime>::do_try_state());
+		})
+	}
+
+	#[test]
+	fn headers_to_keep_is_respected_under_block_number_retention_policy() {
+		run_test(|| {
+			HeadersRetention::set(&HeadersRetentionPolicy::BlockNumber {
+				blocks: 100,
+				mandatory_blocks: 100,
+			});
+			initialize_substrate_bridge();
+
+			for number in 1..7 {
+				assert_ok!(submit_finality_proof(number));
+				next_block();
+			}
+
+			// headers #0 and #1 are pruned, because the ring buffer is full
+			assert!(!Pallet::<TestRuntime>::is_known_header(test_header(0).hash()));
+			assert!(!Pallet::<TestRuntime>::is_known_header(test_header(1).hash()));
+			assert!(Pallet::<TestRuntime>::is_known_header(test_header(2).hash()));
+			assert_eq!(<OldestImportedHashPointer<TestRuntime>>::get(), 2);
+			assert_ok!(Pallet::<TestRuntime>::do_try_state());
+		})
+	}
+
+	#[test]
+	fn try_state_accepts_consistent_storage() {
+		run_test(|| {
+			assert_ok!(Pallet::<TestRuntime>::do_try_state());
+
+			initialize_substrate_bridge();
+			assert_ok!(Pallet::<TestRuntime>::do_try_state());
+
+			for header in 1..8 {
+				assert_ok!(submit_finality_proof(header));
+				assert_ok!(Pallet::<TestRuntime>::do_try_state());
+				next_block();
+			}
+		})
+	}
+
+	#[test]
+	fn try_state_detects_header_missing_from_ring_buffer() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			let header = test_header(42);
+			<ImportedHeaders<TestRuntime>>::insert(header.hash(), header);
+
+			assert_eq!(
+				Pallet::<TestRuntime>::do_try_state(),
+				Err("Imported header is missing from the ring buffer"),
+			);
+		})
+	}
+
+	#[test]
+	fn try_state_detects_outdated_best_finalized_header() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_ok!(submit_finality_proof(1));
+			<BestFinalized<TestRuntime>>::put(test_header(0).hash());
+
+			assert_eq!(
+				Pallet::<TestRuntime>::do_try_state(),
+				Err("Best finalized header is not the latest imported header"),
+			);
+		})
+	}
+
 	#[test]
 	fn storage_keys_computed_properly() {
 		assert_eq!(
@@ -1161,5 +3018,353 @@ mod tests {
 			BestFinalized::<TestRuntime>::storage_value_final_key().to_vec(),
 			bp_header_chain::storage_keys::best_finalized_hash_key("Grandpa").0,
 		);
+
+		assert_eq!(
+			CurrentAuthoritySet::<TestRuntime>::storage_value_final_key().to_vec(),
+			bp_header_chain::storage_keys::current_authority_set_key("Grandpa").0,
+		);
+	}
+
+	#[test]
+	fn conflicting_finality_at_the_same_height_halts_pallet() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			frame_system::Pallet::<TestRuntime>::set_block_number(1);
+
+			let (first_header, second_header) = (test_header(1), fork_header(1));
+			let (first_hash, second_hash) = (first_header.hash(), second_header.hash());
+			let result = report_conflicting_finality(first_header, second_header);
+			assert_eq!(result.unwrap().pays_fee, frame_support::weights::Pays::No);
+
+			assert!(IsHalted::<TestRuntime>::get());
+			assert!(TestRewardPayment::is_reward_paid(1));
+			assert_eq!(
+				frame_system::Pallet::<TestRuntime>::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Grandpa(Event::ConflictingFinalityReported(
+						1,
+						1,
+						first_hash,
+						second_hash,
+					)),
+					topics: vec![],
+				}],
+			);
+			assert_noop!(submit_finality_proof(1), <Error<TestRuntime>>::Halted);
+		})
+	}
+
+	#[test]
+	fn conflicting_finality_at_adjacent_heights_halts_pallet() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let mut second_header = test_header(2);
+			second_header.parent_hash = fork_header(1).hash();
+			assert_ok!(report_conflicting_finality(test_header(1), second_header));
+			assert!(IsHalted::<TestRuntime>::get());
+		})
+	}
+
+	#[test]
+	fn headers_of_the_same_chain_are_not_conflicting() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			assert_noop!(
+				report_conflicting_finality(test_header(1), test_header(1)),
+				<Error<TestRuntime>>::NotConflictingFinality
+			);
+			let mut child_header = test_header(2);
+			child_header.parent_hash = test_header(1).hash();
+			assert_noop!(
+				report_conflicting_finality(child_header, test_header(1)),
+				<Error<TestRuntime>>::NotConflictingFinality
+			);
+			// we don't know headers between them, so they may belong to the same chain
+			assert_noop!(
+				report_conflicting_finality(test_header(1), fork_header(3)),
+				<Error<TestRuntime>>::NotConflictingFinality
+			);
+			// the ancestry proves that the higher header is a descendant of the lower header
+			let mut ancestry = descendants_of(&test_header(1), 3);
+			let higher_header = ancestry.pop().unwrap();
+			assert_noop!(
+				report_conflicting_finality_with_ancestry(higher_header, test_header(1), ancestry),
+				<Error<TestRuntime>>::NotConflictingFinality
+			);
+		})
+	}
+
+	#[test]
+	fn conflicting_finality_at_distant_heights_halts_pallet() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// ancestry contains fork header at the height of the lower header
+			let mut ancestry = vec![fork_header(1)];
+			ancestry.extend(descendants_of(&fork_header(1), 3));
+			let higher_header = ancestry.pop().unwrap();
+			assert_ok!(report_conflicting_finality_with_ancestry(
+				test_header(1),
+				higher_header,
+				ancestry,
+			));
+			assert!(IsHalted::<TestRuntime>::get());
+		})
+	}
+
+	#[test]
+	fn conflicting_finality_report_requires_valid_ancestry() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			// ancestry doesn't reach the lower header
+			let mut ancestry = descendants_of(&fork_header(1), 3);
+			let higher_header = ancestry.pop().unwrap();
+			ancestry.remove(0);
+			assert_noop!(
+				report_conflicting_finality_with_ancestry(
+					test_header(1),
+					higher_header,
+					ancestry,
+				),
+				<Error<TestRuntime>>::NotConflictingFinality
+			);
+
+			// ancestry isn't a chain of headers
+			let mut ancestry = descendants_of(&fork_header(1), 3);
+			let higher_header = ancestry.pop().unwrap();
+			ancestry[0] = fork_header(2);
+			assert_noop!(
+				report_conflicting_finality_with_ancestry(
+					test_header(1),
+					higher_header,
+					ancestry,
+				),
+				<Error<TestRuntime>>::NotConflictingFinality
+			);
+		})
+	}
+
+	#[test]
+	fn conflicting_finality_report_requires_valid_justifications() {
+		run_test(|| {
+			initialize_substrate_bridge();
+
+			let first_header = test_header(1);
+			let first_justification = make_default_justification(&first_header);
+			assert_noop!(
+				Pallet::<TestRuntime>::report_conflicting_finality(
+					Origin::signed(1),
+					Box::new(first_header),
+					first_justification.clone(),
+					Box::new(fork_header(1)),
+					first_justification,
+					vec![],
+				),
+				<Error<TestRuntime>>::InvalidJustification
+			);
+		})
+	}
+
+	#[test]
+	fn conflicting_finality_report_is_rejected_by_halted_or_not_initialized_pallet() {
+		run_test(|| {
+			assert_noop!(
+				report_conflicting_finality(test_header(1), fork_header(1)),
+				<Error<TestRuntime>>::NotInitialized
+			);
+
+			initialize_substrate_bridge();
+			assert_ok!(Pallet::<TestRuntime>::set_operational(Origin::root(), false));
+			assert_noop!(
+				report_conflicting_finality(test_header(1), fork_header(1)),
+				<Error<TestRuntime>>::Halted
+			);
+		})
+	}
+
+	fn submit_authority_set_change(
+		header: u8,
+		set_id: sp_finality_grandpa::SetId,
+		authorities: Vec<(bp_test_utils::Account, u64)>,
+	) {
+		let mut header = test_header(header.into());
+		header.digest = change_log(0);
+		let justification = make_justification_for_header(JustificationGeneratorParams {
+			header: header.clone(),
+			set_id,
+			authorities,
+			..Default::default()
+		});
+
+		assert_ok!(Pallet::<TestRuntime>::submit_finality_proof(
+			Origin::signed(1),
+			Box::new(header),
+			justification,
+		));
+		next_block();
+	}
+
+	fn submit_historical_finality_proof(
+		header: TestHeader,
+	) -> frame_support::dispatch::DispatchResultWithPostInfo {
+		let justification = make_default_justification(&header);
+
+		Pallet::<TestRuntime>::submit_historical_finality_proof(
+			Origin::signed(1),
+			Box::new(header),
+			justification,
+		)
+	}
+
+	#[test]
+	fn authority_sets_history_is_recorded_and_pruned() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			let next_authorities = vec![(ALICE.into(), 1), (BOB.into(), 1)];
+
+			// sets #2, #3 and #4 are enacted by headers #2, #4 and #6
+			submit_authority_set_change(2, 1, bp_test_utils::test_keyring());
+			assert_eq!(
+				Pallet::<TestRuntime>::authority_set_at(2),
+				Some(bp_header_chain::AuthoritySet::new(authority_list(), 1)),
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::authority_set_at(3),
+				Some(bp_header_chain::AuthoritySet::new(next_authorities.clone(), 2)),
+			);
+
+			submit_authority_set_change(4, 2, vec![(ALICE, 1), (BOB, 1)]);
+			submit_authority_set_change(6, 3, vec![(ALICE, 1), (BOB, 1)]);
+			assert_ok!(Pallet::<TestRuntime>::do_try_state());
+
+			// set #1 is pruned, because we only keep three sets
+			assert_eq!(<AuthoritySetHistory<TestRuntime>>::get(1), None);
+			assert_eq!(Pallet::<TestRuntime>::authority_set_at(2), None);
+			assert_eq!(
+				Pallet::<TestRuntime>::authority_set_at(4),
+				Some(bp_header_chain::AuthoritySet::new(next_authorities.clone(), 2)),
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::authority_set_at(5),
+				Some(bp_header_chain::AuthoritySet::new(next_authorities.clone(), 3)),
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::authority_set_at(100),
+				Some(bp_header_chain::AuthoritySet::new(next_authorities, 4)),
+			);
+		})
+	}
+
+	#[test]
+	fn authority_set_at_returns_next_authority_set_if_change_is_pending() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			schedule_delayed_change();
+
+			assert_eq!(
+				Pallet::<TestRuntime>::authority_set_at(4),
+				Some(bp_header_chain::AuthoritySet::new(authority_list(), 1)),
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::authority_set_at(5),
+				Some(bp_header_chain::AuthoritySet::new(
+					vec![(ALICE.into(), 1), (BOB.into(), 1)],
+					2
+				)),
+			);
+		})
+	}
+
+	#[test]
+	fn historical_header_finalized_by_past_authority_set_is_imported() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			submit_authority_set_change(2, 1, bp_test_utils::test_keyring());
+			assert_ok!(submit_finality_proof_of_next_authority_set(5));
+			next_block();
+
+			// header #1 is finalized by the past authority set
+			let header = test_header(1);
+			assert_ok!(
+				submit_historical_finality_proof(header.clone()),
+				PostDispatchInfo {
+					actual_weight: None,
+					pays_fee: frame_support::weights::Pays::Yes,
+				},
+			);
+			assert!(Pallet::<TestRuntime>::is_known_header(header.hash()));
+			assert_eq!(<BestFinalized<TestRuntime>>::get(), test_header(5).hash());
+			assert_ok!(Pallet::<TestRuntime>::do_try_state());
+
+			// header #4 is finalized by the current authority set
+			assert_noop!(
+				submit_historical_finality_proof(test_header(4)),
+				<Error<TestRuntime>>::InvalidJustification
+			);
+		})
+	}
+
+	#[test]
+	fn historical_header_must_be_older_than_best_finalized_header() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_ok!(submit_finality_proof(2));
+			next_block();
+
+			assert_noop!(
+				submit_historical_finality_proof(test_header(2)),
+				<Error<TestRuntime>>::NotHistoricalHeader
+			);
+			assert_noop!(
+				submit_historical_finality_proof(test_header(3)),
+				<Error<TestRuntime>>::NotHistoricalHeader
+			);
+			assert_noop!(
+				submit_historical_finality_proof(test_header(0)),
+				<Error<TestRuntime>>::AlreadyImported
+			);
+		})
+	}
+
+	#[test]
+	fn historical_header_is_rejected_if_authority_set_is_unknown() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			submit_authority_set_change(2, 1, bp_test_utils::test_keyring());
+			submit_authority_set_change(4, 2, vec![(ALICE, 1), (BOB, 1)]);
+			submit_authority_set_change(6, 3, vec![(ALICE, 1), (BOB, 1)]);
+
+			assert_noop!(
+				submit_historical_finality_proof(test_header(1)),
+				<Error<TestRuntime>>::UnknownAuthoritySet
+			);
+		})
+	}
+
+	#[test]
+	fn historical_headers_are_pruned_separately_from_recent_headers() {
+		run_test(|| {
+			initialize_substrate_bridge();
+			assert_ok!(submit_finality_proof(5));
+			next_block();
+
+			// `HistoricalHeadersToKeep` is 2, so the third historical header prunes header #1
+			for header in 1..4 {
+				assert_ok!(submit_historical_finality_proof(test_header(header)));
+				next_block();
+			}
+			assert!(!Pallet::<TestRuntime>::is_known_header(test_header(1).hash()));
+			assert!(Pallet::<TestRuntime>::is_known_header(test_header(2).hash()));
+			assert!(Pallet::<TestRuntime>::is_known_header(test_header(3).hash()));
+
+			// recent headers are not pruned
+			assert!(Pallet::<TestRuntime>::is_known_header(test_header(0).hash()));
+			assert_eq!(Pallet::<TestRuntime>::best_finalized(), test_header(5));
+			assert_ok!(Pallet::<TestRuntime>::do_try_state());
+		})
 	}
 }
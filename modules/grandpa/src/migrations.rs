@@ -0,0 +1,319 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations of the GRANDPA pallet.
+
+use crate::{
+	AuthoritySetHistory, BestFinalized, Config, CurrentAuthoritySet, ImportedHashes,
+	ImportedHashesPointer, ImportedHeaders, OldestImportedHashPointer, Pallet, StoredAuthoritySet,
+};
+
+use frame_support::{
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_runtime::traits::Header as HeaderT;
+use sp_std::marker::PhantomData;
+
+/// Migration to the storage version 1.
+///
+/// The pallet hasn't declared its storage version before, so the on-chain storage version of the
+/// pallet, deployed before the version 1, is 0. All other storage items of the version 0 have the
+/// same encoding in the version 1.
+///
+/// The version 1 has added the `AuthoritySetHistory` map. The current authority set of the
+/// initialized pallet is recorded in the history. The pallet doesn't know the header where the
+/// set has been enacted, so the best finalized header is considered the first header, finalized
+/// by the current set.
+pub mod v1 {
+	use super::*;
+
+	/// Migrates the pallet storage from version 0 to version 1.
+	pub struct Migration<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for Migration<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T, I>::on_chain_storage_version();
+			if on_chain_version != 0 {
+				log::info!(
+					target: "runtime::bridge-grandpa",
+					"Skipping migration to v1: on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads = 3;
+			let mut writes = 0;
+
+			// record the current authority set of the initialized pallet
+			let best_finalized = ImportedHeaders::<T, I>::get(BestFinalized::<T, I>::get());
+			if let Some(best_finalized) = best_finalized {
+				reads += 1;
+				let current_authority_set = CurrentAuthoritySet::<T, I>::get();
+				AuthoritySetHistory::<T, I>::insert(
+					current_authority_set.set_id,
+					StoredAuthoritySet {
+						authorities: current_authority_set.authorities,
+						first_block: *best_finalized.number(),
+					},
+				);
+				writes += 1;
+			}
+
+			StorageVersion::new(1).put::<Pallet<T, I>>();
+			writes += 1;
+
+			log::info!(
+				target: "runtime::bridge-grandpa",
+				"Migrated storage to v1. Reads: {}, writes: {}",
+				reads,
+				writes,
+			);
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			frame_support::ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 0,
+				"Expected on-chain storage version 0 before the migration"
+			);
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			// migrations to next versions may run after this migration
+			frame_support::ensure!(
+				Pallet::<T, I>::on_chain_storage_version() >= 1,
+				"Expected on-chain storage version 1 after the migration"
+			);
+			Pallet::<T, I>::do_try_state()
+		}
+	}
+}
+
+/// Migration to the storage version 2.
+///
+/// The version 2 has added the `OldestImportedHashPointer` value. Before the version 2, headers
+/// have only been pruned when the `ImportedHashes` ring buffer is full. So if the buffer is full,
+/// the oldest imported hash is at the `ImportedHashesPointer` position. Otherwise it is at the
+/// position 0, which is the default value of the `OldestImportedHashPointer`.
+pub mod v2 {
+	use super::*;
+
+	/// Migrates the pallet storage from version 1 to version 2.
+	pub struct Migration<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for Migration<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T, I>::on_chain_storage_version();
+			if on_chain_version != 1 {
+				log::info!(
+					target: "runtime::bridge-grandpa",
+					"Skipping migration to v2: on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let reads = 3;
+			let mut writes = 0;
+
+			// if the ring buffer is full, the next header overwrites the oldest one
+			let pointer = ImportedHashesPointer::<T, I>::get();
+			if ImportedHashes::<T, I>::contains_key(pointer) {
+				OldestImportedHashPointer::<T, I>::put(pointer);
+				writes += 1;
+			}
+
+			StorageVersion::new(2).put::<Pallet<T, I>>();
+			writes += 1;
+
+			log::info!(
+				target: "runtime::bridge-grandpa",
+				"Migrated storage to v2. Reads: {}, writes: {}",
+				reads,
+				writes,
+			);
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			// migration to v1 may run before this migration
+			frame_support::ensure!(
+				Pallet::<T, I>::on_chain_storage_version() <= 1,
+				"Expected on-chain storage version 1 before the migration"
+			);
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			frame_support::ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 2,
+				"Expected on-chain storage version 2 after the migration"
+			);
+			let pointer = ImportedHashesPointer::<T, I>::get();
+			frame_support::ensure!(
+				!ImportedHashes::<T, I>::contains_key(pointer) ||
+					OldestImportedHashPointer::<T, I>::get() == pointer,
+				"Oldest imported hash pointer is not at the start of the full ring buffer"
+			);
+			Pallet::<T, I>::do_try_state()
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		insert_header,
+		mock::{run_test, test_header, TestHeader, TestNumber, TestRuntime},
+		BestFinalized,
+	};
+	use bp_test_utils::authority_list;
+	use frame_support::storage::unhashed;
+	use sp_finality_grandpa::{AuthorityList, SetId};
+
+	fn insert_unversioned_storage(headers: &[TestHeader], authority_set: (AuthorityList, SetId)) {
+		for (index, header) in headers.iter().enumerate() {
+			unhashed::put(
+				&ImportedHashes::<TestRuntime>::hashed_key_for(index as u32),
+				&header.hash(),
+			);
+			unhashed::put(&ImportedHeaders::<TestRuntime>::hashed_key_for(header.hash()), header);
+		}
+		unhashed::put(&ImportedHashesPointer::<TestRuntime>::hashed_key(), &(headers.len() as u32));
+		if let Some(best_finalized) = headers.last() {
+			unhashed::put(&BestFinalized::<TestRuntime>::hashed_key(), &best_finalized.hash());
+		}
+		unhashed::put(&CurrentAuthoritySet::<TestRuntime>::hashed_key(), &authority_set);
+	}
+
+	#[test]
+	fn migration_to_v1_works() {
+		run_test(|| {
+			let header = test_header(5);
+			insert_header::<TestRuntime, ()>(header.clone(), header.hash(), false);
+			CurrentAuthoritySet::<TestRuntime>::put(bp_header_chain::AuthoritySet::new(
+				authority_list(),
+				3,
+			));
+
+			v1::Migration::<TestRuntime>::on_runtime_upgrade();
+
+			assert_eq!(Pallet::<TestRuntime>::on_chain_storage_version(), 1);
+			assert_eq!(
+				AuthoritySetHistory::<TestRuntime>::get(3),
+				Some(StoredAuthoritySet { authorities: authority_list(), first_block: 5 }),
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::authority_set_at(7),
+				Some(bp_header_chain::AuthoritySet::new(authority_list(), 3)),
+			);
+			assert_eq!(Pallet::<TestRuntime>::authority_set_at(4), None);
+		});
+	}
+
+	#[test]
+	fn migration_to_v1_works_with_unversioned_pallet_storage() {
+		run_test(|| {
+			insert_unversioned_storage(&[test_header(4), test_header(5)], (authority_list(), 3));
+			assert_eq!(Pallet::<TestRuntime>::on_chain_storage_version(), 0);
+
+			v1::Migration::<TestRuntime>::on_runtime_upgrade();
+
+			assert_eq!(Pallet::<TestRuntime>::on_chain_storage_version(), 1);
+			assert_eq!(Pallet::<TestRuntime>::best_finalized(), test_header(5));
+			assert_eq!(
+				Pallet::<TestRuntime>::authority_set_at(5),
+				Some(bp_header_chain::AuthoritySet::new(authority_list(), 3)),
+			);
+			assert_eq!(ImportedHeaders::<TestRuntime>::iter_keys().count(), 2);
+			assert_eq!(Pallet::<TestRuntime>::do_try_state(), Ok(()));
+		});
+	}
+
+	#[test]
+	fn migration_to_v1_is_skipped_if_storage_is_already_migrated() {
+		run_test(|| {
+			StorageVersion::new(1).put::<Pallet<TestRuntime>>();
+			let header = test_header(5);
+			insert_header::<TestRuntime, ()>(header.clone(), header.hash(), false);
+
+			v1::Migration::<TestRuntime>::on_runtime_upgrade();
+
+			assert_eq!(AuthoritySetHistory::<TestRuntime>::iter().count(), 0);
+		});
+	}
+
+	fn insert_v1_headers(count: TestNumber) {
+		StorageVersion::new(1).put::<Pallet<TestRuntime>>();
+		for number in 1..=count {
+			let header = test_header(number);
+			insert_header::<TestRuntime, ()>(header.clone(), header.hash(), false);
+		}
+		// the version 1 has no `OldestImportedHashPointer` value
+		OldestImportedHashPointer::<TestRuntime>::kill();
+	}
+
+	#[test]
+	fn migration_to_v2_works_if_ring_buffer_is_full() {
+		run_test(|| {
+			// `HeadersToKeep` is 5, so headers 6 and 7 overwrite headers 1 and 2
+			insert_v1_headers(7);
+			assert_eq!(ImportedHashesPointer::<TestRuntime>::get(), 2);
+
+			v2::Migration::<TestRuntime>::on_runtime_upgrade();
+
+			assert_eq!(Pallet::<TestRuntime>::on_chain_storage_version(), 2);
+			assert_eq!(OldestImportedHashPointer::<TestRuntime>::get(), 2);
+			assert_eq!(ImportedHashes::<TestRuntime>::get(2), Some(test_header(3).hash()));
+		});
+	}
+
+	#[test]
+	fn migration_to_v2_works_if_ring_buffer_is_not_full() {
+		run_test(|| {
+			insert_v1_headers(3);
+			assert_eq!(ImportedHashesPointer::<TestRuntime>::get(), 3);
+
+			v2::Migration::<TestRuntime>::on_runtime_upgrade();
+
+			assert_eq!(Pallet::<TestRuntime>::on_chain_storage_version(), 2);
+			assert_eq!(OldestImportedHashPointer::<TestRuntime>::get(), 0);
+			assert_eq!(ImportedHashes::<TestRuntime>::get(0), Some(test_header(1).hash()));
+		});
+	}
+
+	#[test]
+	fn migration_to_v2_is_skipped_if_storage_is_already_migrated() {
+		run_test(|| {
+			insert_v1_headers(7);
+			StorageVersion::new(2).put::<Pallet<TestRuntime>>();
+
+			v2::Migration::<TestRuntime>::on_runtime_upgrade();
+
+			assert_eq!(OldestImportedHashPointer::<TestRuntime>::get(), 0);
+		});
+	}
+}
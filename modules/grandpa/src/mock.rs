@@ -17,7 +17,9 @@
 // From construct_runtime macro
 #![allow(clippy::from_over_into)]
 
+use bp_header_chain::{FinalityProofRewardPayment, HeadersRetentionPolicy};
 use bp_runtime::Chain;
+use codec::Encode;
 use frame_support::{construct_runtime, parameter_types, weights::Weight};
 use sp_core::sr25519::Signature;
 use sp_runtime::{
@@ -42,7 +44,7 @@ construct_runtime! {
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		Grandpa: grandpa::{Pallet, Call},
+		Grandpa: grandpa::{Pallet, Call, Event<T>},
 	}
 }
 
@@ -63,7 +65,7 @@ impl frame_system::Config for TestRuntime {
 	type AccountId = AccountId;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
-	type Event = ();
+	type Event = Event;
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
@@ -82,18 +84,55 @@ impl frame_system::Config for TestRuntime {
 
 parameter_types! {
 	pub const MaxRequests: u32 = 2;
+	pub const MaxBatchSize: u32 = 2;
 	pub const HeadersToKeep: u32 = 5;
+	pub const AuthoritySetsToKeep: u32 = 3;
+	pub const HistoricalHeadersToKeep: u32 = 2;
 	pub const SessionLength: u64 = 5;
 	pub const NumValidators: u32 = 5;
+	pub storage FreeHeadersInterval: Option<u32> = None;
+	pub storage MaxRewardsPerPeriod: u32 = 2;
+	pub const RewardsPeriod: u64 = 10;
+	pub storage HeadersRetention: HeadersRetentionPolicy = HeadersRetentionPolicy::Count;
 }
 
 impl grandpa::Config for TestRuntime {
+	type Event = Event;
 	type BridgedChain = TestBridgedChain;
 	type MaxRequests = MaxRequests;
+	type MaxBatchSize = MaxBatchSize;
+	type FreeHeadersInterval = FreeHeadersInterval;
+	type MaxRewardsPerPeriod = MaxRewardsPerPeriod;
+	type RewardsPeriod = RewardsPeriod;
+	type RelayerRewardPayment = TestRewardPayment;
 	type HeadersToKeep = HeadersToKeep;
+	type HeadersRetention = HeadersRetention;
+	type AuthoritySetsToKeep = AuthoritySetsToKeep;
+	type HistoricalHeadersToKeep = HistoricalHeadersToKeep;
 	type WeightInfo = ();
 }
 
+/// Reward payment that only records paid rewards in the storage.
+pub struct TestRewardPayment;
+
+impl TestRewardPayment {
+	/// Returns true if reward has been paid to given relayer.
+	pub fn is_reward_paid(relayer: AccountId) -> bool {
+		frame_support::storage::unhashed::get_or_default(&Self::reward_key(relayer))
+	}
+
+	fn reward_key(relayer: AccountId) -> Vec<u8> {
+		(b":finality-relayer-reward:", relayer).encode()
+	}
+}
+
+impl FinalityProofRewardPayment<AccountId> for TestRewardPayment {
+	fn pay_reward(relayer: &AccountId, relayer_fund_account: &AccountId) {
+		assert_eq!(*relayer_fund_account, crate::relayer_fund_account_id::<TestRuntime, ()>());
+		frame_support::storage::unhashed::put(&Self::reward_key(*relayer), &true);
+	}
+}
+
 #[derive(Debug)]
 pub struct TestBridgedChain;
 
@@ -23,8 +23,8 @@ use crate::{
 
 use bp_messages::{
 	source_chain::TargetHeaderChain, target_chain::SourceHeaderChain, DeliveredMessages,
-	InboundLaneData, LaneId, MessageData, MessageNonce, OutboundLaneData, UnrewardedRelayer,
-	UnrewardedRelayersState,
+	InboundLaneData, LaneId, LaneState, MessageData, MessageNonce, OutboundLaneData,
+	UnrewardedRelayer, UnrewardedRelayersState,
 };
 use bp_runtime::messages::DispatchFeePayment;
 use frame_benchmarking::{account, benchmarks_instance_pallet};
@@ -38,18 +38,7 @@ const SEED: u32 = 0;
 pub struct Pallet<T: Config<I>, I: 'static>(crate::Pallet<T, I>);
 
 /// Proof size requirements.
-#[derive(Clone, Copy, Debug)]
-pub enum ProofSize {
-	/// The proof is expected to be minimal. If value size may be changed, then it is expected to
-	/// have given size.
-	Minimal(u32),
-	/// The proof is expected to have at least given size and grow by increasing number of trie
-	/// nodes included in the proof.
-	HasExtraNodes(u32),
-	/// The proof is expected to have at least given size and grow by increasing value that is
-	/// stored in the trie.
-	HasLargeLeaf(u32),
-}
+pub use bp_runtime::StorageProofSize as ProofSize;
 
 /// Benchmark-specific message parameters.
 #[derive(Debug)]
@@ -615,14 +604,24 @@ benchmarks_instance_pallet! {
 	}
 }
 
+fn open_bench_lane<T: Config<I>, I: 'static>() {
+	crate::Lanes::<T, I>::insert(T::bench_lane_id(), LaneState::Opened);
+}
+
 fn send_regular_message<T: Config<I>, I: 'static>() {
+	open_bench_lane::<T, I>();
 	let mut outbound_lane = outbound_lane::<T, I>(T::bench_lane_id());
-	outbound_lane.send_message(MessageData { payload: vec![], fee: T::message_fee() });
+	outbound_lane.send_message(MessageData {
+		payload: vec![],
+		fee: T::message_fee(),
+		expires_at: None,
+	});
 }
 
 fn send_regular_message_with_payload<T: Config<I>, I: 'static>(payload: Vec<u8>) {
+	open_bench_lane::<T, I>();
 	let mut outbound_lane = outbound_lane::<T, I>(T::bench_lane_id());
-	outbound_lane.send_message(MessageData { payload, fee: T::message_fee() });
+	outbound_lane.send_message(MessageData { payload, fee: T::message_fee(), expires_at: None });
 }
 
 fn confirm_message_delivery<T: Config<I>, I: 'static>(nonce: MessageNonce) {
@@ -642,6 +641,7 @@ fn confirm_message_delivery<T: Config<I>, I: 'static>(nonce: MessageNonce) {
 }
 
 fn receive_messages<T: Config<I>, I: 'static>(nonce: MessageNonce) {
+	open_bench_lane::<T, I>();
 	let mut inbound_lane_storage = inbound_lane_storage::<T, I>(T::bench_lane_id());
 	inbound_lane_storage.set_data(InboundLaneData {
 		relayers: vec![UnrewardedRelayer {
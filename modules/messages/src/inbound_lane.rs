@@ -18,8 +18,8 @@
 
 use bp_messages::{
 	target_chain::{DispatchMessage, DispatchMessageData, MessageDispatch},
-	DeliveredMessages, InboundLaneData, LaneId, MessageKey, MessageNonce, OutboundLaneData,
-	UnrewardedRelayer,
+	DeliveredMessages, ExpiredMessages, InboundLaneData, LaneId, MessageKey, MessageNonce,
+	OutboundLaneData, UnrewardedRelayer,
 };
 use bp_runtime::messages::MessageDispatchResult;
 use frame_support::RuntimeDebug;
@@ -42,6 +42,10 @@ pub trait InboundLaneStorage {
 	fn data(&self) -> InboundLaneData<Self::Relayer>;
 	/// Update lane data in the storage.
 	fn set_data(&mut self, data: InboundLaneData<Self::Relayer>);
+	/// Get nonces of unconfirmed expired messages from the storage.
+	fn expired_messages(&self) -> ExpiredMessages;
+	/// Update nonces of unconfirmed expired messages in the storage.
+	fn set_expired_messages(&mut self, expired_messages: ExpiredMessages);
 }
 
 /// Result of single message receival.
@@ -52,6 +56,9 @@ pub enum ReceivalResult {
 	///
 	/// The message dispatch result is also returned.
 	Dispatched(MessageDispatchResult),
+	/// Message has been received, but it has expired before delivery, so it hasn't been
+	/// dispatched.
+	Expired,
 	/// Message has invalid nonce and lane has rejected to accept this message.
 	InvalidNonce,
 	/// There are too many unrewarded relayer entries at the lane.
@@ -112,6 +119,16 @@ impl<S: InboundLaneStorage> InboundLane<S> {
 			_ => {},
 		}
 
+		// Finally, forget about confirmed expired messages
+		let mut expired_messages = self.storage.expired_messages();
+		let expired_messages_count = expired_messages.len();
+		while expired_messages.front().map(|nonce| *nonce <= new_confirmed_nonce).unwrap_or(false) {
+			expired_messages.pop_front();
+		}
+		if expired_messages.len() != expired_messages_count {
+			self.storage.set_expired_messages(expired_messages);
+		}
+
 		self.storage.set_data(data);
 		Some(outbound_lane_data.latest_received_nonce)
 	}
@@ -125,35 +142,90 @@ impl<S: InboundLaneStorage> InboundLane<S> {
 		message_data: DispatchMessageData<P::DispatchPayload, S::MessageFee>,
 	) -> ReceivalResult {
 		let mut data = self.storage.data();
+		if let Some(rejection) = self.check_message(&data, nonce) {
+			return rejection
+		}
+
+		// then, dispatch message
+		let dispatch_result = P::dispatch(
+			relayer_at_this_chain,
+			DispatchMessage {
+				key: MessageKey { lane_id: self.storage.id(), nonce },
+				data: message_data,
+			},
+		);
+
+		// now let's update inbound lane storage
+		Self::note_received_message(
+			&mut data,
+			relayer_at_bridged_chain,
+			nonce,
+			dispatch_result.dispatch_result,
+		);
+		self.storage.set_data(data);
+
+		ReceivalResult::Dispatched(dispatch_result)
+	}
+
+	/// Receive new message that has expired before delivery.
+	///
+	/// The message is not dispatched, but its nonce is remembered, so that the source chain learns
+	/// that it has expired when delivery is confirmed.
+	pub fn receive_expired_message(
+		&mut self,
+		relayer_at_bridged_chain: &S::Relayer,
+		nonce: MessageNonce,
+	) -> ReceivalResult {
+		let mut data = self.storage.data();
+		if let Some(rejection) = self.check_message(&data, nonce) {
+			return rejection
+		}
+
+		Self::note_received_message(&mut data, relayer_at_bridged_chain, nonce, false);
+		self.storage.set_data(data);
+
+		let mut expired_messages = self.storage.expired_messages();
+		expired_messages.push_back(nonce);
+		self.storage.set_expired_messages(expired_messages);
+
+		ReceivalResult::Expired
+	}
+
+	/// Check that the message with given nonce may be received by the lane.
+	fn check_message(
+		&self,
+		data: &InboundLaneData<S::Relayer>,
+		nonce: MessageNonce,
+	) -> Option<ReceivalResult> {
 		let is_correct_message = nonce == data.last_delivered_nonce() + 1;
 		if !is_correct_message {
-			return ReceivalResult::InvalidNonce
+			return Some(ReceivalResult::InvalidNonce)
 		}
 
 		// if there are more unrewarded relayer entries than we may accept, reject this message
 		if data.relayers.len() as MessageNonce >= self.storage.max_unrewarded_relayer_entries() {
-			return ReceivalResult::TooManyUnrewardedRelayers
+			return Some(ReceivalResult::TooManyUnrewardedRelayers)
 		}
 
 		// if there are more unconfirmed messages than we may accept, reject this message
 		let unconfirmed_messages_count = nonce.saturating_sub(data.last_confirmed_nonce);
 		if unconfirmed_messages_count > self.storage.max_unconfirmed_messages() {
-			return ReceivalResult::TooManyUnconfirmedMessages
+			return Some(ReceivalResult::TooManyUnconfirmedMessages)
 		}
 
-		// then, dispatch message
-		let dispatch_result = P::dispatch(
-			relayer_at_this_chain,
-			DispatchMessage {
-				key: MessageKey { lane_id: self.storage.id(), nonce },
-				data: message_data,
-			},
-		);
+		None
+	}
 
-		// now let's update inbound lane storage
+	/// Remember that the message has been received by the relayer.
+	fn note_received_message(
+		data: &mut InboundLaneData<S::Relayer>,
+		relayer_at_bridged_chain: &S::Relayer,
+		nonce: MessageNonce,
+		dispatch_result: bool,
+	) {
 		let push_new = match data.relayers.back_mut() {
 			Some(entry) if entry.relayer == *relayer_at_bridged_chain => {
-				entry.messages.note_dispatched_message(dispatch_result.dispatch_result);
+				entry.messages.note_dispatched_message(dispatch_result);
 				false
 			},
 			_ => true,
@@ -161,12 +233,9 @@ impl<S: InboundLaneStorage> InboundLane<S> {
 		if push_new {
 			data.relayers.push_back(UnrewardedRelayer {
 				relayer: (*relayer_at_bridged_chain).clone(),
-				messages: DeliveredMessages::new(nonce, dispatch_result.dispatch_result),
+				messages: DeliveredMessages::new(nonce, dispatch_result),
 			});
 		}
-		self.storage.set_data(data);
-
-		ReceivalResult::Dispatched(dispatch_result)
 	}
 }
 
@@ -180,7 +249,7 @@ mod tests {
 			TestRuntime, REGULAR_PAYLOAD, TEST_LANE_ID, TEST_RELAYER_A, TEST_RELAYER_B,
 			TEST_RELAYER_C,
 		},
-		RuntimeInboundLaneStorage,
+		ExpiredInboundMessages, RuntimeInboundLaneStorage,
 	};
 
 	fn receive_regular_message(
@@ -501,4 +570,46 @@ mod tests {
 			);
 		});
 	}
+
+	#[test]
+	fn expired_message_is_received_without_dispatch() {
+		run_test(|| {
+			let mut lane = inbound_lane::<TestRuntime, _>(TEST_LANE_ID);
+			assert_eq!(lane.receive_expired_message(&TEST_RELAYER_A, 1), ReceivalResult::Expired);
+			assert_eq!(lane.storage.data().last_delivered_nonce(), 1);
+			let relayers = lane.storage.data().relayers;
+			assert_eq!(relayers.len(), 1);
+			assert_eq!(relayers[0].relayer, TEST_RELAYER_A);
+			assert!(!relayers[0].messages.message_dispatch_result(1));
+			assert_eq!(lane.storage.expired_messages(), vec![1]);
+		});
+	}
+
+	#[test]
+	fn receive_status_update_forgets_confirmed_expired_messages() {
+		run_test(|| {
+			let mut lane = inbound_lane::<TestRuntime, _>(TEST_LANE_ID);
+			assert_eq!(lane.receive_expired_message(&TEST_RELAYER_A, 1), ReceivalResult::Expired);
+			receive_regular_message(&mut lane, 2);
+			assert_eq!(lane.receive_expired_message(&TEST_RELAYER_A, 3), ReceivalResult::Expired);
+			assert_eq!(
+				lane.receive_state_update(OutboundLaneData {
+					latest_received_nonce: 2,
+					..Default::default()
+				}),
+				Some(2),
+			);
+			assert_eq!(lane.storage.expired_messages(), vec![3]);
+
+			assert_eq!(
+				lane.receive_state_update(OutboundLaneData {
+					latest_received_nonce: 3,
+					..Default::default()
+				}),
+				Some(3),
+			);
+			assert!(lane.storage.expired_messages().is_empty());
+			assert!(!ExpiredInboundMessages::<TestRuntime>::contains_key(TEST_LANE_ID));
+		});
+	}
 }
@@ -26,6 +26,33 @@
 //! Once message is sent, its progress can be tracked by looking at module events.
 //! The assigned nonce is reported using `MessageAccepted` event. When message is
 //! delivered to the the bridged chain, it is reported using `MessagesDelivered` event.
+//! Every message that is received and dispatched at the target chain is reported using
+//! `MessageDispatched` event, which includes the message dispatch result.
+//!
+//! Messages may only be sent and received over lanes that are registered by the pallet owner
+//! (or root) using `open_lane()` call. Once lane is no longer needed, it may be closed using
+//! `close_lane()` call. Closed lanes can't be reopened.
+//!
+//! Confirmed outbound messages are pruned when new messages are sent. Spare block weight is also
+//! used to prune confirmed messages in the `on_idle` hook. Only lanes that have received delivery
+//! confirmations since they have been fully pruned are visited there.
+//!
+//! If `FailedMessagesRetryPeriod` is non-zero, inbound messages which dispatch has failed are
+//! kept in the storage for this number of blocks. During this period anyone may retry their
+//! dispatch using `retry_message_dispatch()` call, at most `MaxDispatchRetries` times. The
+//! dispatch fee is only paid when the message is delivered, so it isn't paid again on retry.
+//!
+//! By default, relayers are rewarded right when message delivery is confirmed. If
+//! `ClaimableRelayersRewards` is `true`, rewards are accumulated in the storage instead and
+//! relayers may claim them using `claim_rewards()` call.
+//!
+//! Messages, sent using `send_message_with_deadline()` call, expire at the given source chain
+//! block. The target chain doesn't dispatch messages that have expired according to its view of
+//! the bridged chain (best finalized header) and keeps their nonces in the `ExpiredInboundMessages`
+//! map until their delivery is confirmed. The fee of such message is refunded to its submitter (and
+//! relayers get no reward for it) only when the source chain receives the delivery confirmation
+//! that proves this map entry, so the fee is never refunded for the message that has been
+//! dispatched at the target chain.
 //!
 //! **IMPORTANT NOTE**: after generating weights (custom `WeighInfo` implementation) for
 //! your runtime (where this module is plugged to), please add test for these weights.
@@ -53,24 +80,24 @@ use bp_messages::{
 		LaneMessageVerifier, MessageDeliveryAndDispatchPayment, OnDeliveryConfirmed,
 		OnMessageAccepted, RelayersRewards, SendMessageArtifacts, TargetHeaderChain,
 	},
-	target_chain::{
-		DispatchMessage, MessageDispatch, ProvedLaneMessages, ProvedMessages, SourceHeaderChain,
-	},
-	total_unrewarded_messages, DeliveredMessages, InboundLaneData, LaneId, MessageData, MessageKey,
-	MessageNonce, OperatingMode, OutboundLaneData, Parameter as MessagesParameter,
-	UnrewardedRelayer, UnrewardedRelayersState,
+	target_chain::{DispatchMessage, MessageDispatch, SourceHeaderChain},
+	total_unrewarded_messages, DeliveredMessages, ExpiredMessages, InboundLaneData, LaneId,
+	LaneState, MessageData, MessageKey, MessageNonce, OperatingMode, OutboundLaneData,
+	Parameter as MessagesParameter, UnrewardedRelayer, UnrewardedRelayersState,
 };
-use bp_runtime::{ChainId, Size};
+use bp_runtime::{messages::MessageDispatchResult, ChainId, Size};
 use codec::{Decode, Encode};
 use frame_support::{
 	fail,
 	traits::Get,
-	weights::{Pays, PostDispatchInfo},
+	weights::{Pays, PostDispatchInfo, Weight},
+	RuntimeDebug,
 };
 use frame_system::RawOrigin;
 use num_traits::{SaturatingAdd, Zero};
+use scale_info::TypeInfo;
 use sp_core::H256;
-use sp_runtime::traits::{BadOrigin, Convert};
+use sp_runtime::traits::{BadOrigin, Convert, UniqueSaturatedInto};
 use sp_std::{
 	cell::RefCell, cmp::PartialOrd, collections::vec_deque::VecDeque, marker::PhantomData,
 	ops::RangeInclusive, prelude::*,
@@ -80,6 +107,7 @@ mod inbound_lane;
 mod outbound_lane;
 mod weights_ext;
 
+pub mod migrations;
 pub mod weights;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -156,10 +184,20 @@ pub mod pallet {
 		/// Payload type of inbound messages. This payload is dispatched on this chain.
 		type InboundPayload: Decode;
 		/// Message fee type of inbound messages. This fee is paid on the bridged chain.
-		type InboundMessageFee: Decode;
+		type InboundMessageFee: Parameter;
 		/// Identifier of relayer that deliver messages to this chain. Relayer reward is paid on the
 		/// bridged chain.
 		type InboundRelayer: Parameter;
+		/// Number of blocks during which inbound messages with failed dispatch may be retried
+		/// using `retry_message_dispatch` call.
+		///
+		/// Set it to zero to disable storing failed messages.
+		type FailedMessagesRetryPeriod: Get<Self::BlockNumber>;
+		/// Maximal number of times the dispatch of the failed inbound message may be retried.
+		///
+		/// The message is removed from the storage once its dispatch has failed this number of
+		/// times after delivery.
+		type MaxDispatchRetries: Get<u32>;
 
 		/// A type which can be turned into an AccountId from a 256-bit hash.
 		///
@@ -183,6 +221,12 @@ pub mod pallet {
 			Self::AccountId,
 			Self::OutboundMessageFee,
 		>;
+		/// If `true`, relayers rewards are accumulated in the `RelayerRewards` map when message
+		/// delivery is confirmed. Relayers may then claim their rewards using `claim_rewards`
+		/// call. Otherwise, rewards are paid by the `MessageDeliveryAndDispatchPayment` right
+		/// when delivery is confirmed.
+		#[pallet::constant]
+		type ClaimableRelayersRewards: Get<bool>;
 		/// Handler for accepted messages.
 		type OnMessageAccepted: OnMessageAccepted;
 		/// Handler for delivered messages.
@@ -198,6 +242,10 @@ pub mod pallet {
 			Self::InboundMessageFee,
 			DispatchPayload = Self::InboundPayload,
 		>;
+		/// Number of the best finalized block of the bridged chain, known to this chain.
+		///
+		/// Inbound messages that have expired at this block are not dispatched.
+		type BridgedChainBestFinalizedBlockNumber: Get<u64>;
 	}
 
 	/// Shortcut to messages proof type for Config.
@@ -211,11 +259,94 @@ pub mod pallet {
 			<T as frame_system::Config>::AccountId,
 		>>::MessagesDeliveryProof;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
+			// remove failed messages which retry period has expired
+			let expired_messages = FailedMessagesExpiry::<T, I>::take(block_number);
+			let expired_messages_count = expired_messages.len() as u64;
+			for message_key in expired_messages {
+				FailedMessages::<T, I>::remove(message_key);
+			}
+
+			T::DbWeight::get().reads_writes(1, 1 + expired_messages_count)
+		}
+
+		fn on_idle(_block_number: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			// don't touch the storage if pallet is halted
+			let mut used_weight = T::DbWeight::get().reads(1);
+			if remaining_weight < used_weight {
+				return 0
+			}
+			if ensure_not_halted::<T, I>().is_err() {
+				return used_weight
+			}
+
+			// prune confirmed messages of outbound lanes, while we have enough weight to prune at
+			// least one message
+			let lane_overhead = T::WeightInfo::outbound_lane_pruning_overhead(T::DbWeight::get());
+			let message_weight = T::WeightInfo::single_message_pruning_weight(T::DbWeight::get());
+			let mut lanes = OutboundLanesToPrune::<T, I>::iter_keys();
+			let mut pruned_messages = 0;
+			loop {
+				let weight_left = remaining_weight.saturating_sub(used_weight);
+				if weight_left < lane_overhead.saturating_add(message_weight) {
+					break
+				}
+				let lane_id = match lanes.next() {
+					Some(lane_id) => lane_id,
+					None => break,
+				};
+
+				let max_messages_to_prune = (weight_left - lane_overhead)
+					.checked_div(message_weight)
+					.unwrap_or(MessageNonce::MAX);
+				let lane_pruned_messages =
+					outbound_lane::<T, I>(lane_id).prune_messages(max_messages_to_prune);
+				// if we have pruned less messages than we have asked for, then all confirmed
+				// messages of the lane are pruned and we don't need to visit it again
+				if lane_pruned_messages < max_messages_to_prune {
+					OutboundLanesToPrune::<T, I>::remove(lane_id);
+				}
+				used_weight = used_weight
+					.saturating_add(lane_overhead)
+					.saturating_add(lane_pruned_messages.saturating_mul(message_weight));
+				pruned_messages += lane_pruned_messages;
+			}
+
+			if pruned_messages != 0 {
+				log::trace!(
+					target: "runtime::bridge-messages",
+					"Pruned {} confirmed outbound messages in on_idle. Weight used: {}/{}",
+					pruned_messages,
+					used_weight,
+					remaining_weight,
+				);
+			}
+
+			used_weight
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_block_number: BlockNumberFor<T>) -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// Change `PalletOwner`.
@@ -255,6 +386,30 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Halt or resume all/some operations on the given lane.
+		///
+		/// The lane operating mode is checked in addition to the pallet operating mode, so the
+		/// lane operation is allowed only if it is allowed by both modes.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::DbWeight::get().reads_writes(2, 1), DispatchClass::Operational))]
+		pub fn set_lane_operating_mode(
+			origin: OriginFor<T>,
+			lane_id: LaneId,
+			operating_mode: OperatingMode,
+		) -> DispatchResult {
+			ensure_owner_or_root::<T, I>(origin)?;
+			ensure_lane_is_not_closed::<T, I>(lane_id)?;
+			LaneOperatingModes::<T, I>::insert(&lane_id, operating_mode);
+			log::info!(
+				target: "runtime::bridge-messages",
+				"Setting lane {:?} operating mode to {:?}.",
+				lane_id,
+				operating_mode,
+			);
+			Ok(())
+		}
+
 		/// Update pallet parameter.
 		///
 		/// May only be called either by root, or by `PalletOwner`.
@@ -272,6 +427,72 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Register new lane.
+		///
+		/// Messages may only be sent and received over registered lanes. The lane id must not
+		/// be used before (even if the lane has been closed since then).
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::DbWeight::get().reads_writes(2, 2), DispatchClass::Operational))]
+		pub fn open_lane(origin: OriginFor<T>, lane_id: LaneId) -> DispatchResult {
+			ensure_owner_or_root::<T, I>(origin)?;
+			ensure!(!Lanes::<T, I>::contains_key(&lane_id), Error::<T, I>::LaneAlreadyRegistered);
+
+			Lanes::<T, I>::insert(&lane_id, LaneState::Opened);
+			log::info!(target: "runtime::bridge-messages", "Opened lane {:?}", lane_id);
+			Self::deposit_event(Event::LaneOpened(lane_id));
+			Ok(())
+		}
+
+		/// Start closing the lane or continue closing the lane that is already closing.
+		///
+		/// Once the lane is closing, no new messages may be sent over it. Queued messages are
+		/// still delivered to the bridged chain and inbound messages are still accepted. When
+		/// all outbound messages are delivered and confirmed, and all inbound messages are
+		/// confirmed, the lane is closed and its storage is removed. This call prunes at most
+		/// `MaxMessagesToPruneAtOnce` confirmed messages, so it may need to be called several
+		/// times before the lane is actually closed.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((
+			T::DbWeight::get().reads_writes(4, 6 + T::MaxMessagesToPruneAtOnce::get()),
+			DispatchClass::Operational,
+		))]
+		pub fn close_lane(origin: OriginFor<T>, lane_id: LaneId) -> DispatchResult {
+			ensure_owner_or_root::<T, I>(origin)?;
+			match Lanes::<T, I>::get(&lane_id) {
+				Some(LaneState::Opened) => {
+					Lanes::<T, I>::insert(&lane_id, LaneState::Closing);
+					log::info!(target: "runtime::bridge-messages", "Closing lane {:?}", lane_id);
+					Self::deposit_event(Event::LaneClosing(lane_id));
+				},
+				Some(LaneState::Closing) => (),
+				Some(LaneState::Closed) => fail!(Error::<T, I>::LaneIsClosed),
+				None => fail!(Error::<T, I>::UnknownLane),
+			}
+
+			// prune confirmed messages and close the lane if it is fully drained
+			let mut lane = outbound_lane::<T, I>(lane_id);
+			lane.prune_messages(T::MaxMessagesToPruneAtOnce::get());
+			let outbound_lane_data = lane.data();
+			let is_outbound_lane_drained = outbound_lane_data.latest_received_nonce ==
+				outbound_lane_data.latest_generated_nonce &&
+				outbound_lane_data.oldest_unpruned_nonce >
+					outbound_lane_data.latest_generated_nonce;
+			let is_inbound_lane_drained = InboundLanes::<T, I>::get(&lane_id).relayers.is_empty();
+			if is_outbound_lane_drained && is_inbound_lane_drained {
+				OutboundLanes::<T, I>::remove(&lane_id);
+				OutboundLanesToPrune::<T, I>::remove(&lane_id);
+				InboundLanes::<T, I>::remove(&lane_id);
+				LaneOperatingModes::<T, I>::remove(&lane_id);
+				Lanes::<T, I>::insert(&lane_id, LaneState::Closed);
+				log::info!(target: "runtime::bridge-messages", "Closed lane {:?}", lane_id);
+				Self::deposit_event(Event::LaneClosed(lane_id));
+			}
+
+			Ok(())
+		}
+
 		/// Send message over lane.
 		#[pallet::weight(T::WeightInfo::send_message_weight(payload, T::DbWeight::get()))]
 		pub fn send_message(
@@ -280,12 +501,52 @@ pub mod pallet {
 			payload: T::OutboundPayload,
 			delivery_and_dispatch_fee: T::OutboundMessageFee,
 		) -> DispatchResultWithPostInfo {
-			crate::send_message::<T, I>(origin, lane_id, payload, delivery_and_dispatch_fee).map(
-				|sent_message| PostDispatchInfo {
+			crate::send_message::<T, I>(origin, lane_id, payload, delivery_and_dispatch_fee, None)
+				.map(|sent_message| PostDispatchInfo {
 					actual_weight: Some(sent_message.weight),
 					pays_fee: Pays::Yes,
-				},
-			)
+				})
+		}
+
+		/// Send message over lane, that expires at given block of this chain.
+		///
+		/// If the message is delivered after its deadline, it isn't dispatched at the target
+		/// chain and its fee is refunded to the submitter when the delivery is confirmed.
+		#[pallet::weight(
+			T::WeightInfo::send_message_weight(payload, T::DbWeight::get())
+				.saturating_add(T::DbWeight::get().writes(1))
+		)]
+		pub fn send_message_with_deadline(
+			origin: OriginFor<T>,
+			lane_id: LaneId,
+			payload: T::OutboundPayload,
+			delivery_and_dispatch_fee: T::OutboundMessageFee,
+			expires_at: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let submitter = ensure_signed(origin.clone())?;
+			ensure!(
+				expires_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T, I>::MessageDeadlineIsInThePast
+			);
+
+			let sent_message = crate::send_message::<T, I>(
+				origin,
+				lane_id,
+				payload,
+				delivery_and_dispatch_fee,
+				Some(expires_at.unique_saturated_into()),
+			)?;
+			OutboundMessageSubmitters::<T, I>::insert(
+				MessageKey { lane_id, nonce: sent_message.nonce },
+				submitter,
+			);
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(
+					sent_message.weight.saturating_add(T::DbWeight::get().writes(1)),
+				),
+				pays_fee: Pays::Yes,
+			})
 		}
 
 		/// Pay additional fee for the message.
@@ -297,6 +558,7 @@ pub mod pallet {
 			additional_fee: T::OutboundMessageFee,
 		) -> DispatchResultWithPostInfo {
 			ensure_not_halted::<T, I>()?;
+			ensure_lane_not_halted::<T, I>(lane_id)?;
 			// if someone tries to pay for already-delivered message, we're rejecting this intention
 			// (otherwise this additional fee will be locked forever in relayers fund)
 			//
@@ -394,22 +656,34 @@ pub mod pallet {
 			let mut actual_weight = declared_weight;
 
 			// verify messages proof && convert proof into messages
-			let messages = verify_and_decode_messages_proof::<
-				T::SourceHeaderChain,
-				T::InboundMessageFee,
-				T::InboundPayload,
-			>(proof, messages_count)
-			.map_err(|err| {
-				log::trace!(
-					target: "runtime::bridge-messages",
-					"Rejecting invalid messages proof: {:?}",
-					err,
-				);
+			//
+			// `receive_messages_proof` weight formula and `MaxUnconfirmedMessagesAtInboundLane`
+			// check guarantees that the `message_count` is sane and Vec<Message> may be allocated.
+			// (tx with too many messages will either be rejected from the pool, or will fail
+			// earlier)
+			let messages = T::SourceHeaderChain::verify_messages_proof(proof, messages_count)
+				.map_err(|err| {
+					log::trace!(
+						target: "runtime::bridge-messages",
+						"Rejecting invalid messages proof: {:?}",
+						err,
+					);
 
-				Error::<T, I>::InvalidMessagesProof
-			})?;
+					Error::<T, I>::InvalidMessagesProof
+				})?;
+
+			// reject the whole proof if it brings messages or lane states of unknown, closed or
+			// halted lanes
+			for lane_id in messages.keys() {
+				ensure_lane_is_not_closed::<T, I>(*lane_id)?;
+				ensure_lane_not_halted::<T, I>(*lane_id)?;
+			}
 
 			// dispatch messages and (optionally) update lane(s) state(s)
+			let failed_messages_retry_period = T::FailedMessagesRetryPeriod::get();
+			let is_dispatch_retry_enabled = !failed_messages_retry_period.is_zero();
+			let bridged_chain_best_finalized_block_number =
+				T::BridgedChainBestFinalizedBlockNumber::get();
 			let mut total_messages = 0;
 			let mut valid_messages = 0;
 			let mut dispatch_weight_left = dispatch_weight;
@@ -428,13 +702,34 @@ pub mod pallet {
 					}
 				}
 
-				for mut message in lane_data.messages {
+				for message in lane_data.messages {
 					debug_assert_eq!(message.key.lane_id, lane_id);
 
+					// expired messages are not dispatched, so they don't need any dispatch weight
+					let is_expired = message
+						.data
+						.expires_at
+						.map(|expires_at| bridged_chain_best_finalized_block_number >= expires_at)
+						.unwrap_or(false);
+
+					// the decoded payload may not be encoded back, so if we want to be able to
+					// retry failed dispatch later, we need to keep the raw message data
+					let raw_message_data =
+						if is_dispatch_retry_enabled { Some(message.data.clone()) } else { None };
+					let mut message: DispatchMessage<T::InboundPayload, T::InboundMessageFee> =
+						message.into();
+					let raw_message_data =
+						raw_message_data.filter(|_| message.data.payload.is_ok());
+					let message_key = message.key.clone();
+
 					// ensure that relayer has declared enough weight for dispatching next message
 					// on this lane. We can't dispatch lane messages out-of-order, so if declared
 					// weight is not enough, let's move to next lane
-					let dispatch_weight = T::MessageDispatch::dispatch_weight(&mut message);
+					let dispatch_weight = if is_expired {
+						0
+					} else {
+						T::MessageDispatch::dispatch_weight(&mut message)
+					};
 					if dispatch_weight > dispatch_weight_left {
 						log::trace!(
 							target: "runtime::bridge-messages",
@@ -447,12 +742,19 @@ pub mod pallet {
 					}
 					total_messages += 1;
 
-					let receival_result = lane.receive_message::<T::MessageDispatch, T::AccountId>(
-						&relayer_id_at_bridged_chain,
-						&relayer_id_at_this_chain,
-						message.key.nonce,
-						message.data,
-					);
+					let receival_result = if is_expired {
+						lane.receive_expired_message(
+							&relayer_id_at_bridged_chain,
+							message.key.nonce,
+						)
+					} else {
+						lane.receive_message::<T::MessageDispatch, T::AccountId>(
+							&relayer_id_at_bridged_chain,
+							&relayer_id_at_this_chain,
+							message.key.nonce,
+							message.data,
+						)
+					};
 
 					// note that we're returning unspent weight to relayer even if message has been
 					// rejected by the lane. This allows relayers to submit spam transactions with
@@ -463,10 +765,38 @@ pub mod pallet {
 					let (unspent_weight, refund_pay_dispatch_fee) = match receival_result {
 						ReceivalResult::Dispatched(dispatch_result) => {
 							valid_messages += 1;
-							(
-								dispatch_result.unspent_weight,
-								!dispatch_result.dispatch_fee_paid_during_dispatch,
-							)
+							Self::deposit_event(Event::MessageDispatched(
+								lane_id,
+								message_key.nonce,
+								dispatch_result.clone(),
+							));
+
+							// if dispatch has failed, remember the message so that it may be
+							// retried later. Storing the message costs two db writes, which are
+							// paid from the unspent dispatch weight
+							let mut unspent_weight = dispatch_result.unspent_weight;
+							if let (false, Some(raw_message_data)) =
+								(dispatch_result.dispatch_result, raw_message_data)
+							{
+								store_failed_message::<T, I>(
+									message_key,
+									raw_message_data,
+									dispatch_weight,
+									failed_messages_retry_period,
+								);
+								unspent_weight =
+									unspent_weight.saturating_sub(T::DbWeight::get().writes(2));
+							}
+
+							(unspent_weight, !dispatch_result.dispatch_fee_paid_during_dispatch)
+						},
+						ReceivalResult::Expired => {
+							valid_messages += 1;
+							Self::deposit_event(Event::MessageExpired(lane_id, message_key.nonce));
+
+							// the message is not dispatched, so updating `ExpiredInboundMessages`
+							// is covered by the message delivery overhead
+							(0, true)
 						},
 						ReceivalResult::InvalidNonce |
 						ReceivalResult::TooManyUnrewardedRelayers |
@@ -500,6 +830,121 @@ pub mod pallet {
 			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
 		}
 
+		/// Retry dispatch of the inbound message which dispatch has failed before.
+		///
+		/// Only messages from the `FailedMessages` map may be retried. The message is removed
+		/// from the map once it is dispatched successfully, when its retry period expires or when
+		/// its dispatch has been retried `MaxDispatchRetries` times. The `dispatch_weight` must be
+		/// enough to cover message dispatch. Anyone may call this method. The dispatch fee has
+		/// already been paid when the message has been delivered, so neither the message sender
+		/// is charged nor the caller is paid here.
+		#[pallet::weight(
+			T::DbWeight::get().reads_writes(2, 2).saturating_add(*dispatch_weight)
+		)]
+		pub fn retry_message_dispatch(
+			origin: OriginFor<T>,
+			message_key: MessageKey,
+			dispatch_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			ensure_not_halted::<T, I>()?;
+			ensure_lane_not_halted::<T, I>(message_key.lane_id)?;
+			ensure_signed(origin)?;
+
+			let failed_message = FailedMessages::<T, I>::get(&message_key)
+				.ok_or(Error::<T, I>::FailedMessageNotFound)?;
+			let mut message: DispatchMessage<T::InboundPayload, T::InboundMessageFee> =
+				DispatchMessage {
+					key: message_key.clone(),
+					data: failed_message.data.clone().into(),
+				};
+			let message_dispatch_weight = T::MessageDispatch::dispatch_weight(&mut message);
+			ensure!(
+				message_dispatch_weight <= dispatch_weight,
+				Error::<T, I>::InsufficientDispatchWeight
+			);
+
+			let dispatch_result = T::MessageDispatch::redispatch(message);
+			let retries = failed_message.retries.saturating_add(1);
+			if dispatch_result.dispatch_result || retries >= T::MaxDispatchRetries::get() {
+				FailedMessages::<T, I>::remove(&message_key);
+			} else {
+				FailedMessages::<T, I>::insert(
+					&message_key,
+					FailedMessage { retries, ..failed_message },
+				);
+			}
+
+			log::trace!(
+				target: "runtime::bridge-messages",
+				"Retried dispatch of message {:?}: {:?}",
+				message_key,
+				dispatch_result,
+			);
+
+			Self::deposit_event(Event::MessageDispatchRetried(
+				message_key,
+				dispatch_result.dispatch_result,
+			));
+
+			// refund weight that has been declared, but not spent by the dispatch
+			let declared_weight =
+				T::DbWeight::get().reads_writes(2, 2).saturating_add(dispatch_weight);
+			let unspent_weight =
+				sp_std::cmp::min(dispatch_result.unspent_weight, message_dispatch_weight)
+					.saturating_add(dispatch_weight - message_dispatch_weight);
+			Ok(PostDispatchInfo {
+				actual_weight: Some(declared_weight.saturating_sub(unspent_weight)),
+				pays_fee: Pays::Yes,
+			})
+		}
+
+		/// Claim reward, accumulated by the relayer for delivering messages over given lane.
+		///
+		/// Rewards are only accumulated if `ClaimableRelayersRewards` is `true`. The reward is
+		/// paid by the `MessageDeliveryAndDispatchPayment` from the relayer fund account.
+		#[pallet::weight(T::WeightInfo::claim_rewards_weight(T::DbWeight::get()))]
+		pub fn claim_rewards(origin: OriginFor<T>, lane_id: LaneId) -> DispatchResult {
+			ensure_not_halted::<T, I>()?;
+			let relayer = ensure_signed(origin)?;
+
+			let reward = RelayerRewards::<T, I>::get(&relayer, lane_id);
+			ensure!(!reward.is_zero(), Error::<T, I>::NoRewardToClaim);
+
+			let relayer_fund_account =
+				relayer_fund_account_id::<T::AccountId, T::AccountIdConverter>();
+			T::MessageDeliveryAndDispatchPayment::pay_relayer_reward(
+				lane_id,
+				&relayer,
+				&reward,
+				&relayer_fund_account,
+			)
+			.map_err(|err| {
+				log::trace!(
+					target: "runtime::bridge-messages",
+					"Failed to pay reward of {:?} to relayer {:?} at lane {:?}: {:?}",
+					reward,
+					relayer,
+					lane_id,
+					err,
+				);
+
+				Error::<T, I>::FailedToPayReward
+			})?;
+			RelayerRewards::<T, I>::remove(&relayer, lane_id);
+
+			log::trace!(
+				target: "runtime::bridge-messages",
+				"Relayer {:?} has claimed reward of {:?} at lane {:?}",
+				relayer,
+				reward,
+				lane_id,
+			);
+
+			Self::deposit_event(Event::RewardsClaimed(relayer, lane_id, reward));
+
+			Ok(())
+		}
+
 		/// Receive messages delivery proof from bridged chain.
 		#[pallet::weight(T::WeightInfo::receive_messages_delivery_proof_weight(
 			proof,
@@ -524,6 +969,8 @@ pub mod pallet {
 			// to get pre-computed value (and it has been already computed by the executive).
 			let single_message_callback_overhead =
 				T::WeightInfo::single_message_callback_overhead(T::DbWeight::get());
+			let single_expired_message_refund_overhead =
+				T::WeightInfo::single_expired_message_refund_overhead(T::DbWeight::get());
 			let declared_weight = T::WeightInfo::receive_messages_delivery_proof_weight(
 				&proof,
 				&relayers_state,
@@ -532,8 +979,8 @@ pub mod pallet {
 			let mut actual_weight = declared_weight;
 
 			let confirmation_relayer = ensure_signed(origin)?;
-			let (lane_id, lane_data) = T::TargetHeaderChain::verify_messages_delivery_proof(proof)
-				.map_err(|err| {
+			let (lane_id, lane_data, expired_messages) =
+				T::TargetHeaderChain::verify_messages_delivery_proof(proof).map_err(|err| {
 					log::trace!(
 						target: "runtime::bridge-messages",
 						"Rejecting invalid messages delivery proof: {:?}",
@@ -542,6 +989,8 @@ pub mod pallet {
 
 					Error::<T, I>::InvalidMessagesDeliveryProof
 				})?;
+			ensure_lane_is_not_closed::<T, I>(lane_id)?;
+			ensure_lane_not_halted::<T, I>(lane_id)?;
 
 			// verify that the relayer has declared correct `lane_data::relayers` state
 			// (we only care about total number of entries and messages, because this affects call
@@ -562,8 +1011,11 @@ pub mod pallet {
 				last_delivered_nonce,
 				&lane_data.relayers,
 			) {
-				ReceivalConfirmationResult::ConfirmedMessages(confirmed_messages) =>
-					Some(confirmed_messages),
+				ReceivalConfirmationResult::ConfirmedMessages(confirmed_messages) => {
+					// confirmed messages will be pruned in the `on_idle` hook
+					OutboundLanesToPrune::<T, I>::insert(lane_id, ());
+					Some(confirmed_messages)
+				},
 				ReceivalConfirmationResult::NoNewConfirmations => None,
 				ReceivalConfirmationResult::TryingToConfirmMoreMessagesThanExpected(
 					to_confirm_messages_count,
@@ -622,20 +1074,40 @@ pub mod pallet {
 					},
 				}
 
+				// refund fees of messages that have expired before delivery. It must happen before
+				// relayers are rewarded, because they get no reward for delivering such messages
+				let preliminary_refund_overhead = relayers_state
+					.total_messages
+					.saturating_mul(single_expired_message_refund_overhead);
+				let actual_refund_weight =
+					refund_expired_messages::<T, I>(lane_id, &confirmed_messages, &expired_messages)
+						.saturating_mul(single_expired_message_refund_overhead);
+				let unspent_refund_weight =
+					preliminary_refund_overhead.saturating_sub(actual_refund_weight);
+				actual_weight = actual_weight.saturating_sub(unspent_refund_weight);
+
 				// emit 'delivered' event
 				let received_range = confirmed_messages.begin..=confirmed_messages.end;
 				Self::deposit_event(Event::MessagesDelivered(lane_id, confirmed_messages));
 
 				// if some new messages have been confirmed, reward relayers
-				let relayer_fund_account =
-					relayer_fund_account_id::<T::AccountId, T::AccountIdConverter>();
-				<T as Config<I>>::MessageDeliveryAndDispatchPayment::pay_relayers_rewards(
-					lane_id,
-					lane_data.relayers,
-					&confirmation_relayer,
-					&received_range,
-					&relayer_fund_account,
-				);
+				if T::ClaimableRelayersRewards::get() {
+					accumulate_relayers_rewards::<T, I>(
+						lane_id,
+						lane_data.relayers,
+						&received_range,
+					);
+				} else {
+					let relayer_fund_account =
+						relayer_fund_account_id::<T::AccountId, T::AccountIdConverter>();
+					<T as Config<I>>::MessageDeliveryAndDispatchPayment::pay_relayers_rewards(
+						lane_id,
+						lane_data.relayers,
+						&confirmation_relayer,
+						&received_range,
+						&relayer_fund_account,
+					);
+				}
 			}
 
 			log::trace!(
@@ -658,6 +1130,22 @@ pub mod pallet {
 		MessageAccepted(LaneId, MessageNonce),
 		/// Messages in the inclusive range have been delivered to the bridged chain.
 		MessagesDelivered(LaneId, DeliveredMessages),
+		/// Inbound message has been delivered and dispatched.
+		MessageDispatched(LaneId, MessageNonce, MessageDispatchResult),
+		/// Inbound message has been delivered after its deadline and it hasn't been dispatched.
+		MessageExpired(LaneId, MessageNonce),
+		/// Dispatch of the failed message has been retried. The flag is the dispatch result.
+		MessageDispatchRetried(MessageKey, bool),
+		/// Outbound message has expired before delivery and its fee has been refunded.
+		ExpiredMessageFeeRefunded(LaneId, MessageNonce),
+		/// Relayer has claimed reward for delivering messages over given lane.
+		RewardsClaimed(T::AccountId, LaneId, T::OutboundMessageFee),
+		/// New lane has been registered.
+		LaneOpened(LaneId),
+		/// The lane is closing and it doesn't accept new outbound messages anymore.
+		LaneClosing(LaneId),
+		/// The lane has been closed and its storage has been removed.
+		LaneClosed(LaneId),
 	}
 
 	#[pallet::error]
@@ -688,6 +1176,25 @@ pub mod pallet {
 		/// The number of actually confirmed messages is going to be larger than the number of
 		/// messages in the proof. This may mean that this or bridged chain storage is corrupted.
 		TryingToConfirmMoreMessagesThanExpected,
+		/// The lane is not registered.
+		UnknownLane,
+		/// The lane is already registered.
+		LaneAlreadyRegistered,
+		/// The lane is closing or closed and operation is not allowed.
+		LaneIsClosed,
+		/// The lane operating mode doesn't allow this operation.
+		LaneHalted,
+		/// The message is not in the failed messages map. Either it has been dispatched
+		/// successfully, or its retry period has expired, or it has never failed.
+		FailedMessageNotFound,
+		/// The declared dispatch weight is not enough to dispatch the message.
+		InsufficientDispatchWeight,
+		/// The relayer has no reward to claim at given lane.
+		NoRewardToClaim,
+		/// Error generated by the `MessageDeliveryAndDispatchPayment` when paying relayer reward.
+		FailedToPayReward,
+		/// The message deadline must be in the future.
+		MessageDeadlineIsInThePast,
 	}
 
 	/// Optional pallet owner.
@@ -708,27 +1215,93 @@ pub mod pallet {
 	pub type PalletOperatingMode<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, OperatingMode, ValueQuery>;
 
+	/// Map of lane id => lane state.
+	///
+	/// Only lanes from this map may be used to send and receive messages.
+	#[pallet::storage]
+	#[pallet::getter(fn lane_state)]
+	pub type Lanes<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, LaneState>;
+
+	/// Map of lane id => lane operating mode.
+	///
+	/// Depending on the mode either all, some, or no transactions will be allowed at the lane.
+	/// Lanes that are missing from this map are in the `OperatingMode::Normal` mode.
+	#[pallet::storage]
+	#[pallet::getter(fn lane_operating_mode)]
+	pub type LaneOperatingModes<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, OperatingMode, ValueQuery>;
+
+	/// Map of (relayer, lane id) => reward that relayer may claim for delivering messages.
+	#[pallet::storage]
+	#[pallet::getter(fn relayer_reward)]
+	pub type RelayerRewards<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		LaneId,
+		T::OutboundMessageFee,
+		ValueQuery,
+	>;
+
+	/// Inbound messages which dispatch has failed and that may be retried.
+	#[pallet::storage]
+	#[pallet::getter(fn failed_message)]
+	pub type FailedMessages<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		MessageKey,
+		FailedMessage<T::BlockNumber, T::InboundMessageFee>,
+	>;
+
+	/// Map of block number => keys of failed messages which retry period expires at this block.
+	#[pallet::storage]
+	pub type FailedMessagesExpiry<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::BlockNumber, Vec<MessageKey>, ValueQuery>;
+
 	/// Map of lane id => inbound lane data.
 	#[pallet::storage]
 	pub type InboundLanes<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Blake2_128Concat, LaneId, InboundLaneData<T::InboundRelayer>, ValueQuery>;
 
+	/// Map of lane id => nonces of inbound messages that have been delivered after their deadline
+	/// and whose delivery is not yet confirmed.
+	#[pallet::storage]
+	pub type ExpiredInboundMessages<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, ExpiredMessages, ValueQuery>;
+
 	/// Map of lane id => outbound lane data.
 	#[pallet::storage]
 	pub type OutboundLanes<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Blake2_128Concat, LaneId, OutboundLaneData, ValueQuery>;
 
+	/// Set of outbound lanes that may have confirmed, but not yet pruned messages.
+	///
+	/// Only these lanes are visited when pruning messages in the `on_idle` hook.
+	#[pallet::storage]
+	pub type OutboundLanesToPrune<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, LaneId, ()>;
+
 	/// All queued outbound messages.
 	#[pallet::storage]
 	pub type OutboundMessages<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Blake2_128Concat, MessageKey, MessageData<T::OutboundMessageFee>>;
 
+	/// Submitters of queued outbound messages that have a deadline and whose fee may be refunded
+	/// if they expire before delivery.
+	#[pallet::storage]
+	pub type OutboundMessageSubmitters<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, MessageKey, T::AccountId>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
 		/// Initial pallet operating mode.
 		pub operating_mode: OperatingMode,
 		/// Initial pallet owner.
 		pub owner: Option<T::AccountId>,
+		/// Lanes that are opened at genesis.
+		pub opened_lanes: Vec<LaneId>,
 		/// Dummy marker.
 		pub phantom: sp_std::marker::PhantomData<I>,
 	}
@@ -739,6 +1312,7 @@ pub mod pallet {
 			Self {
 				operating_mode: Default::default(),
 				owner: Default::default(),
+				opened_lanes: Default::default(),
 				phantom: Default::default(),
 			}
 		}
@@ -751,6 +1325,9 @@ pub mod pallet {
 			if let Some(ref owner) = self.owner {
 				PalletOwner::<T, I>::put(owner);
 			}
+			for lane_id in &self.opened_lanes {
+				Lanes::<T, I>::insert(lane_id, LaneState::Opened);
+			}
 		}
 	}
 
@@ -762,9 +1339,65 @@ pub mod pallet {
 		) -> Option<MessageData<T::OutboundMessageFee>> {
 			OutboundMessages::<T, I>::get(MessageKey { lane_id: lane, nonce })
 		}
+
+		/// Ensure that the pallet storage is consistent.
+		///
+		/// Returns an error if any of the storage invariants is broken.
+		#[cfg(any(feature = "try-runtime", test))]
+		pub fn do_try_state() -> Result<(), &'static str> {
+			for lane_data in InboundLanes::<T, I>::iter_values() {
+				ensure_inbound_lane_is_consistent::<T, I>(&lane_data)?;
+			}
+			for (lane_id, lane_data) in OutboundLanes::<T, I>::iter() {
+				ensure_outbound_lane_is_consistent(&lane_data)?;
+				ensure!(
+					lane_data.oldest_unpruned_nonce > lane_data.latest_received_nonce ||
+						OutboundLanesToPrune::<T, I>::contains_key(lane_id),
+					"Outbound lane with confirmed messages is not scheduled for pruning"
+				);
+			}
+			for message_key in OutboundMessages::<T, I>::iter_keys() {
+				let lane_data = OutboundLanes::<T, I>::get(&message_key.lane_id);
+				ensure!(
+					(lane_data.oldest_unpruned_nonce..=lane_data.latest_generated_nonce)
+						.contains(&message_key.nonce),
+					"Outbound message is outside of the unpruned range of its lane"
+				);
+			}
+			for (lane_id, expired_messages) in ExpiredInboundMessages::<T, I>::iter() {
+				let lane_data = InboundLanes::<T, I>::get(&lane_id);
+				for nonce in expired_messages {
+					ensure!(
+						nonce > lane_data.last_confirmed_nonce &&
+							nonce <= lane_data.last_delivered_nonce(),
+						"Expired inbound message is not an unconfirmed message of its lane"
+					);
+				}
+			}
+			for message_key in OutboundMessageSubmitters::<T, I>::iter_keys() {
+				ensure!(
+					OutboundMessages::<T, I>::contains_key(&message_key),
+					"Submitter of the missing outbound message is stored"
+				);
+			}
+			Ok(())
+		}
 	}
 }
 
+/// Inbound message which dispatch has failed.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct FailedMessage<BlockNumber, Fee> {
+	/// Raw message data (payload and fee).
+	pub data: MessageData<Fee>,
+	/// Message dispatch weight, computed when message has been delivered.
+	pub dispatch_weight: Weight,
+	/// Number of block at which the message will be removed from the storage.
+	pub expires_at: BlockNumber,
+	/// Number of times the message dispatch has been retried and failed.
+	pub retries: u32,
+}
+
 /// AccountId of the shared relayer fund account.
 ///
 /// This account is passed to `MessageDeliveryAndDispatchPayment` trait, and depending
@@ -794,7 +1427,7 @@ where
 		message: T::OutboundPayload,
 		delivery_and_dispatch_fee: T::OutboundMessageFee,
 	) -> Result<SendMessageArtifacts, Self::Error> {
-		crate::send_message::<T, I>(sender, lane, message, delivery_and_dispatch_fee)
+		crate::send_message::<T, I>(sender, lane, message, delivery_and_dispatch_fee, None)
 	}
 }
 
@@ -804,11 +1437,14 @@ fn send_message<T: Config<I>, I: 'static>(
 	lane_id: LaneId,
 	payload: T::OutboundPayload,
 	delivery_and_dispatch_fee: T::OutboundMessageFee,
+	expires_at: Option<u64>,
 ) -> sp_std::result::Result<
 	SendMessageArtifacts,
 	sp_runtime::DispatchErrorWithPostInfo<PostDispatchInfo>,
 > {
 	ensure_normal_operating_mode::<T, I>()?;
+	ensure_lane_is_opened::<T, I>(lane_id)?;
+	ensure_lane_normal_operating_mode::<T, I>(lane_id)?;
 
 	// initially, actual (post-dispatch) weight is equal to pre-dispatch weight
 	let mut actual_weight = T::WeightInfo::send_message_weight(&payload, T::DbWeight::get());
@@ -866,8 +1502,11 @@ fn send_message<T: Config<I>, I: 'static>(
 	// finally, save message in outbound storage and emit event
 	let encoded_payload = payload.encode();
 	let encoded_payload_len = encoded_payload.len();
-	let nonce =
-		lane.send_message(MessageData { payload: encoded_payload, fee: delivery_and_dispatch_fee });
+	let nonce = lane.send_message(MessageData {
+		payload: encoded_payload,
+		fee: delivery_and_dispatch_fee,
+		expires_at,
+	});
 	// Guaranteed to be called outside only when the message is accepted.
 	// We assume that the maximum weight call back used is `single_message_callback_overhead`, so do
 	// not perform complex db operation in callback. If you want to, put these magic logic in
@@ -952,6 +1591,84 @@ where
 	relayers_rewards
 }
 
+/// Accumulate rewards of relayers that have delivered confirmed messages in the runtime storage.
+fn accumulate_relayers_rewards<T: Config<I>, I: 'static>(
+	lane_id: LaneId,
+	messages_relayers: VecDeque<UnrewardedRelayer<T::AccountId>>,
+	received_range: &RangeInclusive<MessageNonce>,
+) {
+	let relayers_rewards =
+		calc_relayers_rewards::<T, I>(lane_id, messages_relayers, received_range);
+	for (relayer, relayer_reward) in relayers_rewards {
+		RelayerRewards::<T, I>::mutate(&relayer, lane_id, |reward| {
+			*reward = reward.saturating_add(&relayer_reward.reward);
+		});
+	}
+}
+
+/// Refund fees of confirmed messages that have expired before delivery to their submitters.
+///
+/// Returns number of confirmed messages that have expired before delivery.
+fn refund_expired_messages<T: Config<I>, I: 'static>(
+	lane_id: LaneId,
+	confirmed_messages: &DeliveredMessages,
+	expired_messages: &ExpiredMessages,
+) -> MessageNonce {
+	let relayer_fund_account = relayer_fund_account_id::<T::AccountId, T::AccountIdConverter>();
+	let mut confirmed_expired_messages = 0;
+	// this loop is bound by `T::MaxUnconfirmedMessagesAtInboundLane` on the bridged chain
+	for nonce in expired_messages.iter().cloned() {
+		if !confirmed_messages.contains_message(nonce) {
+			continue
+		}
+		confirmed_expired_messages += 1;
+
+		// only messages, sent using `send_message_with_deadline` call, may expire
+		let message_key = MessageKey { lane_id, nonce };
+		let submitter = match OutboundMessageSubmitters::<T, I>::take(&message_key) {
+			Some(submitter) => submitter,
+			None => continue,
+		};
+		let mut message_data = OutboundMessages::<T, I>::get(&message_key)
+			.expect("message was just confirmed; we never prune unconfirmed messages; qed");
+		let refund_result = T::MessageDeliveryAndDispatchPayment::refund_delivery_and_dispatch_fee(
+			&submitter,
+			&message_data.fee,
+			&relayer_fund_account,
+		);
+		match refund_result {
+			Ok(()) => {
+				// relayers are not rewarded for delivering expired message
+				message_data.fee = Zero::zero();
+				OutboundMessages::<T, I>::insert(&message_key, message_data);
+
+				log::trace!(
+					target: "runtime::bridge-messages",
+					"Refunded fee of expired message {:?}/{:?} to {:?}",
+					lane_id,
+					nonce,
+					submitter,
+				);
+
+				Pallet::<T, I>::deposit_event(Event::ExpiredMessageFeeRefunded(lane_id, nonce));
+			},
+			Err(err) => {
+				// the fee stays in the relayers fund and relayers are rewarded for the message
+				log::trace!(
+					target: "runtime::bridge-messages",
+					"Failed to refund fee {:?} of the expired message {:?}/{:?} to {:?}: {:?}",
+					message_data.fee,
+					lane_id,
+					nonce,
+					submitter,
+					err,
+				);
+			},
+		}
+	}
+	confirmed_expired_messages
+}
+
 /// Ensure that the origin is either root, or `PalletOwner`.
 fn ensure_owner_or_root<T: Config<I>, I: 'static>(origin: T::Origin) -> Result<(), BadOrigin> {
 	match origin.into() {
@@ -981,6 +1698,45 @@ fn ensure_not_halted<T: Config<I>, I: 'static>() -> Result<(), Error<T, I>> {
 	}
 }
 
+/// Ensure that the lane is in normal operational mode.
+fn ensure_lane_normal_operating_mode<T: Config<I>, I: 'static>(
+	lane_id: LaneId,
+) -> Result<(), Error<T, I>> {
+	if LaneOperatingModes::<T, I>::get(&lane_id) != OperatingMode::Normal {
+		Err(Error::<T, I>::LaneHalted)
+	} else {
+		Ok(())
+	}
+}
+
+/// Ensure that the lane is not halted.
+fn ensure_lane_not_halted<T: Config<I>, I: 'static>(lane_id: LaneId) -> Result<(), Error<T, I>> {
+	if LaneOperatingModes::<T, I>::get(&lane_id) == OperatingMode::Halted {
+		Err(Error::<T, I>::LaneHalted)
+	} else {
+		Ok(())
+	}
+}
+
+/// Ensure that the lane is opened and accepts new outbound messages.
+fn ensure_lane_is_opened<T: Config<I>, I: 'static>(lane_id: LaneId) -> Result<(), Error<T, I>> {
+	match Lanes::<T, I>::get(&lane_id) {
+		Some(LaneState::Opened) => Ok(()),
+		Some(LaneState::Closing) | Some(LaneState::Closed) => Err(Error::<T, I>::LaneIsClosed),
+		None => Err(Error::<T, I>::UnknownLane),
+	}
+}
+
+/// Ensure that the lane is either opened or closing, so it accepts inbound messages and
+/// delivery confirmations.
+fn ensure_lane_is_not_closed<T: Config<I>, I: 'static>(lane_id: LaneId) -> Result<(), Error<T, I>> {
+	match Lanes::<T, I>::get(&lane_id) {
+		Some(LaneState::Opened) | Some(LaneState::Closing) => Ok(()),
+		Some(LaneState::Closed) => Err(Error::<T, I>::LaneIsClosed),
+		None => Err(Error::<T, I>::UnknownLane),
+	}
+}
+
 /// Creates new inbound lane object, backed by runtime storage.
 fn inbound_lane<T: Config<I>, I: 'static>(
 	lane_id: LaneId,
@@ -1050,6 +1806,18 @@ impl<T: Config<I>, I: 'static> InboundLaneStorage for RuntimeInboundLaneStorage<
 		) = Some(data.clone());
 		InboundLanes::<T, I>::insert(&self.lane_id, data)
 	}
+
+	fn expired_messages(&self) -> ExpiredMessages {
+		ExpiredInboundMessages::<T, I>::get(&self.lane_id)
+	}
+
+	fn set_expired_messages(&mut self, expired_messages: ExpiredMessages) {
+		if expired_messages.is_empty() {
+			ExpiredInboundMessages::<T, I>::remove(&self.lane_id)
+		} else {
+			ExpiredInboundMessages::<T, I>::insert(&self.lane_id, expired_messages)
+		}
+	}
 }
 
 /// Runtime outbound lane storage.
@@ -1087,48 +1855,105 @@ impl<T: Config<I>, I: 'static> OutboundLaneStorage for RuntimeOutboundLaneStorag
 	}
 
 	fn remove_message(&mut self, nonce: &MessageNonce) {
-		OutboundMessages::<T, I>::remove(MessageKey { lane_id: self.lane_id, nonce: *nonce });
+		let message_key = MessageKey { lane_id: self.lane_id, nonce: *nonce };
+		OutboundMessageSubmitters::<T, I>::remove(&message_key);
+		OutboundMessages::<T, I>::remove(message_key);
 	}
 }
 
-/// Verify messages proof and return proved messages with decoded payload.
-fn verify_and_decode_messages_proof<Chain: SourceHeaderChain<Fee>, Fee, DispatchPayload: Decode>(
-	proof: Chain::MessagesProof,
-	messages_count: u32,
-) -> Result<ProvedMessages<DispatchMessage<DispatchPayload, Fee>>, Chain::Error> {
-	// `receive_messages_proof` weight formula and `MaxUnconfirmedMessagesAtInboundLane` check
-	// guarantees that the `message_count` is sane and Vec<Message> may be allocated.
-	// (tx with too many messages will either be rejected from the pool, or will fail earlier)
-	Chain::verify_messages_proof(proof, messages_count).map(|messages_by_lane| {
-		messages_by_lane
-			.into_iter()
-			.map(|(lane, lane_data)| {
-				(
-					lane,
-					ProvedLaneMessages {
-						lane_state: lane_data.lane_state,
-						messages: lane_data.messages.into_iter().map(Into::into).collect(),
-					},
-				)
-			})
-			.collect()
-	})
+/// Remember inbound message which dispatch has failed, so that it may be retried later.
+fn store_failed_message<T: Config<I>, I: 'static>(
+	message_key: MessageKey,
+	data: MessageData<T::InboundMessageFee>,
+	dispatch_weight: Weight,
+	retry_period: T::BlockNumber,
+) {
+	let expires_at = sp_runtime::traits::Saturating::saturating_add(
+		frame_system::Pallet::<T>::block_number(),
+		retry_period,
+	);
+	FailedMessagesExpiry::<T, I>::append(expires_at, message_key.clone());
+	FailedMessages::<T, I>::insert(
+		message_key,
+		FailedMessage { data, dispatch_weight, expires_at, retries: 0 },
+	);
+}
+
+/// Ensure that the inbound lane data is consistent.
+#[cfg(any(feature = "try-runtime", test))]
+fn ensure_inbound_lane_is_consistent<T: Config<I>, I: 'static>(
+	data: &InboundLaneData<T::InboundRelayer>,
+) -> Result<(), &'static str> {
+	frame_support::ensure!(
+		data.relayers.len() as MessageNonce <= T::MaxUnrewardedRelayerEntriesAtInboundLane::get(),
+		"Too many unrewarded relayer entries at the inbound lane"
+	);
+	frame_support::ensure!(
+		data.last_delivered_nonce().saturating_sub(data.last_confirmed_nonce) <=
+			T::MaxUnconfirmedMessagesAtInboundLane::get(),
+		"Too many unconfirmed messages at the inbound lane"
+	);
+
+	let mut expected_begin = None;
+	for entry in &data.relayers {
+		frame_support::ensure!(
+			entry.messages.begin <= entry.messages.end,
+			"Unrewarded relayer entry at the inbound lane has no messages"
+		);
+		frame_support::ensure!(
+			entry.messages.dispatch_results.len() as MessageNonce ==
+				entry.messages.total_messages(),
+			"Dispatch results of the unrewarded relayer entry do not match its messages range"
+		);
+		frame_support::ensure!(
+			entry.messages.end > data.last_confirmed_nonce,
+			"Unrewarded relayer entry at the inbound lane has only confirmed messages"
+		);
+		if let Some(expected_begin) = expected_begin {
+			frame_support::ensure!(
+				entry.messages.begin == expected_begin,
+				"Unrewarded relayer entries at the inbound lane are not contiguous"
+			);
+		}
+		expected_begin = Some(entry.messages.end.saturating_add(1));
+	}
+
+	Ok(())
+}
+
+/// Ensure that the outbound lane data is consistent.
+#[cfg(any(feature = "try-runtime", test))]
+fn ensure_outbound_lane_is_consistent(data: &OutboundLaneData) -> Result<(), &'static str> {
+	frame_support::ensure!(
+		data.oldest_unpruned_nonce <= data.latest_received_nonce.saturating_add(1),
+		"Outbound lane has pruned messages that are not yet received"
+	);
+	frame_support::ensure!(
+		data.latest_received_nonce <= data.latest_generated_nonce,
+		"Outbound lane has received messages that are not yet generated"
+	);
+	Ok(())
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use crate::mock::{
-		message, message_payload, run_test, unrewarded_relayer, Event as TestEvent, Origin,
-		TestMessageDeliveryAndDispatchPayment, TestMessagesDeliveryProof, TestMessagesParameter,
-		TestMessagesProof, TestOnDeliveryConfirmed1, TestOnDeliveryConfirmed2,
-		TestOnMessageAccepted, TestRuntime, TokenConversionRate, PAYLOAD_REJECTED_BY_TARGET_CHAIN,
-		REGULAR_PAYLOAD, TEST_LANE_ID, TEST_RELAYER_A, TEST_RELAYER_B,
+		message, message_data, message_payload, run_test, unrewarded_relayer,
+		BridgedChainBestFinalizedBlockNumber, ClaimableRelayersRewards, Event as TestEvent,
+		FailedMessagesRetryPeriod, MaxDispatchRetries, Origin,
+		TestMessageDeliveryAndDispatchPayment, TestMessageDispatch, TestMessagesDeliveryProof,
+		TestMessagesParameter, TestMessagesProof, TestOnDeliveryConfirmed1,
+		TestOnDeliveryConfirmed2, TestOnMessageAccepted, TestRelayer, TestRuntime,
+		TokenConversionRate, PAYLOAD_REJECTED_BY_TARGET_CHAIN, REGULAR_PAYLOAD, TEST_LANE_ID,
+		TEST_RELAYER_A, TEST_RELAYER_B,
 	};
 	use bp_messages::{UnrewardedRelayer, UnrewardedRelayersState};
 	use frame_support::{
 		assert_noop, assert_ok,
+		dispatch::DispatchResultWithPostInfo,
 		storage::generator::{StorageMap, StorageValue},
+		traits::Hooks,
 		weights::Weight,
 	};
 	use frame_system::{EventRecord, Pallet as System, Phase};
@@ -1204,6 +2029,7 @@ mod tests {
 					.into_iter()
 					.collect(),
 				},
+				ExpiredMessages::new(),
 			))),
 			UnrewardedRelayersState {
 				unrewarded_relayer_entries: 1,
@@ -1445,6 +2271,7 @@ mod tests {
 								.into_iter()
 								.collect(),
 						},
+						ExpiredMessages::new(),
 					))),
 					UnrewardedRelayersState {
 						unrewarded_relayer_entries: 1,
@@ -1457,6 +2284,146 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn lane_operating_mode_may_only_be_changed_by_owner_or_root() {
+		run_test(|| {
+			PalletOwner::<TestRuntime>::put(2);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::set_lane_operating_mode(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					OperatingMode::Halted,
+				),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(Pallet::<TestRuntime>::set_lane_operating_mode(
+				Origin::signed(2),
+				TEST_LANE_ID,
+				OperatingMode::Halted,
+			));
+			assert_eq!(
+				Pallet::<TestRuntime>::lane_operating_mode(TEST_LANE_ID),
+				OperatingMode::Halted
+			);
+			assert_ok!(Pallet::<TestRuntime>::set_lane_operating_mode(
+				Origin::root(),
+				TEST_LANE_ID,
+				OperatingMode::Normal,
+			));
+			assert_eq!(
+				Pallet::<TestRuntime>::lane_operating_mode(TEST_LANE_ID),
+				OperatingMode::Normal
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::set_lane_operating_mode(
+					Origin::root(),
+					[0, 0, 0, 2],
+					OperatingMode::Halted,
+				),
+				Error::<TestRuntime, ()>::UnknownLane,
+			);
+		});
+	}
+
+	#[test]
+	fn pallet_rejects_lane_transactions_if_lane_is_halted() {
+		run_test(|| {
+			// send message first to be able to check that delivery_proof fails later
+			send_regular_message();
+
+			LaneOperatingModes::<TestRuntime, ()>::insert(TEST_LANE_ID, OperatingMode::Halted);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::send_message(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					REGULAR_PAYLOAD,
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::LaneHalted,
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::increase_message_fee(Origin::signed(1), TEST_LANE_ID, 1, 1,),
+				Error::<TestRuntime, ()>::LaneHalted,
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_proof(
+					Origin::signed(1),
+					TEST_RELAYER_A,
+					Ok(vec![message(2, REGULAR_PAYLOAD)]).into(),
+					1,
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::LaneHalted,
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_delivery_proof(
+					Origin::signed(1),
+					TestMessagesDeliveryProof(Ok((
+						TEST_LANE_ID,
+						InboundLaneData {
+							last_confirmed_nonce: 1,
+							relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
+								.into_iter()
+								.collect(),
+						},
+						ExpiredMessages::new(),
+					))),
+					UnrewardedRelayersState {
+						unrewarded_relayer_entries: 1,
+						messages_in_oldest_entry: 1,
+						total_messages: 1,
+					},
+				),
+				Error::<TestRuntime, ()>::LaneHalted,
+			);
+		});
+	}
+
+	#[test]
+	fn pallet_rejects_new_messages_if_lane_is_rejecting_outbound_messages() {
+		run_test(|| {
+			send_regular_message();
+
+			LaneOperatingModes::<TestRuntime, ()>::insert(
+				TEST_LANE_ID,
+				OperatingMode::RejectingOutboundMessages,
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::send_message(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					REGULAR_PAYLOAD,
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::LaneHalted,
+			);
+
+			assert_ok!(Pallet::<TestRuntime>::increase_message_fee(
+				Origin::signed(1),
+				TEST_LANE_ID,
+				1,
+				1,
+			));
+
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				Origin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
+				1,
+				REGULAR_PAYLOAD.declared_weight,
+			));
+
+			receive_messages_delivery_proof();
+		});
+	}
+
 	#[test]
 	fn pallet_rejects_new_messages_in_rejecting_outbound_messages_operating_mode() {
 		run_test(|| {
@@ -1500,6 +2467,7 @@ mod tests {
 							.into_iter()
 							.collect(),
 					},
+					ExpiredMessages::new(),
 				))),
 				UnrewardedRelayersState {
 					unrewarded_relayer_entries: 1,
@@ -1580,6 +2548,48 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn receive_messages_proof_emits_dispatch_events() {
+		run_test(|| {
+			get_ready_for_events();
+
+			let mut failed_payload = message_payload(1, 50);
+			failed_payload.dispatch_result.dispatch_result = false;
+			failed_payload.dispatch_result.unspent_weight = 10;
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				Origin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![message(1, REGULAR_PAYLOAD), message(2, failed_payload.clone())]).into(),
+				2,
+				REGULAR_PAYLOAD.declared_weight + failed_payload.declared_weight,
+			));
+
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Messages(Event::MessageDispatched(
+							TEST_LANE_ID,
+							1,
+							REGULAR_PAYLOAD.dispatch_result,
+						)),
+						topics: vec![],
+					},
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Messages(Event::MessageDispatched(
+							TEST_LANE_ID,
+							2,
+							failed_payload.dispatch_result,
+						)),
+						topics: vec![],
+					},
+				],
+			);
+		});
+	}
+
 	#[test]
 	fn receive_messages_proof_updates_confirmed_message_nonce() {
 		run_test(|| {
@@ -1727,7 +2737,8 @@ mod tests {
 							.into_iter()
 							.collect(),
 						..Default::default()
-					}
+					},
+					ExpiredMessages::new(),
 				))),
 				UnrewardedRelayersState {
 					unrewarded_relayer_entries: 1,
@@ -1752,7 +2763,8 @@ mod tests {
 						.into_iter()
 						.collect(),
 						..Default::default()
-					}
+					},
+					ExpiredMessages::new(),
 				))),
 				UnrewardedRelayersState {
 					unrewarded_relayer_entries: 2,
@@ -1765,85 +2777,625 @@ mod tests {
 		});
 	}
 
+	fn deliver_messages_to_relayers_a_and_b() {
+		assert_ok!(Pallet::<TestRuntime>::send_message(
+			Origin::signed(1),
+			TEST_LANE_ID,
+			REGULAR_PAYLOAD,
+			1000,
+		));
+		assert_ok!(Pallet::<TestRuntime>::send_message(
+			Origin::signed(1),
+			TEST_LANE_ID,
+			REGULAR_PAYLOAD,
+			2000,
+		));
+		assert_ok!(Pallet::<TestRuntime>::send_message(
+			Origin::signed(1),
+			TEST_LANE_ID,
+			REGULAR_PAYLOAD,
+			3000,
+		));
+		assert_ok!(Pallet::<TestRuntime>::receive_messages_delivery_proof(
+			Origin::signed(1),
+			TestMessagesDeliveryProof(Ok((
+				TEST_LANE_ID,
+				InboundLaneData {
+					relayers: vec![
+						unrewarded_relayer(1, 2, TEST_RELAYER_A),
+						unrewarded_relayer(3, 3, TEST_RELAYER_B)
+					]
+					.into_iter()
+					.collect(),
+					..Default::default()
+				},
+				ExpiredMessages::new(),
+			))),
+			UnrewardedRelayersState {
+				unrewarded_relayer_entries: 2,
+				total_messages: 3,
+				..Default::default()
+			},
+		));
+	}
+
 	#[test]
-	fn receive_messages_delivery_proof_rejects_invalid_proof() {
+	fn receive_messages_delivery_proof_accumulates_claimable_rewards() {
 		run_test(|| {
-			assert_noop!(
-				Pallet::<TestRuntime>::receive_messages_delivery_proof(
-					Origin::signed(1),
-					TestMessagesDeliveryProof(Err(())),
-					Default::default(),
-				),
-				Error::<TestRuntime, ()>::InvalidMessagesDeliveryProof,
-			);
+			ClaimableRelayersRewards::set(&true);
+			deliver_messages_to_relayers_a_and_b();
+
+			assert!(!TestMessageDeliveryAndDispatchPayment::is_reward_paid(TEST_RELAYER_A, 3000));
+			assert!(!TestMessageDeliveryAndDispatchPayment::is_reward_paid(TEST_RELAYER_B, 3000));
+			assert_eq!(Pallet::<TestRuntime>::relayer_reward(TEST_RELAYER_A, TEST_LANE_ID), 3000);
+			assert_eq!(Pallet::<TestRuntime>::relayer_reward(TEST_RELAYER_B, TEST_LANE_ID), 3000);
 		});
 	}
 
 	#[test]
-	fn receive_messages_delivery_proof_rejects_proof_if_declared_relayers_state_is_invalid() {
+	fn claim_rewards_works() {
 		run_test(|| {
-			// when number of relayers entries is invalid
-			assert_noop!(
-				Pallet::<TestRuntime>::receive_messages_delivery_proof(
-					Origin::signed(1),
-					TestMessagesDeliveryProof(Ok((
+			ClaimableRelayersRewards::set(&true);
+			deliver_messages_to_relayers_a_and_b();
+
+			get_ready_for_events();
+			assert_ok!(Pallet::<TestRuntime>::claim_rewards(
+				Origin::signed(TEST_RELAYER_A),
+				TEST_LANE_ID,
+			));
+			assert!(TestMessageDeliveryAndDispatchPayment::is_reward_paid(TEST_RELAYER_A, 3000));
+			assert_eq!(Pallet::<TestRuntime>::relayer_reward(TEST_RELAYER_A, TEST_LANE_ID), 0);
+			assert_eq!(Pallet::<TestRuntime>::relayer_reward(TEST_RELAYER_B, TEST_LANE_ID), 3000);
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Messages(Event::RewardsClaimed(
+						TEST_RELAYER_A,
 						TEST_LANE_ID,
-						InboundLaneData {
-							relayers: vec![
-								unrewarded_relayer(1, 1, TEST_RELAYER_A),
-								unrewarded_relayer(2, 2, TEST_RELAYER_B)
-							]
-							.into_iter()
-							.collect(),
-							..Default::default()
-						}
-					))),
-					UnrewardedRelayersState {
-						unrewarded_relayer_entries: 1,
-						total_messages: 2,
-						..Default::default()
-					},
-				),
-				Error::<TestRuntime, ()>::InvalidUnrewardedRelayersState,
+						3000
+					)),
+					topics: vec![],
+				}],
 			);
 
-			// when number of messages is invalid
+			// reward can't be claimed twice
 			assert_noop!(
-				Pallet::<TestRuntime>::receive_messages_delivery_proof(
-					Origin::signed(1),
-					TestMessagesDeliveryProof(Ok((
-						TEST_LANE_ID,
-						InboundLaneData {
-							relayers: vec![
-								unrewarded_relayer(1, 1, TEST_RELAYER_A),
-								unrewarded_relayer(2, 2, TEST_RELAYER_B)
-							]
-							.into_iter()
-							.collect(),
-							..Default::default()
-						}
-					))),
-					UnrewardedRelayersState {
-						unrewarded_relayer_entries: 2,
-						total_messages: 1,
-						..Default::default()
-					},
-				),
-				Error::<TestRuntime, ()>::InvalidUnrewardedRelayersState,
+				Pallet::<TestRuntime>::claim_rewards(Origin::signed(TEST_RELAYER_A), TEST_LANE_ID),
+				Error::<TestRuntime, ()>::NoRewardToClaim,
 			);
 		});
 	}
 
 	#[test]
-	fn receive_messages_accepts_single_message_with_invalid_payload() {
+	fn claim_rewards_keeps_reward_if_payment_has_failed() {
 		run_test(|| {
-			let mut invalid_message = message(1, REGULAR_PAYLOAD);
-			invalid_message.data.payload = Vec::new();
+			ClaimableRelayersRewards::set(&true);
+			deliver_messages_to_relayers_a_and_b();
 
-			assert_ok!(Pallet::<TestRuntime, ()>::receive_messages_proof(
-				Origin::signed(1),
-				TEST_RELAYER_A,
-				Ok(vec![invalid_message]).into(),
+			TestMessageDeliveryAndDispatchPayment::reject_payments();
+			assert_noop!(
+				Pallet::<TestRuntime>::claim_rewards(Origin::signed(TEST_RELAYER_A), TEST_LANE_ID),
+				Error::<TestRuntime, ()>::FailedToPayReward,
+			);
+			assert_eq!(Pallet::<TestRuntime>::relayer_reward(TEST_RELAYER_A, TEST_LANE_ID), 3000);
+		});
+	}
+
+	#[test]
+	fn claim_rewards_weight_includes_reward_payment() {
+		type TestWeightInfo = <TestRuntime as Config>::WeightInfo;
+		let db_weight = <TestRuntime as frame_system::Config>::DbWeight::get();
+		let transfer_weight = TestWeightInfo::receive_messages_delivery_proof_relayers_overhead(1);
+
+		// operating mode, reward and two accounts are read, reward is removed and two accounts
+		// are written
+		assert!(
+			TestWeightInfo::claim_rewards_weight(db_weight) >=
+				db_weight.reads_writes(4, 3).saturating_add(transfer_weight)
+		);
+	}
+
+	fn send_message_with_deadline(expires_at: u64) {
+		System::<TestRuntime>::set_block_number(1);
+		assert_ok!(Pallet::<TestRuntime>::send_message_with_deadline(
+			Origin::signed(1),
+			TEST_LANE_ID,
+			REGULAR_PAYLOAD,
+			REGULAR_PAYLOAD.declared_weight,
+			expires_at,
+		));
+	}
+
+	#[test]
+	fn send_message_with_deadline_rejects_deadline_in_the_past() {
+		run_test(|| {
+			System::<TestRuntime>::set_block_number(10);
+			assert_noop!(
+				Pallet::<TestRuntime>::send_message_with_deadline(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					REGULAR_PAYLOAD,
+					REGULAR_PAYLOAD.declared_weight,
+					10,
+				),
+				Error::<TestRuntime, ()>::MessageDeadlineIsInThePast,
+			);
+		});
+	}
+
+	fn confirm_delivery_by_relayer_a(
+		messages: DeliveredMessages,
+		expired_messages: ExpiredMessages,
+	) -> DispatchResultWithPostInfo {
+		let total_messages = messages.total_messages();
+		Pallet::<TestRuntime>::receive_messages_delivery_proof(
+			Origin::signed(1),
+			TestMessagesDeliveryProof(Ok((
+				TEST_LANE_ID,
+				InboundLaneData {
+					relayers: vec![UnrewardedRelayer { relayer: TEST_RELAYER_A, messages }]
+						.into_iter()
+						.collect(),
+					..Default::default()
+				},
+				expired_messages,
+			))),
+			UnrewardedRelayersState {
+				unrewarded_relayer_entries: 1,
+				total_messages,
+				..Default::default()
+			},
+		)
+	}
+
+	#[test]
+	fn expired_message_fee_is_refunded_when_delivery_is_confirmed() {
+		run_test(|| {
+			send_message_with_deadline(10);
+			assert_ok!(Pallet::<TestRuntime>::send_message(
+				Origin::signed(1),
+				TEST_LANE_ID,
+				REGULAR_PAYLOAD,
+				2000,
+			));
+			assert_eq!(
+				Pallet::<TestRuntime>::outbound_message_data(TEST_LANE_ID, 1)
+					.and_then(|message_data| message_data.expires_at),
+				Some(10),
+			);
+
+			// the first message has expired at the target chain, the second is dispatched
+			let mut delivered_messages = DeliveredMessages::new(1, false);
+			delivered_messages.note_dispatched_message(true);
+			System::<TestRuntime>::reset_events();
+			assert_ok!(confirm_delivery_by_relayer_a(
+				delivered_messages.clone(),
+				vec![1].into_iter().collect(),
+			));
+
+			// the fee is refunded to the submitter and relayer is only rewarded for the second
+			// message
+			assert!(TestMessageDeliveryAndDispatchPayment::is_fee_refunded(
+				1,
+				REGULAR_PAYLOAD.declared_weight
+			));
+			assert!(TestMessageDeliveryAndDispatchPayment::is_reward_paid(TEST_RELAYER_A, 2000));
+			assert_eq!(
+				Pallet::<TestRuntime>::outbound_message_data(TEST_LANE_ID, 1)
+					.map(|message_data| message_data.fee),
+				Some(0),
+			);
+			assert!(!OutboundMessageSubmitters::<TestRuntime>::contains_key(MessageKey {
+				lane_id: TEST_LANE_ID,
+				nonce: 1,
+			}));
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Messages(Event::ExpiredMessageFeeRefunded(
+							TEST_LANE_ID,
+							1
+						)),
+						topics: vec![],
+					},
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Messages(Event::MessagesDelivered(
+							TEST_LANE_ID,
+							delivered_messages,
+						)),
+						topics: vec![],
+					},
+				],
+			);
+		});
+	}
+
+	#[test]
+	fn expired_message_fee_is_not_refunded_if_message_has_been_dispatched() {
+		run_test(|| {
+			// the deadline has been reached at the source chain, but the target chain has
+			// dispatched the message
+			send_message_with_deadline(10);
+			System::<TestRuntime>::set_block_number(100);
+			assert_ok!(confirm_delivery_by_relayer_a(
+				DeliveredMessages::new(1, true),
+				ExpiredMessages::new(),
+			));
+
+			assert!(!TestMessageDeliveryAndDispatchPayment::is_fee_refunded(
+				1,
+				REGULAR_PAYLOAD.declared_weight
+			));
+			assert!(TestMessageDeliveryAndDispatchPayment::is_reward_paid(
+				TEST_RELAYER_A,
+				REGULAR_PAYLOAD.declared_weight
+			));
+		});
+	}
+
+	#[test]
+	fn relayers_are_rewarded_for_expired_message_if_refund_has_failed() {
+		run_test(|| {
+			send_message_with_deadline(10);
+
+			TestMessageDeliveryAndDispatchPayment::reject_payments();
+			assert_ok!(confirm_delivery_by_relayer_a(
+				DeliveredMessages::new(1, false),
+				vec![1].into_iter().collect(),
+			));
+
+			assert!(!TestMessageDeliveryAndDispatchPayment::is_fee_refunded(
+				1,
+				REGULAR_PAYLOAD.declared_weight
+			));
+			assert!(TestMessageDeliveryAndDispatchPayment::is_reward_paid(
+				TEST_RELAYER_A,
+				REGULAR_PAYLOAD.declared_weight
+			));
+		});
+	}
+
+	#[test]
+	fn receive_messages_delivery_proof_refunds_unused_expired_messages_refund_weight() {
+		run_test(|| {
+			send_message_with_deadline(10);
+			send_message_with_deadline(10);
+
+			let mut delivered_messages = DeliveredMessages::new(1, false);
+			delivered_messages.note_dispatched_message(true);
+			let declared_weight =
+				<TestRuntime as Config>::WeightInfo::receive_messages_delivery_proof_weight(
+					&bp_runtime::PreComputedSize(0),
+					&UnrewardedRelayersState {
+						unrewarded_relayer_entries: 1,
+						total_messages: 2,
+						..Default::default()
+					},
+					crate::mock::DbWeight::get(),
+				);
+			let actual_weight =
+				confirm_delivery_by_relayer_a(delivered_messages, vec![1].into_iter().collect())
+					.unwrap()
+					.actual_weight
+					.unwrap();
+
+			// only the refund of the first message has been performed
+			assert_eq!(
+				declared_weight - actual_weight,
+				<TestRuntime as Config>::WeightInfo::single_expired_message_refund_overhead(
+					crate::mock::DbWeight::get()
+				),
+			);
+		});
+	}
+
+	#[test]
+	fn receive_messages_proof_does_not_dispatch_expired_messages() {
+		run_test(|| {
+			get_ready_for_events();
+			BridgedChainBestFinalizedBlockNumber::set(&10);
+
+			// the first message has expired at the source chain, the second one is not yet
+			let mut expired_message = message(1, REGULAR_PAYLOAD);
+			expired_message.data.expires_at = Some(10);
+			let mut live_message = message(2, REGULAR_PAYLOAD);
+			live_message.data.expires_at = Some(11);
+
+			// relayer only declares dispatch weight of the second message
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				Origin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![expired_message, live_message]).into(),
+				2,
+				REGULAR_PAYLOAD.declared_weight,
+			));
+
+			let inbound_lane_data = InboundLanes::<TestRuntime>::get(TEST_LANE_ID);
+			assert_eq!(inbound_lane_data.last_delivered_nonce(), 2);
+			assert!(!inbound_lane_data.relayers[0].messages.message_dispatch_result(1));
+			assert!(inbound_lane_data.relayers[0].messages.message_dispatch_result(2));
+			assert_eq!(ExpiredInboundMessages::<TestRuntime>::get(TEST_LANE_ID), vec![1]);
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Messages(Event::MessageExpired(TEST_LANE_ID, 1)),
+						topics: vec![],
+					},
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Messages(Event::MessageDispatched(
+							TEST_LANE_ID,
+							2,
+							REGULAR_PAYLOAD.dispatch_result,
+						)),
+						topics: vec![],
+					},
+				],
+			);
+		});
+	}
+
+	fn send_and_confirm_messages(lane_id: LaneId, messages: MessageNonce) {
+		let mut lane = outbound_lane::<TestRuntime, ()>(lane_id);
+		for _ in 0..messages {
+			lane.send_message(message_data(REGULAR_PAYLOAD));
+		}
+		let relayer = unrewarded_relayer(1, messages, TEST_RELAYER_A);
+		assert_eq!(
+			lane.confirm_delivery(
+				messages,
+				messages,
+				&vec![relayer.clone()].into_iter().collect(),
+			),
+			ReceivalConfirmationResult::ConfirmedMessages(relayer.messages),
+		);
+		// this is what `receive_messages_delivery_proof` does after confirmation
+		OutboundLanesToPrune::<TestRuntime>::insert(lane_id, ());
+	}
+
+	fn oldest_unpruned_nonce(lane_id: LaneId) -> MessageNonce {
+		OutboundLanes::<TestRuntime>::get(lane_id).oldest_unpruned_nonce
+	}
+
+	#[test]
+	fn on_idle_prunes_confirmed_messages_at_all_lanes() {
+		run_test(|| {
+			send_and_confirm_messages(TEST_LANE_ID, 3);
+			send_and_confirm_messages([0, 0, 0, 2], 2);
+
+			// 1 read for operating mode + 2 lanes * 6 + 5 messages * 4
+			assert_eq!(Pallet::<TestRuntime>::on_idle(0, Weight::MAX), 33);
+			assert_eq!(oldest_unpruned_nonce(TEST_LANE_ID), 4);
+			assert_eq!(oldest_unpruned_nonce([0, 0, 0, 2]), 3);
+			assert!(Pallet::<TestRuntime>::outbound_message_data(TEST_LANE_ID, 3).is_none());
+		});
+	}
+
+	#[test]
+	fn on_idle_only_visits_lanes_with_confirmed_messages() {
+		run_test(|| {
+			send_and_confirm_messages(TEST_LANE_ID, 3);
+
+			// 1 read for operating mode + 1 lane * 6 + 3 messages * 4
+			assert_eq!(Pallet::<TestRuntime>::on_idle(0, Weight::MAX), 19);
+			assert!(!OutboundLanesToPrune::<TestRuntime>::contains_key(TEST_LANE_ID));
+
+			// when all confirmed messages are pruned, the lane is not visited anymore
+			assert_eq!(Pallet::<TestRuntime>::on_idle(0, Weight::MAX), 1);
+		});
+	}
+
+	#[test]
+	fn receive_messages_delivery_proof_schedules_lane_pruning() {
+		run_test(|| {
+			send_regular_message();
+			assert!(!OutboundLanesToPrune::<TestRuntime>::contains_key(TEST_LANE_ID));
+
+			receive_messages_delivery_proof();
+			assert!(OutboundLanesToPrune::<TestRuntime>::contains_key(TEST_LANE_ID));
+		});
+	}
+
+	#[test]
+	fn on_idle_respects_remaining_weight() {
+		run_test(|| {
+			send_and_confirm_messages(TEST_LANE_ID, 3);
+
+			// not enough weight to prune a single message
+			assert_eq!(Pallet::<TestRuntime>::on_idle(0, 8), 1);
+			assert_eq!(oldest_unpruned_nonce(TEST_LANE_ID), 1);
+
+			// enough weight to prune two messages
+			assert_eq!(Pallet::<TestRuntime>::on_idle(0, 15), 15);
+			assert_eq!(oldest_unpruned_nonce(TEST_LANE_ID), 3);
+			assert!(Pallet::<TestRuntime>::outbound_message_data(TEST_LANE_ID, 3).is_some());
+		});
+	}
+
+	#[test]
+	fn on_idle_does_nothing_if_pallet_is_halted() {
+		run_test(|| {
+			send_and_confirm_messages(TEST_LANE_ID, 3);
+			PalletOperatingMode::<TestRuntime, ()>::put(OperatingMode::Halted);
+
+			assert_eq!(Pallet::<TestRuntime>::on_idle(0, Weight::MAX), 1);
+			assert_eq!(oldest_unpruned_nonce(TEST_LANE_ID), 1);
+		});
+	}
+
+	fn insert_inbound_lane_data(relayers: Vec<UnrewardedRelayer<TestRelayer>>) {
+		InboundLanes::<TestRuntime, ()>::insert(
+			TEST_LANE_ID,
+			InboundLaneData { last_confirmed_nonce: 8, relayers: relayers.into_iter().collect() },
+		);
+	}
+
+	#[test]
+	fn try_state_accepts_consistent_storage() {
+		run_test(|| {
+			send_and_confirm_messages(TEST_LANE_ID, 3);
+			send_message_with_deadline(100);
+			insert_inbound_lane_data(vec![
+				unrewarded_relayer(9, 9, TEST_RELAYER_A),
+				unrewarded_relayer(10, 12, TEST_RELAYER_B),
+			]);
+			assert_ok!(Pallet::<TestRuntime>::do_try_state());
+
+			Pallet::<TestRuntime>::on_idle(0, Weight::MAX);
+			assert_ok!(Pallet::<TestRuntime>::do_try_state());
+		});
+	}
+
+	#[test]
+	fn try_state_detects_inconsistent_outbound_lane() {
+		run_test(|| {
+			OutboundLanes::<TestRuntime, ()>::insert(
+				TEST_LANE_ID,
+				OutboundLaneData {
+					oldest_unpruned_nonce: 1,
+					latest_received_nonce: 5,
+					latest_generated_nonce: 3,
+				},
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::do_try_state(),
+				Err("Outbound lane has received messages that are not yet generated"),
+			);
+		});
+	}
+
+	#[test]
+	fn try_state_detects_pruned_outbound_message() {
+		run_test(|| {
+			send_and_confirm_messages(TEST_LANE_ID, 3);
+			Pallet::<TestRuntime>::on_idle(0, Weight::MAX);
+			OutboundMessages::<TestRuntime, ()>::insert(
+				MessageKey { lane_id: TEST_LANE_ID, nonce: 1 },
+				message_data(REGULAR_PAYLOAD),
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::do_try_state(),
+				Err("Outbound message is outside of the unpruned range of its lane"),
+			);
+		});
+	}
+
+	#[test]
+	fn try_state_detects_non_contiguous_unrewarded_relayer_entries() {
+		run_test(|| {
+			insert_inbound_lane_data(vec![
+				unrewarded_relayer(9, 9, TEST_RELAYER_A),
+				unrewarded_relayer(11, 12, TEST_RELAYER_B),
+			]);
+			assert_eq!(
+				Pallet::<TestRuntime>::do_try_state(),
+				Err("Unrewarded relayer entries at the inbound lane are not contiguous"),
+			);
+		});
+	}
+
+	#[test]
+	fn try_state_detects_confirmed_expired_inbound_message() {
+		run_test(|| {
+			insert_inbound_lane_data(vec![unrewarded_relayer(9, 9, TEST_RELAYER_A)]);
+			ExpiredInboundMessages::<TestRuntime, ()>::insert(
+				TEST_LANE_ID,
+				ExpiredMessages::from(vec![8, 9]),
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::do_try_state(),
+				Err("Expired inbound message is not an unconfirmed message of its lane"),
+			);
+		});
+	}
+
+	#[test]
+	fn receive_messages_delivery_proof_rejects_invalid_proof() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_delivery_proof(
+					Origin::signed(1),
+					TestMessagesDeliveryProof(Err(())),
+					Default::default(),
+				),
+				Error::<TestRuntime, ()>::InvalidMessagesDeliveryProof,
+			);
+		});
+	}
+
+	#[test]
+	fn receive_messages_delivery_proof_rejects_proof_if_declared_relayers_state_is_invalid() {
+		run_test(|| {
+			// when number of relayers entries is invalid
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_delivery_proof(
+					Origin::signed(1),
+					TestMessagesDeliveryProof(Ok((
+						TEST_LANE_ID,
+						InboundLaneData {
+							relayers: vec![
+								unrewarded_relayer(1, 1, TEST_RELAYER_A),
+								unrewarded_relayer(2, 2, TEST_RELAYER_B)
+							]
+							.into_iter()
+							.collect(),
+							..Default::default()
+						},
+						ExpiredMessages::new(),
+					))),
+					UnrewardedRelayersState {
+						unrewarded_relayer_entries: 1,
+						total_messages: 2,
+						..Default::default()
+					},
+				),
+				Error::<TestRuntime, ()>::InvalidUnrewardedRelayersState,
+			);
+
+			// when number of messages is invalid
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_delivery_proof(
+					Origin::signed(1),
+					TestMessagesDeliveryProof(Ok((
+						TEST_LANE_ID,
+						InboundLaneData {
+							relayers: vec![
+								unrewarded_relayer(1, 1, TEST_RELAYER_A),
+								unrewarded_relayer(2, 2, TEST_RELAYER_B)
+							]
+							.into_iter()
+							.collect(),
+							..Default::default()
+						},
+						ExpiredMessages::new(),
+					))),
+					UnrewardedRelayersState {
+						unrewarded_relayer_entries: 2,
+						total_messages: 1,
+						..Default::default()
+					},
+				),
+				Error::<TestRuntime, ()>::InvalidUnrewardedRelayersState,
+			);
+		});
+	}
+
+	#[test]
+	fn receive_messages_accepts_single_message_with_invalid_payload() {
+		run_test(|| {
+			let mut invalid_message = message(1, REGULAR_PAYLOAD);
+			invalid_message.data.payload = Vec::new();
+
+			assert_ok!(Pallet::<TestRuntime, ()>::receive_messages_proof(
+				Origin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![invalid_message]).into(),
 				1,
 				0, // weight may be zero in this case (all messages are improperly encoded)
 			),);
@@ -2040,6 +3592,7 @@ mod tests {
 					.into_iter()
 					.collect(),
 				},
+				ExpiredMessages::new(),
 			));
 			let delivered_message_3 = DeliveredMessages::new(3, true);
 			let messages_3_proof = Ok((
@@ -2053,6 +3606,7 @@ mod tests {
 					.into_iter()
 					.collect(),
 				},
+				ExpiredMessages::new(),
 			));
 
 			// first tx with messages 1+2
@@ -2085,16 +3639,27 @@ mod tests {
 	}
 
 	fn confirm_3_messages_delivery() -> (Weight, Weight) {
-		send_regular_message();
-		send_regular_message();
-		send_regular_message();
-
+		// all messages have expired before delivery, so the weight of refunding their fees is
+		// fully spent
+		send_message_with_deadline(10);
+		send_message_with_deadline(10);
+		send_message_with_deadline(10);
+
+		let mut delivered_messages = DeliveredMessages::new(1, false);
+		delivered_messages.note_dispatched_message(false);
+		delivered_messages.note_dispatched_message(false);
 		let proof = TestMessagesDeliveryProof(Ok((
 			TEST_LANE_ID,
 			InboundLaneData {
 				last_confirmed_nonce: 0,
-				relayers: vec![unrewarded_relayer(1, 3, TEST_RELAYER_A)].into_iter().collect(),
+				relayers: vec![UnrewardedRelayer {
+					relayer: TEST_RELAYER_A,
+					messages: delivered_messages,
+				}]
+				.into_iter()
+				.collect(),
 			},
+			vec![1, 2, 3].into_iter().collect(),
 		)));
 		let relayers_state = UnrewardedRelayersState {
 			unrewarded_relayer_entries: 1,
@@ -2172,6 +3737,7 @@ mod tests {
 					TestMessagesDeliveryProof(Ok((
 						TEST_LANE_ID,
 						InboundLaneData { last_confirmed_nonce: 1, relayers: Default::default() },
+						ExpiredMessages::new(),
 					))),
 					UnrewardedRelayersState::default(),
 				),
@@ -2248,6 +3814,7 @@ mod tests {
 						.into_iter()
 						.collect(),
 					},
+					ExpiredMessages::new(),
 				))),
 				UnrewardedRelayersState {
 					unrewarded_relayer_entries: 1,
@@ -2307,6 +3874,175 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn lane_may_only_be_opened_by_owner_or_root() {
+		run_test(|| {
+			PalletOwner::<TestRuntime>::put(2);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::open_lane(Origin::signed(1), [0, 0, 0, 2]),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(Pallet::<TestRuntime>::open_lane(Origin::signed(2), [0, 0, 0, 2]));
+			assert_ok!(Pallet::<TestRuntime>::open_lane(Origin::root(), [0, 0, 0, 3]));
+			assert_eq!(Pallet::<TestRuntime>::lane_state([0, 0, 0, 2]), Some(LaneState::Opened));
+			assert_eq!(Pallet::<TestRuntime>::lane_state([0, 0, 0, 3]), Some(LaneState::Opened));
+
+			assert_noop!(
+				Pallet::<TestRuntime>::open_lane(Origin::root(), TEST_LANE_ID),
+				Error::<TestRuntime, ()>::LaneAlreadyRegistered,
+			);
+		});
+	}
+
+	#[test]
+	fn pallet_rejects_transactions_for_unknown_lane() {
+		run_test(|| {
+			send_regular_message();
+			Lanes::<TestRuntime>::remove(TEST_LANE_ID);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::send_message(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					REGULAR_PAYLOAD,
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::UnknownLane,
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_proof(
+					Origin::signed(1),
+					TEST_RELAYER_A,
+					Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
+					1,
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::UnknownLane,
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_delivery_proof(
+					Origin::signed(1),
+					TestMessagesDeliveryProof(Ok((
+						TEST_LANE_ID,
+						InboundLaneData {
+							last_confirmed_nonce: 1,
+							relayers: vec![unrewarded_relayer(1, 1, TEST_RELAYER_A)]
+								.into_iter()
+								.collect(),
+						},
+						ExpiredMessages::new(),
+					))),
+					UnrewardedRelayersState {
+						unrewarded_relayer_entries: 1,
+						total_messages: 1,
+						..Default::default()
+					},
+				),
+				Error::<TestRuntime, ()>::UnknownLane,
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::close_lane(Origin::root(), TEST_LANE_ID),
+				Error::<TestRuntime, ()>::UnknownLane,
+			);
+		});
+	}
+
+	#[test]
+	fn closing_lane_rejects_new_messages_but_accepts_deliveries() {
+		run_test(|| {
+			send_regular_message();
+
+			assert_ok!(Pallet::<TestRuntime>::close_lane(Origin::root(), TEST_LANE_ID));
+			assert_eq!(Pallet::<TestRuntime>::lane_state(TEST_LANE_ID), Some(LaneState::Closing));
+
+			assert_noop!(
+				Pallet::<TestRuntime>::send_message(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					REGULAR_PAYLOAD,
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::LaneIsClosed,
+			);
+
+			assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+				Origin::signed(1),
+				TEST_RELAYER_A,
+				Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
+				1,
+				REGULAR_PAYLOAD.declared_weight,
+			));
+			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).last_delivered_nonce(), 1);
+
+			receive_messages_delivery_proof();
+			assert_eq!(
+				OutboundLanes::<TestRuntime, ()>::get(&TEST_LANE_ID).latest_received_nonce,
+				1,
+			);
+
+			// the lane is not yet drained, because inbound message is not yet confirmed
+			assert_ok!(Pallet::<TestRuntime>::close_lane(Origin::root(), TEST_LANE_ID));
+			assert_eq!(Pallet::<TestRuntime>::lane_state(TEST_LANE_ID), Some(LaneState::Closing));
+		});
+	}
+
+	#[test]
+	fn drained_lane_is_closed_and_its_storage_is_removed() {
+		run_test(|| {
+			send_regular_message();
+			assert_ok!(Pallet::<TestRuntime>::close_lane(Origin::root(), TEST_LANE_ID));
+			receive_messages_delivery_proof();
+
+			get_ready_for_events();
+			assert_ok!(Pallet::<TestRuntime>::close_lane(Origin::root(), TEST_LANE_ID));
+			assert_eq!(Pallet::<TestRuntime>::lane_state(TEST_LANE_ID), Some(LaneState::Closed));
+			assert!(!OutboundLanes::<TestRuntime>::contains_key(&TEST_LANE_ID));
+			assert!(!InboundLanes::<TestRuntime>::contains_key(&TEST_LANE_ID));
+			assert_eq!(OutboundMessages::<TestRuntime>::iter().count(), 0);
+			assert_eq!(
+				System::<TestRuntime>::events(),
+				vec![EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Messages(Event::LaneClosed(TEST_LANE_ID)),
+					topics: vec![],
+				}],
+			);
+
+			// closed lane can't be reopened and it rejects all transactions
+			assert_noop!(
+				Pallet::<TestRuntime>::open_lane(Origin::root(), TEST_LANE_ID),
+				Error::<TestRuntime, ()>::LaneAlreadyRegistered,
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::close_lane(Origin::root(), TEST_LANE_ID),
+				Error::<TestRuntime, ()>::LaneIsClosed,
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::send_message(
+					Origin::signed(1),
+					TEST_LANE_ID,
+					REGULAR_PAYLOAD,
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::LaneIsClosed,
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::receive_messages_proof(
+					Origin::signed(1),
+					TEST_RELAYER_A,
+					Ok(vec![message(1, REGULAR_PAYLOAD)]).into(),
+					1,
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::LaneIsClosed,
+			);
+		});
+	}
+
 	#[test]
 	fn storage_keys_computed_properly() {
 		assert_eq!(
@@ -2331,5 +4067,169 @@ mod tests {
 			InboundLanes::<TestRuntime>::storage_map_final_key(TEST_LANE_ID),
 			bp_messages::storage_keys::inbound_lane_data_key("Messages", &TEST_LANE_ID).0,
 		);
+
+		assert_eq!(
+			LaneOperatingModes::<TestRuntime>::storage_map_final_key(TEST_LANE_ID),
+			bp_messages::storage_keys::lane_operating_mode_key("Messages", &TEST_LANE_ID).0,
+		);
+	}
+
+	fn receive_message_with_failed_dispatch() -> MessageKey {
+		let mut payload = REGULAR_PAYLOAD;
+		payload.dispatch_result.dispatch_result = false;
+		assert_ok!(Pallet::<TestRuntime>::receive_messages_proof(
+			Origin::signed(1),
+			TEST_RELAYER_A,
+			Ok(vec![message(1, payload)]).into(),
+			1,
+			REGULAR_PAYLOAD.declared_weight,
+		));
+		MessageKey { lane_id: TEST_LANE_ID, nonce: 1 }
+	}
+
+	#[test]
+	fn failed_message_is_not_stored_if_retries_are_disabled() {
+		run_test(|| {
+			let message_key = receive_message_with_failed_dispatch();
+			assert_eq!(InboundLanes::<TestRuntime>::get(TEST_LANE_ID).last_delivered_nonce(), 1);
+			assert_eq!(FailedMessages::<TestRuntime>::get(&message_key), None);
+		});
+	}
+
+	#[test]
+	fn failed_message_is_stored_if_retries_are_enabled() {
+		run_test(|| {
+			FailedMessagesRetryPeriod::set(&10);
+			System::<TestRuntime>::set_block_number(5);
+
+			let message_key = receive_message_with_failed_dispatch();
+			let failed_message = FailedMessages::<TestRuntime>::get(&message_key).unwrap();
+			assert_eq!(failed_message.dispatch_weight, REGULAR_PAYLOAD.declared_weight);
+			assert_eq!(failed_message.expires_at, 15);
+			assert_eq!(FailedMessagesExpiry::<TestRuntime>::get(15), vec![message_key]);
+		});
+	}
+
+	#[test]
+	fn retry_message_dispatch_works() {
+		run_test(|| {
+			FailedMessagesRetryPeriod::set(&10);
+			get_ready_for_events();
+
+			let message_key = receive_message_with_failed_dispatch();
+
+			TestMessageDispatch::succeed_all_dispatches();
+			assert_ok!(Pallet::<TestRuntime>::retry_message_dispatch(
+				Origin::signed(1),
+				message_key.clone(),
+				REGULAR_PAYLOAD.declared_weight,
+			));
+			assert_eq!(FailedMessages::<TestRuntime>::get(&message_key), None);
+			assert_eq!(
+				System::<TestRuntime>::events().last(),
+				Some(&EventRecord {
+					phase: Phase::Initialization,
+					event: TestEvent::Messages(Event::MessageDispatchRetried(message_key, true)),
+					topics: vec![],
+				}),
+			);
+		});
+	}
+
+	#[test]
+	fn retry_message_dispatch_does_not_pay_dispatch_fee() {
+		run_test(|| {
+			FailedMessagesRetryPeriod::set(&10);
+
+			let message_key = receive_message_with_failed_dispatch();
+			assert!(TestMessageDispatch::is_dispatch_fee_paid(1));
+
+			assert_ok!(Pallet::<TestRuntime>::retry_message_dispatch(
+				Origin::signed(2),
+				message_key,
+				REGULAR_PAYLOAD.declared_weight,
+			));
+			assert!(!TestMessageDispatch::is_dispatch_fee_paid(2));
+		});
+	}
+
+	#[test]
+	fn failed_message_is_removed_when_max_dispatch_retries_are_used() {
+		run_test(|| {
+			FailedMessagesRetryPeriod::set(&10);
+
+			let message_key = receive_message_with_failed_dispatch();
+			for retry in 1..=MaxDispatchRetries::get() {
+				assert_ok!(Pallet::<TestRuntime>::retry_message_dispatch(
+					Origin::signed(1),
+					message_key.clone(),
+					REGULAR_PAYLOAD.declared_weight,
+				));
+				if retry < MaxDispatchRetries::get() {
+					assert_eq!(
+						FailedMessages::<TestRuntime>::get(&message_key).map(|m| m.retries),
+						Some(retry),
+					);
+				}
+			}
+
+			assert_eq!(FailedMessages::<TestRuntime>::get(&message_key), None);
+			assert_noop!(
+				Pallet::<TestRuntime>::retry_message_dispatch(
+					Origin::signed(1),
+					message_key,
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::FailedMessageNotFound,
+			);
+		});
+	}
+
+	#[test]
+	fn retry_message_dispatch_rejects_unknown_message() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::retry_message_dispatch(
+					Origin::signed(1),
+					MessageKey { lane_id: TEST_LANE_ID, nonce: 1 },
+					REGULAR_PAYLOAD.declared_weight,
+				),
+				Error::<TestRuntime, ()>::FailedMessageNotFound,
+			);
+		});
+	}
+
+	#[test]
+	fn retry_message_dispatch_rejects_insufficient_dispatch_weight() {
+		run_test(|| {
+			FailedMessagesRetryPeriod::set(&10);
+
+			let message_key = receive_message_with_failed_dispatch();
+			assert_noop!(
+				Pallet::<TestRuntime>::retry_message_dispatch(
+					Origin::signed(1),
+					message_key,
+					REGULAR_PAYLOAD.declared_weight - 1,
+				),
+				Error::<TestRuntime, ()>::InsufficientDispatchWeight,
+			);
+		});
+	}
+
+	#[test]
+	fn failed_message_is_removed_when_retry_period_expires() {
+		run_test(|| {
+			FailedMessagesRetryPeriod::set(&10);
+			System::<TestRuntime>::set_block_number(5);
+
+			let message_key = receive_message_with_failed_dispatch();
+
+			Pallet::<TestRuntime>::on_initialize(14);
+			assert!(FailedMessages::<TestRuntime>::get(&message_key).is_some());
+
+			Pallet::<TestRuntime>::on_initialize(15);
+			assert_eq!(FailedMessages::<TestRuntime>::get(&message_key), None);
+			assert!(FailedMessagesExpiry::<TestRuntime>::get(15).is_empty());
+		});
 	}
 }
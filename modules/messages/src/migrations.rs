@@ -0,0 +1,209 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations of the messages pallet.
+
+use crate::{
+	Config, InboundLanes, Lanes, OutboundLanes, OutboundLanesToPrune, OutboundMessages, Pallet,
+};
+
+use bp_messages::{LaneState, MessageData, MessagePayload};
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+	RuntimeDebug,
+};
+use sp_std::marker::PhantomData;
+
+/// Migration to the storage version 1.
+///
+/// The version 1 has added the `expires_at` field to the `MessageData`, stored in the
+/// `OutboundMessages` map, the `Lanes` registry and the `OutboundLanesToPrune` set. All lanes,
+/// used before the migration, are registered as opened.
+pub mod v1 {
+	use super::*;
+
+	/// Outbound message data, as it has been stored before the version 1.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub struct OldMessageData<Fee> {
+		/// Message payload.
+		pub payload: MessagePayload,
+		/// Message delivery and dispatch fee, paid by the submitter.
+		pub fee: Fee,
+	}
+
+	/// Migrates the pallet storage from version 0 to version 1.
+	pub struct Migration<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for Migration<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T, I>::on_chain_storage_version();
+			if on_chain_version != 0 {
+				log::info!(
+					target: "runtime::bridge-messages",
+					"Skipping migration to v1: on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads = 1;
+			let mut writes = 0;
+
+			// all messages that are queued at the moment never expire
+			OutboundMessages::<T, I>::translate::<OldMessageData<T::OutboundMessageFee>, _>(
+				|_, old_message_data| {
+					reads += 1;
+					writes += 1;
+					Some(MessageData {
+						payload: old_message_data.payload,
+						fee: old_message_data.fee,
+						expires_at: None,
+					})
+				},
+			);
+
+			// all lanes that have been used before are opened
+			let used_lanes =
+				InboundLanes::<T, I>::iter_keys().chain(OutboundLanes::<T, I>::iter_keys());
+			for lane_id in used_lanes {
+				reads += 2;
+				if !Lanes::<T, I>::contains_key(&lane_id) {
+					Lanes::<T, I>::insert(&lane_id, LaneState::Opened);
+					writes += 1;
+				}
+			}
+
+			// all outbound lanes that have confirmed messages are pruned in `on_idle`
+			for (lane_id, lane_data) in OutboundLanes::<T, I>::iter() {
+				reads += 1;
+				if lane_data.oldest_unpruned_nonce <= lane_data.latest_received_nonce {
+					OutboundLanesToPrune::<T, I>::insert(&lane_id, ());
+					writes += 1;
+				}
+			}
+
+			StorageVersion::new(1).put::<Pallet<T, I>>();
+			writes += 1;
+
+			log::info!(
+				target: "runtime::bridge-messages",
+				"Migrated storage to v1. Reads: {}, writes: {}",
+				reads,
+				writes,
+			);
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			frame_support::ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 0,
+				"Expected on-chain storage version 0 before the migration"
+			);
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			frame_support::ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 1,
+				"Expected on-chain storage version 1 after the migration"
+			);
+			for message_key in OutboundMessages::<T, I>::iter_keys() {
+				frame_support::ensure!(
+					OutboundMessages::<T, I>::try_get(message_key).is_ok(),
+					"Outbound message has not been migrated"
+				);
+			}
+			let used_lanes =
+				InboundLanes::<T, I>::iter_keys().chain(OutboundLanes::<T, I>::iter_keys());
+			for lane_id in used_lanes {
+				frame_support::ensure!(
+					Lanes::<T, I>::contains_key(lane_id),
+					"Used lane has not been registered"
+				);
+			}
+			Ok(())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{run_test, TestMessageFee, TestRuntime, TEST_LANE_ID};
+	use bp_messages::{LaneId, MessageKey, OutboundLaneData};
+
+	const USED_LANE_ID: LaneId = [0, 0, 0, 2];
+
+	fn insert_old_message(nonce: u64, fee: TestMessageFee) {
+		frame_support::storage::unhashed::put(
+			&OutboundMessages::<TestRuntime>::hashed_key_for(MessageKey {
+				lane_id: TEST_LANE_ID,
+				nonce,
+			}),
+			&v1::OldMessageData { payload: vec![42], fee },
+		);
+	}
+
+	#[test]
+	fn migration_to_v1_works() {
+		run_test(|| {
+			StorageVersion::new(0).put::<Pallet<TestRuntime>>();
+			insert_old_message(1, 100);
+			insert_old_message(2, 200);
+			OutboundLanes::<TestRuntime>::insert(
+				USED_LANE_ID,
+				OutboundLaneData {
+					latest_received_nonce: 1,
+					latest_generated_nonce: 1,
+					..Default::default()
+				},
+			);
+
+			v1::Migration::<TestRuntime>::on_runtime_upgrade();
+
+			assert_eq!(Pallet::<TestRuntime>::on_chain_storage_version(), 1);
+			assert_eq!(
+				Pallet::<TestRuntime>::outbound_message_data(TEST_LANE_ID, 1),
+				Some(MessageData { payload: vec![42], fee: 100, expires_at: None }),
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::outbound_message_data(TEST_LANE_ID, 2),
+				Some(MessageData { payload: vec![42], fee: 200, expires_at: None }),
+			);
+			assert_eq!(Lanes::<TestRuntime>::get(USED_LANE_ID), Some(LaneState::Opened));
+			assert!(OutboundLanesToPrune::<TestRuntime>::contains_key(USED_LANE_ID));
+		});
+	}
+
+	#[test]
+	fn migration_to_v1_is_skipped_if_storage_is_already_migrated() {
+		run_test(|| {
+			StorageVersion::new(1).put::<Pallet<TestRuntime>>();
+			insert_old_message(1, 100);
+
+			assert_eq!(
+				v1::Migration::<TestRuntime>::on_runtime_upgrade(),
+				<TestRuntime as frame_system::Config>::DbWeight::get().reads(1),
+			);
+			assert!(Pallet::<TestRuntime>::outbound_message_data(TEST_LANE_ID, 1).is_none());
+		});
+	}
+}
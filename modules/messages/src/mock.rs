@@ -28,13 +28,14 @@ use bp_messages::{
 	target_chain::{
 		DispatchMessage, MessageDispatch, ProvedLaneMessages, ProvedMessages, SourceHeaderChain,
 	},
-	DeliveredMessages, InboundLaneData, LaneId, Message, MessageData, MessageKey, MessageNonce,
-	OutboundLaneData, Parameter as MessagesParameter, UnrewardedRelayer,
+	DeliveredMessages, ExpiredMessages, InboundLaneData, LaneId, Message, MessageData, MessageKey,
+	MessageNonce, OutboundLaneData, Parameter as MessagesParameter, UnrewardedRelayer,
 };
 use bp_runtime::{messages::MessageDispatchResult, Size};
 use codec::{Decode, Encode};
 use frame_support::{
 	parameter_types,
+	traits::GenesisBuild,
 	weights::{RuntimeDbWeight, Weight},
 };
 use scale_info::TypeInfo;
@@ -149,6 +150,10 @@ parameter_types! {
 	pub const MaxUnrewardedRelayerEntriesAtInboundLane: u64 = 16;
 	pub const MaxUnconfirmedMessagesAtInboundLane: u64 = 32;
 	pub storage TokenConversionRate: FixedU128 = 1.into();
+	pub storage FailedMessagesRetryPeriod: u64 = 0;
+	pub const MaxDispatchRetries: u32 = 2;
+	pub storage ClaimableRelayersRewards: bool = false;
+	pub storage BridgedChainBestFinalizedBlockNumber: u64 = 0;
   pub const TestBridgedChainId: bp_runtime::ChainId = *b"test";
 }
 
@@ -180,17 +185,21 @@ impl Config for TestRuntime {
 	type InboundPayload = TestPayload;
 	type InboundMessageFee = TestMessageFee;
 	type InboundRelayer = TestRelayer;
+	type FailedMessagesRetryPeriod = FailedMessagesRetryPeriod;
+	type MaxDispatchRetries = MaxDispatchRetries;
 
 	type AccountIdConverter = AccountIdConverter;
 
 	type TargetHeaderChain = TestTargetHeaderChain;
 	type LaneMessageVerifier = TestLaneMessageVerifier;
 	type MessageDeliveryAndDispatchPayment = TestMessageDeliveryAndDispatchPayment;
+	type ClaimableRelayersRewards = ClaimableRelayersRewards;
 	type OnMessageAccepted = TestOnMessageAccepted;
 	type OnDeliveryConfirmed = (TestOnDeliveryConfirmed1, TestOnDeliveryConfirmed2);
 
 	type SourceHeaderChain = TestSourceHeaderChain;
 	type MessageDispatch = TestMessageDispatch;
+	type BridgedChainBestFinalizedBlockNumber = BridgedChainBestFinalizedBlockNumber;
 	type BridgedChainId = TestBridgedChainId;
 }
 
@@ -268,7 +277,9 @@ impl From<Result<Vec<Message<TestMessageFee>>, ()>> for TestMessagesProof {
 
 /// Messages delivery proof used in tests.
 #[derive(Debug, Encode, Decode, Eq, Clone, PartialEq, TypeInfo)]
-pub struct TestMessagesDeliveryProof(pub Result<(LaneId, InboundLaneData<TestRelayer>), ()>);
+pub struct TestMessagesDeliveryProof(
+	pub Result<(LaneId, InboundLaneData<TestRelayer>, ExpiredMessages), ()>,
+);
 
 impl Size for TestMessagesDeliveryProof {
 	fn size_hint(&self) -> u32 {
@@ -295,7 +306,7 @@ impl TargetHeaderChain<TestPayload, TestRelayer> for TestTargetHeaderChain {
 
 	fn verify_messages_delivery_proof(
 		proof: Self::MessagesDeliveryProof,
-	) -> Result<(LaneId, InboundLaneData<TestRelayer>), Self::Error> {
+	) -> Result<(LaneId, InboundLaneData<TestRelayer>, ExpiredMessages), Self::Error> {
 		proof.0.map_err(|_| TEST_ERROR)
 	}
 }
@@ -346,6 +357,11 @@ impl TestMessageDeliveryAndDispatchPayment {
 		let key = (b":relayer-reward:", relayer, fee).encode();
 		frame_support::storage::unhashed::take::<bool>(&key).is_some()
 	}
+
+	/// Returns true if given fee has been refunded to given submitter.
+	pub fn is_fee_refunded(submitter: AccountId, fee: TestMessageFee) -> bool {
+		frame_support::storage::unhashed::get(b":message-fee-refund:") == Some((submitter, fee))
+	}
 }
 
 impl MessageDeliveryAndDispatchPayment<Origin, AccountId, TestMessageFee>
@@ -381,6 +397,34 @@ impl MessageDeliveryAndDispatchPayment<Origin, AccountId, TestMessageFee>
 			frame_support::storage::unhashed::put(&key, &true);
 		}
 	}
+
+	fn pay_relayer_reward(
+		_lane_id: LaneId,
+		relayer: &AccountId,
+		reward: &TestMessageFee,
+		_relayer_fund_account: &AccountId,
+	) -> Result<(), Self::Error> {
+		if frame_support::storage::unhashed::get(b":reject-message-fee:") == Some(true) {
+			return Err(TEST_ERROR)
+		}
+
+		let key = (b":relayer-reward:", relayer, reward).encode();
+		frame_support::storage::unhashed::put(&key, &true);
+		Ok(())
+	}
+
+	fn refund_delivery_and_dispatch_fee(
+		submitter: &AccountId,
+		fee: &TestMessageFee,
+		_relayer_fund_account: &AccountId,
+	) -> Result<(), Self::Error> {
+		if frame_support::storage::unhashed::get(b":reject-message-fee:") == Some(true) {
+			return Err(TEST_ERROR)
+		}
+
+		frame_support::storage::unhashed::put(b":message-fee-refund:", &(submitter, fee));
+		Ok(())
+	}
 }
 
 #[derive(Debug)]
@@ -486,6 +530,33 @@ impl SourceHeaderChain<TestMessageFee> for TestSourceHeaderChain {
 #[derive(Debug)]
 pub struct TestMessageDispatch;
 
+impl TestMessageDispatch {
+	/// Make all subsequent message dispatches successful, ignoring the payload.
+	pub fn succeed_all_dispatches() {
+		frame_support::storage::unhashed::put(b":succeed-all-dispatches:", &true);
+	}
+
+	/// Returns true if dispatch fee has been paid to given relayer.
+	pub fn is_dispatch_fee_paid(relayer_account: AccountId) -> bool {
+		frame_support::storage::unhashed::get_or_default(&Self::dispatch_fee_key(relayer_account))
+	}
+
+	fn dispatch_fee_key(relayer_account: AccountId) -> Vec<u8> {
+		(b":dispatch-fee-paid:", relayer_account).encode()
+	}
+
+	fn do_dispatch(message: DispatchMessage<TestPayload, TestMessageFee>) -> MessageDispatchResult {
+		if frame_support::storage::unhashed::get(b":succeed-all-dispatches:") == Some(true) {
+			return dispatch_result(0)
+		}
+
+		match message.data.payload.as_ref() {
+			Ok(payload) => payload.dispatch_result.clone(),
+			Err(_) => dispatch_result(0),
+		}
+	}
+}
+
 impl MessageDispatch<AccountId, TestMessageFee> for TestMessageDispatch {
 	type DispatchPayload = TestPayload;
 
@@ -497,13 +568,18 @@ impl MessageDispatch<AccountId, TestMessageFee> for TestMessageDispatch {
 	}
 
 	fn dispatch(
-		_relayer_account: &AccountId,
+		relayer_account: &AccountId,
 		message: DispatchMessage<TestPayload, TestMessageFee>,
 	) -> MessageDispatchResult {
-		match message.data.payload.as_ref() {
-			Ok(payload) => payload.dispatch_result.clone(),
-			Err(_) => dispatch_result(0),
+		let dispatch_result = Self::do_dispatch(message);
+		if dispatch_result.dispatch_fee_paid_during_dispatch {
+			frame_support::storage::unhashed::put(&Self::dispatch_fee_key(*relayer_account), &true);
 		}
+		dispatch_result
+	}
+
+	fn redispatch(message: DispatchMessage<TestPayload, TestMessageFee>) -> MessageDispatchResult {
+		Self::do_dispatch(message)
 	}
 }
 
@@ -519,7 +595,7 @@ pub const fn message_payload(id: u64, declared_weight: Weight) -> TestPayload {
 
 /// Return message data with valid fee for given payload.
 pub fn message_data(payload: TestPayload) -> MessageData<TestMessageFee> {
-	MessageData { payload: payload.encode(), fee: 1 }
+	MessageData { payload: payload.encode(), fee: 1, expires_at: None }
 }
 
 /// Returns message dispatch result with given unspent weight.
@@ -557,6 +633,14 @@ pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
 	pallet_balances::GenesisConfig::<TestRuntime> { balances: vec![(ENDOWED_ACCOUNT, 1_000_000)] }
 		.assimilate_storage(&mut t)
 		.unwrap();
+	GenesisBuild::<TestRuntime>::assimilate_storage(
+		&pallet_bridge_messages::GenesisConfig::<TestRuntime> {
+			opened_lanes: vec![TEST_LANE_ID],
+			..Default::default()
+		},
+		&mut t,
+	)
+	.unwrap();
 	let mut ext = sp_io::TestExternalities::new(t);
 	ext.execute_with(test)
 }
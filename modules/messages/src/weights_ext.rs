@@ -264,11 +264,31 @@ pub trait WeightInfoExt: WeightInfo {
 			.total_messages
 			.saturating_mul(Self::single_message_callback_overhead(db_weight));
 
+		// and cost of refunding fee of every confirmed message, if it has expired
+		let refund_overhead = relayers_state
+			.total_messages
+			.saturating_mul(Self::single_expired_message_refund_overhead(db_weight));
+
+		// and cost of scheduling pruning of confirmed messages
+		let pruning_overhead = db_weight.writes(1);
+
 		transaction_overhead
 			.saturating_add(messages_overhead)
 			.saturating_add(relayers_overhead)
 			.saturating_add(proof_size_overhead)
 			.saturating_add(callback_overhead)
+			.saturating_add(refund_overhead)
+			.saturating_add(pruning_overhead)
+	}
+
+	/// Weight of the reward claim extrinsic.
+	fn claim_rewards_weight(db_weight: RuntimeDbWeight) -> Weight {
+		// we need to read the pallet operating mode and the reward, and then remove the reward
+		let transaction_overhead = db_weight.reads_writes(2, 1);
+		// the reward is transferred from the relayers fund account to the relayer account
+		let payment_overhead = Self::pay_relayer_reward_overhead(db_weight);
+
+		transaction_overhead.saturating_add(payment_overhead)
 	}
 
 	// Functions that are used by extrinsics weights formulas.
@@ -374,6 +394,16 @@ pub trait WeightInfoExt: WeightInfo {
 			.saturating_sub(Self::receive_single_prepaid_message_proof())
 	}
 
+	/// Returns weight of paying reward to the single relayer from the relayers fund account.
+	///
+	/// The cost of transfer is the difference between confirming messages delivered by two
+	/// relayers and by single relayer. Both the relayers fund account and the relayer account are
+	/// read and written by the transfer.
+	fn pay_relayer_reward_overhead(db_weight: RuntimeDbWeight) -> Weight {
+		Self::receive_messages_delivery_proof_relayers_overhead(1)
+			.saturating_add(db_weight.reads_writes(2, 2))
+	}
+
 	/// Returns pre-dispatch weight of single callback call.
 	///
 	/// When benchmarking the weight please take into consideration both the `OnMessageAccepted` and
@@ -382,6 +412,30 @@ pub trait WeightInfoExt: WeightInfo {
 	fn single_message_callback_overhead(db_weight: RuntimeDbWeight) -> Weight {
 		db_weight.reads_writes(1, 1)
 	}
+
+	/// Returns weight of refunding fee of single expired message when its delivery is confirmed.
+	///
+	/// It includes reading the message and its submitter, transferring the fee from the relayers
+	/// fund account to the submitter, writing the message with zero fee back and removing the
+	/// submitter from the storage.
+	fn single_expired_message_refund_overhead(db_weight: RuntimeDbWeight) -> Weight {
+		db_weight.reads_writes(4, 4)
+	}
+
+	/// Returns weight of visiting single outbound lane when pruning messages in `on_idle`.
+	///
+	/// It includes reading the lane key and lane state, writing updated lane state back and
+	/// removing the lane from the set of lanes to prune.
+	fn outbound_lane_pruning_overhead(db_weight: RuntimeDbWeight) -> Weight {
+		db_weight.reads_writes(2, 2)
+	}
+
+	/// Returns weight of pruning single confirmed outbound message.
+	///
+	/// It includes removing the message and its (optional) submitter from the storage.
+	fn single_message_pruning_weight(db_weight: RuntimeDbWeight) -> Weight {
+		db_weight.writes(2)
+	}
 }
 
 impl WeightInfoExt for () {
@@ -0,0 +1,105 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Parachains finality pallet benchmarking.
+
+use crate::{
+	weights_ext::DEFAULT_PARACHAIN_HEAD_SIZE, BestParaHeads, Call, RelayBlockHash,
+	RelayBlockHasher, RelayBlockNumber,
+};
+
+use bp_polkadot_core::parachains::{ParaHash, ParaId, ParachainHeadsProof};
+use bp_runtime::StorageProofSize;
+use frame_benchmarking::{account, benchmarks_instance_pallet};
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+/// Pallet we're benchmarking here.
+pub struct Pallet<T: Config<I>, I: 'static>(crate::Pallet<T, I>);
+
+/// Trait that must be implemented by runtime to benchmark the parachains finality pallet.
+pub trait Config<I: 'static>: crate::Config<I> {
+	/// Returns vector of supported parachains.
+	fn parachains() -> Vec<ParaId>;
+	/// Generate parachain heads proof and prepare environment for verifying this proof.
+	fn prepare_parachain_heads_proof(
+		parachains: &[ParaId],
+		parachain_head_size: u32,
+		proof_size: StorageProofSize,
+	) -> (RelayBlockHash, ParachainHeadsProof, Vec<(ParaId, ParaHash)>);
+}
+
+benchmarks_instance_pallet! {
+	where_clause {
+		where
+			<T as pallet_bridge_grandpa::Config<T::BridgesGrandpaPalletInstance>>::BridgedChain:
+				bp_runtime::Chain<
+					BlockNumber = RelayBlockNumber,
+					Hash = RelayBlockHash,
+					Hasher = RelayBlockHasher,
+				>,
+	}
+
+	// Benchmark `submit_parachain_heads` extrinsic with different number of parachains.
+	submit_parachain_heads_with_n_parachains {
+		let p in 1..T::parachains().len() as u32;
+
+		let sender = account("sender", 0, 0);
+		let parachains = T::parachains().into_iter().take(p as _).collect::<Vec<_>>();
+		let (relay_block_hash, parachain_heads_proof, parachains_heads) = T::prepare_parachain_heads_proof(
+			&parachains,
+			DEFAULT_PARACHAIN_HEAD_SIZE,
+			StorageProofSize::Minimal(0),
+		);
+	}: submit_parachain_heads(RawOrigin::Signed(sender), relay_block_hash, parachains.clone(), parachain_heads_proof)
+	verify {
+		for (parachain, parachain_head_hash) in parachains_heads {
+			assert_eq!(BestParaHeads::<T, I>::get(parachain).map(|h| h.head_hash), Some(parachain_head_hash));
+		}
+	}
+
+	// Benchmark `submit_parachain_heads` extrinsic with 1kb proof size.
+	submit_parachain_heads_with_1kb_proof {
+		let sender = account("sender", 0, 0);
+		let parachains = T::parachains().into_iter().take(1).collect::<Vec<_>>();
+		let (relay_block_hash, parachain_heads_proof, parachains_heads) = T::prepare_parachain_heads_proof(
+			&parachains,
+			DEFAULT_PARACHAIN_HEAD_SIZE,
+			StorageProofSize::HasLargeLeaf(1024),
+		);
+	}: submit_parachain_heads(RawOrigin::Signed(sender), relay_block_hash, parachains.clone(), parachain_heads_proof)
+	verify {
+		for (parachain, parachain_head_hash) in parachains_heads {
+			assert_eq!(BestParaHeads::<T, I>::get(parachain).map(|h| h.head_hash), Some(parachain_head_hash));
+		}
+	}
+
+	// Benchmark `submit_parachain_heads` extrinsic with 16kb proof size.
+	submit_parachain_heads_with_16kb_proof {
+		let sender = account("sender", 0, 0);
+		let parachains = T::parachains().into_iter().take(1).collect::<Vec<_>>();
+		let (relay_block_hash, parachain_heads_proof, parachains_heads) = T::prepare_parachain_heads_proof(
+			&parachains,
+			DEFAULT_PARACHAIN_HEAD_SIZE,
+			StorageProofSize::HasLargeLeaf(16 * 1024),
+		);
+	}: submit_parachain_heads(RawOrigin::Signed(sender), relay_block_hash, parachains.clone(), parachain_heads_proof)
+	verify {
+		for (parachain, parachain_head_hash) in parachains_heads {
+			assert_eq!(BestParaHeads::<T, I>::get(parachain).map(|h| h.head_hash), Some(parachain_head_hash));
+		}
+	}
+}
@@ -23,15 +23,28 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use bp_polkadot_core::parachains::{ParaHash, ParaHead, ParaId, ParachainHeadsProof};
+use bp_polkadot_core::parachains::{
+	BestParaHeadInfo, ParaHash, ParaHead, ParaId, ParachainHeadsProof,
+};
+use bp_runtime::{PreComputedSize, StorageProofChecker};
 use codec::{Decode, Encode};
-use frame_support::RuntimeDebug;
+use frame_support::{dispatch::PostDispatchInfo, traits::Get, weights::Pays, RuntimeDebug};
 use scale_info::TypeInfo;
+use sp_core::Hasher;
 use sp_runtime::traits::Header as HeaderT;
-use sp_std::vec::Vec;
+use sp_std::{marker::PhantomData, vec::Vec};
 
 // Re-export in crate namespace for `construct_runtime!`.
 pub use pallet::*;
+pub use weights::WeightInfo;
+pub use weights_ext::WeightInfoExt;
+
+pub mod migrations;
+pub mod weights;
+pub mod weights_ext;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
 
 #[cfg(test)]
 mod mock;
@@ -42,6 +55,11 @@ pub type RelayBlockHash = bp_polkadot_core::Hash;
 pub type RelayBlockNumber = bp_polkadot_core::BlockNumber;
 /// Hasher of the bridged relay chain.
 pub type RelayBlockHasher = bp_polkadot_core::Hasher;
+/// Block number of the bridged parachain.
+pub type ParaBlockNumber = bp_polkadot_core::BlockNumber;
+
+/// Weight info of the given parachains pallet.
+pub type WeightInfoOf<T, I> = <T as Config<I>>::WeightInfo;
 
 /// Best known parachain head as it is stored in the runtime storage.
 #[derive(Decode, Encode, PartialEq, RuntimeDebug, TypeInfo)]
@@ -54,11 +72,101 @@ pub struct BestParaHead {
 	pub next_imported_hash_position: u32,
 }
 
+/// Parachain header data that is decoded from the parachain head and stored by the pallet.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ParaStoredHeaderData {
+	/// Number of the parachain header.
+	pub number: ParaBlockNumber,
+	/// Hash of the parachain header.
+	pub hash: ParaHash,
+	/// State root of the parachain header.
+	pub state_root: ParaHash,
+}
+
+/// Decoder of bridged parachain heads.
+pub trait ParaHeadDecoder {
+	/// Decode head of given parachain.
+	///
+	/// Returns `None` if decoder doesn't support given parachain or if the head can't be decoded.
+	fn decode_head(parachain: ParaId, head: &ParaHead) -> Option<ParaStoredHeaderData>;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl ParaHeadDecoder for Tuple {
+	fn decode_head(parachain: ParaId, head: &ParaHead) -> Option<ParaStoredHeaderData> {
+		for_tuples!(
+			#(
+				if let Some(header_data) = Tuple::decode_head(parachain, head) {
+					return Some(header_data)
+				}
+			)*
+		);
+		None
+	}
+}
+
+/// Decoder of heads of the single parachain, which are encoded headers of type `Header`.
+pub struct SingleParaHeadDecoder<Header, Parachain>(PhantomData<(Header, Parachain)>);
+
+impl<Header, Parachain> ParaHeadDecoder for SingleParaHeadDecoder<Header, Parachain>
+where
+	Header: HeaderT<Number = ParaBlockNumber, Hash = ParaHash>,
+	Parachain: Get<ParaId>,
+{
+	fn decode_head(parachain: ParaId, head: &ParaHead) -> Option<ParaStoredHeaderData> {
+		if parachain != Parachain::get() {
+			return None
+		}
+
+		let header = Header::decode(&mut &head.0[..]).ok()?;
+		Some(ParaStoredHeaderData {
+			number: *header.number(),
+			hash: header.hash(),
+			state_root: *header.state_root(),
+		})
+	}
+}
+
+/// Operating mode of the single bridged parachain.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum ParachainOperatingMode {
+	/// Heads of the parachain are accepted.
+	Normal,
+	/// Heads of the parachain are rejected. Other parachains are not affected.
+	Halted,
+}
+
+/// Artifacts of the parachains head update.
+struct UpdateParachainHeadArtifacts {
+	/// New best head of the parachain.
+	pub best_head: BestParaHead,
+	/// If `true`, some old parachain head has been pruned during update.
+	pub prune_happened: bool,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::*;
-	use frame_system::pallet_prelude::*;
+	use frame_system::{pallet_prelude::*, RawOrigin};
+	use sp_runtime::traits::BadOrigin;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// The head of the parachain has been updated.
+		UpdatedParachainHead(ParaId, ParaHash),
+		/// The head of the parachain has been rejected, because the parachain is not tracked by
+		/// the pallet.
+		UntrackedParachainRejected(ParaId),
+		/// The head of the parachain has been rejected, because the parachain is halted.
+		HaltedParachainRejected(ParaId),
+		/// The head of the parachain has been declared, but it is missing from the proof.
+		MissingParachainHead(ParaId),
+		/// The head of the parachain has been rejected, because the same or better head has
+		/// already been imported.
+		RejectedObsoleteParachainHead(ParaId, ParaHash),
+	}
 
 	#[pallet::error]
 	pub enum Error<T, I = ()> {
@@ -66,6 +174,14 @@ pub mod pallet {
 		UnknownRelayChainBlock,
 		/// Invalid storage proof has been passed.
 		InvalidStorageProof,
+		/// The parachain is not tracked by the pallet.
+		UntrackedParachain,
+		/// All pallet operations are halted.
+		Halted,
+		/// The parachain head is unknown to us or it has not been decoded.
+		UnknownParaHead,
+		/// The storage proof doesn't contains storage root. So it is invalid for given header.
+		StorageRootMismatch,
 	}
 
 	#[pallet::config]
@@ -73,6 +189,11 @@ pub mod pallet {
 	pub trait Config<I: 'static = ()>:
 		pallet_bridge_grandpa::Config<Self::BridgesGrandpaPalletInstance>
 	{
+		/// The overarching event type.
+		type Event: From<Event<Self, I>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Benchmarks results from runtime we're plugged into.
+		type WeightInfo: WeightInfoExt;
+
 		/// Instance of bridges GRANDPA pallet that this pallet is linked to.
 		///
 		/// The GRANDPA pallet instance must be configured to import headers of relay chain that
@@ -86,8 +207,36 @@ pub mod pallet {
 		/// items in the storage, so it doesn't guarantee any fixed timeframe for heads.
 		#[pallet::constant]
 		type HeadsToKeep: Get<u32>;
+
+		/// Decoder of bridged parachain heads.
+		///
+		/// If the head is decoded, the decoded header data is stored in the `ImportedParaHeaders`
+		/// map and may be used to verify storage proofs of the parachain. Heads that can't be
+		/// decoded are still imported, but their data is not stored.
+		type HeadDecoder: ParaHeadDecoder;
 	}
 
+	/// Optional pallet owner.
+	///
+	/// Pallet owner has a right to halt all pallet operations and then resume them, to start
+	/// and stop tracking parachains and to halt or resume single parachain. If it is `None`,
+	/// then there are no direct ways to do that, but other runtime methods may still be used
+	/// (i.e. democracy::referendum to update halt flag directly or call the `set_operational`).
+	#[pallet::storage]
+	pub type PalletOwner<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// If true, all pallet transactions are failed immediately.
+	#[pallet::storage]
+	pub(super) type IsHalted<T: Config<I>, I: 'static = ()> = StorageValue<_, bool, ValueQuery>;
+
+	/// Parachains that are tracked by the pallet, with their operating modes.
+	///
+	/// Heads of parachains that are missing from this map are rejected.
+	#[pallet::storage]
+	pub type TrackedParachains<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, ParaId, ParachainOperatingMode>;
+
 	/// Best parachain heads.
 	#[pallet::storage]
 	pub type BestParaHeads<T: Config<I>, I: 'static = ()> =
@@ -98,16 +247,78 @@ pub mod pallet {
 	pub type ImportedParaHeads<T: Config<I>, I: 'static = ()> =
 		StorageDoubleMap<_, Blake2_128Concat, ParaId, Blake2_128Concat, ParaHash, ParaHead>;
 
+	/// Decoded data of parachain heads which have been imported into the pallet.
+	#[pallet::storage]
+	pub type ImportedParaHeaders<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ParaId,
+		Blake2_128Concat,
+		ParaHash,
+		ParaStoredHeaderData,
+	>;
+
 	/// A ring buffer of imported parachain head hashes. Ordered by the insertion time.
 	#[pallet::storage]
 	pub(super) type ImportedParaHashes<T: Config<I>, I: 'static = ()> =
 		StorageDoubleMap<_, Blake2_128Concat, ParaId, Twox64Concat, u32, ParaHash>;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
+		/// Optional pallet owner account.
+		pub owner: Option<T::AccountId>,
+		/// Parachains that are tracked by the pallet from genesis.
+		pub parachains: Vec<ParaId>,
+		/// Dummy marker.
+		pub phantom: sp_std::marker::PhantomData<I>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
+		fn default() -> Self {
+			Self {
+				owner: Default::default(),
+				parachains: Default::default(),
+				phantom: Default::default(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config<I>, I: 'static> GenesisBuild<T, I> for GenesisConfig<T, I> {
+		fn build(&self) {
+			if let Some(ref owner) = self.owner {
+				PalletOwner::<T, I>::put(owner);
+			}
+
+			for parachain in &self.parachains {
+				TrackedParachains::<T, I>::insert(parachain, ParachainOperatingMode::Normal);
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_block_number: BlockNumberFor<T>) -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I>
 	where
@@ -124,13 +335,19 @@ pub mod pallet {
 		/// `polkadot-runtime-parachains::paras` pallet instance, deployed at the bridged chain.
 		/// The proof is supposed to be crafted at the `relay_header_hash` that must already be
 		/// imported by corresponding GRANDPA pallet at this chain.
-		#[pallet::weight(0)] // TODO: https://github.com/paritytech/parity-bridges-common/issues/1391
+		#[pallet::weight(WeightInfoOf::<T, I>::submit_parachain_heads_weight(
+			T::DbWeight::get(),
+			&PreComputedSize(parachain_heads_proof.iter().map(|node| node.len()).sum()),
+			parachains.len() as _,
+		))]
 		pub fn submit_parachain_heads(
 			_origin: OriginFor<T>,
 			relay_block_hash: RelayBlockHash,
 			parachains: Vec<ParaId>,
 			parachain_heads_proof: ParachainHeadsProof,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
+			ensure_operational::<T, I>()?;
+
 			// we'll need relay chain header to verify that parachains heads are always increasing.
 			let relay_block = pallet_bridge_grandpa::ImportedHeaders::<
 				T,
@@ -139,52 +356,298 @@ pub mod pallet {
 			.ok_or(Error::<T, I>::UnknownRelayChainBlock)?;
 			let relay_block_number = *relay_block.number();
 
+			// we'll refund weight of heads that are not updated and of heads that have not been
+			// pruned
+			let mut actual_weight = WeightInfoOf::<T, I>::submit_parachain_heads_weight(
+				T::DbWeight::get(),
+				&PreComputedSize(parachain_heads_proof.iter().map(|node| node.len()).sum()),
+				parachains.len() as _,
+			);
+
 			// now parse storage proof and read parachain heads
-			pallet_bridge_grandpa::Pallet::<T, T::BridgesGrandpaPalletInstance>::parse_finalized_storage_proof(
+			actual_weight = pallet_bridge_grandpa::Pallet::<T, T::BridgesGrandpaPalletInstance>::parse_finalized_storage_proof(
 				relay_block_hash,
 				sp_trie::StorageProof::new(parachain_heads_proof),
 				move |storage| {
 					for parachain in parachains {
-						// TODO: https://github.com/paritytech/parity-bridges-common/issues/1393
-						let parachain_head = match Pallet::<T, I>::read_parachain_head(&storage, parachain) {
-							Some(parachain_head) => parachain_head,
+						// we only accept heads of tracked parachains that are not halted
+						let rejection_event = match TrackedParachains::<T, I>::get(parachain) {
+							Some(ParachainOperatingMode::Normal) => None,
+							Some(ParachainOperatingMode::Halted) => {
+								log::trace!(
+									target: "runtime::bridge-parachains",
+									"The head of parachain {:?} has been rejected, because the parachain is halted",
+									parachain,
+								);
+								Some(Event::HaltedParachainRejected(parachain))
+							},
 							None => {
+								log::trace!(
+									target: "runtime::bridge-parachains",
+									"The head of parachain {:?} has been rejected, because the parachain is not tracked",
+									parachain,
+								);
+								Some(Event::UntrackedParachainRejected(parachain))
+							},
+						};
+
+						// TODO: https://github.com/paritytech/parity-bridges-common/issues/1393
+						let parachain_head = match rejection_event {
+							Some(rejection_event) => Err(rejection_event),
+							None => Pallet::<T, I>::read_parachain_head(&storage, parachain).ok_or_else(|| {
 								log::trace!(
 									target: "runtime::bridge-parachains",
 									"The head of parachain {:?} has been declared, but is missing from the proof",
 									parachain,
 								);
+								Event::MissingParachainHead(parachain)
+							}),
+						};
+						let parachain_head = match parachain_head {
+							Ok(parachain_head) => parachain_head,
+							Err(rejection_event) => {
+								Pallet::<T, I>::deposit_event(rejection_event);
+								actual_weight = actual_weight
+									.saturating_sub(WeightInfoOf::<T, I>::parachain_head_storage_write_weight(T::DbWeight::get()))
+									.saturating_sub(WeightInfoOf::<T, I>::parachain_head_pruning_weight(T::DbWeight::get()));
 								continue;
-							}
+							},
 						};
 
-						let _: Result<_, ()> = BestParaHeads::<T, I>::try_mutate(parachain, |stored_best_head| {
-							*stored_best_head = Some(Pallet::<T, I>::update_parachain_head(
+						let update_result: Result<_, ()> = BestParaHeads::<T, I>::try_mutate(parachain, |stored_best_head| {
+							let artifacts = Pallet::<T, I>::update_parachain_head(
 								parachain,
 								stored_best_head.take(),
 								relay_block_number,
 								parachain_head,
-							)?);
-							Ok(())
+							)?;
+							*stored_best_head = Some(artifacts.best_head);
+							Ok(artifacts.prune_happened)
 						});
+
+						// we're refunding weight if update has not happened and if pruning has not happened
+						let is_update_happened = matches!(update_result, Ok(_));
+						if !is_update_happened {
+							actual_weight = actual_weight.saturating_sub(
+								WeightInfoOf::<T, I>::parachain_head_storage_write_weight(T::DbWeight::get()),
+							);
+						}
+						let is_prune_happened = matches!(update_result, Ok(true));
+						if !is_prune_happened {
+							actual_weight = actual_weight.saturating_sub(
+								WeightInfoOf::<T, I>::parachain_head_pruning_weight(T::DbWeight::get()),
+							);
+						}
 					}
+
+					actual_weight
 				},
 			)
 			.map_err(|_| Error::<T, I>::InvalidStorageProof)?;
 
-			// TODO: there may be parachains we are not interested in - so we only need to accept
-			// intersection of `parachains-interesting-to-us` and `parachains`
-			// https://github.com/paritytech/parity-bridges-common/issues/1392
-
 			// TODO: if some parachain is no more interesting to us, we should start pruning its
 			// heads
 			// https://github.com/paritytech/parity-bridges-common/issues/1392
 
-			Ok(())
+			Ok(PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes })
+		}
+
+		/// Change `PalletOwner`.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::DbWeight::get().reads_writes(1, 1), DispatchClass::Operational))]
+		pub fn set_owner(
+			origin: OriginFor<T>,
+			new_owner: Option<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			ensure_owner_or_root::<T, I>(origin)?;
+			match new_owner {
+				Some(new_owner) => {
+					PalletOwner::<T, I>::put(&new_owner);
+					log::info!(target: "runtime::bridge-parachains", "Setting pallet Owner to: {:?}", new_owner);
+				},
+				None => {
+					PalletOwner::<T, I>::kill();
+					log::info!(target: "runtime::bridge-parachains", "Removed Owner of pallet.");
+				},
+			}
+
+			Ok(().into())
+		}
+
+		/// Halt or resume all pallet operations.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::DbWeight::get().reads_writes(1, 1), DispatchClass::Operational))]
+		pub fn set_operational(
+			origin: OriginFor<T>,
+			operational: bool,
+		) -> DispatchResultWithPostInfo {
+			ensure_owner_or_root::<T, I>(origin)?;
+			<IsHalted<T, I>>::put(!operational);
+
+			if operational {
+				log::info!(target: "runtime::bridge-parachains", "Resuming pallet operations.");
+			} else {
+				log::warn!(target: "runtime::bridge-parachains", "Stopping pallet operations.");
+			}
+
+			Ok(().into())
+		}
+
+		/// Start tracking heads of given parachain.
+		///
+		/// The parachain is tracked in the `Normal` operating mode. If the parachain is already
+		/// tracked, its operating mode is not changed.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::DbWeight::get().reads_writes(2, 1), DispatchClass::Operational))]
+		pub fn track_parachain(
+			origin: OriginFor<T>,
+			parachain: ParaId,
+		) -> DispatchResultWithPostInfo {
+			ensure_owner_or_root::<T, I>(origin)?;
+			if !TrackedParachains::<T, I>::contains_key(parachain) {
+				TrackedParachains::<T, I>::insert(parachain, ParachainOperatingMode::Normal);
+				log::info!(target: "runtime::bridge-parachains", "Started tracking parachain {:?}.", parachain);
+			}
+
+			Ok(().into())
+		}
+
+		/// Stop tracking heads of given parachain.
+		///
+		/// Heads of the parachain that have already been imported are kept in the storage.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::DbWeight::get().reads_writes(1, 1), DispatchClass::Operational))]
+		pub fn untrack_parachain(
+			origin: OriginFor<T>,
+			parachain: ParaId,
+		) -> DispatchResultWithPostInfo {
+			ensure_owner_or_root::<T, I>(origin)?;
+			TrackedParachains::<T, I>::remove(parachain);
+			log::info!(target: "runtime::bridge-parachains", "Stopped tracking parachain {:?}.", parachain);
+
+			Ok(().into())
+		}
+
+		/// Halt or resume operations of single tracked parachain.
+		///
+		/// May only be called either by root, or by `PalletOwner`.
+		#[pallet::weight((T::DbWeight::get().reads_writes(2, 1), DispatchClass::Operational))]
+		pub fn set_parachain_operating_mode(
+			origin: OriginFor<T>,
+			parachain: ParaId,
+			operating_mode: ParachainOperatingMode,
+		) -> DispatchResultWithPostInfo {
+			ensure_owner_or_root::<T, I>(origin)?;
+			TrackedParachains::<T, I>::try_mutate(parachain, |stored_operating_mode| {
+				let stored_operating_mode =
+					stored_operating_mode.as_mut().ok_or(Error::<T, I>::UntrackedParachain)?;
+				*stored_operating_mode = operating_mode;
+				Ok::<_, Error<T, I>>(())
+			})?;
+
+			log::info!(
+				target: "runtime::bridge-parachains",
+				"Setting operating mode of parachain {:?} to: {:?}",
+				parachain,
+				operating_mode,
+			);
+
+			Ok(().into())
 		}
 	}
 
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Returns decoded data of the best known head of given parachain.
+		pub fn best_parachain_header(parachain: ParaId) -> Option<ParaStoredHeaderData> {
+			let best_para_head = BestParaHeads::<T, I>::get(parachain)?;
+			ImportedParaHeaders::<T, I>::get(parachain, best_para_head.head_hash)
+		}
+
+		/// Returns information about the best known head of given parachain.
+		pub fn best_parachain_head_info(parachain: ParaId) -> Option<BestParaHeadInfo> {
+			let best_para_head = BestParaHeads::<T, I>::get(parachain)?;
+			let head_number = ImportedParaHeaders::<T, I>::get(parachain, best_para_head.head_hash)
+				.map(|header_data| header_data.number);
+			Some(BestParaHeadInfo {
+				at_relay_block_number: best_para_head.at_relay_block_number,
+				head_hash: best_para_head.head_hash,
+				head_number,
+			})
+		}
+
+		/// Verify that the passed storage proof is valid, given it is crafted using
+		/// known parachain header. If the proof is valid, then the `parse` callback
+		/// is called and the function returns its result.
+		pub fn parse_finalized_storage_proof<H: Hasher<Out = ParaHash>, R>(
+			parachain: ParaId,
+			parachain_head_hash: ParaHash,
+			storage_proof: sp_trie::StorageProof,
+			parse: impl FnOnce(StorageProofChecker<H>) -> R,
+		) -> Result<R, sp_runtime::DispatchError> {
+			let header = ImportedParaHeaders::<T, I>::get(parachain, parachain_head_hash)
+				.ok_or(Error::<T, I>::UnknownParaHead)?;
+			let storage_proof_checker = StorageProofChecker::new(header.state_root, storage_proof)
+				.map_err(|_| Error::<T, I>::StorageRootMismatch)?;
+
+			Ok(parse(storage_proof_checker))
+		}
+
+		/// Ensure that the pallet storage is consistent.
+		///
+		/// Returns an error if any of the storage invariants is broken.
+		#[cfg(any(feature = "try-runtime", test))]
+		pub fn do_try_state() -> Result<(), &'static str> {
+			let heads_to_keep = T::HeadsToKeep::get();
+
+			// the best head of every parachain is the latest head in its ring buffer
+			for (parachain, best_head) in BestParaHeads::<T, I>::iter() {
+				ensure!(
+					best_head.next_imported_hash_position < heads_to_keep,
+					"Imported hashes position of the parachain is outside of the ring buffer"
+				);
+				let best_head_position = best_head
+					.next_imported_hash_position
+					.checked_sub(1)
+					.unwrap_or(heads_to_keep - 1);
+				ensure!(
+					ImportedParaHashes::<T, I>::get(parachain, best_head_position) ==
+						Some(best_head.head_hash),
+					"Best head of the parachain is not the latest imported head"
+				);
+			}
+
+			// every head in the ring buffer is imported and every imported head is in the buffer
+			let mut imported_hashes = 0;
+			for (parachain, index, head_hash) in ImportedParaHashes::<T, I>::iter() {
+				ensure!(index < heads_to_keep, "Imported head hash is outside of the ring buffer");
+				ensure!(
+					BestParaHeads::<T, I>::contains_key(parachain),
+					"Head of the parachain without best head is imported"
+				);
+				ensure!(
+					ImportedParaHeads::<T, I>::contains_key(parachain, head_hash),
+					"Head from the ring buffer is not imported"
+				);
+				imported_hashes += 1;
+			}
+			ensure!(
+				ImportedParaHeads::<T, I>::iter_keys().count() == imported_hashes,
+				"Imported head is missing from the ring buffer"
+			);
+			for (parachain, head_hash) in ImportedParaHeaders::<T, I>::iter_keys() {
+				ensure!(
+					ImportedParaHeads::<T, I>::contains_key(parachain, head_hash),
+					"Decoded header data of the pruned head is stored"
+				);
+			}
+
+			Ok(())
+		}
+
 		/// Read parachain head from storage proof.
 		fn read_parachain_head(
 			storage: &bp_runtime::StorageProofChecker<RelayBlockHasher>,
@@ -202,7 +665,7 @@ pub mod pallet {
 			stored_best_head: Option<BestParaHead>,
 			updated_at_relay_block_number: RelayBlockNumber,
 			updated_head: ParaHead,
-		) -> Result<BestParaHead, ()> {
+		) -> Result<UpdateParachainHeadArtifacts, ()> {
 			// check if head has been already updated at better relay chain block. Without this
 			// check, we may import heads in random order
 			let updated_head_hash = updated_head.hash();
@@ -212,6 +675,10 @@ pub mod pallet {
 				{
 					// check if this head has already been imported before
 					if updated_head_hash == stored_best_head.head_hash {
+						Self::deposit_event(Event::RejectedObsoleteParachainHead(
+							parachain,
+							updated_head_hash,
+						));
 						log::trace!(
 							target: "runtime::bridge-parachains",
 							"The head of parachain {:?} can't be updated to {}, because it has been already updated\
@@ -228,6 +695,10 @@ pub mod pallet {
 				},
 				None => 0,
 				Some(stored_best_head) => {
+					Self::deposit_event(Event::RejectedObsoleteParachainHead(
+						parachain,
+						updated_head_hash,
+					));
 					log::trace!(
 						target: "runtime::bridge-parachains",
 						"The head of parachain {:?} can't be updated to {}, because it has been already updated\
@@ -256,9 +727,23 @@ pub mod pallet {
 				next_imported_hash_position,
 				updated_head_hash,
 			);
+			match T::HeadDecoder::decode_head(parachain, &updated_head) {
+				Some(updated_header_data) => ImportedParaHeaders::<T, I>::insert(
+					parachain,
+					updated_head_hash,
+					updated_header_data,
+				),
+				None => log::trace!(
+					target: "runtime::bridge-parachains",
+					"The head {} of parachain {:?} can't be decoded. Its data won't be available",
+					updated_head_hash,
+					parachain,
+				),
+			}
 			ImportedParaHeads::<T, I>::insert(parachain, updated_head_hash, updated_head);
 
 			// remove old head
+			let prune_happened = head_hash_to_prune.is_ok();
 			if let Ok(head_hash_to_prune) = head_hash_to_prune {
 				log::trace!(
 					target: "runtime::bridge-parachains",
@@ -267,9 +752,32 @@ pub mod pallet {
 					head_hash_to_prune,
 				);
 				ImportedParaHeads::<T, I>::remove(parachain, head_hash_to_prune);
+				ImportedParaHeaders::<T, I>::remove(parachain, head_hash_to_prune);
 			}
 
-			Ok(updated_best_para_head)
+			Self::deposit_event(Event::UpdatedParachainHead(parachain, updated_head_hash));
+
+			Ok(UpdateParachainHeadArtifacts { best_head: updated_best_para_head, prune_happened })
+		}
+	}
+
+	/// Ensure that the origin is either root, or `PalletOwner`.
+	fn ensure_owner_or_root<T: Config<I>, I: 'static>(origin: T::Origin) -> Result<(), BadOrigin> {
+		match origin.into() {
+			Ok(RawOrigin::Root) => Ok(()),
+			Ok(RawOrigin::Signed(ref signer))
+				if Some(signer) == <PalletOwner<T, I>>::get().as_ref() =>
+				Ok(()),
+			_ => Err(BadOrigin),
+		}
+	}
+
+	/// Ensure that the pallet is in operational mode (not halted).
+	fn ensure_operational<T: Config<I>, I: 'static>() -> Result<(), Error<T, I>> {
+		if <IsHalted<T, I>>::get() {
+			Err(<Error<T, I>>::Halted)
+		} else {
+			Ok(())
 		}
 	}
 }
@@ -277,22 +785,40 @@ pub mod pallet {
 pub mod storage_keys {
 	use super::*;
 	use bp_runtime::storage_map_final_key;
-	use frame_support::Twox64Concat;
+	use frame_support::{Blake2_128Concat, Twox64Concat};
 	use sp_core::storage::StorageKey;
 
 	/// Storage key of the parachain head in the runtime storage of relay chain.
 	pub fn parachain_head_key(parachain: ParaId) -> StorageKey {
 		storage_map_final_key::<Twox64Concat>("Paras", "Heads", &parachain.encode())
 	}
+
+	/// Storage key of the best parachain head in the runtime storage of this pallet.
+	pub fn best_parachain_head_key(pallet_prefix: &str, parachain: ParaId) -> StorageKey {
+		storage_map_final_key::<Blake2_128Concat>(
+			pallet_prefix,
+			"BestParaHeads",
+			&parachain.encode(),
+		)
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::mock::{run_test, test_relay_header, Origin, TestRuntime};
+	use crate::mock::{
+		run_test, test_relay_header, Event as TestEvent, Origin, ParaHeader, System, TestRuntime,
+	};
 
 	use bp_test_utils::{authority_list, make_default_justification};
-	use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+	use frame_support::{
+		assert_noop, assert_ok,
+		dispatch::DispatchResultWithPostInfo,
+		traits::{Get, OnInitialize},
+		weights::Weight,
+	};
+	use frame_system::{EventRecord, Phase};
+	use sp_runtime::DispatchError;
 	use sp_trie::{
 		record_all_keys, trie_types::TrieDBMutV1, LayoutV1, MemoryDB, Recorder, TrieMut,
 	};
@@ -310,6 +836,13 @@ mod tests {
 			},
 		)
 		.unwrap();
+
+		for parachain in 1..=3 {
+			TrackedParachains::<TestRuntime>::insert(
+				ParaId(parachain),
+				ParachainOperatingMode::Normal,
+			);
+		}
 	}
 
 	fn proceed(num: RelayBlockNumber, state_root: RelayBlockHash) {
@@ -353,6 +886,23 @@ mod tests {
 		(root, storage_proof)
 	}
 
+	fn weight_of_import_parachain_1_head(
+		proof: &ParachainHeadsProof,
+		prune_expected: bool,
+	) -> Weight {
+		let db_weight = <TestRuntime as frame_system::Config>::DbWeight::get();
+		WeightInfoOf::<TestRuntime, ()>::submit_parachain_heads_weight(
+			db_weight,
+			&PreComputedSize(proof.iter().map(|node| node.len()).sum()),
+			1,
+		)
+		.saturating_sub(if prune_expected {
+			0
+		} else {
+			WeightInfoOf::<TestRuntime, ()>::parachain_head_pruning_weight(db_weight)
+		})
+	}
+
 	fn initial_best_head(parachain: u32) -> BestParaHead {
 		BestParaHead {
 			at_relay_block_number: 0,
@@ -373,7 +923,7 @@ mod tests {
 		relay_chain_block: RelayBlockNumber,
 		relay_state_root: RelayBlockHash,
 		proof: ParachainHeadsProof,
-	) -> sp_runtime::DispatchResult {
+	) -> DispatchResultWithPostInfo {
 		Pallet::<TestRuntime>::submit_parachain_heads(
 			Origin::signed(1),
 			test_relay_header(relay_chain_block, relay_state_root).hash(),
@@ -568,6 +1118,126 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn try_state_accepts_consistent_storage() {
+		run_test(|| {
+			let heads_to_keep = crate::mock::HeadsToKeep::get();
+			assert_ok!(Pallet::<TestRuntime>::do_try_state());
+
+			// import more than `HeadsToKeep` headers, so that the ring buffer is wrapped
+			for i in 0..heads_to_keep + 2 {
+				let (state_root, proof) =
+					prepare_parachain_heads_proof(vec![(ParaId(1), head_data(1, i))]);
+				if i == 0 {
+					initialize(state_root);
+				} else {
+					proceed(i, state_root);
+				}
+				assert_ok!(import_parachain_1_head(i, state_root, proof));
+				assert_ok!(Pallet::<TestRuntime>::do_try_state());
+			}
+		});
+	}
+
+	#[test]
+	fn try_state_detects_missing_imported_head() {
+		run_test(|| {
+			let (state_root, proof) =
+				prepare_parachain_heads_proof(vec![(ParaId(1), head_data(1, 0))]);
+			initialize(state_root);
+			assert_ok!(import_parachain_1_head(0, state_root, proof));
+			ImportedParaHeads::<TestRuntime>::remove(ParaId(1), head_hash(1, 0));
+
+			assert_eq!(
+				Pallet::<TestRuntime>::do_try_state(),
+				Err("Head from the ring buffer is not imported"),
+			);
+		});
+	}
+
+	#[test]
+	fn try_state_detects_outdated_best_head() {
+		run_test(|| {
+			let (state_root, proof) =
+				prepare_parachain_heads_proof(vec![(ParaId(1), head_data(1, 0))]);
+			initialize(state_root);
+			assert_ok!(import_parachain_1_head(0, state_root, proof));
+			BestParaHeads::<TestRuntime>::mutate(ParaId(1), |best_head| {
+				best_head.as_mut().unwrap().head_hash = head_hash(1, 1);
+			});
+
+			assert_eq!(
+				Pallet::<TestRuntime>::do_try_state(),
+				Err("Best head of the parachain is not the latest imported head"),
+			);
+		});
+	}
+
+	#[test]
+	fn weight_is_refunded_when_head_is_not_updated() {
+		let (state_root, proof) = prepare_parachain_heads_proof(vec![(ParaId(1), head_data(1, 0))]);
+		run_test(|| {
+			let db_weight = <TestRuntime as frame_system::Config>::DbWeight::get();
+			let expected_weight = weight_of_import_parachain_1_head(&proof, false);
+
+			// import head#0 of parachain#1 at relay block#0 => nothing is pruned
+			initialize(state_root);
+			let result = import_parachain_1_head(0, state_root, proof.clone());
+			assert_eq!(result.unwrap().actual_weight, Some(expected_weight));
+
+			// try to import head#0 of parachain#1 at relay block#1 => head is not updated
+			proceed(1, state_root);
+			let result = import_parachain_1_head(1, state_root, proof);
+			assert_eq!(
+				result.unwrap().actual_weight,
+				Some(expected_weight.saturating_sub(
+					WeightInfoOf::<TestRuntime, ()>::parachain_head_storage_write_weight(db_weight)
+				)),
+			);
+		});
+	}
+
+	#[test]
+	fn weight_is_not_refunded_when_old_head_is_pruned() {
+		run_test(|| {
+			let heads_to_keep = crate::mock::HeadsToKeep::get();
+
+			// import exactly `HeadsToKeep` headers
+			for i in 0..heads_to_keep {
+				let (state_root, proof) =
+					prepare_parachain_heads_proof(vec![(ParaId(1), head_data(1, i))]);
+				if i == 0 {
+					initialize(state_root);
+				} else {
+					proceed(i, state_root);
+				}
+				assert_ok!(import_parachain_1_head(i, state_root, proof));
+			}
+
+			// import next relay chain header and next parachain head => head#0 is pruned
+			let (state_root, proof) =
+				prepare_parachain_heads_proof(vec![(ParaId(1), head_data(1, heads_to_keep))]);
+			let expected_weight = weight_of_import_parachain_1_head(&proof, true);
+			proceed(heads_to_keep, state_root);
+			let result = import_parachain_1_head(heads_to_keep, state_root, proof);
+			assert_eq!(result.unwrap().actual_weight, Some(expected_weight));
+		});
+	}
+
+	#[test]
+	fn weight_of_every_parachain_head_covers_its_storage_accesses() {
+		let db_weight = <TestRuntime as frame_system::Config>::DbWeight::get();
+		let weight_of_1_head =
+			WeightInfoOf::<TestRuntime, ()>::submit_parachain_heads_with_n_parachains(1);
+		let weight_of_2_heads =
+			WeightInfoOf::<TestRuntime, ()>::submit_parachain_heads_with_n_parachains(2);
+
+		// `TrackedParachains`, `BestParaHeads` and `ImportedParaHashes` are read and
+		// `BestParaHeads`, `ImportedParaHashes`, `ImportedParaHeads` and `ImportedParaHeaders` are
+		// written for every parachain
+		assert!(weight_of_2_heads - weight_of_1_head >= db_weight.reads_writes(3, 4));
+	}
+
 	#[test]
 	fn fails_on_unknown_relay_chain_block() {
 		let (state_root, proof) = prepare_parachain_heads_proof(vec![(ParaId(1), head_data(1, 5))]);
@@ -598,4 +1268,419 @@ mod tests {
 			);
 		});
 	}
+
+	#[test]
+	fn pallet_owner_may_change_owner() {
+		run_test(|| {
+			PalletOwner::<TestRuntime>::put(2);
+
+			assert_ok!(Pallet::<TestRuntime>::set_owner(Origin::root(), Some(1)));
+			assert_noop!(
+				Pallet::<TestRuntime>::set_operational(Origin::signed(2), false),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(Pallet::<TestRuntime>::set_operational(Origin::root(), false));
+
+			assert_ok!(Pallet::<TestRuntime>::set_owner(Origin::signed(1), None));
+			assert_noop!(
+				Pallet::<TestRuntime>::set_operational(Origin::signed(1), true),
+				DispatchError::BadOrigin,
+			);
+			assert_noop!(
+				Pallet::<TestRuntime>::set_operational(Origin::signed(2), true),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(Pallet::<TestRuntime>::set_operational(Origin::root(), true));
+		});
+	}
+
+	#[test]
+	fn pallet_rejects_transactions_if_halted() {
+		let (state_root, proof) = prepare_parachain_heads_proof(vec![(ParaId(1), head_data(1, 0))]);
+		run_test(|| {
+			initialize(state_root);
+
+			assert_ok!(Pallet::<TestRuntime>::set_operational(Origin::root(), false));
+			assert_noop!(
+				import_parachain_1_head(0, state_root, proof.clone()),
+				Error::<TestRuntime>::Halted
+			);
+
+			assert_ok!(Pallet::<TestRuntime>::set_operational(Origin::root(), true));
+			assert_ok!(import_parachain_1_head(0, state_root, proof));
+		});
+	}
+
+	#[test]
+	fn only_owner_or_root_may_change_tracked_parachains() {
+		run_test(|| {
+			PalletOwner::<TestRuntime>::put(2);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::track_parachain(Origin::signed(1), ParaId(1)),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(Pallet::<TestRuntime>::track_parachain(Origin::signed(2), ParaId(1)));
+			assert_ok!(Pallet::<TestRuntime>::track_parachain(Origin::root(), ParaId(2)));
+			assert_eq!(
+				TrackedParachains::<TestRuntime>::get(ParaId(1)),
+				Some(ParachainOperatingMode::Normal)
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::set_parachain_operating_mode(
+					Origin::signed(1),
+					ParaId(1),
+					ParachainOperatingMode::Halted,
+				),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(Pallet::<TestRuntime>::set_parachain_operating_mode(
+				Origin::signed(2),
+				ParaId(1),
+				ParachainOperatingMode::Halted,
+			));
+			assert_eq!(
+				TrackedParachains::<TestRuntime>::get(ParaId(1)),
+				Some(ParachainOperatingMode::Halted)
+			);
+
+			// tracking already tracked parachain doesn't change its operating mode
+			assert_ok!(Pallet::<TestRuntime>::track_parachain(Origin::root(), ParaId(1)));
+			assert_eq!(
+				TrackedParachains::<TestRuntime>::get(ParaId(1)),
+				Some(ParachainOperatingMode::Halted)
+			);
+
+			assert_noop!(
+				Pallet::<TestRuntime>::untrack_parachain(Origin::signed(1), ParaId(1)),
+				DispatchError::BadOrigin,
+			);
+			assert_ok!(Pallet::<TestRuntime>::untrack_parachain(Origin::signed(2), ParaId(1)));
+			assert_ok!(Pallet::<TestRuntime>::untrack_parachain(Origin::root(), ParaId(2)));
+			assert_eq!(TrackedParachains::<TestRuntime>::get(ParaId(1)), None);
+			assert_eq!(TrackedParachains::<TestRuntime>::get(ParaId(2)), None);
+		});
+	}
+
+	#[test]
+	fn operating_mode_of_untracked_parachain_cant_be_changed() {
+		run_test(|| {
+			assert_noop!(
+				Pallet::<TestRuntime>::set_parachain_operating_mode(
+					Origin::root(),
+					ParaId(1),
+					ParachainOperatingMode::Halted,
+				),
+				Error::<TestRuntime>::UntrackedParachain,
+			);
+		});
+	}
+
+	#[test]
+	fn rejects_heads_of_untracked_and_halted_parachains() {
+		let (state_root, proof) = prepare_parachain_heads_proof(vec![
+			(ParaId(1), head_data(1, 0)),
+			(ParaId(2), head_data(2, 0)),
+			(ParaId(4), head_data(4, 0)),
+		]);
+		run_test(|| {
+			initialize(state_root);
+			assert_ok!(Pallet::<TestRuntime>::set_parachain_operating_mode(
+				Origin::root(),
+				ParaId(2),
+				ParachainOperatingMode::Halted,
+			));
+
+			// parachain#1 is tracked, parachain#2 is halted and parachain#4 is not tracked
+			assert_ok!(Pallet::<TestRuntime>::submit_parachain_heads(
+				Origin::signed(1),
+				test_relay_header(0, state_root).hash(),
+				vec![ParaId(1), ParaId(2), ParaId(4)],
+				proof,
+			));
+
+			// so only head of parachain#1 is imported
+			assert_eq!(BestParaHeads::<TestRuntime>::get(ParaId(1)), Some(initial_best_head(1)));
+			assert_eq!(BestParaHeads::<TestRuntime>::get(ParaId(2)), None);
+			assert_eq!(BestParaHeads::<TestRuntime>::get(ParaId(4)), None);
+			assert_eq!(
+				System::events(),
+				vec![
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Parachains(Event::UpdatedParachainHead(
+							ParaId(1),
+							head_hash(1, 0),
+						)),
+						topics: vec![],
+					},
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Parachains(Event::HaltedParachainRejected(ParaId(2))),
+						topics: vec![],
+					},
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Parachains(Event::UntrackedParachainRejected(ParaId(4))),
+						topics: vec![],
+					},
+				],
+			);
+		});
+	}
+
+	#[test]
+	fn emits_events_when_heads_are_missing_or_obsolete() {
+		let (state_root, proof) = prepare_parachain_heads_proof(vec![(ParaId(1), head_data(1, 0))]);
+		run_test(|| {
+			initialize(state_root);
+			assert_ok!(import_parachain_1_head(0, state_root, proof.clone()));
+			System::reset_events();
+
+			// head#0 of parachain#1 is already known and head of parachain#2 is missing
+			proceed(1, state_root);
+			assert_ok!(Pallet::<TestRuntime>::submit_parachain_heads(
+				Origin::signed(1),
+				test_relay_header(1, state_root).hash(),
+				vec![ParaId(1), ParaId(2)],
+				proof,
+			));
+			assert_eq!(
+				System::events(),
+				vec![
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Parachains(Event::RejectedObsoleteParachainHead(
+							ParaId(1),
+							head_hash(1, 0),
+						)),
+						topics: vec![],
+					},
+					EventRecord {
+						phase: Phase::Initialization,
+						event: TestEvent::Parachains(Event::MissingParachainHead(ParaId(2))),
+						topics: vec![],
+					},
+				],
+			);
+		});
+	}
+
+	#[test]
+	fn stores_decoded_data_of_parachain_heads() {
+		let para_header = ParaHeader::new(
+			42,
+			Default::default(),
+			[1u8; 32].into(),
+			Default::default(),
+			Default::default(),
+		);
+		let para_head = ParaHead(para_header.encode());
+		let (state_root, proof) = prepare_parachain_heads_proof(vec![
+			(ParaId(1), head_data(1, 0)),
+			(ParaId(3), para_head.clone()),
+		]);
+		run_test(|| {
+			initialize(state_root);
+			assert_ok!(Pallet::<TestRuntime>::submit_parachain_heads(
+				Origin::signed(1),
+				test_relay_header(0, state_root).hash(),
+				vec![ParaId(1), ParaId(3)],
+				proof,
+			));
+
+			// head of parachain#1 is imported, but its data is not stored, because there's no
+			// decoder for this parachain
+			assert_eq!(BestParaHeads::<TestRuntime>::get(ParaId(1)), Some(initial_best_head(1)));
+			assert_eq!(Pallet::<TestRuntime>::best_parachain_header(ParaId(1)), None);
+
+			// head of parachain#3 is imported and decoded
+			assert_eq!(
+				Pallet::<TestRuntime>::best_parachain_header(ParaId(3)),
+				Some(ParaStoredHeaderData {
+					number: 42,
+					hash: para_header.hash(),
+					state_root: [1u8; 32].into(),
+				}),
+			);
+			assert_eq!(
+				ImportedParaHeaders::<TestRuntime>::get(ParaId(3), para_head.hash()),
+				Pallet::<TestRuntime>::best_parachain_header(ParaId(3)),
+			);
+		});
+	}
+
+	#[test]
+	fn prunes_decoded_data_of_old_parachain_heads() {
+		let para_head = |number| {
+			ParaHead(
+				ParaHeader::new(
+					number,
+					Default::default(),
+					Default::default(),
+					Default::default(),
+					Default::default(),
+				)
+				.encode(),
+			)
+		};
+		let heads_to_keep = crate::mock::HeadsToKeep::get();
+		run_test(|| {
+			// import exactly `HeadsToKeep` headers
+			for i in 0..heads_to_keep {
+				let (state_root, proof) =
+					prepare_parachain_heads_proof(vec![(ParaId(3), para_head(i))]);
+				if i == 0 {
+					initialize(state_root);
+				} else {
+					proceed(i, state_root);
+				}
+
+				assert_ok!(Pallet::<TestRuntime>::submit_parachain_heads(
+					Origin::signed(1),
+					test_relay_header(i, state_root).hash(),
+					vec![ParaId(3)],
+					proof,
+				));
+			}
+
+			// all decoded headers are retained
+			assert!((0..heads_to_keep).all(|i| ImportedParaHeaders::<TestRuntime>::contains_key(
+				ParaId(3),
+				para_head(i).hash()
+			)));
+
+			// import next header
+			let (state_root, proof) =
+				prepare_parachain_heads_proof(vec![(ParaId(3), para_head(heads_to_keep))]);
+			proceed(heads_to_keep, state_root);
+			assert_ok!(Pallet::<TestRuntime>::submit_parachain_heads(
+				Origin::signed(1),
+				test_relay_header(heads_to_keep, state_root).hash(),
+				vec![ParaId(3)],
+				proof,
+			));
+
+			// and the decoded data of the oldest header is pruned
+			assert!(!ImportedParaHeaders::<TestRuntime>::contains_key(
+				ParaId(3),
+				para_head(0).hash()
+			));
+			assert!((1..=heads_to_keep).all(|i| ImportedParaHeaders::<TestRuntime>::contains_key(
+				ParaId(3),
+				para_head(i).hash()
+			)));
+		});
+	}
+
+	#[test]
+	fn parse_finalized_storage_proof_works() {
+		// the storage of the parachain is represented by the same trie that we're using for heads
+		let (para_state_root, para_storage_proof) =
+			prepare_parachain_heads_proof(vec![(ParaId(42), head_data(42, 0))]);
+		let para_head = ParaHead(
+			ParaHeader::new(
+				0,
+				Default::default(),
+				para_state_root,
+				Default::default(),
+				Default::default(),
+			)
+			.encode(),
+		);
+		let (state_root, proof) =
+			prepare_parachain_heads_proof(vec![(ParaId(3), para_head.clone())]);
+		run_test(|| {
+			initialize(state_root);
+
+			// parachain head is unknown
+			assert_noop!(
+				Pallet::<TestRuntime>::parse_finalized_storage_proof::<RelayBlockHasher, _>(
+					ParaId(3),
+					para_head.hash(),
+					sp_trie::StorageProof::new(para_storage_proof.clone()),
+					|_| (),
+				),
+				Error::<TestRuntime>::UnknownParaHead,
+			);
+
+			// import parachain head
+			assert_ok!(Pallet::<TestRuntime>::submit_parachain_heads(
+				Origin::signed(1),
+				test_relay_header(0, state_root).hash(),
+				vec![ParaId(3)],
+				proof.clone(),
+			));
+
+			// proof that is not crafted at the parachain header is rejected
+			assert_noop!(
+				Pallet::<TestRuntime>::parse_finalized_storage_proof::<RelayBlockHasher, _>(
+					ParaId(3),
+					para_head.hash(),
+					sp_trie::StorageProof::new(proof),
+					|_| (),
+				),
+				Error::<TestRuntime>::StorageRootMismatch,
+			);
+
+			// valid proof is accepted
+			assert_eq!(
+				Pallet::<TestRuntime>::parse_finalized_storage_proof::<RelayBlockHasher, _>(
+					ParaId(3),
+					para_head.hash(),
+					sp_trie::StorageProof::new(para_storage_proof),
+					|storage| storage
+						.read_value(storage_keys::parachain_head_key(ParaId(42)).0.as_ref())
+						.unwrap(),
+				),
+				Ok(Some(head_data(42, 0).encode())),
+			);
+		});
+	}
+
+	#[test]
+	fn best_parachain_head_info_works() {
+		let para_header = ParaHeader::new(
+			42,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		let para_head = ParaHead(para_header.encode());
+		let (state_root, proof) = prepare_parachain_heads_proof(vec![
+			(ParaId(1), head_data(1, 0)),
+			(ParaId(3), para_head.clone()),
+		]);
+		run_test(|| {
+			initialize(state_root);
+			assert_eq!(Pallet::<TestRuntime>::best_parachain_head_info(ParaId(1)), None);
+
+			assert_ok!(Pallet::<TestRuntime>::submit_parachain_heads(
+				Origin::signed(1),
+				test_relay_header(0, state_root).hash(),
+				vec![ParaId(1), ParaId(3)],
+				proof,
+			));
+
+			// head of parachain#1 can't be decoded, so its number is unknown
+			assert_eq!(
+				Pallet::<TestRuntime>::best_parachain_head_info(ParaId(1)),
+				Some(BestParaHeadInfo {
+					at_relay_block_number: 0,
+					head_hash: head_hash(1, 0),
+					head_number: None,
+				}),
+			);
+			assert_eq!(
+				Pallet::<TestRuntime>::best_parachain_head_info(ParaId(3)),
+				Some(BestParaHeadInfo {
+					at_relay_block_number: 0,
+					head_hash: para_head.hash(),
+					head_number: Some(42),
+				}),
+			);
+		});
+	}
 }
@@ -0,0 +1,183 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage migrations of the parachains pallet.
+
+use crate::{
+	BestParaHeads, Config, ImportedParaHeaders, ImportedParaHeads, Pallet, ParaHeadDecoder,
+	ParachainOperatingMode, TrackedParachains,
+};
+
+use frame_support::{
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::Weight,
+};
+use sp_std::marker::PhantomData;
+
+/// Migration to the storage version 1.
+///
+/// The version 1 has added the `TrackedParachains` map and the `ImportedParaHeaders` map. All
+/// parachains, known to the pallet before the migration, are tracked in the normal operating
+/// mode. Imported heads of these parachains are decoded using `Config::HeadDecoder`.
+pub mod v1 {
+	use super::*;
+
+	/// Migrates the pallet storage from version 0 to version 1.
+	pub struct Migration<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for Migration<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T, I>::on_chain_storage_version();
+			if on_chain_version != 0 {
+				log::info!(
+					target: "runtime::bridge-parachains",
+					"Skipping migration to v1: on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut reads = 1;
+			let mut writes = 0;
+
+			// all known parachains are tracked
+			for parachain in BestParaHeads::<T, I>::iter_keys() {
+				reads += 2;
+				if !TrackedParachains::<T, I>::contains_key(parachain) {
+					TrackedParachains::<T, I>::insert(parachain, ParachainOperatingMode::Normal);
+					writes += 1;
+				}
+			}
+
+			// decode all imported heads, so that they may be used to verify parachain storage
+			// proofs
+			for (parachain, head_hash, head) in ImportedParaHeads::<T, I>::iter() {
+				reads += 1;
+				if let Some(header_data) = T::HeadDecoder::decode_head(parachain, &head) {
+					ImportedParaHeaders::<T, I>::insert(parachain, head_hash, header_data);
+					writes += 1;
+				}
+			}
+
+			StorageVersion::new(1).put::<Pallet<T, I>>();
+			writes += 1;
+
+			log::info!(
+				target: "runtime::bridge-parachains",
+				"Migrated storage to v1. Reads: {}, writes: {}",
+				reads,
+				writes,
+			);
+
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			frame_support::ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 0,
+				"Expected on-chain storage version 0 before the migration"
+			);
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			frame_support::ensure!(
+				Pallet::<T, I>::on_chain_storage_version() == 1,
+				"Expected on-chain storage version 1 after the migration"
+			);
+			for parachain in BestParaHeads::<T, I>::iter_keys() {
+				frame_support::ensure!(
+					TrackedParachains::<T, I>::contains_key(parachain),
+					"Known parachain is not tracked"
+				);
+			}
+			Ok(())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		mock::{run_test, ParaHeader, TestRuntime},
+		BestParaHead, ParaStoredHeaderData,
+	};
+	use bp_polkadot_core::parachains::{ParaHead, ParaId};
+	use codec::Encode;
+	use sp_runtime::traits::Header as HeaderT;
+
+	fn insert_best_head(parachain: ParaId, head: ParaHead) {
+		let head_hash = head.hash();
+		BestParaHeads::<TestRuntime>::insert(
+			parachain,
+			BestParaHead { at_relay_block_number: 0, head_hash, next_imported_hash_position: 1 },
+		);
+		ImportedParaHeads::<TestRuntime>::insert(parachain, head_hash, head);
+	}
+
+	#[test]
+	fn migration_to_v1_works() {
+		run_test(|| {
+			let para_header = ParaHeader::new(
+				42,
+				Default::default(),
+				[1u8; 32].into(),
+				Default::default(),
+				Default::default(),
+			);
+			let para_head = ParaHead(para_header.encode());
+			insert_best_head(ParaId(1), ParaHead(vec![1, 2, 3]));
+			insert_best_head(ParaId(3), para_head.clone());
+			TrackedParachains::<TestRuntime>::insert(ParaId(1), ParachainOperatingMode::Halted);
+
+			v1::Migration::<TestRuntime>::on_runtime_upgrade();
+
+			assert_eq!(Pallet::<TestRuntime>::on_chain_storage_version(), 1);
+			assert_eq!(
+				TrackedParachains::<TestRuntime>::get(ParaId(1)),
+				Some(ParachainOperatingMode::Halted),
+			);
+			assert_eq!(
+				TrackedParachains::<TestRuntime>::get(ParaId(3)),
+				Some(ParachainOperatingMode::Normal),
+			);
+			assert_eq!(
+				ImportedParaHeaders::<TestRuntime>::get(ParaId(3), para_head.hash()),
+				Some(ParaStoredHeaderData {
+					number: 42,
+					hash: para_header.hash(),
+					state_root: [1u8; 32].into(),
+				}),
+			);
+			assert_eq!(ImportedParaHeaders::<TestRuntime>::iter().count(), 1);
+		});
+	}
+
+	#[test]
+	fn migration_to_v1_is_skipped_if_storage_is_already_migrated() {
+		run_test(|| {
+			StorageVersion::new(1).put::<Pallet<TestRuntime>>();
+			insert_best_head(ParaId(1), ParaHead(vec![1, 2, 3]));
+
+			v1::Migration::<TestRuntime>::on_runtime_upgrade();
+
+			assert_eq!(TrackedParachains::<TestRuntime>::get(ParaId(1)), None);
+		});
+	}
+}
@@ -14,8 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
 
+use bp_polkadot_core::parachains::ParaId;
 use bp_runtime::Chain;
-use frame_support::{construct_runtime, parameter_types, weights::Weight};
+use frame_support::{
+	construct_runtime, parameter_types,
+	weights::{constants::RocksDbWeight, Weight},
+};
 use sp_runtime::{
 	testing::{Header, H256},
 	traits::{BlakeTwo256, Header as HeaderT, IdentityLookup},
@@ -29,6 +33,7 @@ pub type TestNumber = u64;
 
 pub type RelayBlockHeader =
 	sp_runtime::generic::Header<crate::RelayBlockNumber, crate::RelayBlockHasher>;
+pub type ParaHeader = sp_runtime::generic::Header<crate::ParaBlockNumber, BlakeTwo256>;
 
 type Block = frame_system::mocking::MockBlock<TestRuntime>;
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
@@ -40,9 +45,9 @@ construct_runtime! {
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		Grandpa1: pallet_bridge_grandpa::<Instance1>::{Pallet},
-		Grandpa2: pallet_bridge_grandpa::<Instance2>::{Pallet},
-		Parachains: pallet_bridge_parachains::{Pallet},
+		Grandpa1: pallet_bridge_grandpa::<Instance1>::{Pallet, Event<T>},
+		Grandpa2: pallet_bridge_grandpa::<Instance2>::{Pallet, Event<T>},
+		Parachains: pallet_bridge_parachains::{Pallet, Event<T>},
 	}
 }
 
@@ -63,7 +68,7 @@ impl frame_system::Config for TestRuntime {
 	type AccountId = AccountId;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
-	type Event = ();
+	type Event = Event;
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
@@ -72,7 +77,7 @@ impl frame_system::Config for TestRuntime {
 	type OnKilledAccount = ();
 	type BaseCallFilter = frame_support::traits::Everything;
 	type SystemWeightInfo = ();
-	type DbWeight = ();
+	type DbWeight = RocksDbWeight;
 	type BlockWeights = ();
 	type BlockLength = ();
 	type SS58Prefix = ();
@@ -82,32 +87,60 @@ impl frame_system::Config for TestRuntime {
 
 parameter_types! {
 	pub const MaxRequests: u32 = 2;
+	pub const FreeHeadersInterval: Option<u32> = None;
 	pub const HeadersToKeep: u32 = 5;
+	pub const HeadersRetention: bp_header_chain::HeadersRetentionPolicy =
+		bp_header_chain::HeadersRetentionPolicy::Count;
+	pub const AuthoritySetsToKeep: u32 = 3;
+	pub const HistoricalHeadersToKeep: u32 = 2;
 	pub const SessionLength: u64 = 5;
 	pub const NumValidators: u32 = 5;
 }
 
 impl pallet_bridge_grandpa::Config<pallet_bridge_grandpa::Instance1> for TestRuntime {
+	type Event = Event;
 	type BridgedChain = TestBridgedChain;
 	type MaxRequests = MaxRequests;
+	type MaxBatchSize = MaxRequests;
+	type FreeHeadersInterval = FreeHeadersInterval;
+	type MaxRewardsPerPeriod = frame_support::traits::ConstU32<0>;
+	type RewardsPeriod = frame_support::traits::ConstU64<1>;
+	type RelayerRewardPayment = ();
 	type HeadersToKeep = HeadersToKeep;
+	type HeadersRetention = HeadersRetention;
+	type AuthoritySetsToKeep = AuthoritySetsToKeep;
+	type HistoricalHeadersToKeep = HistoricalHeadersToKeep;
 	type WeightInfo = ();
 }
 
 impl pallet_bridge_grandpa::Config<pallet_bridge_grandpa::Instance2> for TestRuntime {
+	type Event = Event;
 	type BridgedChain = TestBridgedChain;
 	type MaxRequests = MaxRequests;
+	type MaxBatchSize = MaxRequests;
+	type FreeHeadersInterval = FreeHeadersInterval;
+	type MaxRewardsPerPeriod = frame_support::traits::ConstU32<0>;
+	type RewardsPeriod = frame_support::traits::ConstU64<1>;
+	type RelayerRewardPayment = ();
 	type HeadersToKeep = HeadersToKeep;
+	type HeadersRetention = HeadersRetention;
+	type AuthoritySetsToKeep = AuthoritySetsToKeep;
+	type HistoricalHeadersToKeep = HistoricalHeadersToKeep;
 	type WeightInfo = ();
 }
 
 parameter_types! {
 	pub const HeadsToKeep: u32 = 4;
+	pub const ParachainWithDecodableHeads: ParaId = ParaId(3);
 }
 
 impl pallet_bridge_parachains::Config for TestRuntime {
+	type Event = Event;
+	type WeightInfo = ();
 	type BridgesGrandpaPalletInstance = pallet_bridge_grandpa::Instance1;
 	type HeadsToKeep = HeadsToKeep;
+	type HeadDecoder =
+		pallet_bridge_parachains::SingleParaHeadDecoder<ParaHeader, ParachainWithDecodableHeads>;
 }
 
 #[derive(Debug)]
@@ -157,7 +190,11 @@ impl Chain for OtherBridgedChain {
 }
 
 pub fn run_test<T>(test: impl FnOnce() -> T) -> T {
-	sp_io::TestExternalities::new(Default::default()).execute_with(test)
+	sp_io::TestExternalities::new(Default::default()).execute_with(|| {
+		System::set_block_number(1);
+		System::reset_events();
+		test()
+	})
 }
 
 pub fn test_relay_header(
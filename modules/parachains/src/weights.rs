@@ -0,0 +1,101 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_bridge_parachains`
+//!
+//! NOTE: these are placeholder weights. They have not been produced by the benchmark CLI yet, so
+//! the constant parts are estimates and only the number of DB operations follows the benchmarked
+//! code. The file must be regenerated before the pallet is deployed to a production chain.
+//!
+//! Every `submit_parachain_heads` call reads the `IsHalted` flag and the relay chain header from
+//! the GRANDPA pallet. Every accepted parachain head costs 3 reads (`TrackedParachains`,
+//! `BestParaHeads` and `ImportedParaHashes`) and 4 writes (`BestParaHeads`, `ImportedParaHashes`,
+//! `ImportedParaHeads` and `ImportedParaHeaders`).
+
+// Command to regenerate:
+// target/release/millau-bridge-node
+// benchmark
+// pallet
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_bridge_parachains
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=Compiled
+// --heap-pages=4096
+// --output=./modules/parachains/src/weights.rs
+// --template=./.maintain/millau-weight-template.hbs
+
+#![allow(clippy::all)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for `pallet_bridge_parachains`.
+pub trait WeightInfo {
+	fn submit_parachain_heads_with_n_parachains(p: u32) -> Weight;
+	fn submit_parachain_heads_with_1kb_proof() -> Weight;
+	fn submit_parachain_heads_with_16kb_proof() -> Weight;
+}
+
+/// Weights for `pallet_bridge_parachains` using the Millau node and recommended hardware.
+pub struct MillauWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for MillauWeight<T> {
+	fn submit_parachain_heads_with_n_parachains(p: u32) -> Weight {
+		(18_706_000 as Weight)
+			.saturating_add((24_786_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(p as Weight)))
+	}
+	fn submit_parachain_heads_with_1kb_proof() -> Weight {
+		(44_925_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn submit_parachain_heads_with_16kb_proof() -> Weight {
+		(103_411_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn submit_parachain_heads_with_n_parachains(p: u32) -> Weight {
+		(18_706_000 as Weight)
+			.saturating_add((24_786_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(p as Weight)))
+	}
+	fn submit_parachain_heads_with_1kb_proof() -> Weight {
+		(44_925_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn submit_parachain_heads_with_16kb_proof() -> Weight {
+		(103_411_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+}
@@ -0,0 +1,234 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Primitives that are used to bridge with chains that are using BEEFY finality.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use beefy_merkle_tree::{merkle_root, Hash as BeefyMerkleHash, Hasher as BeefyMerkleHasher};
+pub use beefy_primitives::{
+	crypto::{AuthorityId as EcdsaValidatorId, AuthoritySignature as EcdsaValidatorSignature},
+	known_payload_ids::MMR_ROOT_ID as MMR_ROOT_PAYLOAD_ID,
+	mmr::{BeefyNextAuthoritySet, MmrLeaf, MmrLeafVersion},
+	Commitment, Payload as BeefyPayload, SignedCommitment, ValidatorSet, ValidatorSetId,
+	VersionedFinalityProof, BEEFY_ENGINE_ID,
+};
+
+use bp_header_chain::{FinalityProof, Parameter};
+use bp_runtime::{BlockNumberOf, Chain, HashOf};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{Convert, Hash as HashT},
+	RuntimeDebug,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+pub mod storage_keys;
+
+/// Substrate-based chain that is using BEEFY finality together with the MMR pallet.
+///
+/// BEEFY validators are signing commitments to the MMR root at some block. Every MMR leaf
+/// contains the number and hash of its parent block and the merkle root of the next BEEFY
+/// validator set. Only ECDSA BEEFY validators are supported.
+pub trait ChainWithBeefy: Chain {
+	/// Hash algorithm that is used to compute MMR nodes and the validator set merkle root.
+	type MmrHashing: HashT<Output = Self::MmrHash>;
+	/// Type of MMR nodes and roots.
+	type MmrHash: Parameter
+		+ Copy
+		+ Default
+		+ AsRef<[u8]>
+		+ From<BeefyMerkleHash>
+		+ Into<BeefyMerkleHash>;
+	/// Extra data that is appended to every BEEFY MMR leaf.
+	type BeefyMmrLeafExtra: Parameter;
+	/// Conversion of the BEEFY validator id into the leaf of the validator set merkle tree.
+	type AuthorityIdToMerkleLeaf: Convert<EcdsaValidatorId, Vec<u8>>;
+}
+
+/// MMR hash of the `ChainWithBeefy`.
+pub type MmrHashOf<C> = <C as ChainWithBeefy>::MmrHash;
+/// Extra data of the BEEFY MMR leaf of the `ChainWithBeefy`.
+pub type BeefyMmrLeafExtraOf<C> = <C as ChainWithBeefy>::BeefyMmrLeafExtra;
+/// BEEFY commitment, signed by the validators of the `ChainWithBeefy`.
+pub type BeefySignedCommitmentOf<C> = SignedCommitment<BlockNumberOf<C>, EcdsaValidatorSignature>;
+/// BEEFY finality proof, generated by the `ChainWithBeefy` validators.
+pub type BeefyVersionedFinalityProofOf<C> =
+	VersionedFinalityProof<BlockNumberOf<C>, EcdsaValidatorSignature>;
+/// Information about the BEEFY validator set of the `ChainWithBeefy`.
+pub type BeefyAuthoritySetInfoOf<C> = BeefyAuthoritySetInfo<MmrHashOf<C>>;
+/// BEEFY MMR leaf of the `ChainWithBeefy`.
+pub type BeefyMmrLeafOf<C> =
+	MmrLeaf<BlockNumberOf<C>, HashOf<C>, MmrHashOf<C>, BeefyMmrLeafExtraOf<C>>;
+/// MMR leaf proof of the `ChainWithBeefy`.
+pub type BeefyMmrProofOf<C> = BeefyMmrProof<MmrHashOf<C>>;
+/// BEEFY commitment, imported by the bridge pallet.
+pub type ImportedCommitmentOf<C> = ImportedCommitment<BlockNumberOf<C>, HashOf<C>, MmrHashOf<C>>;
+/// BEEFY bridge pallet initialization data for the `ChainWithBeefy`.
+pub type InitializationDataOf<C> = InitializationData<BlockNumberOf<C>, MmrHashOf<C>>;
+/// BEEFY finality proof of the `ChainWithBeefy` block, that is verified by the bridge pallet.
+pub type BeefyFinalityProofOf<C> =
+	BeefyFinalityProof<BlockNumberOf<C>, HashOf<C>, MmrHashOf<C>, BeefyMmrLeafExtraOf<C>>;
+
+/// Information about the BEEFY validator set, that is required to verify BEEFY commitments.
+///
+/// It has the same structure as the next validator set information, included in the BEEFY MMR
+/// leaf: the set id, the number of validators and the merkle root of the validator set.
+pub type BeefyAuthoritySetInfo<MmrHash> = BeefyNextAuthoritySet<MmrHash>;
+
+/// Data required for initializing the BEEFY bridge pallet.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct InitializationData<BlockNumber, MmrHash> {
+	/// Should the pallet block transaction immediately after initialization.
+	pub is_halted: bool,
+	/// Number of the best block, finalized by BEEFY.
+	pub best_block_number: BlockNumber,
+	/// BEEFY validator set that will be finalizing descendants of the `best_block_number` block.
+	pub current_authority_set: BeefyAuthoritySetInfo<MmrHash>,
+	/// Next BEEFY validator set, that will be enacted at the next session.
+	pub next_authority_set: BeefyAuthoritySetInfo<MmrHash>,
+}
+
+/// BEEFY commitment, imported by the bridge pallet.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct ImportedCommitment<BlockNumber, BlockHash, MmrHash> {
+	/// Number and hash of the parent of the block, finalized by the commitment.
+	///
+	/// The MMR leaf of the committed block contains the number and hash of its parent. So this
+	/// is the latest block, which hash is known to the pallet.
+	pub parent_number_and_hash: (BlockNumber, BlockHash),
+	/// MMR root at the committed block.
+	pub mmr_root: MmrHash,
+}
+
+/// MMR leaf proof.
+///
+/// This is a copy of the `sp_mmr_primitives::Proof`, that may be used as an argument of
+/// dispatchable functions.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct BeefyMmrProof<MmrHash> {
+	/// The index of the leaf the proof is for.
+	pub leaf_index: u64,
+	/// Number of leaves in the MMR, when the proof was generated.
+	pub leaf_count: u64,
+	/// Proof elements (hashes of siblings of inner nodes on the path to the leaf).
+	pub items: Vec<MmrHash>,
+}
+
+impl<MmrHash> From<sp_mmr_primitives::Proof<MmrHash>> for BeefyMmrProof<MmrHash> {
+	fn from(proof: sp_mmr_primitives::Proof<MmrHash>) -> Self {
+		BeefyMmrProof {
+			leaf_index: proof.leaf_index,
+			leaf_count: proof.leaf_count,
+			items: proof.items,
+		}
+	}
+}
+
+impl<MmrHash> From<BeefyMmrProof<MmrHash>> for sp_mmr_primitives::Proof<MmrHash> {
+	fn from(proof: BeefyMmrProof<MmrHash>) -> Self {
+		sp_mmr_primitives::Proof {
+			leaf_index: proof.leaf_index,
+			leaf_count: proof.leaf_count,
+			items: proof.items,
+		}
+	}
+}
+
+/// BEEFY finality proof of the bridged chain block.
+///
+/// Contains everything that is required to verify the signed commitment and to import the MMR
+/// root, committed by the BEEFY validators.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct BeefyFinalityProof<BlockNumber, BlockHash, MmrHash, LeafExtra> {
+	/// Signed BEEFY commitment.
+	pub commitment: SignedCommitment<BlockNumber, EcdsaValidatorSignature>,
+	/// All validators of the validator set that has signed the commitment.
+	pub validator_set: Vec<EcdsaValidatorId>,
+	/// Latest MMR leaf at the committed block.
+	pub mmr_leaf: MmrLeaf<BlockNumber, BlockHash, MmrHash, LeafExtra>,
+	/// Proof of the `mmr_leaf` inclusion into the MMR with committed root.
+	pub mmr_proof: BeefyMmrProof<MmrHash>,
+}
+
+impl<BlockNumber, BlockHash, MmrHash, LeafExtra> FinalityProof<BlockNumber>
+	for BeefyFinalityProof<BlockNumber, BlockHash, MmrHash, LeafExtra>
+where
+	BlockNumber: Clone + Send + Sync + sp_std::fmt::Debug,
+	BlockHash: Clone + Send + Sync + sp_std::fmt::Debug,
+	MmrHash: Clone + Send + Sync + sp_std::fmt::Debug,
+	LeafExtra: Clone + Send + Sync + sp_std::fmt::Debug,
+{
+	fn target_header_number(&self) -> BlockNumber {
+		self.commitment.commitment.block_number.clone()
+	}
+}
+
+/// Adapter that allows to use MMR hashing to build the BEEFY validator set merkle tree.
+pub struct BeefyMerkleHasherAdapter<H>(PhantomData<H>);
+
+impl<H: HashT> BeefyMerkleHasher for BeefyMerkleHasherAdapter<H>
+where
+	H::Output: Into<BeefyMerkleHash>,
+{
+	fn hash(data: &[u8]) -> BeefyMerkleHash {
+		H::hash(data).into()
+	}
+}
+
+/// Compute merkle root of the BEEFY validator set.
+pub fn beefy_authority_set_root<C: ChainWithBeefy>(
+	validators: &[EcdsaValidatorId],
+) -> MmrHashOf<C> {
+	let leaves = validators.iter().cloned().map(C::AuthorityIdToMerkleLeaf::convert);
+	merkle_root::<BeefyMerkleHasherAdapter<C::MmrHashing>, _, _>(leaves).into()
+}
+
+/// Returns information about the BEEFY validator set with given id.
+pub fn beefy_authority_set_info<C: ChainWithBeefy>(
+	id: ValidatorSetId,
+	validators: &[EcdsaValidatorId],
+) -> BeefyAuthoritySetInfoOf<C> {
+	BeefyAuthoritySetInfo {
+		id,
+		len: validators.len() as u32,
+		root: beefy_authority_set_root::<C>(validators),
+	}
+}
+
+/// Returns the minimal number of correct signatures that is required to accept the commitment,
+/// signed by the validator set of given size.
+///
+/// BEEFY commitment must be signed by more than 2/3 of validators.
+pub fn signatures_required(validators_len: usize) -> usize {
+	validators_len - validators_len.saturating_sub(1) / 3
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn signatures_required_works() {
+		assert_eq!(signatures_required(0), 0);
+		assert_eq!(signatures_required(1), 1);
+		assert_eq!(signatures_required(3), 3);
+		assert_eq!(signatures_required(4), 3);
+		assert_eq!(signatures_required(5), 4);
+		assert_eq!(signatures_required(100), 67);
+	}
+}
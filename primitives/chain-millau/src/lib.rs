@@ -278,6 +278,8 @@ pub const TO_MILLAU_ESTIMATE_MESSAGE_FEE_METHOD: &str =
 	"ToMillauOutboundLaneApi_estimate_message_delivery_and_dispatch_fee";
 /// Name of the `ToMillauOutboundLaneApi::message_details` runtime method.
 pub const TO_MILLAU_MESSAGE_DETAILS_METHOD: &str = "ToMillauOutboundLaneApi_message_details";
+/// Name of the `ToMillauOutboundLaneApi::relayer_reward` runtime method.
+pub const TO_MILLAU_RELAYER_REWARD_METHOD: &str = "ToMillauOutboundLaneApi_relayer_reward";
 
 sp_api::decl_runtime_apis! {
 	/// API for querying information about the finalized Millau headers.
@@ -287,13 +289,21 @@ sp_api::decl_runtime_apis! {
 	pub trait MillauFinalityApi {
 		/// Returns number and hash of the best finalized header known to the bridge module.
 		fn best_finalized() -> (BlockNumber, Hash);
+		/// Returns GRANDPA authority set that has finalized the header with given number.
+		///
+		/// Returns `None` if the authority set is unknown to the bridge module.
+		fn authority_set_at(number: BlockNumber) -> Option<bp_header_chain::AuthoritySet>;
 	}
 
 	/// Outbound message lane API for messages that are sent to Millau chain.
 	///
 	/// This API is implemented by runtimes that are sending messages to Millau chain, not the
 	/// Millau runtime itself.
-	pub trait ToMillauOutboundLaneApi<OutboundMessageFee: Parameter, OutboundPayload: Parameter> {
+	pub trait ToMillauOutboundLaneApi<
+		OutboundMessageFee: Parameter,
+		OutboundPayload: Parameter,
+		RelayerAccountId: Parameter,
+	> {
 		/// Estimate message delivery and dispatch fee that needs to be paid by the sender on
 		/// this chain.
 		///
@@ -318,6 +328,11 @@ sp_api::decl_runtime_apis! {
 			begin: MessageNonce,
 			end: MessageNonce,
 		) -> Vec<MessageDetails<OutboundMessageFee>>;
+		/// Returns reward that the relayer may claim for delivering messages over given lane.
+		///
+		/// Rewards are only accumulated if the messages pallet is configured to do so. Otherwise,
+		/// this method always returns zero.
+		fn relayer_reward(relayer: RelayerAccountId, lane: LaneId) -> OutboundMessageFee;
 	}
 }
 
@@ -42,6 +42,9 @@ pub const AVERAGE_ON_INITIALIZE_RATIO: Perbill = Perbill::from_percent(10);
 /// Represents the portion of a block that will be used by Normal extrinsics.
 pub const NORMAL_DISPATCH_RATIO: Perbill = Perbill::from_percent(75);
 
+/// Identifier of the Rialto parachain, registered at the Rialto relay chain.
+pub const RIALTO_PARACHAIN_ID: u32 = 2000;
+
 /// Block number type used in Rialto.
 pub type BlockNumber = u32;
 
@@ -19,6 +19,7 @@
 #![allow(clippy::too_many_arguments)]
 
 use bp_messages::{LaneId, MessageDetails, MessageNonce};
+use bp_polkadot_core::parachains::{BestParaHeadInfo, ParaId};
 use bp_runtime::Chain;
 use frame_support::{
 	weights::{constants::WEIGHT_PER_SECOND, DispatchClass, IdentityFee, Weight},
@@ -228,6 +229,8 @@ frame_support::parameter_types! {
 pub const WITH_RIALTO_GRANDPA_PALLET_NAME: &str = "BridgeRialtoGrandpa";
 /// Name of the With-Rialto messages pallet instance that is deployed at bridged chains.
 pub const WITH_RIALTO_MESSAGES_PALLET_NAME: &str = "BridgeRialtoMessages";
+/// Name of the With-Rialto parachains bridge pallet instance that is deployed at bridged chains.
+pub const WITH_RIALTO_BRIDGE_PARAS_PALLET_NAME: &str = "BridgeRialtoParachains";
 
 /// Name of the Millau->Rialto (actually KSM->DOT) conversion rate stored in the Rialto runtime.
 pub const MILLAU_TO_RIALTO_CONVERSION_RATE_PARAMETER_NAME: &str = "MillauToRialtoConversionRate";
@@ -240,6 +243,8 @@ pub const PARAS_PALLET_NAME: &str = "Paras";
 
 /// Name of the `RialtoFinalityApi::best_finalized` runtime method.
 pub const BEST_FINALIZED_RIALTO_HEADER_METHOD: &str = "RialtoFinalityApi_best_finalized";
+/// Name of the `RialtoParachainsApi::best_parachain_head` runtime method.
+pub const BEST_RIALTO_PARACHAIN_HEAD_METHOD: &str = "RialtoParachainsApi_best_parachain_head";
 
 /// Name of the `ToRialtoOutboundLaneApi::estimate_message_delivery_and_dispatch_fee` runtime
 /// method.
@@ -247,6 +252,8 @@ pub const TO_RIALTO_ESTIMATE_MESSAGE_FEE_METHOD: &str =
 	"ToRialtoOutboundLaneApi_estimate_message_delivery_and_dispatch_fee";
 /// Name of the `ToRialtoOutboundLaneApi::message_details` runtime method.
 pub const TO_RIALTO_MESSAGE_DETAILS_METHOD: &str = "ToRialtoOutboundLaneApi_message_details";
+/// Name of the `ToRialtoOutboundLaneApi::relayer_reward` runtime method.
+pub const TO_RIALTO_RELAYER_REWARD_METHOD: &str = "ToRialtoOutboundLaneApi_relayer_reward";
 
 sp_api::decl_runtime_apis! {
 	/// API for querying information about the finalized Rialto headers.
@@ -256,13 +263,30 @@ sp_api::decl_runtime_apis! {
 	pub trait RialtoFinalityApi {
 		/// Returns number and hash of the best finalized header known to the bridge module.
 		fn best_finalized() -> (BlockNumber, Hash);
+		/// Returns GRANDPA authority set that has finalized the header with given number.
+		///
+		/// Returns `None` if the authority set is unknown to the bridge module.
+		fn authority_set_at(number: BlockNumber) -> Option<bp_header_chain::AuthoritySet>;
+	}
+
+	/// API for querying information about the heads of Rialto parachains.
+	///
+	/// This API is implemented by runtimes that are bridging with the Rialto parachains, not the
+	/// Rialto runtime itself.
+	pub trait RialtoParachainsApi {
+		/// Returns information about the best known head of given Rialto parachain.
+		fn best_parachain_head(parachain: ParaId) -> Option<BestParaHeadInfo>;
 	}
 
 	/// Outbound message lane API for messages that are sent to Rialto chain.
 	///
 	/// This API is implemented by runtimes that are sending messages to Rialto chain, not the
 	/// Rialto runtime itself.
-	pub trait ToRialtoOutboundLaneApi<OutboundMessageFee: Parameter, OutboundPayload: Parameter> {
+	pub trait ToRialtoOutboundLaneApi<
+		OutboundMessageFee: Parameter,
+		OutboundPayload: Parameter,
+		RelayerAccountId: Parameter,
+	> {
 		/// Estimate message delivery and dispatch fee that needs to be paid by the sender on
 		/// this chain.
 		///
@@ -287,6 +311,11 @@ sp_api::decl_runtime_apis! {
 			begin: MessageNonce,
 			end: MessageNonce,
 		) -> Vec<MessageDetails<OutboundMessageFee>>;
+		/// Returns reward that the relayer may claim for delivering messages over given lane.
+		///
+		/// Rewards are only accumulated if the messages pallet is configured to do so. Otherwise,
+		/// this method always returns zero.
+		fn relayer_reward(relayer: RelayerAccountId, lane: LaneId) -> OutboundMessageFee;
 	}
 }
 
@@ -107,5 +107,9 @@ sp_api::decl_runtime_apis! {
 	pub trait WestendFinalityApi {
 		/// Returns number and hash of the best finalized header known to the bridge module.
 		fn best_finalized() -> (BlockNumber, Hash);
+		/// Returns GRANDPA authority set that has finalized the header with given number.
+		///
+		/// Returns `None` if the authority set is unknown to the bridge module.
+		fn authority_set_at(number: BlockNumber) -> Option<bp_header_chain::AuthoritySet>;
 	}
 }
@@ -70,6 +70,26 @@ pub struct InitializationData<H: HeaderT> {
 	pub is_halted: bool,
 }
 
+/// Policy of pruning finalized headers, imported by the bridge GRANDPA pallet.
+///
+/// Under any policy, the pallet never keeps more than `HeadersToKeep` headers.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum HeadersRetentionPolicy {
+	/// Keep `HeadersToKeep` most recently imported headers.
+	Count,
+	/// Keep headers that are at most `blocks` blocks behind the best finalized header.
+	///
+	/// Mandatory headers (headers that schedule or enact authority set changes) are kept while
+	/// they are at most `mandatory_blocks` blocks behind the best finalized header.
+	BlockNumber {
+		/// Number of bridged chain blocks to keep regular headers for.
+		blocks: u32,
+		/// Number of bridged chain blocks to keep mandatory headers for.
+		mandatory_blocks: u32,
+	},
+}
+
 /// base trait for verifying transaction inclusion proofs.
 pub trait InclusionProofVerifier {
 	/// Transaction type.
@@ -111,6 +131,17 @@ impl<H: Default, E> HeaderChain<H, E> for () {
 	}
 }
 
+/// Payment of rewards to relayers that are submitting finality proofs.
+pub trait FinalityProofRewardPayment<AccountId> {
+	/// Pay reward for submitting useful finality proof to the relayer. The reward is paid from
+	/// the `relayer_fund_account`.
+	fn pay_reward(relayer: &AccountId, relayer_fund_account: &AccountId);
+}
+
+impl<AccountId> FinalityProofRewardPayment<AccountId> for () {
+	fn pay_reward(_relayer: &AccountId, _relayer_fund_account: &AccountId) {}
+}
+
 /// Abstract finality proof that is justifying block finality.
 pub trait FinalityProof<Number>: Clone + Send + Sync + Debug {
 	/// Return number of header that this proof is generated for.
@@ -132,3 +163,22 @@ pub fn find_grandpa_authorities_scheduled_change<H: HeaderT>(
 	// the right kind of consensus log.
 	header.digest().convert_first(|l| l.try_to(id).and_then(filter_log))
 }
+
+/// Find header digest that forces next GRANDPA authorities set.
+///
+/// Returns the median last finalized block number, announced in the signal, along with
+/// the forced change itself.
+pub fn find_grandpa_authorities_forced_change<H: HeaderT>(
+	header: &H,
+) -> Option<(H::Number, sp_finality_grandpa::ScheduledChange<H::Number>)> {
+	let id = OpaqueDigestItemId::Consensus(&GRANDPA_ENGINE_ID);
+
+	let filter_log = |log: ConsensusLog<H::Number>| match log {
+		ConsensusLog::ForcedChange(median, change) => Some((median, change)),
+		_ => None,
+	};
+
+	// find the first consensus digest with the right ID which converts to
+	// the right kind of consensus log.
+	header.digest().convert_first(|l| l.try_to(id).and_then(filter_log))
+}
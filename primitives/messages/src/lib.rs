@@ -58,6 +58,22 @@ impl Default for OperatingMode {
 	}
 }
 
+/// State of the lane, registered at the messages pallet.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum LaneState {
+	/// The lane is opened and all operations are allowed.
+	Opened,
+	/// The lane is closing. New outbound messages are rejected, but queued messages are still
+	/// delivered to the bridged chain and inbound messages are still accepted.
+	Closing,
+	/// The lane is closed and its storage has been removed. All operations are prohibited.
+	///
+	/// Closed lane may not be reopened, because it would allow to replay messages, that have
+	/// been delivered over this lane before.
+	Closed,
+}
+
 /// Messages pallet parameter.
 pub trait Parameter: frame_support::Parameter {
 	/// Save parameter value in the runtime storage.
@@ -96,6 +112,12 @@ pub struct MessageData<Fee> {
 	pub payload: MessagePayload,
 	/// Message delivery and dispatch fee, paid by the submitter.
 	pub fee: Fee,
+	/// Number of the source chain block, starting from which the message is considered expired.
+	///
+	/// Expired messages are not dispatched at the target chain and their fee is refunded to the
+	/// submitter when the source chain receives confirmation of their delivery. If it is `None`,
+	/// the message never expires.
+	pub expires_at: Option<u64>,
 }
 
 /// Message as it is stored in the storage.
@@ -189,6 +211,9 @@ pub struct MessageDetails<OutboundMessageFee> {
 	pub delivery_and_dispatch_fee: OutboundMessageFee,
 	/// Where the fee for dispatching message is paid?
 	pub dispatch_fee_payment: DispatchFeePayment,
+	/// Source chain block number at which the message expires. Messages that have expired
+	/// according to the target chain view of the source chain are not dispatched there.
+	pub expires_at: Option<u64>,
 }
 
 /// Bit vector of message dispatch results.
@@ -264,6 +289,15 @@ impl DeliveredMessages {
 	}
 }
 
+/// Nonces of messages that have been delivered to the inbound lane after their deadline.
+///
+/// Expired messages are not dispatched at the target chain. Their nonces are kept in the ascending
+/// order until their delivery is confirmed, so that the source chain may refund their fees. The
+/// nonces are stored separately from the `InboundLaneData`, because the lane data is decoded from
+/// storage proofs by the source chain and its encoding must be the same at both sides of the
+/// bridge.
+pub type ExpiredMessages = VecDeque<MessageNonce>;
+
 /// Gist of `InboundLaneData::relayers` field used by runtime APIs.
 #[derive(Clone, Default, Encode, Decode, RuntimeDebug, PartialEq, Eq, TypeInfo)]
 pub struct UnrewardedRelayersState {
@@ -399,4 +433,28 @@ mod tests {
 
 		assert!(delivered_messages.message_dispatch_result(125));
 	}
+
+	#[test]
+	fn inbound_lane_data_encoding_is_not_changed() {
+		// If this test fails, then the inbound lane data encoding has been changed and the
+		// bridged chain, which is not yet upgraded, won't be able to decode messages delivery
+		// proofs, crafted at this chain.
+		let inbound_lane_data = InboundLaneData {
+			relayers: vec![UnrewardedRelayer {
+				relayer: 1u8,
+				messages: DeliveredMessages {
+					begin: 1,
+					end: 2,
+					dispatch_results: bitvec![u8, Msb0; 1, 0],
+				},
+			}]
+			.into_iter()
+			.collect(),
+			last_confirmed_nonce: 0,
+		};
+		assert_eq!(
+			inbound_lane_data.encode(),
+			hex_literal::hex!("04010100000000000000020000000000000008800000000000000000").to_vec(),
+		);
+	}
 }
@@ -16,7 +16,9 @@
 
 //! Primitives of messages module, that are used on the source chain.
 
-use crate::{DeliveredMessages, InboundLaneData, LaneId, MessageNonce, OutboundLaneData};
+use crate::{
+	DeliveredMessages, ExpiredMessages, InboundLaneData, LaneId, MessageNonce, OutboundLaneData,
+};
 
 use crate::UnrewardedRelayer;
 use bp_runtime::Size;
@@ -82,10 +84,11 @@ pub trait TargetHeaderChain<Payload, AccountId> {
 	/// never be delivered.
 	fn verify_message(payload: &Payload) -> Result<(), Self::Error>;
 
-	/// Verify messages delivery proof and return lane && nonce of the latest received message.
+	/// Verify messages delivery proof and return lane, its state at the bridged chain and nonces of
+	/// messages that have been delivered after their deadline.
 	fn verify_messages_delivery_proof(
 		proof: Self::MessagesDeliveryProof,
-	) -> Result<(LaneId, InboundLaneData<AccountId>), Self::Error>;
+	) -> Result<(LaneId, InboundLaneData<AccountId>, ExpiredMessages), Self::Error>;
 }
 
 /// Lane message verifier.
@@ -147,6 +150,27 @@ pub trait MessageDeliveryAndDispatchPayment<SenderOrigin, AccountId, Balance> {
 		received_range: &RangeInclusive<MessageNonce>,
 		relayer_fund_account: &AccountId,
 	);
+
+	/// Pay reward, accumulated by the relayer for delivering messages over the given lane.
+	///
+	/// This method is only called when the messages pallet is configured to accumulate relayers
+	/// rewards in the storage, instead of paying them right when delivery is confirmed.
+	fn pay_relayer_reward(
+		lane_id: LaneId,
+		relayer: &AccountId,
+		reward: &Balance,
+		relayer_fund_account: &AccountId,
+	) -> Result<(), Self::Error>;
+
+	/// Return delivery and dispatch fee of the expired message back to its submitter.
+	///
+	/// This method is called when the bridged chain confirms delivery of the message that has
+	/// expired before delivery and hasn't been dispatched.
+	fn refund_delivery_and_dispatch_fee(
+		submitter: &AccountId,
+		fee: &Balance,
+		relayer_fund_account: &AccountId,
+	) -> Result<(), Self::Error>;
 }
 
 impl<SenderOrigin, AccountId, Balance>
@@ -170,6 +194,23 @@ impl<SenderOrigin, AccountId, Balance>
 		_relayer_fund_account: &AccountId,
 	) {
 	}
+
+	fn pay_relayer_reward(
+		_lane_id: LaneId,
+		_relayer: &AccountId,
+		_reward: &Balance,
+		_relayer_fund_account: &AccountId,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+
+	fn refund_delivery_and_dispatch_fee(
+		_submitter: &AccountId,
+		_fee: &Balance,
+		_relayer_fund_account: &AccountId,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
 }
 
 /// Send message artifacts.
@@ -277,7 +318,7 @@ impl<Payload, AccountId> TargetHeaderChain<Payload, AccountId> for ForbidOutboun
 
 	fn verify_messages_delivery_proof(
 		_proof: Self::MessagesDeliveryProof,
-	) -> Result<(LaneId, InboundLaneData<AccountId>), Self::Error> {
+	) -> Result<(LaneId, InboundLaneData<AccountId>, ExpiredMessages), Self::Error> {
 		Err(ALL_OUTBOUND_MESSAGES_REJECTED)
 	}
 }
@@ -319,4 +360,21 @@ impl<SenderOrigin, AccountId, Balance>
 		_relayer_fund_account: &AccountId,
 	) {
 	}
+
+	fn pay_relayer_reward(
+		_lane_id: LaneId,
+		_relayer: &AccountId,
+		_reward: &Balance,
+		_relayer_fund_account: &AccountId,
+	) -> Result<(), Self::Error> {
+		Err(ALL_OUTBOUND_MESSAGES_REJECTED)
+	}
+
+	fn refund_delivery_and_dispatch_fee(
+		_submitter: &AccountId,
+		_fee: &Balance,
+		_relayer_fund_account: &AccountId,
+	) -> Result<(), Self::Error> {
+		Err(ALL_OUTBOUND_MESSAGES_REJECTED)
+	}
 }
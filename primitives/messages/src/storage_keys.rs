@@ -18,12 +18,16 @@
 
 /// Name of the `OPERATING_MODE_VALUE_NAME` storage value.
 pub const OPERATING_MODE_VALUE_NAME: &str = "PalletOperatingMode";
+/// Name of the `LaneOperatingModes` storage map.
+pub const LANE_OPERATING_MODES_MAP_NAME: &str = "LaneOperatingModes";
 /// Name of the `OutboundMessages` storage map.
 pub const OUTBOUND_MESSAGES_MAP_NAME: &str = "OutboundMessages";
 /// Name of the `OutboundLanes` storage map.
 pub const OUTBOUND_LANES_MAP_NAME: &str = "OutboundLanes";
 /// Name of the `InboundLanes` storage map.
 pub const INBOUND_LANES_MAP_NAME: &str = "InboundLanes";
+/// Name of the `ExpiredInboundMessages` storage map.
+pub const EXPIRED_INBOUND_MESSAGES_MAP_NAME: &str = "ExpiredInboundMessages";
 
 use crate::{LaneId, MessageKey, MessageNonce};
 
@@ -42,6 +46,15 @@ pub fn operating_mode_key(pallet_prefix: &str) -> StorageKey {
 	)
 }
 
+/// Storage key of the lane operating mode in the runtime storage.
+pub fn lane_operating_mode_key(pallet_prefix: &str, lane: &LaneId) -> StorageKey {
+	bp_runtime::storage_map_final_key::<Blake2_128Concat>(
+		pallet_prefix,
+		LANE_OPERATING_MODES_MAP_NAME,
+		lane,
+	)
+}
+
 /// Storage key of the outbound message in the runtime storage.
 pub fn message_key(pallet_prefix: &str, lane: &LaneId, nonce: MessageNonce) -> StorageKey {
 	bp_runtime::storage_map_final_key::<Blake2_128Concat>(
@@ -69,6 +82,16 @@ pub fn inbound_lane_data_key(pallet_prefix: &str, lane: &LaneId) -> StorageKey {
 	)
 }
 
+/// Storage key of nonces of expired messages, delivered to the inbound lane, in the runtime
+/// storage.
+pub fn expired_inbound_messages_key(pallet_prefix: &str, lane: &LaneId) -> StorageKey {
+	bp_runtime::storage_map_final_key::<Blake2_128Concat>(
+		pallet_prefix,
+		EXPIRED_INBOUND_MESSAGES_MAP_NAME,
+		lane,
+	)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -87,6 +110,19 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn lane_operating_mode_key_computed_properly() {
+		// If this test fails, then something has been changed in module storage that is possibly
+		// breaking all existing message relays.
+		let storage_key = lane_operating_mode_key("BridgeMessages", &*b"test").0;
+		assert_eq!(
+			storage_key,
+			hex!("dd16c784ebd3390a9bc0357c7511ed01421884be2ba562ac021181f17f1b00f044a8995dd50b6657a037a7839304535b74657374").to_vec(),
+			"Unexpected storage key: {}",
+			hex::encode(&storage_key),
+		);
+	}
+
 	#[test]
 	fn storage_message_key_computed_properly() {
 		// If this test fails, then something has been changed in module storage that is breaking
@@ -125,4 +161,17 @@ mod tests {
 			hex::encode(&storage_key),
 		);
 	}
+
+	#[test]
+	fn expired_inbound_messages_key_computed_properly() {
+		// If this test fails, then something has been changed in module storage that is breaking
+		// all previously crafted inbound lane state proofs.
+		let storage_key = expired_inbound_messages_key("BridgeMessages", &*b"test").0;
+		assert_eq!(
+			storage_key,
+			hex!("dd16c784ebd3390a9bc0357c7511ed016aa2a6ad3b41eb4f1cd675a8e13f4da644a8995dd50b6657a037a7839304535b74657374").to_vec(),
+			"Unexpected storage key: {}",
+			hex::encode(&storage_key),
+		);
+	}
 }
@@ -108,6 +108,12 @@ pub trait MessageDispatch<AccountId, Fee> {
 		relayer_account: &AccountId,
 		message: DispatchMessage<Self::DispatchPayload, Fee>,
 	) -> MessageDispatchResult;
+
+	/// Called when dispatch of the inbound message, which has failed before, is retried.
+	///
+	/// The dispatch fee (if any) has already been paid when the message has been delivered,
+	/// so it must not be paid again inside this method.
+	fn redispatch(message: DispatchMessage<Self::DispatchPayload, Fee>) -> MessageDispatchResult;
 }
 
 impl<Message> Default for ProvedLaneMessages<Message> {
@@ -170,4 +176,12 @@ impl<AccountId, Fee> MessageDispatch<AccountId, Fee> for ForbidInboundMessages {
 			dispatch_fee_paid_during_dispatch: false,
 		}
 	}
+
+	fn redispatch(_: DispatchMessage<Self::DispatchPayload, Fee>) -> MessageDispatchResult {
+		MessageDispatchResult {
+			dispatch_result: false,
+			unspent_weight: 0,
+			dispatch_fee_paid_during_dispatch: false,
+		}
+	}
 }
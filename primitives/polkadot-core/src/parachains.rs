@@ -53,6 +53,7 @@ use parity_util_mem::MallocSizeOf;
 	RuntimeDebug,
 	TypeInfo,
 )]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct ParaId(pub u32);
 
 /// Parachain head.
@@ -80,3 +81,14 @@ pub type ParaHash = crate::Hash;
 
 /// Raw storage proof of parachain heads, stored in polkadot-like chain runtime.
 pub type ParachainHeadsProof = Vec<Vec<u8>>;
+
+/// Information about the best known head of the bridged parachain.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct BestParaHeadInfo {
+	/// Number of the relay chain block, where this head has been read.
+	pub at_relay_block_number: crate::BlockNumber,
+	/// Hash of the parachain head.
+	pub head_hash: ParaHash,
+	/// Number of the parachain header. It is `None` if the head has not been decoded.
+	pub head_number: Option<crate::BlockNumber>,
+}
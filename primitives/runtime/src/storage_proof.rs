@@ -64,6 +64,22 @@ pub enum Error {
 	StorageValueUnavailable,
 }
 
+/// Storage proof size requirements.
+///
+/// This is currently used by benchmarks when generating storage proofs.
+#[derive(Clone, Copy, Debug)]
+pub enum StorageProofSize {
+	/// The proof is expected to be minimal. If value size may be changed, then it is expected to
+	/// have given size.
+	Minimal(u32),
+	/// The proof is expected to have at least given size and grow by increasing number of trie
+	/// nodes included in the proof.
+	HasExtraNodes(u32),
+	/// The proof is expected to have at least given size and grow by increasing value that is
+	/// stored in the trie.
+	HasLargeLeaf(u32),
+}
+
 /// Return valid storage proof and state root.
 ///
 /// NOTE: This should only be used for **testing**.
@@ -0,0 +1,83 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Utilities for generating test BEEFY commitments.
+
+use bp_beefy::{
+	BeefyPayload, Commitment, EcdsaValidatorId, EcdsaValidatorSignature, SignedCommitment,
+	ValidatorSetId, MMR_ROOT_PAYLOAD_ID,
+};
+use codec::Encode;
+use sp_core::{ecdsa, hashing::keccak_256, Pair};
+use sp_std::prelude::*;
+
+pub const TEST_BEEFY_VALIDATOR_SET_ID: ValidatorSetId = 0;
+
+/// Get BEEFY validator key pair with given index.
+pub fn beefy_validator_pair(index: u32) -> ecdsa::Pair {
+	// zero secret key is invalid, so seeds are starting from 1
+	let mut seed = [0_u8; 32];
+	seed[..4].copy_from_slice(&(index + 1).to_le_bytes());
+	ecdsa::Pair::from_seed(&seed)
+}
+
+/// Get key pairs of the BEEFY validator set of given size.
+pub fn beefy_validator_pairs(count: u32) -> Vec<ecdsa::Pair> {
+	(0..count).map(beefy_validator_pair).collect()
+}
+
+/// Get identifiers of given BEEFY validators.
+pub fn beefy_validator_ids(validators: &[ecdsa::Pair]) -> Vec<EcdsaValidatorId> {
+	validators.iter().map(|pair| pair.public().into()).collect()
+}
+
+/// Make BEEFY commitment to the given MMR root at given block.
+pub fn make_beefy_commitment<BlockNumber>(
+	block_number: BlockNumber,
+	validator_set_id: ValidatorSetId,
+	mmr_root: impl Encode,
+) -> Commitment<BlockNumber> {
+	Commitment {
+		payload: BeefyPayload::new(MMR_ROOT_PAYLOAD_ID, mmr_root.encode()),
+		block_number,
+		validator_set_id,
+	}
+}
+
+/// Sign BEEFY commitment by first `signers` validators of the given validator set.
+///
+/// The signature is generated over `keccak256` hash of the encoded commitment, the same way it is
+/// done by the BEEFY gadget.
+pub fn sign_beefy_commitment<BlockNumber: Encode>(
+	commitment: Commitment<BlockNumber>,
+	validators: &[ecdsa::Pair],
+	signers: usize,
+) -> SignedCommitment<BlockNumber, EcdsaValidatorSignature> {
+	let commitment_hash = keccak_256(&commitment.encode());
+	let signatures = validators
+		.iter()
+		.enumerate()
+		.map(|(index, validator)| {
+			if index < signers {
+				Some(validator.sign_prehashed(&commitment_hash).into())
+			} else {
+				None
+			}
+		})
+		.collect();
+
+	SignedCommitment { commitment, signatures }
+}
@@ -24,9 +24,11 @@ use sp_finality_grandpa::{AuthorityId, AuthoritySignature, AuthorityWeight, SetI
 use sp_runtime::traits::{Header as HeaderT, One, Zero};
 use sp_std::prelude::*;
 
-// Re-export all our test account utilities
+// Re-export all our test account and BEEFY commitment utilities
+pub use beefy::*;
 pub use keyring::*;
 
+mod beefy;
 mod keyring;
 
 pub const TEST_GRANDPA_ROUND: u64 = 1;
@@ -42,7 +42,8 @@ substrate_relay_helper::generate_mocked_submit_finality_proof_call_builder!(
 	KusamaFinalityToPolkadot,
 	KusamaFinalityToPolkadotCallBuilder,
 	relay_polkadot_client::runtime::Call::BridgeKusamaGrandpa,
-	relay_polkadot_client::runtime::BridgeKusamaGrandpaCall::submit_finality_proof
+	relay_polkadot_client::runtime::BridgeKusamaGrandpaCall::submit_finality_proof,
+	relay_polkadot_client::runtime::BridgeKusamaGrandpaCall::submit_finality_proofs
 );
 
 #[async_trait]
@@ -49,6 +49,22 @@ impl CliEncodeMessage for Millau {
 			),
 		})
 	}
+
+	fn encode_claim_rewards_call(
+		lane: LaneId,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(match bridge_instance_index {
+			bridge::MILLAU_TO_RIALTO_INDEX => millau_runtime::Call::BridgeRialtoMessages(
+				millau_runtime::MessagesCall::claim_rewards { lane_id: lane },
+			)
+			.into(),
+			_ => anyhow::bail!(
+				"Unsupported target bridge pallet with instance index: {}",
+				bridge_instance_index
+			),
+		})
+	}
 }
 
 impl CliChain for Millau {
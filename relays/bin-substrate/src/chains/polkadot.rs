@@ -47,6 +47,13 @@ impl CliEncodeMessage for Polkadot {
 			),
 		})
 	}
+
+	fn encode_claim_rewards_call(
+		_lane: LaneId,
+		_bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		anyhow::bail!("Claiming relayer rewards is not supported by Polkadot runtime")
+	}
 }
 
 impl CliChain for Polkadot {
@@ -42,7 +42,8 @@ substrate_relay_helper::generate_mocked_submit_finality_proof_call_builder!(
 	PolkadotFinalityToKusama,
 	PolkadotFinalityToKusamaCallBuilder,
 	relay_kusama_client::runtime::Call::BridgePolkadotGrandpa,
-	relay_kusama_client::runtime::BridgePolkadotGrandpaCall::submit_finality_proof
+	relay_kusama_client::runtime::BridgePolkadotGrandpaCall::submit_finality_proof,
+	relay_kusama_client::runtime::BridgePolkadotGrandpaCall::submit_finality_proofs
 );
 
 #[async_trait]
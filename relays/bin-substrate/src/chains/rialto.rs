@@ -49,6 +49,22 @@ impl CliEncodeMessage for Rialto {
 			),
 		})
 	}
+
+	fn encode_claim_rewards_call(
+		lane: LaneId,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		Ok(match bridge_instance_index {
+			bridge::RIALTO_TO_MILLAU_INDEX => rialto_runtime::Call::BridgeMillauMessages(
+				rialto_runtime::MessagesCall::claim_rewards { lane_id: lane },
+			)
+			.into(),
+			_ => anyhow::bail!(
+				"Unsupported target bridge pallet with instance index: {}",
+				bridge_instance_index
+			),
+		})
+	}
 }
 
 impl CliChain for Rialto {
@@ -0,0 +1,46 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Rialto-to-Millau parachains sync entrypoint.
+
+use bp_polkadot_core::parachains::ParaId;
+use substrate_relay_helper::parachains::{
+	DirectSubmitParachainHeadsCallBuilder, SubstrateParachainsPipeline,
+};
+
+/// Description of Rialto -> Millau parachains bridge.
+#[derive(Clone, Debug)]
+pub struct RialtoParachainsToMillau;
+
+impl SubstrateParachainsPipeline for RialtoParachainsToMillau {
+	type SourceChain = relay_rialto_client::Rialto;
+	type TargetChain = relay_millau_client::Millau;
+
+	type SubmitParachainHeadsCallBuilder = RialtoParachainsToMillauSubmitParachainHeadsCallBuilder;
+	type TransactionSignScheme = relay_millau_client::Millau;
+
+	const PARACHAINS_PALLET_NAME: &'static str = bp_rialto::WITH_RIALTO_BRIDGE_PARAS_PALLET_NAME;
+	const PARACHAINS: &'static [ParaId] = &[ParaId(bp_rialto_parachain::RIALTO_PARACHAIN_ID)];
+	const BEST_PARACHAIN_HEAD_METHOD: &'static str = bp_rialto::BEST_RIALTO_PARACHAIN_HEAD_METHOD;
+}
+
+/// `submit_parachain_heads` call builder for Rialto -> Millau parachains bridge.
+pub type RialtoParachainsToMillauSubmitParachainHeadsCallBuilder =
+	DirectSubmitParachainHeadsCallBuilder<
+		RialtoParachainsToMillau,
+		millau_runtime::Runtime,
+		millau_runtime::WithRialtoParachainsInstance,
+	>;
@@ -47,6 +47,13 @@ impl CliEncodeMessage for Rococo {
 			),
 		})
 	}
+
+	fn encode_claim_rewards_call(
+		_lane: LaneId,
+		_bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>> {
+		anyhow::bail!("Claiming relayer rewards is not supported by Rococo runtime")
+	}
 }
 impl CliChain for Rococo {
 	const RUNTIME_VERSION: RuntimeVersion = bp_rococo::VERSION;
@@ -32,7 +32,8 @@ substrate_relay_helper::generate_mocked_submit_finality_proof_call_builder!(
 	RococoFinalityToWococo,
 	RococoFinalityToWococoCallBuilder,
 	relay_wococo_client::runtime::Call::BridgeGrandpaRococo,
-	relay_wococo_client::runtime::BridgeGrandpaRococoCall::submit_finality_proof
+	relay_wococo_client::runtime::BridgeGrandpaRococoCall::submit_finality_proof,
+	relay_wococo_client::runtime::BridgeGrandpaRococoCall::submit_finality_proofs
 );
 
 #[async_trait]
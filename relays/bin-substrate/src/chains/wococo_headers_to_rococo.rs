@@ -37,7 +37,8 @@ substrate_relay_helper::generate_mocked_submit_finality_proof_call_builder!(
 	WococoFinalityToRococo,
 	WococoFinalityToRococoCallBuilder,
 	relay_rococo_client::runtime::Call::BridgeGrandpaWococo,
-	relay_rococo_client::runtime::BridgeGrandpaWococoCall::submit_finality_proof
+	relay_rococo_client::runtime::BridgeGrandpaWococoCall::submit_finality_proof,
+	relay_rococo_client::runtime::BridgeGrandpaWococoCall::submit_finality_proofs
 );
 
 #[async_trait]
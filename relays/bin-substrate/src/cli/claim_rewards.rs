@@ -0,0 +1,119 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::cli::{
+	bridge::FullBridge, encode_message::CliEncodeMessage, HexBytes, HexLaneId,
+	SourceConnectionParams, SourceSigningParams,
+};
+use codec::Encode;
+use relay_substrate_client::{Chain, SignParam, TransactionSignScheme, UnsignedTransaction};
+use sp_core::{Bytes, Pair};
+use sp_runtime::AccountId32;
+use structopt::StructOpt;
+use strum::VariantNames;
+
+/// Claim relayer rewards, accumulated by the source chain messages pallet.
+#[derive(StructOpt)]
+pub struct ClaimRewards {
+	/// A bridge instance to claim rewards at.
+	#[structopt(possible_values = FullBridge::VARIANTS, case_insensitive = true)]
+	bridge: FullBridge,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	#[structopt(flatten)]
+	source_sign: SourceSigningParams,
+	/// Hex-encoded id of the lane to claim rewards at. Defaults to `00000000`.
+	#[structopt(long, default_value = "00000000")]
+	lane: HexLaneId,
+}
+
+impl ClaimRewards {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		crate::select_full_bridge!(self.bridge, {
+			let source_client = self.source.to_client::<Source>().await?;
+			let source_sign = self.source_sign.to_keypair::<Source>()?;
+
+			let lane = self.lane.0;
+			let claim_call =
+				Source::encode_claim_rewards_call(lane, self.bridge.bridge_instance_index())?;
+
+			let source_genesis_hash = *source_client.genesis_hash();
+			let (spec_version, transaction_version) =
+				source_client.simple_runtime_version().await?;
+			source_client
+				.submit_signed_extrinsic(
+					source_sign.public().into(),
+					move |_, transaction_nonce| {
+						let signed_source_call = Source::sign_transaction(SignParam {
+							spec_version,
+							transaction_version,
+							genesis_hash: source_genesis_hash,
+							signer: source_sign.clone(),
+							era: relay_substrate_client::TransactionEra::immortal(),
+							unsigned: UnsignedTransaction::new(claim_call, transaction_nonce),
+						})?
+						.encode();
+
+						log::info!(
+							target: "bridge",
+							"Claiming rewards of {:?} at {} lane {:?}",
+							AccountId32::from(source_sign.public()),
+							Source::NAME,
+							lane,
+						);
+						log::info!(
+							target: "bridge",
+							"Signed {} Call: {:?}",
+							Source::NAME,
+							HexBytes::encode(&signed_source_call)
+						);
+
+						Ok(Bytes(signed_source_call))
+					},
+				)
+				.await?;
+		});
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn claim_rewards_params_are_parsed() {
+		// given
+		let claim_rewards = ClaimRewards::from_iter(vec![
+			"claim-rewards",
+			"rialto-to-millau",
+			"--source-port",
+			"1234",
+			"--source-signer",
+			"//Alice",
+			"--lane",
+			"00000001",
+		]);
+
+		// then
+		assert_eq!(claim_rewards.bridge, FullBridge::RialtoToMillau);
+		assert_eq!(claim_rewards.source.source_port, 1234);
+		assert_eq!(claim_rewards.source_sign.source_signer, Some("//Alice".into()));
+		assert_eq!(claim_rewards.lane, HexLaneId([0, 0, 0, 1]));
+	}
+}
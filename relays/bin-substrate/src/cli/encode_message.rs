@@ -49,6 +49,12 @@ pub trait CliEncodeMessage: Chain {
 		fee: Self::Balance,
 		bridge_instance_index: u8,
 	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>>;
+
+	/// Encode a claim relayer rewards call.
+	fn encode_claim_rewards_call(
+		lane: LaneId,
+		bridge_instance_index: u8,
+	) -> anyhow::Result<EncodedOrDecodedCall<Self::Call>>;
 }
 
 /// Encode message payload passed through CLI flags.
@@ -38,6 +38,8 @@ pub struct EstimateFee {
 	#[structopt(flatten)]
 	source: SourceConnectionParams,
 	/// Hex-encoded id of lane that will be delivering the message.
+	///
+	/// The fee grows with the number of messages that are already queued at this lane.
 	#[structopt(long, default_value = "00000000")]
 	lane: HexLaneId,
 	/// A way to override conversion rate between bridge tokens.
@@ -30,12 +30,14 @@ pub(crate) mod encode_message;
 pub(crate) mod estimate_fee;
 pub(crate) mod send_message;
 
+mod claim_rewards;
 mod init_bridge;
 mod register_parachain;
 mod reinit_bridge;
 mod relay_headers;
 mod relay_headers_and_messages;
 mod relay_messages;
+mod relay_parachains;
 mod resubmit_transactions;
 
 /// Parse relay CLI args.
@@ -57,6 +59,11 @@ pub enum Command {
 	/// Ties up to `Messages` pallets on both chains and starts relaying messages.
 	/// Requires the header relay to be already running.
 	RelayMessages(relay_messages::RelayMessages),
+	/// Start parachain heads relay between two chains.
+	///
+	/// The on-chain bridge components (GRANDPA and parachains pallets) should have been already
+	/// initialized and the headers relay of the source relay chain should be running.
+	RelayParachains(relay_parachains::RelayParachains),
 	/// Start headers and messages relay between two Substrate chains.
 	///
 	/// This high-level relay internally starts four low-level relays: two `RelayHeaders`
@@ -81,6 +88,12 @@ pub enum Command {
 	SendMessage(send_message::SendMessage),
 	/// Estimate Delivery and Dispatch Fee required for message submission to messages pallet.
 	EstimateFee(estimate_fee::EstimateFee),
+	/// Claim relayer rewards.
+	///
+	/// Claims rewards that have been accumulated by the source chain `Messages` pallet for
+	/// delivering messages over given lane. Rewards are only accumulated if the pallet is
+	/// configured to do so.
+	ClaimRewards(claim_rewards::ClaimRewards),
 	/// Resubmit transactions with increased tip if they are stalled.
 	ResubmitTransactions(resubmit_transactions::ResubmitTransactions),
 	/// Register parachain.
@@ -95,6 +108,7 @@ impl Command {
 		match self {
 			Self::RelayHeaders(_) |
 			Self::RelayMessages(_) |
+			Self::RelayParachains(_) |
 			Self::RelayHeadersAndMessages(_) |
 			Self::InitBridge(_) => {
 				initialize_relay();
@@ -111,11 +125,13 @@ impl Command {
 		match self {
 			Self::RelayHeaders(arg) => arg.run().await?,
 			Self::RelayMessages(arg) => arg.run().await?,
+			Self::RelayParachains(arg) => arg.run().await?,
 			Self::RelayHeadersAndMessages(arg) => arg.run().await?,
 			Self::InitBridge(arg) => arg.run().await?,
 			Self::ReinitBridge(arg) => arg.run().await?,
 			Self::SendMessage(arg) => arg.run().await?,
 			Self::EstimateFee(arg) => arg.run().await?,
+			Self::ClaimRewards(arg) => arg.run().await?,
 			Self::ResubmitTransactions(arg) => arg.run().await?,
 			Self::RegisterParachain(arg) => arg.run().await?,
 		}
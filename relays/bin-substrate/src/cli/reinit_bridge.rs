@@ -24,6 +24,7 @@ use crate::{
 		TargetConnectionParams, TargetSigningParams,
 	},
 };
+use bp_header_chain::find_grandpa_authorities_forced_change;
 use bp_runtime::Chain;
 use codec::Encode;
 use finality_relay::{SourceClient, SourceHeader};
@@ -42,7 +43,7 @@ use substrate_relay_helper::{
 	finality::{
 		source::{SubstrateFinalityProof, SubstrateFinalitySource},
 		target::SubstrateFinalityTarget,
-		SubstrateFinalitySyncPipeline,
+		FinalitySyncPipelineAdapter, SubstrateFinalitySyncPipeline,
 	},
 	messages_source::read_client_state,
 	TransactionParams,
@@ -90,6 +91,20 @@ macro_rules! select_bridge {
 					)
 				}
 
+				fn submit_forced_change_call(
+					forced_change: ForcedChangeAndProof<Finality>,
+				) -> runtime::Call {
+					let (forced_change_header, ancestry, (header, proof)) = forced_change;
+					runtime::Call::BridgeKusamaGrandpa(
+						runtime::BridgeKusamaGrandpaCall::enact_forced_change(
+							Box::new(forced_change_header.into_inner()),
+							ancestry.into_iter().map(|header| header.into_inner()).collect(),
+							Box::new(header.into_inner()),
+							proof,
+						),
+					)
+				}
+
 				fn set_pallet_operation_mode_call(operational: bool) -> runtime::Call {
 					runtime::Call::BridgeKusamaGrandpa(
 						runtime::BridgeKusamaGrandpaCall::set_operational(operational),
@@ -119,6 +134,20 @@ macro_rules! select_bridge {
 					)
 				}
 
+				fn submit_forced_change_call(
+					forced_change: ForcedChangeAndProof<Finality>,
+				) -> runtime::Call {
+					let (forced_change_header, ancestry, (header, proof)) = forced_change;
+					runtime::Call::BridgePolkadotGrandpa(
+						runtime::BridgePolkadotGrandpaCall::enact_forced_change(
+							Box::new(forced_change_header.into_inner()),
+							ancestry.into_iter().map(|header| header.into_inner()).collect(),
+							Box::new(header.into_inner()),
+							proof,
+						),
+					)
+				}
+
 				fn set_pallet_operation_mode_call(operational: bool) -> runtime::Call {
 					runtime::Call::BridgePolkadotGrandpa(
 						runtime::BridgePolkadotGrandpaCall::set_operational(operational),
@@ -175,124 +204,177 @@ impl ReinitBridge {
 				current_number,
 			);
 
-			// prepare list of mandatory headers from the range `(current_number; target_number]`
-			let headers_to_submit = find_mandatory_headers_in_range(
-				&finality_source,
-				(current_number + 1, target_number),
-			)
-			.await?;
-			let latest_mandatory_header_number = headers_to_submit.last().map(|(h, _)| h.number());
-			log::info!(
-				target: "bridge",
-				"Missing {} mandatory {} headers at {}",
-				headers_to_submit.len(),
-				Source::NAME,
-				Target::NAME,
-			);
-
-			// split all mandatory headers into batches
-			let headers_batches =
-				make_mandatory_headers_batches::<Finality, _>(headers_to_submit, |(_, proof)| {
-					// we don't have an access to the Kusama/Polkadot chain runtimes here, so we'll
-					// be using Millau weights. It isn't super-critical, unless real weights are
-					// magnitude higher or so
-					pallet_bridge_grandpa::weights::MillauWeight::<millau_runtime::Runtime>::submit_finality_proof(
-						proof.commit.precommits.len().try_into().unwrap_or(u32::MAX),
-						proof.votes_ancestries.len().try_into().unwrap_or(u32::MAX),
-					)
-				});
-			log::info!(
-				target: "bridge",
-				"We're going to submit {} transactions to {} node",
-				headers_batches.len(),
-				Target::NAME,
-			);
-
-			// each batch is submitted as a separate transaction
 			let signer_account_id: AccountIdOf<Target> = transaction_params.signer.public().into();
 			let genesis_hash = *target_client.genesis_hash();
 			let (spec_version, transaction_version) =
 				target_client.simple_runtime_version().await?;
-			let last_batch_index = headers_batches.len() - 1;
-			for (i, headers_batch) in headers_batches.into_iter().enumerate() {
-				let is_last_batch = i == last_batch_index;
-				let expected_number =
-					headers_batch.last().expect("all batches are non-empty").0.number();
-				let transaction_params = transaction_params.clone();
+
+			// headers that are signalling forced authority set changes can't be submitted using
+			// `submit_finality_proof` call. So if there's such header in the range, we submit all
+			// mandatory headers before it, then enact the forced change and then restart from the
+			// header that has enacted the change
+			let mut current_number = current_number;
+			loop {
+				// prepare list of mandatory headers from the range `(current_number;
+				// target_number]`
+				let (headers_to_submit, forced_change) =
+					find_mandatory_headers_in_range::<Finality, _>(
+						&finality_source,
+						(current_number + 1, target_number),
+					)
+					.await?;
+				let is_last_range = forced_change.is_none();
 				log::info!(
 					target: "bridge",
-					"Going to submit transaction that updates best {} header at {} to {}",
+					"Missing {} mandatory {} headers at {}{}",
+					headers_to_submit.len(),
 					Source::NAME,
 					Target::NAME,
-					expected_number,
+					match forced_change {
+						Some((ref forced_change_header, _, _)) => format!(
+							" before header {} that forces authority set change",
+							forced_change_header.number(),
+						),
+						None => String::new(),
+					},
+				);
+
+				// split all mandatory headers into batches
+				let headers_batches = make_mandatory_headers_batches::<Finality, _>(
+					headers_to_submit,
+					|(_, proof)| {
+						// we don't have an access to the Kusama/Polkadot chain runtimes here, so
+						// we'll be using Millau weights. It isn't super-critical, unless real
+						// weights are magnitude higher or so
+						pallet_bridge_grandpa::weights::MillauWeight::<millau_runtime::Runtime>::submit_finality_proof(
+							proof.commit.precommits.len().try_into().unwrap_or(u32::MAX),
+							proof.votes_ancestries.len().try_into().unwrap_or(u32::MAX),
+						)
+					},
 				);
 
-				// prepare `batch_all` call
-				let mut batch_calls = Vec::with_capacity(headers_batch.len() + 2);
-				// the first call is always resumes pallet operation
-				batch_calls.push(set_pallet_operation_mode_call(true));
-				// followed by submit-finality-proofs calls
-				for header_and_proof in headers_batch {
-					batch_calls.push(submit_finality_proof_call(header_and_proof));
+				// every transaction is represented by its call, expected best finalized header
+				// number after the transaction and expected pallet operating mode
+				let mut transactions = Vec::with_capacity(headers_batches.len() + 1);
+				let last_batch_index = headers_batches.len().saturating_sub(1);
+				for (i, headers_batch) in headers_batches.into_iter().enumerate() {
+					let is_last_transaction = is_last_range && i == last_batch_index;
+					let expected_number =
+						headers_batch.last().expect("all batches are non-empty").0.number();
+
+					// prepare `batch_all` call
+					let mut batch_calls = Vec::with_capacity(headers_batch.len() + 2);
+					// the first call is always resumes pallet operation
+					batch_calls.push(set_pallet_operation_mode_call(true));
+					// followed by submit-finality-proofs calls
+					for header_and_proof in headers_batch {
+						batch_calls.push(submit_finality_proof_call(header_and_proof));
+					}
+					// if it isn't the last batch, we shall halt pallet again
+					if !is_last_transaction {
+						batch_calls.push(set_pallet_operation_mode_call(false));
+					}
+					transactions.push((
+						batch_all_call(batch_calls),
+						expected_number,
+						is_last_transaction,
+					));
 				}
-				// if it isn't the last batch, we shall halt pallet again
-				if !is_last_batch {
-					batch_calls.push(set_pallet_operation_mode_call(false));
+				match forced_change {
+					// forced changes are accepted by the halted pallet, so there's no need to
+					// resume it here
+					Some(forced_change) => {
+						let expected_number = (forced_change.2).0.number();
+						transactions.push((
+							submit_forced_change_call(forced_change),
+							expected_number,
+							false,
+						));
+					},
+					// if there are no mandatory headers left, we still need to resume the pallet
+					None if transactions.is_empty() => {
+						transactions.push((
+							set_pallet_operation_mode_call(true),
+							current_number,
+							true,
+						));
+					},
+					None => (),
 				}
-				let submit_batch_call = batch_all_call(batch_calls);
-
-				let batch_transaction_events = target_client
-					.submit_and_watch_signed_extrinsic(
-						signer_account_id.clone(),
-						move |best_block_id, transaction_nonce| {
-							Ok(Bytes(
-								Target::sign_transaction(SignParam {
-									spec_version,
-									transaction_version,
-									genesis_hash,
-									signer: transaction_params.signer.clone(),
-									era: TransactionEra::new(
-										best_block_id,
-										transaction_params.mortality,
-									),
-									unsigned: UnsignedTransaction::new(
-										submit_batch_call.into(),
-										transaction_nonce,
-									),
-								})?
-								.encode(),
-							))
-						},
-					)
-					.await?;
-				wait_until_transaction_is_finalized::<Target>(batch_transaction_events).await?;
+				log::info!(
+					target: "bridge",
+					"We're going to submit {} transactions to {} node",
+					transactions.len(),
+					Target::NAME,
+				);
 
-				// verify that the best finalized header at target has been updated
-				let current_number =
-					best_source_block_number_at_target::<Finality>(&target_client).await?;
-				if current_number != expected_number {
-					return Err(anyhow::format_err!(
-						"Transaction has failed to update best {} header at {} to {}. It is {}",
+				// each batch is submitted as a separate transaction
+				for (submit_call, expected_number, is_operational) in transactions {
+					let transaction_params = transaction_params.clone();
+					log::info!(
+						target: "bridge",
+						"Going to submit transaction that updates best {} header at {} to {}",
 						Source::NAME,
 						Target::NAME,
 						expected_number,
-						current_number,
-					))
+					);
+
+					let transaction_events = target_client
+						.submit_and_watch_signed_extrinsic(
+							signer_account_id.clone(),
+							move |best_block_id, transaction_nonce| {
+								Ok(Bytes(
+									Target::sign_transaction(SignParam {
+										spec_version,
+										transaction_version,
+										genesis_hash,
+										signer: transaction_params.signer.clone(),
+										era: TransactionEra::new(
+											best_block_id,
+											transaction_params.mortality,
+										),
+										unsigned: UnsignedTransaction::new(
+											submit_call.into(),
+											transaction_nonce,
+										),
+									})?
+									.encode(),
+								))
+							},
+						)
+						.await?;
+					wait_until_transaction_is_finalized::<Target>(transaction_events).await?;
+
+					// verify that the best finalized header at target has been updated
+					current_number =
+						best_source_block_number_at_target::<Finality>(&target_client).await?;
+					if current_number != expected_number {
+						return Err(anyhow::format_err!(
+							"Transaction has failed to update best {} header at {} to {}. It is {}",
+							Source::NAME,
+							Target::NAME,
+							expected_number,
+							current_number,
+						))
+					}
+
+					// verify that the pallet is still halted (or operational if it is the last
+					// transaction)
+					ensure_pallet_operating_mode(&finality_target, is_operational).await?;
 				}
 
-				// verify that the pallet is still halted (or operational if it is the last batch)
-				ensure_pallet_operating_mode(&finality_target, is_last_batch).await?;
+				if is_last_range {
+					break
+				}
 			}
 
-			if let Some(latest_mandatory_header_number) = latest_mandatory_header_number {
-				log::info!(
-					target: "bridge",
-					"Successfully updated best {} header at {} to {}. Pallet is now operational",
-					Source::NAME,
-					Target::NAME,
-					latest_mandatory_header_number,
-				);
-			}
+			log::info!(
+				target: "bridge",
+				"Successfully updated best {} header at {} to {}. Pallet is now operational",
+				Source::NAME,
+				Target::NAME,
+				current_number,
+			);
 
 			Ok(())
 		})
@@ -306,6 +388,13 @@ type HeaderAndProof<P> = (
 );
 /// Vector of mandatory headers and their finality proofs.
 type HeadersAndProofs<P> = Vec<HeaderAndProof<P>>;
+/// Header that signals forced authority set change, followed by all headers between it and the
+/// header, finalized by the new authority set, and the finalized header with its finality proof.
+type ForcedChangeAndProof<P> = (
+	SyncHeader<HeaderOf<<P as SubstrateFinalitySyncPipeline>::SourceChain>>,
+	Vec<SyncHeader<HeaderOf<<P as SubstrateFinalitySyncPipeline>::SourceChain>>>,
+	HeaderAndProof<P>,
+);
 
 /// Returns best finalized source header number known to the bridge GRANDPA pallet at the target
 /// chain.
@@ -342,14 +431,33 @@ async fn ensure_pallet_operating_mode<P: SubstrateFinalitySyncPipeline>(
 }
 
 /// Returns list of all mandatory headers in given range.
-async fn find_mandatory_headers_in_range<P: SubstrateFinalitySyncPipeline>(
-	finality_source: &SubstrateFinalitySource<P>,
+///
+/// If there's a header that signals forced authority set change in the range, the search stops
+/// at this header. All mandatory headers before it are returned, along with the forced change
+/// header, the first justified header that is finalized by the new authority set and all headers
+/// between them.
+async fn find_mandatory_headers_in_range<P, SC>(
+	finality_source: &SC,
 	range: (BlockNumberOf<P::SourceChain>, BlockNumberOf<P::SourceChain>),
-) -> anyhow::Result<HeadersAndProofs<P>> {
+) -> anyhow::Result<(HeadersAndProofs<P>, Option<ForcedChangeAndProof<P>>)>
+where
+	P: SubstrateFinalitySyncPipeline,
+	SC: SourceClient<FinalitySyncPipelineAdapter<P>, Error = SubstrateError>,
+{
 	let mut mandatory_headers = Vec::new();
 	let mut current = range.0;
 	while current <= range.1 {
 		let (header, proof) = finality_source.header_and_finality_proof(current).await?;
+		if let Some((_, forced_change)) = find_grandpa_authorities_forced_change(&*header) {
+			let (ancestry, finality_target) = find_forced_change_finality_target::<P, _>(
+				finality_source,
+				current,
+				current + forced_change.delay,
+				range.1,
+			)
+			.await?;
+			return Ok((mandatory_headers, Some((header, ancestry, finality_target))))
+		}
 		if header.is_mandatory() {
 			match proof {
 				Some(proof) => mandatory_headers.push((header, proof)),
@@ -365,7 +473,43 @@ async fn find_mandatory_headers_in_range<P: SubstrateFinalitySyncPipeline>(
 		current += One::one();
 	}
 
-	Ok(mandatory_headers)
+	Ok((mandatory_headers, None))
+}
+
+/// Returns first header in the range `[enacted_at; range_end]` that has a justification, along
+/// with all headers between the forced change header and this header.
+///
+/// The header is expected to be finalized by the authority set, enacted by the forced change.
+/// Headers between the forced change header and the finality target are required by the pallet to
+/// prove that the finality target is a descendant of the forced change header.
+async fn find_forced_change_finality_target<P, SC>(
+	finality_source: &SC,
+	forced_change_at: BlockNumberOf<P::SourceChain>,
+	enacted_at: BlockNumberOf<P::SourceChain>,
+	range_end: BlockNumberOf<P::SourceChain>,
+) -> anyhow::Result<(Vec<SyncHeader<HeaderOf<P::SourceChain>>>, HeaderAndProof<P>)>
+where
+	P: SubstrateFinalitySyncPipeline,
+	SC: SourceClient<FinalitySyncPipelineAdapter<P>, Error = SubstrateError>,
+{
+	let mut ancestry = Vec::new();
+	let mut current = forced_change_at + One::one();
+	while current <= range_end {
+		let (header, proof) = finality_source.header_and_finality_proof(current).await?;
+		match proof {
+			Some(proof) if current >= enacted_at => return Ok((ancestry, (header, proof))),
+			_ => ancestry.push(header),
+		}
+
+		current += One::one();
+	}
+
+	Err(anyhow::format_err!(
+		"Missing GRANDPA justification for {} headers in range [{}; {}], finalized by forced authority set",
+		P::SourceChain::NAME,
+		enacted_at,
+		range_end,
+	))
 }
 
 /// Given list of mandatory headers, prepare batches of headers, so that every batch may fit into
@@ -428,11 +572,83 @@ fn make_mandatory_headers_batches<
 mod tests {
 	use super::*;
 	use crate::cli::{RuntimeVersionType, SourceRuntimeVersionParams, TargetRuntimeVersionParams};
+	use async_trait::async_trait;
 	use bp_header_chain::justification::GrandpaJustification;
 	use bp_test_utils::{make_default_justification, test_header};
 	use relay_polkadot_client::Polkadot;
+	use sp_finality_grandpa::{ConsensusLog, ScheduledChange, GRANDPA_ENGINE_ID};
 	use sp_runtime::{traits::Header as _, DigestItem};
 
+	type TestHeaderAndProof =
+		(SyncHeader<bp_kusama::Header>, Option<GrandpaJustification<bp_kusama::Header>>);
+
+	#[derive(Clone)]
+	struct TestFinalitySource {
+		headers: Vec<TestHeaderAndProof>,
+	}
+
+	#[async_trait]
+	impl relay_utils::relay_loop::Client for TestFinalitySource {
+		type Error = SubstrateError;
+
+		async fn reconnect(&mut self) -> Result<(), SubstrateError> {
+			Ok(())
+		}
+	}
+
+	#[async_trait]
+	impl SourceClient<FinalitySyncPipelineAdapter<KusamaFinalityToPolkadot>> for TestFinalitySource {
+		type FinalityProofsStream =
+			futures::stream::Pending<GrandpaJustification<bp_kusama::Header>>;
+
+		async fn best_finalized_block_number(
+			&self,
+		) -> Result<bp_kusama::BlockNumber, SubstrateError> {
+			Ok(self.headers.len() as _)
+		}
+
+		async fn header_and_finality_proof(
+			&self,
+			number: bp_kusama::BlockNumber,
+		) -> Result<TestHeaderAndProof, SubstrateError> {
+			self.headers
+				.get(number as usize - 1)
+				.cloned()
+				.ok_or_else(|| SubstrateError::Custom(format!("Missing header {}", number)))
+		}
+
+		async fn finality_proofs(&self) -> Result<Self::FinalityProofsStream, SubstrateError> {
+			Ok(futures::stream::pending())
+		}
+	}
+
+	/// Returns source client with the chain of headers `1..=count`. Given headers are justified.
+	fn make_finality_source(
+		count: bp_kusama::BlockNumber,
+		justified: &[bp_kusama::BlockNumber],
+		digests: &[(bp_kusama::BlockNumber, ConsensusLog<bp_kusama::BlockNumber>)],
+	) -> TestFinalitySource {
+		let mut headers: Vec<TestHeaderAndProof> = Vec::with_capacity(count as usize);
+		for number in 1..=count {
+			let mut header: bp_kusama::Header = test_header(number);
+			if let Some((parent, _)) = headers.last() {
+				header.set_parent_hash(parent.hash());
+			}
+			for (_, log) in digests.iter().filter(|(at, _)| *at == number) {
+				header.digest_mut().push(DigestItem::Consensus(GRANDPA_ENGINE_ID, log.encode()));
+			}
+
+			let justification =
+				justified.contains(&number).then(|| make_default_justification(&header));
+			headers.push((header.into(), justification));
+		}
+		TestFinalitySource { headers }
+	}
+
+	fn forced_change_log(delay: bp_kusama::BlockNumber) -> ConsensusLog<bp_kusama::BlockNumber> {
+		ConsensusLog::ForcedChange(0, ScheduledChange { next_authorities: vec![], delay })
+	}
+
 	fn make_header_and_justification(
 		i: u32,
 		size: u32,
@@ -554,4 +770,83 @@ mod tests {
 		);
 		assert_eq!(batches.into_iter().map(|x| x.len()).collect::<Vec<_>>(), vec![2, 1, 1],);
 	}
+
+	#[test]
+	fn find_mandatory_headers_in_range_returns_forced_change_with_ancestry() {
+		// header#2 schedules regular change, header#3 forces change that is enacted at header#5.
+		// Header#4 is justified by the old authority set, so the first header that is finalized by
+		// the new set is header#6
+		let finality_source = make_finality_source(
+			8,
+			&[2, 4, 6, 8],
+			&[
+				(
+					2,
+					ConsensusLog::ScheduledChange(ScheduledChange {
+						next_authorities: vec![],
+						delay: 0,
+					}),
+				),
+				(3, forced_change_log(2)),
+			],
+		);
+
+		let (mandatory_headers, forced_change) = async_std::task::block_on(
+			find_mandatory_headers_in_range::<KusamaFinalityToPolkadot, _>(
+				&finality_source,
+				(1, 8),
+			),
+		)
+		.unwrap();
+		assert_eq!(
+			mandatory_headers
+				.into_iter()
+				.map(|(header, _)| header.number())
+				.collect::<Vec<_>>(),
+			vec![2],
+		);
+
+		let (forced_change_header, ancestry, (finality_target, _)) = forced_change.unwrap();
+		assert_eq!(forced_change_header.number(), 3);
+		assert_eq!(ancestry.iter().map(|header| header.number()).collect::<Vec<_>>(), vec![4, 5]);
+		assert_eq!(finality_target.number(), 6);
+
+		// the pallet expects that the ancestry connects the finality target to the forced change
+		let mut expected_parent_hash = forced_change_header.hash();
+		for header in ancestry.iter().chain(std::iter::once(&finality_target)) {
+			assert_eq!(*header.parent_hash(), expected_parent_hash);
+			expected_parent_hash = header.hash();
+		}
+	}
+
+	#[test]
+	fn find_mandatory_headers_in_range_returns_forced_change_without_ancestry() {
+		// header#3 forces change that is enacted immediately and is justified by the new set
+		let finality_source = make_finality_source(4, &[3], &[(3, forced_change_log(0))]);
+
+		let (mandatory_headers, forced_change) = async_std::task::block_on(
+			find_mandatory_headers_in_range::<KusamaFinalityToPolkadot, _>(
+				&finality_source,
+				(1, 4),
+			),
+		)
+		.unwrap();
+		assert!(mandatory_headers.is_empty());
+
+		let (forced_change_header, ancestry, (finality_target, _)) = forced_change.unwrap();
+		assert_eq!(forced_change_header.number(), 3);
+		assert!(ancestry.is_empty());
+		assert_eq!(finality_target.number(), 3);
+	}
+
+	#[test]
+	fn find_mandatory_headers_in_range_fails_if_forced_change_is_not_justified() {
+		let finality_source = make_finality_source(6, &[4], &[(3, forced_change_log(2))]);
+
+		assert!(async_std::task::block_on(find_mandatory_headers_in_range::<
+			KusamaFinalityToPolkadot,
+			_,
+		>(&finality_source, (1, 6)))
+		.is_err());
+	}
 }
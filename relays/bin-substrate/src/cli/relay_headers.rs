@@ -34,6 +34,12 @@ pub struct RelayHeaders {
 	/// are relayed.
 	#[structopt(long)]
 	only_mandatory_headers: bool,
+	/// Maximal number of mandatory headers that may be submitted in a single transaction. If
+	/// it is larger than one and several mandatory headers are missing at the target chain, they
+	/// are submitted in batches. It must not exceed the `MaxBatchSize` of the bridge GRANDPA
+	/// pallet at the target chain.
+	#[structopt(long, default_value = "1")]
+	max_mandatory_headers_in_batch: usize,
 	#[structopt(flatten)]
 	source: SourceConnectionParams,
 	#[structopt(flatten)]
@@ -140,6 +146,7 @@ impl RelayHeaders {
 				source_client,
 				target_client,
 				self.only_mandatory_headers,
+				self.max_mandatory_headers_in_batch,
 				target_transactions_params,
 				metrics_params,
 			)
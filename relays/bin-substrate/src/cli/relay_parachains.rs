@@ -0,0 +1,101 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use structopt::StructOpt;
+use strum::{EnumString, EnumVariantNames, VariantNames};
+
+use relay_substrate_client::{metrics::BestParachainHeadMetric, Chain};
+use relay_utils::metrics::{GlobalMetrics, StandaloneMetric};
+use substrate_relay_helper::parachains::SubstrateParachainsPipeline;
+
+use crate::cli::{
+	PrometheusParams, SourceConnectionParams, TargetConnectionParams, TargetSigningParams,
+};
+
+/// Start parachain heads relayer process.
+#[derive(StructOpt)]
+pub struct RelayParachains {
+	/// A bridge instance to relay parachains heads for.
+	#[structopt(possible_values = RelayParachainsBridge::VARIANTS, case_insensitive = true)]
+	bridge: RelayParachainsBridge,
+	#[structopt(flatten)]
+	source: SourceConnectionParams,
+	#[structopt(flatten)]
+	target: TargetConnectionParams,
+	#[structopt(flatten)]
+	target_sign: TargetSigningParams,
+	#[structopt(flatten)]
+	prometheus_params: PrometheusParams,
+}
+
+#[derive(Debug, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab_case")]
+/// Parachain heads relay bridge.
+pub enum RelayParachainsBridge {
+	RialtoToMillau,
+}
+
+macro_rules! select_bridge {
+	($bridge: expr, $generic: tt) => {
+		match $bridge {
+			RelayParachainsBridge::RialtoToMillau => {
+				type Source = relay_rialto_client::Rialto;
+				type Target = relay_millau_client::Millau;
+				type Parachains =
+					crate::chains::rialto_parachains_to_millau::RialtoParachainsToMillau;
+
+				$generic
+			},
+		}
+	};
+}
+
+impl RelayParachains {
+	/// Run the command.
+	pub async fn run(self) -> anyhow::Result<()> {
+		select_bridge!(self.bridge, {
+			let source_client = self.source.to_client::<Source>().await?;
+			let target_client = self.target.to_client::<Target>().await?;
+			let target_transactions_mortality = self.target_sign.target_transactions_mortality;
+			let target_sign = self.target_sign.to_keypair::<Target>()?;
+
+			let metrics_params: relay_utils::metrics::MetricsParams = self.prometheus_params.into();
+			GlobalMetrics::new()?.register_and_spawn(&metrics_params.registry)?;
+			for parachain in Parachains::PARACHAINS {
+				BestParachainHeadMetric::new(
+					target_client.clone(),
+					Parachains::BEST_PARACHAIN_HEAD_METHOD,
+					*parachain,
+					&format!("{}_{}_parachain_{}", Target::NAME, Source::NAME, parachain.0),
+				)?
+				.register_and_spawn(&metrics_params.registry)?;
+			}
+
+			let target_transactions_params = substrate_relay_helper::TransactionParams {
+				signer: target_sign,
+				mortality: target_transactions_mortality,
+			};
+
+			substrate_relay_helper::parachains::run::<Parachains>(
+				source_client,
+				target_client,
+				target_transactions_params,
+				metrics_params,
+			)
+			.await
+		})
+	}
+}
@@ -83,6 +83,20 @@ pub enum BridgePolkadotGrandpaCall {
 	initialize(bp_header_chain::InitializationData<<PolkadotLike as Chain>::Header>),
 	#[codec(index = 3)]
 	set_operational(bool),
+	#[codec(index = 4)]
+	enact_forced_change(
+		Box<<PolkadotLike as Chain>::Header>,
+		Vec<<PolkadotLike as Chain>::Header>,
+		Box<<PolkadotLike as Chain>::Header>,
+		bp_header_chain::justification::GrandpaJustification<<PolkadotLike as Chain>::Header>,
+	),
+	#[codec(index = 5)]
+	submit_finality_proofs(
+		Vec<(
+			Box<<PolkadotLike as Chain>::Header>,
+			bp_header_chain::justification::GrandpaJustification<<PolkadotLike as Chain>::Header>,
+		)>,
+	),
 }
 
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, TypeInfo)]
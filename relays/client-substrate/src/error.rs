@@ -54,6 +54,9 @@ pub enum Error {
 	/// The bridge pallet is halted and all transactions will be rejected.
 	#[error("Bridge pallet is halted.")]
 	BridgePalletIsHalted,
+	/// The bridge messages lane is halted and all lane transactions will be rejected.
+	#[error("Bridge messages lane {0:?} is halted.")]
+	BridgeLaneIsHalted(bp_messages::LaneId),
 	/// An error has happened when we have tried to parse storage proof.
 	#[error("Error when parsing storage proof: {0:?}.")]
 	StorageProofError(bp_runtime::StorageProofError),
@@ -0,0 +1,127 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{chain::Chain, client::Client, error::Error};
+
+use async_trait::async_trait;
+use bp_polkadot_core::parachains::{BestParaHeadInfo, ParaId};
+use codec::{Decode, Encode};
+use relay_utils::metrics::{
+	metric_name, register, Gauge, Metric, PrometheusError, Registry, StandaloneMetric, U64,
+};
+use sp_core::Bytes;
+use std::time::Duration;
+
+/// Best parachain head update interval (in blocks).
+const UPDATE_INTERVAL_IN_BLOCKS: u32 = 5;
+
+/// Metric that represents the best head of the bridged parachain, known to the chain runtime.
+///
+/// The head is read using the runtime API method that returns `Option<BestParaHeadInfo>` for
+/// the given parachain. Two values are exported: number of the relay chain block, where the head
+/// has been read and the number of the parachain header (if it is known to the runtime).
+#[derive(Debug)]
+pub struct BestParachainHeadMetric<C: Chain> {
+	client: Client<C>,
+	method: &'static str,
+	parachain: ParaId,
+	at_relay_block_number: Gauge<U64>,
+	head_number: Gauge<U64>,
+}
+
+impl<C: Chain> Clone for BestParachainHeadMetric<C> {
+	fn clone(&self) -> Self {
+		BestParachainHeadMetric {
+			client: self.client.clone(),
+			method: self.method,
+			parachain: self.parachain,
+			at_relay_block_number: self.at_relay_block_number.clone(),
+			head_number: self.head_number.clone(),
+		}
+	}
+}
+
+impl<C: Chain> BestParachainHeadMetric<C> {
+	/// Create new metric instance.
+	///
+	/// The `method` is the name of the runtime API method that returns the best head of given
+	/// parachain. Names of metrics are prefixed with `prefix`.
+	pub fn new(
+		client: Client<C>,
+		method: &'static str,
+		parachain: ParaId,
+		prefix: &str,
+	) -> Result<Self, PrometheusError> {
+		Ok(BestParachainHeadMetric {
+			client,
+			method,
+			parachain,
+			at_relay_block_number: Gauge::new(
+				metric_name(Some(prefix), "best_parachain_head_at_relay_block_number"),
+				format!(
+					"Relay chain block, where the best head of parachain {} was read",
+					parachain.0
+				),
+			)?,
+			head_number: Gauge::new(
+				metric_name(Some(prefix), "best_parachain_head_number"),
+				format!("Number of the best known header of parachain {}", parachain.0),
+			)?,
+		})
+	}
+
+	/// Read the best parachain head information from the runtime.
+	async fn best_parachain_head(&self) -> Result<Option<BestParaHeadInfo>, Error> {
+		let encoded_best_head = self
+			.client
+			.state_call(self.method.into(), Bytes(self.parachain.encode()), None)
+			.await?;
+		Decode::decode(&mut &encoded_best_head.0[..]).map_err(Error::ResponseParseFailed)
+	}
+}
+
+impl<C: Chain> Metric for BestParachainHeadMetric<C> {
+	fn register(&self, registry: &Registry) -> Result<(), PrometheusError> {
+		register(self.at_relay_block_number.clone(), registry)?;
+		register(self.head_number.clone(), registry)?;
+		Ok(())
+	}
+}
+
+#[async_trait]
+impl<C: Chain> StandaloneMetric for BestParachainHeadMetric<C> {
+	fn update_interval(&self) -> Duration {
+		C::AVERAGE_BLOCK_INTERVAL * UPDATE_INTERVAL_IN_BLOCKS
+	}
+
+	async fn update(&self) {
+		let best_head = self.best_parachain_head().await;
+		relay_utils::metrics::set_gauge_value(
+			&self.at_relay_block_number,
+			best_head.as_ref().map(|best_head| {
+				best_head.as_ref().map(|best_head| best_head.at_relay_block_number as u64)
+			}),
+		);
+		relay_utils::metrics::set_gauge_value(
+			&self.head_number,
+			best_head.as_ref().map(|best_head| {
+				best_head
+					.as_ref()
+					.and_then(|best_head| best_head.head_number.map(|number| number as u64))
+			}),
+		);
+	}
+}
@@ -16,8 +16,10 @@
 
 //! Contains several Substrate-specific metrics that may be exposed by relay.
 
+pub use best_parachain_head::BestParachainHeadMetric;
 pub use float_storage_value::{FixedU128OrOne, FloatStorageValue, FloatStorageValueMetric};
 pub use storage_proof_overhead::StorageProofOverheadMetric;
 
+mod best_parachain_head;
 mod float_storage_value;
 mod storage_proof_overhead;
@@ -54,6 +54,8 @@ impl<Header: HeaderT> FinalitySourceHeader<Header::Hash, Header::Number> for Syn
 	}
 
 	fn is_mandatory(&self) -> bool {
+		// delayed changes are enacted by the bridge pallet once it sees header finalized by the new
+		// authorities set, so only the header that schedules the change is mandatory
 		find_grandpa_authorities_scheduled_change(&self.0).is_some()
 	}
 }
@@ -77,6 +77,13 @@ pub enum BridgeGrandpaRococoCall {
 	),
 	#[codec(index = 1)]
 	initialize(bp_header_chain::InitializationData<<PolkadotLike as Chain>::Header>),
+	#[codec(index = 5)]
+	submit_finality_proofs(
+		Vec<(
+			Box<<PolkadotLike as Chain>::Header>,
+			bp_header_chain::justification::GrandpaJustification<<PolkadotLike as Chain>::Header>,
+		)>,
+	),
 }
 
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, TypeInfo)]
@@ -61,6 +61,11 @@ pub struct FinalitySyncParams {
 	pub stall_timeout: Duration,
 	/// If true, only mandatory headers are relayed.
 	pub only_mandatory_headers: bool,
+	/// Maximal number of mandatory headers that may be submitted in a single transaction.
+	///
+	/// If it is larger than one and there are several mandatory headers missing at the target
+	/// node, they're submitted in a batch. Otherwise, mandatory headers are submitted one by one.
+	pub max_mandatory_headers_in_batch: usize,
 }
 
 /// Source client used in finality synchronization loop.
@@ -97,6 +102,13 @@ pub trait TargetClient<P: FinalitySyncPipeline>: RelayClient {
 		header: P::Header,
 		proof: P::FinalityProof,
 	) -> Result<(), Self::Error>;
+
+	/// Submit finality proofs of multiple headers in a single transaction. Headers are ordered
+	/// by their numbers.
+	async fn submit_finality_proofs(
+		&self,
+		headers_and_proofs: HeadersAndProofs<P>,
+	) -> Result<(), Self::Error>;
 }
 
 /// Return prefix that will be used by default to expose Prometheus metrics of the finality proofs
@@ -135,6 +147,9 @@ pub async fn run<P: FinalitySyncPipeline>(
 		.await
 }
 
+/// Headers and their finality proofs. Ordered by header number.
+pub type HeadersAndProofs<P> =
+	Vec<(<P as FinalitySyncPipeline>::Header, <P as FinalitySyncPipeline>::FinalityProof)>;
 /// Unjustified headers container. Ordered by header number.
 pub(crate) type UnjustifiedHeaders<H> = Vec<H>;
 /// Finality proofs container. Ordered by target header number.
@@ -362,22 +377,44 @@ where
 	)
 	.await?
 	{
-		Some((header, justification)) => {
-			let new_transaction =
-				Transaction { time: Instant::now(), submitted_header_number: header.number() };
-
-			log::debug!(
-				target: "bridge",
-				"Going to submit finality proof of {} header #{:?} to {}",
-				P::SOURCE_NAME,
-				new_transaction.submitted_header_number,
-				P::TARGET_NAME,
-			);
-
-			target_client
-				.submit_finality_proof(header, justification)
-				.await
-				.map_err(Error::Target)?;
+		Some(mut headers_and_proofs) => {
+			let submitted_header_number = match headers_and_proofs.last() {
+				Some((header, _)) => header.number(),
+				None => return Ok(None),
+			};
+			let new_transaction = Transaction { time: Instant::now(), submitted_header_number };
+
+			if headers_and_proofs.len() > 1 {
+				log::debug!(
+					target: "bridge",
+					"Going to submit finality proofs of {} {} headers up to #{:?} to {}",
+					headers_and_proofs.len(),
+					P::SOURCE_NAME,
+					new_transaction.submitted_header_number,
+					P::TARGET_NAME,
+				);
+
+				target_client
+					.submit_finality_proofs(headers_and_proofs)
+					.await
+					.map_err(Error::Target)?;
+			} else {
+				log::debug!(
+					target: "bridge",
+					"Going to submit finality proof of {} header #{:?} to {}",
+					P::SOURCE_NAME,
+					new_transaction.submitted_header_number,
+					P::TARGET_NAME,
+				);
+
+				let (header, justification) = headers_and_proofs
+					.pop()
+					.expect("we have checked that the vec is not empty; qed");
+				target_client
+					.submit_finality_proof(header, justification)
+					.await
+					.map_err(Error::Target)?;
+			}
 			Ok(Some(new_transaction))
 		},
 		None => Ok(None),
@@ -392,7 +429,7 @@ pub(crate) async fn select_header_to_submit<P, SC, TC>(
 	best_number_at_source: P::Number,
 	best_number_at_target: P::Number,
 	sync_params: &FinalitySyncParams,
-) -> Result<Option<(P::Header, P::FinalityProof)>, Error<P, SC::Error, TC::Error>>
+) -> Result<Option<HeadersAndProofs<P>>, Error<P, SC::Error, TC::Error>>
 where
 	P: FinalitySyncPipeline,
 	SC: SourceClient<P>,
@@ -413,11 +450,11 @@ where
 		target_client,
 		best_number_at_source,
 		best_number_at_target,
+		sync_params.max_mandatory_headers_in_batch,
 	)
 	.await?;
 	let (mut unjustified_headers, mut selected_finality_proof) = match selected_finality_proof {
-		SelectedFinalityProof::Mandatory(header, finality_proof) =>
-			return Ok(Some((header, finality_proof))),
+		SelectedFinalityProof::Mandatory(mandatory_headers) => return Ok(Some(mandatory_headers)),
 		_ if sync_params.only_mandatory_headers => {
 			// we are not reading finality proofs from the stream, so eventually it'll break
 			// but we don't care about transient proofs at all, so it is acceptable
@@ -449,7 +486,7 @@ where
 		sync_params.recent_finality_proofs_limit,
 	);
 
-	Ok(selected_finality_proof)
+	Ok(selected_finality_proof.map(|header_and_proof| vec![header_and_proof]))
 }
 
 /// Ensures that both clients are on the same fork.
@@ -470,8 +507,9 @@ async fn ensure_same_fork<P: FinalitySyncPipeline, SC: SourceClient<P>>(
 
 /// Finality proof that has been selected by the `read_missing_headers` function.
 pub(crate) enum SelectedFinalityProof<Header, FinalityProof> {
-	/// Mandatory header and its proof has been selected. We shall submit proof for this header.
-	Mandatory(Header, FinalityProof),
+	/// Mandatory headers and their proofs have been selected. We shall submit proofs for these
+	/// headers.
+	Mandatory(Vec<(Header, FinalityProof)>),
 	/// Regular header and its proof has been selected. We may submit this proof, or proof for
 	/// some better header.
 	Regular(UnjustifiedHeaders<Header>, Header, FinalityProof),
@@ -484,7 +522,10 @@ pub(crate) enum SelectedFinalityProof<Header, FinalityProof> {
 /// If we have found some header with known proof, it is returned.
 /// Otherwise, `SelectedFinalityProof::None` is returned.
 ///
-/// Unless we have found mandatory header, all missing headers are collected and returned.
+/// Unless we have found mandatory header, all missing headers are collected and returned. If
+/// `max_mandatory_headers_in_batch` is larger than one, we continue reading headers after the
+/// first mandatory header, until we have found that many mandatory headers, or have reached the
+/// best source header.
 pub(crate) async fn read_missing_headers<
 	P: FinalitySyncPipeline,
 	SC: SourceClient<P>,
@@ -494,9 +535,11 @@ pub(crate) async fn read_missing_headers<
 	_target_client: &TC,
 	best_number_at_source: P::Number,
 	best_number_at_target: P::Number,
+	max_mandatory_headers_in_batch: usize,
 ) -> Result<SelectedFinalityProof<P::Header, P::FinalityProof>, Error<P, SC::Error, TC::Error>> {
 	let mut unjustified_headers = Vec::new();
 	let mut selected_finality_proof = None;
+	let mut mandatory_headers = Vec::new();
 	let mut header_number = best_number_at_target + One::one();
 	while header_number <= best_number_at_source {
 		let (header, finality_proof) = source_client
@@ -508,9 +551,14 @@ pub(crate) async fn read_missing_headers<
 		match (is_mandatory, finality_proof) {
 			(true, Some(finality_proof)) => {
 				log::trace!(target: "bridge", "Header {:?} is mandatory", header_number);
-				return Ok(SelectedFinalityProof::Mandatory(header, finality_proof))
+				mandatory_headers.push((header, finality_proof));
+				if mandatory_headers.len() >= max_mandatory_headers_in_batch {
+					return Ok(SelectedFinalityProof::Mandatory(mandatory_headers))
+				}
 			},
 			(true, None) => return Err(Error::MissingMandatoryFinalityProof(header.number())),
+			// non-mandatory headers are ignored once we have found the first mandatory header
+			(false, _) if !mandatory_headers.is_empty() => (),
 			(false, Some(finality_proof)) => {
 				log::trace!(target: "bridge", "Header {:?} has persistent finality proof", header_number);
 				unjustified_headers.clear();
@@ -532,6 +580,10 @@ pub(crate) async fn read_missing_headers<
 		selected_finality_proof.as_ref().map(|(header, _)| header),
 	);
 
+	if !mandatory_headers.is_empty() {
+		return Ok(SelectedFinalityProof::Mandatory(mandatory_headers))
+	}
+
 	Ok(match selected_finality_proof {
 		Some((header, proof)) => SelectedFinalityProof::Regular(unjustified_headers, header, proof),
 		None => SelectedFinalityProof::None(unjustified_headers),
@@ -26,7 +26,7 @@ use crate::{
 		TargetClient,
 	},
 	sync_loop_metrics::SyncLoopMetrics,
-	FinalityProof, FinalitySyncPipeline, SourceHeader,
+	FinalityProof, FinalitySyncPipeline, HeadersAndProofs, SourceHeader,
 };
 
 use async_trait::async_trait;
@@ -183,6 +183,19 @@ impl TargetClient<TestFinalitySyncPipeline> for TestTargetClient {
 		data.target_headers.push((header, proof));
 		Ok(())
 	}
+
+	async fn submit_finality_proofs(
+		&self,
+		headers_and_proofs: HeadersAndProofs<TestFinalitySyncPipeline>,
+	) -> Result<(), TestError> {
+		let mut data = self.data.lock();
+		(self.on_method_call)(&mut *data);
+		if let Some((header, _)) = headers_and_proofs.last() {
+			data.target_best_block_id = HeaderId(header.number(), header.hash());
+		}
+		data.target_headers.extend(headers_and_proofs);
+		Ok(())
+	}
 }
 
 fn prepare_test_clients(
@@ -219,6 +232,7 @@ fn test_sync_params() -> FinalitySyncParams {
 		recent_finality_proofs_limit: 1024,
 		stall_timeout: Duration::from_secs(1),
 		only_mandatory_headers: false,
+		max_mandatory_headers_in_batch: 1,
 	}
 }
 
@@ -304,7 +318,7 @@ fn finality_sync_loop_works() {
 fn run_only_mandatory_headers_mode_test(
 	only_mandatory_headers: bool,
 	has_mandatory_headers: bool,
-) -> Option<(TestSourceHeader, TestFinalityProof)> {
+) -> Option<HeadersAndProofs<TestFinalitySyncPipeline>> {
 	let (exit_sender, _) = futures::channel::mpsc::unbounded();
 	let (source_client, target_client) = prepare_test_clients(
 		exit_sender,
@@ -331,6 +345,7 @@ fn run_only_mandatory_headers_mode_test(
 			recent_finality_proofs_limit: 0,
 			stall_timeout: Duration::from_secs(0),
 			only_mandatory_headers,
+			max_mandatory_headers_in_batch: 1,
 		},
 	))
 	.unwrap()
@@ -341,7 +356,7 @@ fn select_header_to_submit_skips_non_mandatory_headers_when_only_mandatory_heade
 	assert_eq!(run_only_mandatory_headers_mode_test(true, false), None);
 	assert_eq!(
 		run_only_mandatory_headers_mode_test(false, false),
-		Some((TestSourceHeader(false, 10, 10), TestFinalityProof(10))),
+		Some(vec![(TestSourceHeader(false, 10, 10), TestFinalityProof(10))]),
 	);
 }
 
@@ -349,11 +364,69 @@ fn select_header_to_submit_skips_non_mandatory_headers_when_only_mandatory_heade
 fn select_header_to_submit_selects_mandatory_headers_when_only_mandatory_headers_are_required() {
 	assert_eq!(
 		run_only_mandatory_headers_mode_test(true, true),
-		Some((TestSourceHeader(true, 8, 8), TestFinalityProof(8))),
+		Some(vec![(TestSourceHeader(true, 8, 8), TestFinalityProof(8))]),
 	);
 	assert_eq!(
 		run_only_mandatory_headers_mode_test(false, true),
-		Some((TestSourceHeader(true, 8, 8), TestFinalityProof(8))),
+		Some(vec![(TestSourceHeader(true, 8, 8), TestFinalityProof(8))]),
+	);
+}
+
+fn run_mandatory_headers_batch_test(
+	max_mandatory_headers_in_batch: usize,
+) -> Option<HeadersAndProofs<TestFinalitySyncPipeline>> {
+	let (exit_sender, _) = futures::channel::mpsc::unbounded();
+	let (source_client, target_client) = prepare_test_clients(
+		exit_sender,
+		|_| false,
+		vec![
+			(6, (TestSourceHeader(false, 6, 6), Some(TestFinalityProof(6)))),
+			(7, (TestSourceHeader(true, 7, 7), Some(TestFinalityProof(7)))),
+			(8, (TestSourceHeader(true, 8, 8), Some(TestFinalityProof(8)))),
+			(9, (TestSourceHeader(false, 9, 9), Some(TestFinalityProof(9)))),
+			(10, (TestSourceHeader(true, 10, 10), Some(TestFinalityProof(10)))),
+		]
+		.into_iter()
+		.collect(),
+	);
+	async_std::task::block_on(select_header_to_submit(
+		&source_client,
+		&target_client,
+		&mut RestartableFinalityProofsStream::from(futures::stream::empty().boxed()),
+		&mut vec![],
+		10,
+		5,
+		&FinalitySyncParams {
+			tick: Duration::from_secs(0),
+			recent_finality_proofs_limit: 0,
+			stall_timeout: Duration::from_secs(0),
+			only_mandatory_headers: false,
+			max_mandatory_headers_in_batch,
+		},
+	))
+	.unwrap()
+}
+
+#[test]
+fn select_header_to_submit_selects_batch_of_mandatory_headers() {
+	assert_eq!(
+		run_mandatory_headers_batch_test(1),
+		Some(vec![(TestSourceHeader(true, 7, 7), TestFinalityProof(7))]),
+	);
+	assert_eq!(
+		run_mandatory_headers_batch_test(2),
+		Some(vec![
+			(TestSourceHeader(true, 7, 7), TestFinalityProof(7)),
+			(TestSourceHeader(true, 8, 8), TestFinalityProof(8)),
+		]),
+	);
+	assert_eq!(
+		run_mandatory_headers_batch_test(10),
+		Some(vec![
+			(TestSourceHeader(true, 7, 7), TestFinalityProof(7)),
+			(TestSourceHeader(true, 8, 8), TestFinalityProof(8)),
+			(TestSourceHeader(true, 10, 10), TestFinalityProof(10)),
+		]),
 	);
 }
 
@@ -20,7 +20,9 @@
 //! to submit all source headers to the target node.
 
 pub use crate::{
-	finality_loop::{metrics_prefix, run, FinalitySyncParams, SourceClient, TargetClient},
+	finality_loop::{
+		metrics_prefix, run, FinalitySyncParams, HeadersAndProofs, SourceClient, TargetClient,
+	},
 	sync_loop_metrics::SyncLoopMetrics,
 };
 
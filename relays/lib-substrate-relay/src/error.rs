@@ -49,6 +49,10 @@ pub enum Error<Hash: Debug + MaybeDisplay, HeaderNumber: Debug + MaybeDisplay> {
 	/// Failed to retrieve GRANDPA authorities at the given header from the source chain.
 	#[error("Failed to retrive {0} GRANDPA authorities set at header {1}: {2:?}")]
 	RetrieveAuthorities(&'static str, Hash, client::Error),
+	/// Failed to retrieve pending GRANDPA authorities set change at the given header from the
+	/// source chain.
+	#[error("Failed to retrieve pending {0} GRANDPA authorities set change at header {1}: {2:?}")]
+	RetrievePendingAuthoritiesChange(&'static str, Hash, client::Error),
 	/// Failed to decode GRANDPA authorities at the given header of the source chain.
 	#[error("Failed to decode {0} GRANDPA authorities set at header {1}: {2:?}")]
 	DecodeAuthorities(&'static str, Hash, codec::Error),
@@ -16,34 +16,56 @@
 
 //! Support of different finality engines, available in Substrate.
 
-use crate::error::Error;
+use crate::{
+	error::Error,
+	finality::optimization::{optimize_beefy_commitment, optimize_grandpa_justification},
+};
 use async_trait::async_trait;
+use bp_beefy::{
+	beefy_authority_set_info, BeefyAuthoritySetInfoOf, BeefyFinalityProofOf, BeefyMmrLeafOf,
+	BeefyVersionedFinalityProofOf, EcdsaValidatorId, InitializationDataOf, MmrHashOf, ValidatorSet,
+	VersionedFinalityProof,
+};
 use bp_header_chain::{
 	find_grandpa_authorities_scheduled_change,
 	justification::{verify_justification, GrandpaJustification},
-	FinalityProof,
+	AuthoritySet, FinalityProof,
 };
 use codec::{Decode, Encode};
 use finality_grandpa::voter_set::VoterSet;
+use frame_support::weights::Weight;
 use num_traits::{One, Zero};
 use relay_substrate_client::{
-	BlockNumberOf, Chain, ChainWithGrandpa, Client, Error as SubstrateError, HashOf, HeaderOf,
-	Subscription,
+	BlockNumberOf, Chain, ChainWithBeefy, ChainWithGrandpa, Client, Error as SubstrateError,
+	HashOf, HeaderOf, Subscription,
 };
 use sp_core::{storage::StorageKey, Bytes};
 use sp_finality_grandpa::AuthorityList as GrandpaAuthoritiesSet;
-use sp_runtime::{traits::Header, ConsensusEngineId};
+use sp_mmr_primitives::{EncodableOpaqueLeaf, Error as MmrError, Proof as MmrProof};
+use sp_runtime::{
+	traits::{Header, Saturating, UniqueSaturatedInto},
+	ConsensusEngineId,
+};
 use std::marker::PhantomData;
 
+/// Name of the GRANDPA pallet at the source chain.
+const GRANDPA_PALLET_NAME: &str = "Grandpa";
+/// Name of the `PendingChange` storage value of the GRANDPA pallet at the source chain.
+const GRANDPA_PENDING_CHANGE_VALUE_NAME: &str = "PendingChange";
+
 /// Finality enfine, used by the Substrate chain.
 #[async_trait]
 pub trait Engine<C: Chain> {
 	/// Unique consensus engine identifier.
 	const ID: ConsensusEngineId;
-	/// Type of finality proofs, used by consensus engine.
+	/// Type of finality proofs, produced by consensus engine and read from the source chain.
+	type RawFinalityProof: Decode + Send;
+	/// Type of finality proofs, verified by the bridge pallet at the bridged (target) chain.
 	type FinalityProof: FinalityProof<BlockNumberOf<C>> + Decode + Encode;
 	/// Type of bridge pallet initialization data.
 	type InitializationData: std::fmt::Debug + Send + Sync + 'static;
+	/// Type of the data, stored by the bridge pallet, that is required to verify finality proofs.
+	type VerificationContext: Decode + Send + Sync + 'static;
 
 	/// Returns storage key at the bridged (target) chain that corresponds to the `bool` value,
 	/// which is true when the bridge pallet is halted.
@@ -53,8 +75,30 @@ pub trait Engine<C: Chain> {
 	///
 	/// Note that we don't care about type of the value - just if it present or not.
 	fn is_initialized_key() -> StorageKey;
+	/// Returns storage key at the bridged (target) chain that corresponds to the
+	/// `VerificationContext` value.
+	fn verification_context_key() -> StorageKey;
+	/// Returns estimated weight of the finality proof verification at the bridged (target) chain.
+	fn proof_verification_weight(proof: &Self::FinalityProof) -> Weight;
+	/// Optimize finality proof of given header, so that it is cheaper to submit it to the bridged
+	/// (target) chain.
+	///
+	/// The optimized proof is verified using given context. If the proof can't be optimized,
+	/// it is returned unchanged. The context is updated, so that it may be used to optimize proof
+	/// of the next header.
+	fn optimize_proof(
+		context: &mut Self::VerificationContext,
+		header: &C::Header,
+		proof: Self::FinalityProof,
+	) -> Self::FinalityProof;
 	/// A method to subscribe to encoded finality proofs, given source client.
 	async fn finality_proofs(client: Client<C>) -> Result<Subscription<Bytes>, SubstrateError>;
+	/// Complete raw finality proof, read from the source chain, with the data that is required
+	/// to verify it at the bridged (target) chain.
+	async fn complete_finality_proof(
+		client: &Client<C>,
+		proof: Self::RawFinalityProof,
+	) -> Result<Self::FinalityProof, SubstrateError>;
 	/// Prepare initialization data for the finality bridge pallet.
 	async fn prepare_initialization_data(
 		client: Client<C>,
@@ -88,13 +132,38 @@ impl<C: ChainWithGrandpa> Grandpa<C> {
 		GrandpaAuthoritiesSet::decode(&mut &raw_authorities_set[..])
 			.map_err(|err| Error::DecodeAuthorities(C::NAME, header_hash, err))
 	}
+
+	/// Returns true if there's GRANDPA authorities set change that is scheduled, but not yet
+	/// enacted at given header.
+	///
+	/// Zero-delay changes are enacted by the same header that schedules them, so they're never
+	/// pending.
+	async fn has_pending_authorities_change(
+		source_client: &Client<C>,
+		header_hash: C::Hash,
+	) -> Result<bool, Error<HashOf<C>, BlockNumberOf<C>>> {
+		let pending_change_key = StorageKey(
+			bp_runtime::storage_value_final_key(
+				GRANDPA_PALLET_NAME.as_bytes(),
+				GRANDPA_PENDING_CHANGE_VALUE_NAME.as_bytes(),
+			)
+			.to_vec(),
+		);
+		source_client
+			.raw_storage_value(pending_change_key, Some(header_hash))
+			.await
+			.map(|pending_change| pending_change.is_some())
+			.map_err(|err| Error::RetrievePendingAuthoritiesChange(C::NAME, header_hash, err))
+	}
 }
 
 #[async_trait]
 impl<C: ChainWithGrandpa> Engine<C> for Grandpa<C> {
 	const ID: ConsensusEngineId = sp_finality_grandpa::GRANDPA_ENGINE_ID;
+	type RawFinalityProof = GrandpaJustification<HeaderOf<C>>;
 	type FinalityProof = GrandpaJustification<HeaderOf<C>>;
 	type InitializationData = bp_header_chain::InitializationData<C::Header>;
+	type VerificationContext = AuthoritySet;
 
 	fn is_halted_key() -> StorageKey {
 		bp_header_chain::storage_keys::is_halted_key(C::WITH_CHAIN_GRANDPA_PALLET_NAME)
@@ -104,10 +173,67 @@ impl<C: ChainWithGrandpa> Engine<C> for Grandpa<C> {
 		bp_header_chain::storage_keys::best_finalized_hash_key(C::WITH_CHAIN_GRANDPA_PALLET_NAME)
 	}
 
+	fn verification_context_key() -> StorageKey {
+		bp_header_chain::storage_keys::current_authority_set_key(C::WITH_CHAIN_GRANDPA_PALLET_NAME)
+	}
+
+	fn proof_verification_weight(proof: &Self::FinalityProof) -> Weight {
+		// we don't know actual weights of the target chain, so default weights are used
+		<() as pallet_bridge_grandpa::WeightInfo>::submit_finality_proof(
+			proof.commit.precommits.len().unique_saturated_into(),
+			proof.votes_ancestries.len().unique_saturated_into(),
+		)
+	}
+
+	fn optimize_proof(
+		context: &mut Self::VerificationContext,
+		header: &C::Header,
+		proof: Self::FinalityProof,
+	) -> Self::FinalityProof {
+		let optimized_proof = match VoterSet::new(context.authorities.clone()) {
+			Some(authorities_set) => optimize_grandpa_justification::<C::Header>(
+				(header.hash(), *header.number()),
+				context.set_id,
+				&authorities_set,
+				proof.clone(),
+			)
+			.map_err(|e| format!("{:?}", e)),
+			None => Err(format!("invalid authorities set {:?}", context.authorities)),
+		};
+
+		// zero-delay authorities set change is enacted by the bridge pallet when the header that
+		// schedules it is imported
+		if let Some(change) = find_grandpa_authorities_scheduled_change(header) {
+			if change.delay.is_zero() {
+				*context = AuthoritySet::new(change.next_authorities, context.set_id + 1);
+			}
+		}
+
+		optimized_proof.unwrap_or_else(|e| {
+			log::debug!(
+				target: "bridge",
+				"Failed to optimize {} justification of header {}/{}: {}. Using original justification",
+				C::NAME,
+				header.number(),
+				header.hash(),
+				e,
+			);
+			proof
+		})
+	}
+
 	async fn finality_proofs(client: Client<C>) -> Result<Subscription<Bytes>, SubstrateError> {
 		client.subscribe_grandpa_justifications().await
 	}
 
+	async fn complete_finality_proof(
+		_client: &Client<C>,
+		proof: Self::RawFinalityProof,
+	) -> Result<Self::FinalityProof, SubstrateError> {
+		// GRANDPA justification is self-contained
+		Ok(proof)
+	}
+
 	/// Prepare initialization data for the GRANDPA verifier pallet.
 	async fn prepare_initialization_data(
 		source_client: Client<C>,
@@ -119,28 +245,54 @@ impl<C: ChainWithGrandpa> Engine<C> for Grandpa<C> {
 		// But now there are problems with this approach - `CurrentSetId` may return invalid value.
 		// So here we're waiting for the next justification, read the authorities set and then try
 		// to figure out the set id with bruteforce.
+		//
+		// The initialization data has no place for pending (delayed) authorities set changes, so
+		// we're skipping all justifications of headers that have such changes pending or are
+		// enacting them.
 		let justifications = source_client
 			.subscribe_grandpa_justifications()
 			.await
 			.map_err(|err| Error::Subscribe(C::NAME, err))?;
-		// Read next justification - the header that it finalizes will be used as initial header.
-		let justification = justifications
-			.next()
-			.await
-			.map_err(|e| Error::ReadJustification(C::NAME, e))
-			.and_then(|justification| {
-				justification.ok_or(Error::ReadJustificationStreamEnded(C::NAME))
-			})?;
+		let (justification, initial_header) = loop {
+			// Read next justification - the header that it finalizes may be used as initial
+			// header.
+			let justification = justifications
+				.next()
+				.await
+				.map_err(|e| Error::ReadJustification(C::NAME, e))
+				.and_then(|justification| {
+					justification.ok_or(Error::ReadJustificationStreamEnded(C::NAME))
+				})?;
 
-		// Read initial header.
-		let justification: GrandpaJustification<C::Header> =
-			Decode::decode(&mut &justification.0[..])
-				.map_err(|err| Error::DecodeJustification(C::NAME, err))?;
+			// Read initial header.
+			let justification: GrandpaJustification<C::Header> =
+				Decode::decode(&mut &justification.0[..])
+					.map_err(|err| Error::DecodeJustification(C::NAME, err))?;
+
+			// Header that enacts delayed change is finalized by the previous authorities set, so
+			// we're also skipping it (its parent has the change pending).
+			let header_hash = justification.commit.target_hash;
+			let header = Self::source_header(&source_client, header_hash).await?;
+			let has_pending_change =
+				Self::has_pending_authorities_change(&source_client, header_hash).await? ||
+					Self::has_pending_authorities_change(&source_client, *header.parent_hash())
+						.await?;
+			if has_pending_change {
+				log::trace!(
+					target: "bridge",
+					"{} header {}/{} has pending GRANDPA authorities set change. Waiting for next justification",
+					C::NAME,
+					justification.commit.target_number,
+					header_hash,
+				);
+				continue
+			}
+
+			break (justification, header)
+		};
 
 		let (initial_header_hash, initial_header_number) =
 			(justification.commit.target_hash, justification.commit.target_number);
-
-		let initial_header = Self::source_header(&source_client, initial_header_hash).await?;
 		log::trace!(target: "bridge", "Selected {} initial header: {}/{}",
 			C::NAME,
 			initial_header_number,
@@ -156,17 +308,10 @@ impl<C: ChainWithGrandpa> Engine<C> for Grandpa<C> {
 		);
 
 		// If initial header changes the GRANDPA authorities set, then we need previous authorities
-		// to verify justification.
+		// to verify justification. Since we have skipped all headers with pending changes, the
+		// change (if any) has zero delay and is enacted by the initial header itself.
 		let mut authorities_for_verification = initial_authorities_set.clone();
-		let scheduled_change = find_grandpa_authorities_scheduled_change(&initial_header);
-		assert!(
-			scheduled_change.as_ref().map(|c| c.delay.is_zero()).unwrap_or(true),
-			"GRANDPA authorities change at {} scheduled to happen in {:?} blocks. We expect\
-			regular hange to have zero delay",
-			initial_header_hash,
-			scheduled_change.as_ref().map(|c| c.delay),
-		);
-		let schedules_change = scheduled_change.is_some();
+		let schedules_change = find_grandpa_authorities_scheduled_change(&initial_header).is_some();
 		if schedules_change {
 			authorities_for_verification =
 				Self::source_authorities_set(&source_client, *initial_header.parent_hash()).await?;
@@ -224,3 +369,201 @@ impl<C: ChainWithGrandpa> Engine<C> for Grandpa<C> {
 		})
 	}
 }
+
+/// BEEFY finality engine.
+///
+/// BEEFY justification only contains the signed commitment. The finality proof, that is verified
+/// by the bridge BEEFY pallet, also contains the validator set that has signed the commitment and
+/// the latest MMR leaf at the committed block. They are read from the source chain, using runtime
+/// APIs. The engine assumes that the MMR pallet has been deployed at the source chain genesis, so
+/// the latest leaf at block `N` has index `N - 1`.
+///
+/// The finality relay detects mandatory headers using GRANDPA digests. It works for BEEFY too,
+/// because both GRANDPA and BEEFY validator sets are changed at session boundaries and BEEFY
+/// justifications of session boundary blocks are always stored by the source node. The bridge
+/// pallet accepts commitments, signed by both current and next validator sets, so it is fine to
+/// skip the header that enacts the next set. But we can't skip the whole validator set - at least
+/// one commitment of every set must be submitted.
+pub struct Beefy<C>(PhantomData<C>);
+
+impl<C: ChainWithBeefy> Beefy<C> {
+	/// Read BEEFY validator set at given block.
+	async fn source_validator_set(
+		source_client: &Client<C>,
+		block_hash: C::Hash,
+	) -> Result<ValidatorSet<EcdsaValidatorId>, SubstrateError> {
+		let encoded_validator_set = source_client
+			.state_call("BeefyApi_validator_set".into(), Bytes(Vec::new()), Some(block_hash))
+			.await?;
+		let validator_set: Option<ValidatorSet<EcdsaValidatorId>> =
+			Decode::decode(&mut &encoded_validator_set.0[..])?;
+		validator_set.ok_or_else(|| {
+			SubstrateError::Custom(format!(
+				"BEEFY validator set is missing from {} block {}",
+				C::NAME,
+				block_hash,
+			))
+		})
+	}
+
+	/// Read the latest MMR leaf and its proof at given block.
+	async fn source_mmr_leaf_and_proof(
+		source_client: &Client<C>,
+		block_number: C::BlockNumber,
+		block_hash: C::Hash,
+	) -> Result<(BeefyMmrLeafOf<C>, MmrProof<MmrHashOf<C>>), SubstrateError> {
+		let leaf_index: u64 = block_number.saturating_sub(One::one()).unique_saturated_into();
+		let encoded_leaf_and_proof = source_client
+			.state_call(
+				"MmrApi_generate_proof".into(),
+				Bytes(leaf_index.encode()),
+				Some(block_hash),
+			)
+			.await?;
+		let leaf_and_proof: Result<(EncodableOpaqueLeaf, MmrProof<MmrHashOf<C>>), MmrError> =
+			Decode::decode(&mut &encoded_leaf_and_proof.0[..])?;
+		let (leaf, proof) = leaf_and_proof.map_err(|e| {
+			SubstrateError::Custom(format!(
+				"Failed to generate proof of {} MMR leaf {} at block {}: {:?}",
+				C::NAME,
+				leaf_index,
+				block_hash,
+				e,
+			))
+		})?;
+		let leaf = leaf.into_opaque_leaf().try_decode().ok_or_else(|| {
+			SubstrateError::Custom(format!(
+				"Failed to decode {} MMR leaf {} at block {}",
+				C::NAME,
+				leaf_index,
+				block_hash,
+			))
+		})?;
+
+		Ok((leaf, proof))
+	}
+}
+
+#[async_trait]
+impl<C: ChainWithBeefy> Engine<C> for Beefy<C> {
+	const ID: ConsensusEngineId = bp_beefy::BEEFY_ENGINE_ID;
+	type RawFinalityProof = BeefyVersionedFinalityProofOf<C>;
+	type FinalityProof = BeefyFinalityProofOf<C>;
+	type InitializationData = InitializationDataOf<C>;
+	type VerificationContext = BeefyAuthoritySetInfoOf<C>;
+
+	fn is_halted_key() -> StorageKey {
+		bp_beefy::storage_keys::is_halted_key(C::WITH_CHAIN_BEEFY_PALLET_NAME)
+	}
+
+	fn is_initialized_key() -> StorageKey {
+		bp_beefy::storage_keys::best_block_number_key(C::WITH_CHAIN_BEEFY_PALLET_NAME)
+	}
+
+	fn verification_context_key() -> StorageKey {
+		bp_beefy::storage_keys::current_authority_set_info_key(C::WITH_CHAIN_BEEFY_PALLET_NAME)
+	}
+
+	fn proof_verification_weight(_proof: &Self::FinalityProof) -> Weight {
+		// the bridge BEEFY pallet has no benchmarks yet
+		0
+	}
+
+	fn optimize_proof(
+		context: &mut Self::VerificationContext,
+		header: &C::Header,
+		mut proof: Self::FinalityProof,
+	) -> Self::FinalityProof {
+		// commitment, signed by the next validator set, enacts this set at the bridge pallet
+		let validator_set_id = proof.commitment.commitment.validator_set_id;
+		if validator_set_id != context.id {
+			*context = beefy_authority_set_info::<C>(validator_set_id, &proof.validator_set);
+		}
+
+		match optimize_beefy_commitment(&proof.validator_set, proof.commitment.clone()) {
+			Ok(optimized_commitment) => proof.commitment = optimized_commitment,
+			Err(e) => log::debug!(
+				target: "bridge",
+				"Failed to optimize {} commitment of header {}/{}: {:?}. Using original commitment",
+				C::NAME,
+				header.number(),
+				header.hash(),
+				e,
+			),
+		}
+
+		proof
+	}
+
+	async fn finality_proofs(client: Client<C>) -> Result<Subscription<Bytes>, SubstrateError> {
+		client.subscribe_beefy_justifications().await
+	}
+
+	async fn complete_finality_proof(
+		client: &Client<C>,
+		proof: Self::RawFinalityProof,
+	) -> Result<Self::FinalityProof, SubstrateError> {
+		let VersionedFinalityProof::V1(commitment) = proof;
+
+		let block_number = commitment.commitment.block_number;
+		let block_hash = client.block_hash_by_number(block_number).await?;
+		let validator_set = Self::source_validator_set(client, block_hash).await?;
+		let (mmr_leaf, mmr_proof) =
+			Self::source_mmr_leaf_and_proof(client, block_number, block_hash).await?;
+
+		Ok(BeefyFinalityProofOf::<C> {
+			commitment,
+			validator_set: validator_set.validators().to_vec(),
+			mmr_leaf,
+			mmr_proof: mmr_proof.into(),
+		})
+	}
+
+	/// Prepare initialization data for the BEEFY verifier pallet.
+	async fn prepare_initialization_data(
+		source_client: Client<C>,
+	) -> Result<Self::InitializationData, Error<HashOf<C>, BlockNumberOf<C>>> {
+		// We are waiting for the next BEEFY justification and then read the validator set that
+		// has signed it. The next validator set is read from the latest MMR leaf at the
+		// committed block.
+		let justifications = source_client
+			.subscribe_beefy_justifications()
+			.await
+			.map_err(|err| Error::Subscribe(C::NAME, err))?;
+		let justification = justifications
+			.next()
+			.await
+			.map_err(|e| Error::ReadJustification(C::NAME, e))
+			.and_then(|justification| {
+				justification.ok_or(Error::ReadJustificationStreamEnded(C::NAME))
+			})?;
+		let justification: BeefyVersionedFinalityProofOf<C> =
+			Decode::decode(&mut &justification.0[..])
+				.map_err(|err| Error::DecodeJustification(C::NAME, err))?;
+		let proof = Self::complete_finality_proof(&source_client, justification)
+			.await
+			.map_err(|err| Error::ReadJustification(C::NAME, err))?;
+
+		let initial_block_number = proof.commitment.commitment.block_number;
+		let current_authority_set = beefy_authority_set_info::<C>(
+			proof.commitment.commitment.validator_set_id,
+			&proof.validator_set,
+		);
+		let next_authority_set = proof.mmr_leaf.beefy_next_authority_set;
+		log::trace!(
+			target: "bridge",
+			"Selected {} initial block: {}. Current BEEFY validator set: {:?}. Next: {:?}",
+			C::NAME,
+			initial_block_number,
+			current_authority_set,
+			next_authority_set,
+		);
+
+		Ok(bp_beefy::InitializationData {
+			is_halted: false,
+			best_block_number: initial_block_number,
+			current_authority_set,
+			next_authority_set,
+		})
+	}
+}
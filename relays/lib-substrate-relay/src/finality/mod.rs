@@ -20,6 +20,7 @@
 use crate::{
 	finality::{
 		engine::Engine,
+		optimization::FinalityProofsOptimizationMetrics,
 		source::{SubstrateFinalityProof, SubstrateFinalitySource},
 		target::SubstrateFinalityTarget,
 	},
@@ -27,20 +28,23 @@ use crate::{
 };
 
 use async_trait::async_trait;
+use bp_beefy::BeefyFinalityProofOf;
 use bp_header_chain::justification::GrandpaJustification;
-use finality_relay::FinalitySyncPipeline;
+use finality_relay::{FinalitySyncPipeline, HeadersAndProofs};
+use pallet_bridge_beefy::{Call as BridgeBeefyCall, Config as BridgeBeefyConfig};
 use pallet_bridge_grandpa::{Call as BridgeGrandpaCall, Config as BridgeGrandpaConfig};
 use relay_substrate_client::{
 	transaction_stall_timeout, AccountIdOf, AccountKeyPairOf, BlockNumberOf, CallOf, Chain, Client,
 	HashOf, HeaderOf, SyncHeader, TransactionSignScheme,
 };
-use relay_utils::metrics::MetricsParams;
+use relay_utils::metrics::{Metric, MetricsParams};
 use sp_core::Pair;
 use std::{fmt::Debug, marker::PhantomData};
 
 pub mod engine;
 pub mod guards;
 pub mod initialize;
+pub mod optimization;
 pub mod source;
 pub mod target;
 
@@ -99,6 +103,12 @@ pub trait SubmitFinalityProofCallBuilder<P: SubstrateFinalitySyncPipeline> {
 		header: SyncHeader<HeaderOf<P::SourceChain>>,
 		proof: SubstrateFinalityProof<P>,
 	) -> CallOf<P::TargetChain>;
+
+	/// Given multiple source chain headers and their finality proofs, build call of
+	/// `submit_finality_proofs` function of bridge GRANDPA module at the target chain.
+	fn build_submit_finality_proofs_call(
+		headers_and_proofs: HeadersAndProofs<FinalitySyncPipelineAdapter<P>>,
+	) -> CallOf<P::TargetChain>;
 }
 
 /// Building `submit_finality_proof` call when you have direct access to the target
@@ -128,16 +138,76 @@ where
 		}
 		.into()
 	}
+
+	fn build_submit_finality_proofs_call(
+		headers_and_proofs: HeadersAndProofs<FinalitySyncPipelineAdapter<P>>,
+	) -> CallOf<P::TargetChain> {
+		BridgeGrandpaCall::<R, I>::submit_finality_proofs {
+			finality_proofs: headers_and_proofs
+				.into_iter()
+				.map(|(header, proof)| (Box::new(header.into_inner()), proof))
+				.collect(),
+		}
+		.into()
+	}
+}
+
+/// Building `submit_commitment` call of the bridge BEEFY pallet when you have direct access to
+/// the target chain runtime.
+pub struct DirectSubmitBeefyCommitmentCallBuilder<P, R, I> {
+	_phantom: PhantomData<(P, R, I)>,
+}
+
+impl<P, R, I> SubmitFinalityProofCallBuilder<P> for DirectSubmitBeefyCommitmentCallBuilder<P, R, I>
+where
+	P: SubstrateFinalitySyncPipeline,
+	P::SourceChain: relay_substrate_client::ChainWithBeefy,
+	R: BridgeBeefyConfig<I, BridgedChain = P::SourceChain>,
+	I: 'static,
+	CallOf<P::TargetChain>: From<BridgeBeefyCall<R, I>>,
+	P::FinalityEngine: Engine<P::SourceChain, FinalityProof = BeefyFinalityProofOf<P::SourceChain>>,
+{
+	fn build_submit_finality_proof_call(
+		_header: SyncHeader<HeaderOf<P::SourceChain>>,
+		proof: BeefyFinalityProofOf<P::SourceChain>,
+	) -> CallOf<P::TargetChain> {
+		BridgeBeefyCall::<R, I>::submit_commitment {
+			commitment: proof.commitment,
+			validator_set: proof.validator_set,
+			mmr_leaf: Box::new(proof.mmr_leaf),
+			mmr_proof: proof.mmr_proof,
+		}
+		.into()
+	}
+
+	fn build_submit_finality_proofs_call(
+		headers_and_proofs: HeadersAndProofs<FinalitySyncPipelineAdapter<P>>,
+	) -> CallOf<P::TargetChain> {
+		// the bridge BEEFY pallet can't import multiple commitments in a single call, so we are
+		// only submitting the first commitment. Other commitments will be submitted later
+		let (header, proof) = headers_and_proofs
+			.into_iter()
+			.next()
+			.expect("finality loop never submits empty batches; qed");
+		Self::build_submit_finality_proof_call(header, proof)
+	}
 }
 
 /// Macro that generates `SubmitFinalityProofCallBuilder` implementation for the case when
 /// you only have an access to the mocked version of target chain runtime. In this case you
-/// should provide "name" of the call variant for the bridge GRANDPA calls and the "name" of
-/// the variant for the `submit_finality_proof` call within that first option.
+/// should provide "name" of the call variant for the bridge GRANDPA calls and the "names" of
+/// the variants for the `submit_finality_proof` and `submit_finality_proofs` calls within that
+/// first option.
 #[rustfmt::skip]
 #[macro_export]
 macro_rules! generate_mocked_submit_finality_proof_call_builder {
-	($pipeline:ident, $mocked_builder:ident, $bridge_grandpa:path, $submit_finality_proof:path) => {
+	(
+		$pipeline:ident,
+		$mocked_builder:ident,
+		$bridge_grandpa:path,
+		$submit_finality_proof:path,
+		$submit_finality_proofs:path
+	) => {
 		pub struct $mocked_builder;
 
 		impl $crate::finality::SubmitFinalityProofCallBuilder<$pipeline>
@@ -159,6 +229,21 @@ macro_rules! generate_mocked_submit_finality_proof_call_builder {
 			> {
 				$bridge_grandpa($submit_finality_proof(Box::new(header.into_inner()), proof))
 			}
+
+			fn build_submit_finality_proofs_call(
+				headers_and_proofs: finality_relay::HeadersAndProofs<
+					$crate::finality::FinalitySyncPipelineAdapter<$pipeline>
+				>,
+			) -> relay_substrate_client::CallOf<
+				<$pipeline as $crate::finality::SubstrateFinalitySyncPipeline>::TargetChain
+			> {
+				$bridge_grandpa($submit_finality_proofs(
+					headers_and_proofs
+						.into_iter()
+						.map(|(header, proof)| (Box::new(header.into_inner()), proof))
+						.collect()
+				))
+			}
 		}
 	};
 }
@@ -168,6 +253,7 @@ pub async fn run<P: SubstrateFinalitySyncPipeline>(
 	source_client: Client<P::SourceChain>,
 	target_client: Client<P::TargetChain>,
 	only_mandatory_headers: bool,
+	max_mandatory_headers_in_batch: usize,
 	transaction_params: TransactionParams<AccountKeyPairOf<P::TransactionSignScheme>>,
 	metrics_params: MetricsParams,
 ) -> anyhow::Result<()>
@@ -182,9 +268,14 @@ where
 		P::TargetChain::NAME,
 	);
 
+	let metrics_prefix = finality_relay::metrics_prefix::<FinalitySyncPipelineAdapter<P>>();
+	let optimization_metrics = FinalityProofsOptimizationMetrics::new(Some(&metrics_prefix))?;
+	optimization_metrics.register(&metrics_params.registry)?;
+
 	finality_relay::run(
 		SubstrateFinalitySource::<P>::new(source_client, None),
-		SubstrateFinalityTarget::<P>::new(target_client, transaction_params.clone()),
+		SubstrateFinalityTarget::<P>::new(target_client, transaction_params.clone())
+			.with_optimization_metrics(optimization_metrics),
 		finality_relay::FinalitySyncParams {
 			tick: std::cmp::max(
 				P::SourceChain::AVERAGE_BLOCK_INTERVAL,
@@ -197,6 +288,7 @@ where
 				crate::STALL_TIMEOUT,
 			),
 			only_mandatory_headers,
+			max_mandatory_headers_in_batch,
 		},
 		metrics_params,
 		futures::future::pending(),
@@ -0,0 +1,360 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Optimization of finality proofs before they're submitted to the target chain.
+
+use bp_beefy::{signatures_required, EcdsaValidatorId, EcdsaValidatorSignature, SignedCommitment};
+use bp_header_chain::justification::{
+	verify_justification, AncestryChain, Error as JustificationError, GrandpaJustification,
+};
+use codec::Encode;
+use finality_grandpa::voter_set::VoterSet;
+use frame_support::weights::Weight;
+use relay_utils::metrics::{
+	metric_name, register, Counter, Metric, PrometheusError, Registry, U64,
+};
+use sp_core::{ecdsa, hashing::keccak_256};
+use sp_finality_grandpa::{AuthorityId, SetId};
+use sp_runtime::traits::Header as HeaderT;
+use std::collections::BTreeSet;
+
+/// Optimize GRANDPA justification, so that it is cheaper to submit it to the target chain.
+///
+/// Redundant precommits are removed, so that the cumulative weight of remaining precommits
+/// is just above the threshold. Precommits of authorities with larger weights are preferred.
+/// Ancestry headers that are not required by remaining precommits are removed too. The
+/// optimized justification is verified before it is returned.
+pub fn optimize_grandpa_justification<Header: HeaderT>(
+	finalized_target: (Header::Hash, Header::Number),
+	authorities_set_id: SetId,
+	authorities_set: &VoterSet<AuthorityId>,
+	mut justification: GrandpaJustification<Header>,
+) -> Result<GrandpaJustification<Header>, JustificationError>
+where
+	Header::Number: finality_grandpa::BlockNumberOps,
+{
+	// only first precommit of every known authority is accounted by the verifier
+	let mut voted_authorities = BTreeSet::new();
+	let mut precommits = justification
+		.commit
+		.precommits
+		.drain(..)
+		.filter_map(|signed| {
+			let weight: u64 = authorities_set.get(&signed.id)?.weight().0.into();
+			if !voted_authorities.insert(signed.id.clone()) {
+				return None
+			}
+			Some((weight, signed))
+		})
+		.collect::<Vec<_>>();
+	// the sort is stable, so precommits with equal weights keep their original order
+	precommits.sort_by(|(weight1, _), (weight2, _)| weight2.cmp(weight1));
+
+	// leave just enough precommits to cross the threshold
+	let threshold: u64 = authorities_set.threshold().0.into();
+	let mut cumulative_weight = 0u64;
+	justification.commit.precommits = precommits
+		.into_iter()
+		.take_while(|(weight, _)| {
+			let is_required = cumulative_weight < threshold;
+			cumulative_weight = cumulative_weight.saturating_add(*weight);
+			is_required
+		})
+		.map(|(_, signed)| signed)
+		.collect();
+
+	// remove ancestry headers that are not used by remaining precommits
+	let mut chain = AncestryChain::new(&justification.votes_ancestries);
+	for signed in &justification.commit.precommits {
+		chain = chain
+			.ensure_descendant(&justification.commit.target_hash, &signed.precommit.target_hash)?;
+	}
+	justification
+		.votes_ancestries
+		.retain(|header| !chain.unvisited.contains(&header.hash()));
+
+	verify_justification::<Header>(
+		finalized_target,
+		authorities_set_id,
+		authorities_set,
+		&justification,
+	)?;
+
+	Ok(justification)
+}
+
+/// Error that may happen when optimizing BEEFY commitment.
+#[derive(Debug, PartialEq)]
+pub enum BeefyCommitmentOptimizationError {
+	/// The number of signatures doesn't match the number of validators.
+	InvalidSignaturesLength,
+	/// The commitment is not signed by enough validators.
+	NotEnoughCorrectSignatures,
+}
+
+/// Optimize BEEFY signed commitment, so that it is cheaper to submit it to the target chain.
+///
+/// Every signature is verified by the bridge pallet, so redundant signatures are removed and only
+/// the required number of valid signatures is left. Invalid signatures are removed too.
+pub fn optimize_beefy_commitment<BlockNumber: Encode>(
+	validators: &[EcdsaValidatorId],
+	mut commitment: SignedCommitment<BlockNumber, EcdsaValidatorSignature>,
+) -> Result<SignedCommitment<BlockNumber, EcdsaValidatorSignature>, BeefyCommitmentOptimizationError>
+{
+	if commitment.signatures.len() != validators.len() {
+		return Err(BeefyCommitmentOptimizationError::InvalidSignaturesLength)
+	}
+
+	let commitment_hash = keccak_256(&commitment.commitment.encode());
+	let mut missing_signatures = signatures_required(validators.len());
+	for (validator, signature) in validators.iter().zip(commitment.signatures.iter_mut()) {
+		let is_required = missing_signatures != 0 &&
+			signature
+				.as_ref()
+				.map(|signature| {
+					ecdsa::Pair::verify_prehashed(
+						signature.as_ref(),
+						&commitment_hash,
+						validator.as_ref(),
+					)
+				})
+				.unwrap_or(false);
+		if is_required {
+			missing_signatures -= 1;
+		} else {
+			*signature = None;
+		}
+	}
+
+	if missing_signatures != 0 {
+		return Err(BeefyCommitmentOptimizationError::NotEnoughCorrectSignatures)
+	}
+
+	Ok(commitment)
+}
+
+/// Finality proofs optimization metrics.
+#[derive(Clone)]
+pub struct FinalityProofsOptimizationMetrics {
+	/// Total number of bytes, saved by optimizing finality proofs.
+	saved_bytes: Counter<U64>,
+	/// Total estimated weight, saved by optimizing finality proofs.
+	saved_weight: Counter<U64>,
+}
+
+impl FinalityProofsOptimizationMetrics {
+	/// Create finality proofs optimization metrics.
+	pub fn new(prefix: Option<&str>) -> Result<Self, PrometheusError> {
+		Ok(FinalityProofsOptimizationMetrics {
+			saved_bytes: Counter::new(
+				metric_name(prefix, "finality_proofs_optimization_saved_bytes"),
+				"Total number of bytes, saved by optimizing submitted finality proofs",
+			)?,
+			saved_weight: Counter::new(
+				metric_name(prefix, "finality_proofs_optimization_saved_weight"),
+				"Total estimated weight, saved by optimizing submitted finality proofs",
+			)?,
+		})
+	}
+
+	/// Note that the finality proof has been optimized.
+	pub fn note_optimized_proof(&self, saved_bytes: u64, saved_weight: Weight) {
+		self.saved_bytes.inc_by(saved_bytes);
+		self.saved_weight.inc_by(saved_weight);
+	}
+}
+
+impl Metric for FinalityProofsOptimizationMetrics {
+	fn register(&self, registry: &Registry) -> Result<(), PrometheusError> {
+		register(self.saved_bytes.clone(), registry)?;
+		register(self.saved_weight.clone(), registry)?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bp_test_utils::{
+		beefy_validator_ids, beefy_validator_pairs, make_beefy_commitment,
+		make_justification_for_header, sign_beefy_commitment, test_header,
+		JustificationGeneratorParams, ALICE, BOB, CHARLIE, DAVE, EVE, TEST_BEEFY_VALIDATOR_SET_ID,
+		TEST_GRANDPA_SET_ID,
+	};
+
+	type TestHeader = sp_runtime::testing::Header;
+
+	fn voter_set(authorities: &[(bp_test_utils::Account, u64)]) -> VoterSet<AuthorityId> {
+		VoterSet::new(authorities.iter().map(|(id, weight)| (AuthorityId::from(*id), *weight)))
+			.unwrap()
+	}
+
+	fn optimize(
+		authorities: &[(bp_test_utils::Account, u64)],
+		justification: GrandpaJustification<TestHeader>,
+	) -> Result<GrandpaJustification<TestHeader>, JustificationError> {
+		optimize_grandpa_justification::<TestHeader>(
+			(justification.commit.target_hash, justification.commit.target_number),
+			TEST_GRANDPA_SET_ID,
+			&voter_set(authorities),
+			justification,
+		)
+	}
+
+	#[test]
+	fn redundant_precommits_are_removed() {
+		let authorities = vec![(ALICE, 1), (BOB, 1), (CHARLIE, 1), (DAVE, 1), (EVE, 1)];
+		let justification =
+			make_justification_for_header::<TestHeader>(JustificationGeneratorParams {
+				header: test_header(1),
+				authorities: authorities.clone(),
+				..Default::default()
+			});
+
+		// threshold is 4 of 5
+		let optimized_justification = optimize(&authorities, justification.clone()).unwrap();
+		assert_eq!(
+			optimized_justification.commit.precommits,
+			justification.commit.precommits[..4].to_vec(),
+		);
+		assert_eq!(optimized_justification.votes_ancestries, justification.votes_ancestries);
+	}
+
+	#[test]
+	fn unused_ancestry_headers_are_removed() {
+		// precommits are signed in the round-robin fashion, so ALICE, CHARLIE and EVE are voting
+		// for the first fork and BOB and DAVE are voting for the second fork
+		let authorities = vec![(ALICE, 10), (BOB, 1), (CHARLIE, 10), (DAVE, 1), (EVE, 1)];
+		let justification =
+			make_justification_for_header::<TestHeader>(JustificationGeneratorParams {
+				header: test_header(1),
+				authorities: authorities.clone(),
+				ancestors: 4,
+				forks: 2,
+				..Default::default()
+			});
+
+		// threshold is 16 of 23, so precommits of ALICE and CHARLIE are enough
+		let optimized_justification = optimize(&authorities, justification.clone()).unwrap();
+		assert_eq!(
+			optimized_justification.commit.precommits,
+			vec![
+				justification.commit.precommits[0].clone(),
+				justification.commit.precommits[2].clone(),
+			],
+		);
+		assert_eq!(
+			optimized_justification.votes_ancestries,
+			justification.votes_ancestries[..2].to_vec(),
+		);
+	}
+
+	#[test]
+	fn precommits_of_unknown_and_duplicate_authorities_are_removed() {
+		let authorities = vec![(ALICE, 1), (BOB, 1), (CHARLIE, 1)];
+		let justification =
+			make_justification_for_header::<TestHeader>(JustificationGeneratorParams {
+				header: test_header(1),
+				authorities: vec![(DAVE, 1), (ALICE, 1), (ALICE, 1), (BOB, 1), (CHARLIE, 1)],
+				..Default::default()
+			});
+
+		// threshold is 3 of 3
+		let optimized_justification = optimize(&authorities, justification.clone()).unwrap();
+		assert_eq!(
+			optimized_justification.commit.precommits,
+			vec![
+				justification.commit.precommits[1].clone(),
+				justification.commit.precommits[3].clone(),
+				justification.commit.precommits[4].clone(),
+			],
+		);
+	}
+
+	#[test]
+	fn justification_with_too_low_weight_is_not_optimized() {
+		let authorities = vec![(ALICE, 1), (BOB, 1), (CHARLIE, 1)];
+		let justification =
+			make_justification_for_header::<TestHeader>(JustificationGeneratorParams {
+				header: test_header(1),
+				authorities: vec![(ALICE, 1), (BOB, 1)],
+				..Default::default()
+			});
+
+		assert_eq!(
+			optimize(&authorities, justification),
+			Err(JustificationError::TooLowCumulativeWeight),
+		);
+	}
+
+	fn beefy_commitment(
+		validators: &[ecdsa::Pair],
+		signers: usize,
+	) -> SignedCommitment<u64, EcdsaValidatorSignature> {
+		sign_beefy_commitment(
+			make_beefy_commitment(1, TEST_BEEFY_VALIDATOR_SET_ID, sp_core::H256::zero()),
+			validators,
+			signers,
+		)
+	}
+
+	#[test]
+	fn redundant_beefy_signatures_are_removed() {
+		let validators = beefy_validator_pairs(5);
+		let commitment = beefy_commitment(&validators, 5);
+
+		// 4 of 5 signatures are required
+		let optimized_commitment =
+			optimize_beefy_commitment(&beefy_validator_ids(&validators), commitment.clone())
+				.unwrap();
+		assert_eq!(optimized_commitment.commitment, commitment.commitment);
+		assert_eq!(optimized_commitment.signatures[..4], commitment.signatures[..4]);
+		assert_eq!(optimized_commitment.signatures[4], None);
+	}
+
+	#[test]
+	fn invalid_beefy_signatures_are_removed() {
+		let validators = beefy_validator_pairs(5);
+		let mut commitment = beefy_commitment(&validators, 5);
+		commitment.signatures[0] = commitment.signatures[1].clone();
+
+		let optimized_commitment =
+			optimize_beefy_commitment(&beefy_validator_ids(&validators), commitment.clone())
+				.unwrap();
+		assert_eq!(optimized_commitment.signatures[0], None);
+		assert_eq!(optimized_commitment.signatures[1..], commitment.signatures[1..]);
+	}
+
+	#[test]
+	fn beefy_commitment_with_not_enough_signatures_is_not_optimized() {
+		let validators = beefy_validator_pairs(5);
+
+		assert_eq!(
+			optimize_beefy_commitment(
+				&beefy_validator_ids(&validators),
+				beefy_commitment(&validators, 3),
+			),
+			Err(BeefyCommitmentOptimizationError::NotEnoughCorrectSignatures),
+		);
+		assert_eq!(
+			optimize_beefy_commitment(
+				&beefy_validator_ids(&validators[..4]),
+				beefy_commitment(&validators, 5),
+			),
+			Err(BeefyCommitmentOptimizationError::InvalidSignaturesLength),
+		);
+	}
+}
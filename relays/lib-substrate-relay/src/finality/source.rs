@@ -44,6 +44,12 @@ pub type SubstrateFinalityProofsStream<P> = Pin<
 	>,
 >;
 
+/// Substrate finality proof, read from the source chain. Specific to the used `FinalityEngine`.
+pub type SubstrateRawFinalityProof<P> =
+	<<P as SubstrateFinalitySyncPipeline>::FinalityEngine as Engine<
+		<P as SubstrateFinalitySyncPipeline>::SourceChain,
+	>>::RawFinalityProof;
+
 /// Substrate finality proof. Specific to the used `FinalityEngine`.
 pub type SubstrateFinalityProof<P> =
 	<<P as SubstrateFinalitySyncPipeline>::FinalityEngine as Engine<
@@ -132,21 +138,27 @@ impl<P: SubstrateFinalitySyncPipeline> SourceClient<FinalitySyncPipelineAdapter<
 		let header_hash = self.client.block_hash_by_number(number).await?;
 		let signed_block = self.client.get_block(Some(header_hash)).await?;
 
-		let justification = signed_block
-			.justification()
+		let raw_justification = signed_block
+			.justification(P::FinalityEngine::ID)
 			.map(|raw_justification| {
-				SubstrateFinalityProof::<P>::decode(&mut raw_justification.as_slice())
+				SubstrateRawFinalityProof::<P>::decode(&mut raw_justification.as_slice())
 			})
 			.transpose()
 			.map_err(Error::ResponseParseFailed)?;
+		let mut justification = None;
+		if let Some(raw_justification) = raw_justification {
+			justification = Some(
+				P::FinalityEngine::complete_finality_proof(&self.client, raw_justification).await?,
+			);
+		}
 
 		Ok((signed_block.header().into(), justification))
 	}
 
 	async fn finality_proofs(&self) -> Result<Self::FinalityProofsStream, Error> {
 		Ok(unfold(
-			P::FinalityEngine::finality_proofs(self.client.clone()).await?,
-			move |subscription| async move {
+			(self.client.clone(), P::FinalityEngine::finality_proofs(self.client.clone()).await?),
+			move |(client, subscription)| async move {
 				loop {
 					let log_error = |err| {
 						log::error!(
@@ -164,9 +176,7 @@ impl<P: SubstrateFinalitySyncPipeline> SourceClient<FinalitySyncPipelineAdapter<
 						.ok()??;
 
 					let decoded_justification =
-						<P::FinalityEngine as Engine<P::SourceChain>>::FinalityProof::decode(
-							&mut &next_justification[..],
-						);
+						SubstrateRawFinalityProof::<P>::decode(&mut &next_justification[..]);
 
 					let justification = match decoded_justification {
 						Ok(j) => j,
@@ -176,7 +186,18 @@ impl<P: SubstrateFinalitySyncPipeline> SourceClient<FinalitySyncPipelineAdapter<
 						},
 					};
 
-					return Some((justification, subscription))
+					let justification =
+						match P::FinalityEngine::complete_finality_proof(&client, justification)
+							.await
+						{
+							Ok(j) => j,
+							Err(err) => {
+								log_error(format!("completion failed with error {:?}", err));
+								continue
+							},
+						};
+
+					return Some((justification, (client, subscription)))
 				}
 			},
 		)
@@ -18,7 +18,8 @@
 
 use crate::{
 	finality::{
-		engine::Engine, source::SubstrateFinalityProof, FinalitySyncPipelineAdapter,
+		engine::Engine, optimization::FinalityProofsOptimizationMetrics,
+		source::SubstrateFinalityProof, FinalitySyncPipelineAdapter,
 		SubmitFinalityProofCallBuilder, SubstrateFinalitySyncPipeline,
 	},
 	TransactionParams,
@@ -26,18 +27,20 @@ use crate::{
 
 use async_trait::async_trait;
 use codec::Encode;
-use finality_relay::TargetClient;
+use finality_relay::{HeadersAndProofs, TargetClient};
 use relay_substrate_client::{
-	AccountIdOf, AccountKeyPairOf, Chain, Client, Error, HeaderIdOf, HeaderOf, SignParam,
+	AccountIdOf, AccountKeyPairOf, CallOf, Chain, Client, Error, HeaderIdOf, HeaderOf, SignParam,
 	SyncHeader, TransactionEra, TransactionSignScheme, UnsignedTransaction,
 };
 use relay_utils::relay_loop::Client as RelayClient;
 use sp_core::{Bytes, Pair};
+use sp_runtime::traits::Header as HeaderT;
 
 /// Substrate client as Substrate finality target.
 pub struct SubstrateFinalityTarget<P: SubstrateFinalitySyncPipeline> {
 	client: Client<P::TargetChain>,
 	transaction_params: TransactionParams<AccountKeyPairOf<P::TransactionSignScheme>>,
+	optimization_metrics: Option<FinalityProofsOptimizationMetrics>,
 }
 
 impl<P: SubstrateFinalitySyncPipeline> SubstrateFinalityTarget<P> {
@@ -46,7 +49,13 @@ impl<P: SubstrateFinalitySyncPipeline> SubstrateFinalityTarget<P> {
 		client: Client<P::TargetChain>,
 		transaction_params: TransactionParams<AccountKeyPairOf<P::TransactionSignScheme>>,
 	) -> Self {
-		SubstrateFinalityTarget { client, transaction_params }
+		SubstrateFinalityTarget { client, transaction_params, optimization_metrics: None }
+	}
+
+	/// Report results of finality proofs optimization to given metrics.
+	pub fn with_optimization_metrics(mut self, metrics: FinalityProofsOptimizationMetrics) -> Self {
+		self.optimization_metrics = Some(metrics);
+		self
 	}
 
 	/// Ensure that the bridge pallet at target chain is active.
@@ -58,6 +67,82 @@ impl<P: SubstrateFinalitySyncPipeline> SubstrateFinalityTarget<P> {
 			Ok(())
 		}
 	}
+
+	/// Optimize finality proofs before submitting them to the target chain.
+	///
+	/// Headers and proofs must be ordered by header number. Proofs that can't be optimized are
+	/// left unchanged.
+	async fn optimize_proofs(
+		&self,
+		headers_and_proofs: HeadersAndProofs<FinalitySyncPipelineAdapter<P>>,
+	) -> Result<HeadersAndProofs<FinalitySyncPipelineAdapter<P>>, Error> {
+		let mut context = self
+			.client
+			.storage_value::<<P::FinalityEngine as Engine<P::SourceChain>>::VerificationContext>(
+				P::FinalityEngine::verification_context_key(),
+				None,
+			)
+			.await?
+			.ok_or(Error::UninitializedBridgePallet)?;
+
+		Ok(headers_and_proofs
+			.into_iter()
+			.map(|(header, proof)| {
+				let original_size = proof.encoded_size();
+				let original_weight = P::FinalityEngine::proof_verification_weight(&proof);
+				let proof = P::FinalityEngine::optimize_proof(&mut context, &header, proof);
+				let saved_bytes = original_size.saturating_sub(proof.encoded_size());
+				let saved_weight = original_weight
+					.saturating_sub(P::FinalityEngine::proof_verification_weight(&proof));
+
+				log::trace!(
+					target: "bridge",
+					"Optimized {} finality proof of header {:?}: saved {} bytes and {} weight",
+					P::SourceChain::NAME,
+					header.hash(),
+					saved_bytes,
+					saved_weight,
+				);
+				if let Some(ref metrics) = self.optimization_metrics {
+					metrics.note_optimized_proof(saved_bytes as u64, saved_weight);
+				}
+
+				(header, proof)
+			})
+			.collect())
+	}
+}
+
+impl<P: SubstrateFinalitySyncPipeline> SubstrateFinalityTarget<P>
+where
+	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TransactionSignScheme> as Pair>::Public>,
+	P::TransactionSignScheme: TransactionSignScheme<Chain = P::TargetChain>,
+{
+	/// Sign and submit transaction with given call to the target chain.
+	async fn submit_call(&self, call: CallOf<P::TargetChain>) -> Result<(), Error> {
+		let genesis_hash = *self.client.genesis_hash();
+		let transaction_params = self.transaction_params.clone();
+		let (spec_version, transaction_version) = self.client.simple_runtime_version().await?;
+		self.client
+			.submit_signed_extrinsic(
+				self.transaction_params.signer.public().into(),
+				move |best_block_id, transaction_nonce| {
+					Ok(Bytes(
+						P::TransactionSignScheme::sign_transaction(SignParam {
+							spec_version,
+							transaction_version,
+							genesis_hash,
+							signer: transaction_params.signer.clone(),
+							era: TransactionEra::new(best_block_id, transaction_params.mortality),
+							unsigned: UnsignedTransaction::new(call.into(), transaction_nonce),
+						})?
+						.encode(),
+					))
+				},
+			)
+			.await
+			.map(drop)
+	}
 }
 
 impl<P: SubstrateFinalitySyncPipeline> Clone for SubstrateFinalityTarget<P> {
@@ -65,6 +150,7 @@ impl<P: SubstrateFinalitySyncPipeline> Clone for SubstrateFinalityTarget<P> {
 		SubstrateFinalityTarget {
 			client: self.client.clone(),
 			transaction_params: self.transaction_params.clone(),
+			optimization_metrics: self.optimization_metrics.clone(),
 		}
 	}
 }
@@ -106,29 +192,25 @@ where
 		header: SyncHeader<HeaderOf<P::SourceChain>>,
 		proof: SubstrateFinalityProof<P>,
 	) -> Result<(), Error> {
-		let genesis_hash = *self.client.genesis_hash();
-		let transaction_params = self.transaction_params.clone();
-		let call =
-			P::SubmitFinalityProofCallBuilder::build_submit_finality_proof_call(header, proof);
-		let (spec_version, transaction_version) = self.client.simple_runtime_version().await?;
-		self.client
-			.submit_signed_extrinsic(
-				self.transaction_params.signer.public().into(),
-				move |best_block_id, transaction_nonce| {
-					Ok(Bytes(
-						P::TransactionSignScheme::sign_transaction(SignParam {
-							spec_version,
-							transaction_version,
-							genesis_hash,
-							signer: transaction_params.signer.clone(),
-							era: TransactionEra::new(best_block_id, transaction_params.mortality),
-							unsigned: UnsignedTransaction::new(call.into(), transaction_nonce),
-						})?
-						.encode(),
-					))
-				},
-			)
-			.await
-			.map(drop)
+		let (header, proof) = self
+			.optimize_proofs(vec![(header, proof)])
+			.await?
+			.pop()
+			.expect("optimize_proofs returns the same number of proofs; qed");
+		self.submit_call(P::SubmitFinalityProofCallBuilder::build_submit_finality_proof_call(
+			header, proof,
+		))
+		.await
+	}
+
+	async fn submit_finality_proofs(
+		&self,
+		headers_and_proofs: HeadersAndProofs<FinalitySyncPipelineAdapter<P>>,
+	) -> Result<(), Error> {
+		let headers_and_proofs = self.optimize_proofs(headers_and_proofs).await?;
+		self.submit_call(P::SubmitFinalityProofCallBuilder::build_submit_finality_proofs_call(
+			headers_and_proofs,
+		))
+		.await
 	}
 }
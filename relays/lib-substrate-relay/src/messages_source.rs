@@ -29,7 +29,7 @@ use crate::{
 
 use async_trait::async_trait;
 use bp_messages::{
-	storage_keys::{operating_mode_key, outbound_lane_data_key},
+	storage_keys::{lane_operating_mode_key, operating_mode_key, outbound_lane_data_key},
 	LaneId, MessageNonce, OperatingMode, OutboundLaneData, UnrewardedRelayersState,
 };
 use bridge_runtime_common::messages::{
@@ -103,9 +103,13 @@ impl<P: SubstrateMessageLane> SubstrateMessagesSource<P> {
 			.await
 	}
 
-	/// Ensure that the messages pallet at source chain is active.
+	/// Ensure that the messages pallet and the lane at source chain are active.
 	async fn ensure_pallet_active(&self) -> Result<(), SubstrateError> {
-		ensure_messages_pallet_active::<P::SourceChain, P::TargetChain>(&self.source_client).await
+		ensure_messages_pallet_active::<P::SourceChain, P::TargetChain>(
+			&self.source_client,
+			&self.lane_id,
+		)
+		.await
 	}
 }
 
@@ -142,7 +146,7 @@ where
 		// we can't continue to deliver confirmations if source node is out of sync, because
 		// it may have already received confirmations that we're going to deliver
 		self.source_client.ensure_synced().await?;
-		// we can't relay confirmations if messages pallet at source chain is halted
+		// we can't relay confirmations if messages pallet or the lane at source chain is halted
 		self.ensure_pallet_active().await?;
 
 		read_client_state(
@@ -314,9 +318,10 @@ where
 	}
 }
 
-/// Ensure that the messages pallet at source chain is active.
+/// Ensure that the messages pallet and the lane at given chain are active.
 pub(crate) async fn ensure_messages_pallet_active<AtChain, WithChain>(
 	client: &Client<AtChain>,
+	lane_id: &LaneId,
 ) -> Result<(), SubstrateError>
 where
 	AtChain: ChainWithMessages,
@@ -327,7 +332,19 @@ where
 		.await?;
 	let is_halted = operating_mode == Some(OperatingMode::Halted);
 	if is_halted {
-		Err(SubstrateError::BridgePalletIsHalted)
+		return Err(SubstrateError::BridgePalletIsHalted)
+	}
+
+	// lane operating mode is missing from the storage if it has never been changed
+	let lane_operating_mode = client
+		.storage_value(
+			lane_operating_mode_key(WithChain::WITH_CHAIN_MESSAGES_PALLET_NAME, lane_id),
+			None,
+		)
+		.await?;
+	let is_lane_halted = lane_operating_mode == Some(OperatingMode::Halted);
+	if is_lane_halted {
+		Err(SubstrateError::BridgeLaneIsHalted(*lane_id))
 	} else {
 		Ok(())
 	}
@@ -513,6 +530,7 @@ fn make_message_details_map<C: Chain>(
 				size: details.size as _,
 				reward: details.delivery_and_dispatch_fee,
 				dispatch_fee_payment: details.dispatch_fee_payment,
+				expires_at: details.expires_at,
 			},
 		);
 		expected_nonce = details.nonce + 1;
@@ -540,6 +558,7 @@ mod tests {
 				size: 0,
 				delivery_and_dispatch_fee: 0,
 				dispatch_fee_payment: DispatchFeePayment::AtSourceChain,
+				expires_at: None,
 			})
 			.collect()
 	}
@@ -556,6 +575,7 @@ mod tests {
 						size: 0,
 						reward: 0,
 						dispatch_fee_payment: DispatchFeePayment::AtSourceChain,
+						expires_at: None,
 					}
 				),
 				(
@@ -565,6 +585,7 @@ mod tests {
 						size: 0,
 						reward: 0,
 						dispatch_fee_payment: DispatchFeePayment::AtSourceChain,
+						expires_at: None,
 					}
 				),
 				(
@@ -574,6 +595,7 @@ mod tests {
 						size: 0,
 						reward: 0,
 						dispatch_fee_payment: DispatchFeePayment::AtSourceChain,
+						expires_at: None,
 					}
 				),
 			]
@@ -594,6 +616,7 @@ mod tests {
 						size: 0,
 						reward: 0,
 						dispatch_fee_payment: DispatchFeePayment::AtSourceChain,
+						expires_at: None,
 					}
 				),
 				(
@@ -603,6 +626,7 @@ mod tests {
 						size: 0,
 						reward: 0,
 						dispatch_fee_payment: DispatchFeePayment::AtSourceChain,
+						expires_at: None,
 					}
 				),
 			]
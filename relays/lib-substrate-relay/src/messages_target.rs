@@ -104,9 +104,13 @@ impl<P: SubstrateMessageLane> SubstrateMessagesTarget<P> {
 			.await
 	}
 
-	/// Ensure that the messages pallet at target chain is active.
+	/// Ensure that the messages pallet and the lane at target chain are active.
 	async fn ensure_pallet_active(&self) -> Result<(), SubstrateError> {
-		ensure_messages_pallet_active::<P::TargetChain, P::SourceChain>(&self.target_client).await
+		ensure_messages_pallet_active::<P::TargetChain, P::SourceChain>(
+			&self.target_client,
+			&self.lane_id,
+		)
+		.await
 	}
 }
 
@@ -146,7 +150,7 @@ where
 		// we can't continue to deliver messages if target node is out of sync, because
 		// it may have already received (some of) messages that we're going to deliver
 		self.target_client.ensure_synced().await?;
-		// we can't relay messages if messages pallet at target chain is halted
+		// we can't relay messages if messages pallet or the lane at target chain is halted
 		self.ensure_pallet_active().await?;
 
 		read_client_state(
@@ -219,9 +223,13 @@ where
 			P::SourceChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
 			&self.lane_id,
 		);
+		let expired_messages_key = bp_messages::storage_keys::expired_inbound_messages_key(
+			P::SourceChain::WITH_CHAIN_MESSAGES_PALLET_NAME,
+			&self.lane_id,
+		);
 		let proof = self
 			.target_client
-			.prove_storage(vec![inbound_data_key], id.1)
+			.prove_storage(vec![inbound_data_key, expired_messages_key], id.1)
 			.await?
 			.iter_nodes()
 			.collect();
@@ -270,6 +270,7 @@ async fn background_task<P: SubstrateFinalitySyncPipeline>(
 						recent_finality_proofs_limit: RECENT_FINALITY_PROOFS_LIMIT,
 						stall_timeout,
 						only_mandatory_headers,
+						max_mandatory_headers_in_batch: 1,
 					},
 					MetricsParams::disabled(),
 					futures::future::pending(),
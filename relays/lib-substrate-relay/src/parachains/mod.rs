@@ -0,0 +1,164 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types and functions intended to ease adding of new Substrate -> Substrate
+//! parachain heads synchronization pipelines.
+
+use crate::{
+	parachains::{source::ParachainsSource, target::ParachainsTarget},
+	TransactionParams,
+};
+
+use bp_polkadot_core::parachains::{ParaId, ParachainHeadsProof};
+use pallet_bridge_parachains::{
+	Call as BridgeParachainsCall, Config as BridgeParachainsConfig, RelayBlockHash,
+	RelayBlockHasher, RelayBlockNumber,
+};
+use parachains_relay::{ParachainSyncParams, ParachainsPipeline};
+use relay_substrate_client::{
+	transaction_stall_timeout, AccountIdOf, AccountKeyPairOf, CallOf, Chain, Client, HeaderIdOf,
+	TransactionSignScheme,
+};
+use relay_utils::metrics::MetricsParams;
+use sp_core::Pair;
+use std::{fmt::Debug, marker::PhantomData};
+
+pub mod source;
+pub mod target;
+
+/// Substrate -> Substrate parachain heads synchronization pipeline.
+pub trait SubstrateParachainsPipeline: 'static + Clone + Debug + Send + Sync {
+	/// Relay chain, which is storing heads of parachains that we are going to relay.
+	type SourceChain: Chain<
+		BlockNumber = RelayBlockNumber,
+		Hash = RelayBlockHash,
+		Hasher = RelayBlockHasher,
+	>;
+	/// Heads of the `SourceChain` parachains are submitted to this chain.
+	type TargetChain: Chain;
+
+	/// How submit parachain heads call is built?
+	type SubmitParachainHeadsCallBuilder: SubmitParachainHeadsCallBuilder<Self>;
+	/// Scheme used to sign target chain transactions.
+	type TransactionSignScheme: TransactionSignScheme;
+
+	/// Name of the bridge parachains pallet (used in `construct_runtime` macro call) that is
+	/// deployed at the `TargetChain`.
+	const PARACHAINS_PALLET_NAME: &'static str;
+	/// Parachains, which heads are relayed by this pipeline.
+	const PARACHAINS: &'static [ParaId];
+	/// Name of the runtime API method that is returning the best known head of the
+	/// `SourceChain` parachain.
+	///
+	/// Keep in mind that this method is provided by the `TargetChain` runtime.
+	const BEST_PARACHAIN_HEAD_METHOD: &'static str;
+}
+
+/// Adapter that allows all `SubstrateParachainsPipeline` to act as `ParachainsPipeline`.
+#[derive(Clone, Debug)]
+pub struct ParachainsPipelineAdapter<P: SubstrateParachainsPipeline> {
+	_phantom: PhantomData<P>,
+}
+
+impl<P: SubstrateParachainsPipeline> ParachainsPipeline for ParachainsPipelineAdapter<P> {
+	type SourceChain = P::SourceChain;
+	type TargetChain = P::TargetChain;
+}
+
+/// Different ways of building `submit_parachain_heads` calls.
+pub trait SubmitParachainHeadsCallBuilder<P: SubstrateParachainsPipeline> {
+	/// Given parachains and their heads proof, build call of `submit_parachain_heads`
+	/// function of bridge parachains module at the target chain.
+	fn build_submit_parachain_heads_call(
+		relay_block: HeaderIdOf<P::SourceChain>,
+		parachains: Vec<ParaId>,
+		parachain_heads_proof: ParachainHeadsProof,
+	) -> CallOf<P::TargetChain>;
+}
+
+/// Building `submit_parachain_heads` call when you have direct access to the target
+/// chain runtime.
+pub struct DirectSubmitParachainHeadsCallBuilder<P, R, I> {
+	_phantom: PhantomData<(P, R, I)>,
+}
+
+impl<P, R, I> SubmitParachainHeadsCallBuilder<P> for DirectSubmitParachainHeadsCallBuilder<P, R, I>
+where
+	P: SubstrateParachainsPipeline,
+	R: BridgeParachainsConfig<I>,
+	I: 'static,
+	<R as pallet_bridge_grandpa::Config<R::BridgesGrandpaPalletInstance>>::BridgedChain:
+		bp_runtime::Chain<
+			BlockNumber = RelayBlockNumber,
+			Hash = RelayBlockHash,
+			Hasher = RelayBlockHasher,
+		>,
+	CallOf<P::TargetChain>: From<BridgeParachainsCall<R, I>>,
+{
+	fn build_submit_parachain_heads_call(
+		relay_block: HeaderIdOf<P::SourceChain>,
+		parachains: Vec<ParaId>,
+		parachain_heads_proof: ParachainHeadsProof,
+	) -> CallOf<P::TargetChain> {
+		BridgeParachainsCall::<R, I>::submit_parachain_heads {
+			relay_block_hash: relay_block.1,
+			parachains,
+			parachain_heads_proof,
+		}
+		.into()
+	}
+}
+
+/// Run Substrate-to-Substrate parachain heads sync loop.
+pub async fn run<P: SubstrateParachainsPipeline>(
+	source_client: Client<P::SourceChain>,
+	target_client: Client<P::TargetChain>,
+	transaction_params: TransactionParams<AccountKeyPairOf<P::TransactionSignScheme>>,
+	metrics_params: MetricsParams,
+) -> anyhow::Result<()>
+where
+	AccountIdOf<P::TargetChain>: From<<AccountKeyPairOf<P::TransactionSignScheme> as Pair>::Public>,
+	P::TransactionSignScheme: TransactionSignScheme<Chain = P::TargetChain>,
+{
+	log::info!(
+		target: "bridge",
+		"Starting {} -> {} parachains relay. Relaying heads of parachains: {:?}",
+		P::SourceChain::NAME,
+		P::TargetChain::NAME,
+		P::PARACHAINS,
+	);
+
+	parachains_relay::run(
+		ParachainsSource::<P>::new(source_client),
+		ParachainsTarget::<P>::new(target_client, transaction_params.clone()),
+		ParachainSyncParams {
+			parachains: P::PARACHAINS.to_vec(),
+			tick: std::cmp::max(
+				P::SourceChain::AVERAGE_BLOCK_INTERVAL,
+				P::TargetChain::AVERAGE_BLOCK_INTERVAL,
+			),
+			stall_timeout: transaction_stall_timeout(
+				transaction_params.mortality,
+				P::TargetChain::AVERAGE_BLOCK_INTERVAL,
+				crate::STALL_TIMEOUT,
+			),
+		},
+		metrics_params,
+		futures::future::pending(),
+	)
+	.await
+	.map_err(|e| anyhow::format_err!("{}", e))
+}
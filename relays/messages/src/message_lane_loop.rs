@@ -104,6 +104,8 @@ pub struct MessageDetails<SourceChainBalance> {
 	pub reward: SourceChainBalance,
 	/// Where the fee for dispatching message is paid?
 	pub dispatch_fee_payment: DispatchFeePayment,
+	/// Source chain block number at which the message expires.
+	pub expires_at: Option<u64>,
 }
 
 /// Messages details map.
@@ -609,6 +611,7 @@ pub(crate) mod tests {
 							size: 1,
 							reward: 1,
 							dispatch_fee_payment: DispatchFeePayment::AtSourceChain,
+							expires_at: None,
 						},
 					)
 				})
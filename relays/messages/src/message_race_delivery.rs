@@ -295,6 +295,26 @@ impl<P: MessageLane, Strategy: RelayStrategy, SC, TC> MessageDeliveryStrategy<P,
 			.flat_map(|(_, range)| range.values().map(|details| details.dispatch_weight))
 			.fold(0, |total, weight| total.saturating_add(weight))
 	}
+
+	/// Forget dispatch weight of undelivered messages that have expired according to the target
+	/// chain view of the source chain. The target chain doesn't dispatch such messages, so we
+	/// don't need to declare (and pay for) their dispatch weight.
+	fn forget_dispatch_weight_of_expired_messages(
+		&mut self,
+		best_finalized_source_header_number_at_target: u64,
+	) {
+		self.strategy
+			.source_queue_mut()
+			.iter_mut()
+			.flat_map(|(_, range)| range.values_mut())
+			.filter(|details| {
+				details
+					.expires_at
+					.map(|expires_at| best_finalized_source_header_number_at_target >= expires_at)
+					.unwrap_or(false)
+			})
+			.for_each(|details| details.dispatch_weight = 0);
+	}
 }
 
 #[async_trait]
@@ -506,6 +526,10 @@ where
 		let lane_source_client = self.lane_source_client.clone();
 		let lane_target_client = self.lane_target_client.clone();
 
+		self.forget_dispatch_weight_of_expired_messages(
+			best_finalized_source_header_id_at_best_target.0.into(),
+		);
+
 		let maximal_source_queue_index =
 			self.strategy.maximal_available_source_queue_index(race_state)?;
 		let previous_total_dispatch_weight = self.total_queued_dispatch_weight();
@@ -603,6 +627,7 @@ mod tests {
 							size: DEFAULT_SIZE,
 							reward,
 							dispatch_fee_payment,
+							expires_at: None,
 						},
 					)
 				})
@@ -683,6 +708,7 @@ mod tests {
 							size: idx as _,
 							reward: idx as _,
 							dispatch_fee_payment: DispatchFeePayment::AtSourceChain,
+							expires_at: None,
 						},
 					)
 				})
@@ -712,6 +738,20 @@ mod tests {
 		);
 	}
 
+	#[async_std::test]
+	async fn message_delivery_strategy_ignores_dispatch_weight_of_expired_messages() {
+		let (state, mut strategy) = prepare_strategy();
+
+		// message 20 has expired according to the target view of the source chain (best
+		// finalized source header at target is 1) and message 21 has not
+		strategy.strategy.source_queue_mut()[0].1.get_mut(&20).unwrap().expires_at = Some(1);
+		strategy.strategy.source_queue_mut()[0].1.get_mut(&21).unwrap().expires_at = Some(2);
+		assert_eq!(
+			strategy.select_nonces_to_deliver(state).await,
+			Some(((20..=23), proof_parameters(false, 3)))
+		);
+	}
+
 	#[async_std::test]
 	async fn message_delivery_strategy_selects_nothing_if_too_many_confirmations_missing() {
 		let (state, mut strategy) = prepare_strategy();
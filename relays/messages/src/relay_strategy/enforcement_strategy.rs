@@ -85,6 +85,7 @@ impl<Strategy: RelayStrategy> EnforcementStrategy<Strategy> {
 				size: 0,
 				reward: P::SourceChainBalance::zero(),
 				dispatch_fee_payment: DispatchFeePayment::AtSourceChain,
+				expires_at: None,
 			},
 		};
 
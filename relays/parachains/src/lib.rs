@@ -0,0 +1,37 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! This crate has single entrypoint to run parachain heads synchronization loop. The loop
+//! reads heads of the configured parachains from the source relay chain and submits their
+//! storage proofs to the target chain, where the bridge parachains pallet is deployed.
+
+use std::fmt::Debug;
+
+use relay_substrate_client::Chain;
+
+pub use parachains_loop::{metrics_prefix, run, ParachainSyncParams, SourceClient, TargetClient};
+pub use parachains_loop_metrics::ParachainsLoopMetrics;
+
+mod parachains_loop;
+mod parachains_loop_metrics;
+
+/// Parachain heads synchronization pipeline.
+pub trait ParachainsPipeline: 'static + Clone + Debug + Send + Sync {
+	/// Relay chain which is storing parachain heads in its `paras` module.
+	type SourceChain: Chain;
+	/// Target chain (either relay or para) which wants to know about new parachain heads.
+	type TargetChain: Chain;
+}
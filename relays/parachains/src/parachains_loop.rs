@@ -0,0 +1,559 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The loop reads best finalized source relay chain block, known to the target chain. Then it
+//! reads heads of all configured parachains at this relay block, both from the source relay
+//! chain and from the target chain. If some heads differ, the proof of updated heads is
+//! crafted at the source relay chain and submitted to the target chain.
+
+use crate::{parachains_loop_metrics::ParachainsLoopMetrics, ParachainsPipeline};
+
+use async_trait::async_trait;
+use bp_polkadot_core::parachains::{ParaHash, ParaId, ParachainHeadsProof};
+use futures::{future::FutureExt, select, Future};
+use relay_substrate_client::{BlockNumberOf, Chain, HeaderIdOf};
+use relay_utils::{
+	metrics::MetricsParams, relay_loop::Client as RelayClient, FailedClient, MaybeConnectionError,
+};
+use std::{
+	collections::BTreeMap,
+	time::{Duration, Instant},
+};
+
+/// Parachain heads synchronization loop parameters.
+#[derive(Debug, Clone)]
+pub struct ParachainSyncParams {
+	/// Parachains that we're relaying here.
+	pub parachains: Vec<ParaId>,
+	/// Interval at which we check updates on both clients. Normally should be larger than
+	/// `min(source_block_time, target_block_time)`.
+	pub tick: Duration,
+	/// Timeout before we treat our transactions as lost and restart the whole sync process.
+	pub stall_timeout: Duration,
+}
+
+/// Source client used in parachain heads synchronization loop.
+#[async_trait]
+pub trait SourceClient<P: ParachainsPipeline>: RelayClient {
+	/// Returns `Ok(true)` if client is in synced state.
+	async fn ensure_synced(&self) -> Result<bool, Self::Error>;
+
+	/// Get parachain head hash at given block.
+	async fn parachain_head(
+		&self,
+		at_block: HeaderIdOf<P::SourceChain>,
+		para_id: ParaId,
+	) -> Result<Option<ParaHash>, Self::Error>;
+
+	/// Get parachain heads proof.
+	async fn prove_parachain_heads(
+		&self,
+		at_block: HeaderIdOf<P::SourceChain>,
+		parachains: &[ParaId],
+	) -> Result<ParachainHeadsProof, Self::Error>;
+}
+
+/// Target client used in parachain heads synchronization loop.
+#[async_trait]
+pub trait TargetClient<P: ParachainsPipeline>: RelayClient {
+	/// Get best block id.
+	async fn best_block(&self) -> Result<HeaderIdOf<P::TargetChain>, Self::Error>;
+
+	/// Get best finalized source relay chain block id, known to the target chain at given block.
+	async fn best_finalized_source_block(
+		&self,
+		at_block: &HeaderIdOf<P::TargetChain>,
+	) -> Result<HeaderIdOf<P::SourceChain>, Self::Error>;
+
+	/// Get parachain head hash, known to the target chain at given block.
+	async fn parachain_head(
+		&self,
+		at_block: HeaderIdOf<P::TargetChain>,
+		para_id: ParaId,
+	) -> Result<Option<ParaHash>, Self::Error>;
+
+	/// Submit parachain heads proof.
+	async fn submit_parachain_heads_proof(
+		&self,
+		at_source_block: HeaderIdOf<P::SourceChain>,
+		updated_parachains: Vec<ParaId>,
+		proof: ParachainHeadsProof,
+	) -> Result<(), Self::Error>;
+}
+
+/// Return prefix that will be used by default to expose Prometheus metrics of the parachains
+/// sync loop.
+pub fn metrics_prefix<P: ParachainsPipeline>() -> String {
+	format!("{}_to_{}_Parachains", P::SourceChain::NAME, P::TargetChain::NAME)
+}
+
+/// Run parachain heads synchronization.
+pub async fn run<P: ParachainsPipeline>(
+	source_client: impl SourceClient<P>,
+	target_client: impl TargetClient<P>,
+	sync_params: ParachainSyncParams,
+	metrics_params: MetricsParams,
+	exit_signal: impl Future<Output = ()> + 'static + Send,
+) -> Result<(), relay_utils::Error> {
+	let exit_signal = exit_signal.shared();
+	relay_utils::relay_loop(source_client, target_client)
+		.with_metrics(metrics_params)
+		.loop_metric(ParachainsLoopMetrics::new(Some(&metrics_prefix::<P>()))?)?
+		.expose()
+		.await?
+		.run(metrics_prefix::<P>(), move |source_client, target_client, metrics| {
+			run_until_connection_lost(
+				source_client,
+				target_client,
+				sync_params.clone(),
+				metrics,
+				exit_signal.clone(),
+			)
+		})
+		.await
+}
+
+/// Information about transaction that we have submitted.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Transaction {
+	/// Time when we have submitted this transaction.
+	pub time: Instant,
+	/// Heads of updated parachains, known to the target chain before transaction submission.
+	pub heads_before_submission: BTreeMap<ParaId, Option<ParaHash>>,
+}
+
+impl Transaction {
+	/// Returns true if the transaction has been mined (or other relayer has updated the same
+	/// heads).
+	fn is_completed(&self, heads_at_target: &BTreeMap<ParaId, Option<ParaHash>>) -> bool {
+		self.heads_before_submission.iter().all(|(para_id, head_before_submission)| {
+			heads_at_target
+				.get(para_id)
+				.map(|head_at_target| head_at_target != head_before_submission)
+				.unwrap_or(false)
+		})
+	}
+}
+
+/// Error that may happen inside parachains synchronization loop.
+#[derive(Debug)]
+pub(crate) enum Error<SourceError, TargetError> {
+	/// Source client request has failed with given error.
+	Source(SourceError),
+	/// Target client request has failed with given error.
+	Target(TargetError),
+	/// The synchronization has stalled.
+	Stalled,
+}
+
+impl<SourceError, TargetError> Error<SourceError, TargetError>
+where
+	SourceError: MaybeConnectionError,
+	TargetError: MaybeConnectionError,
+{
+	fn fail_if_connection_error(&self) -> Result<(), FailedClient> {
+		match *self {
+			Error::Source(ref error) if error.is_connection_error() => Err(FailedClient::Source),
+			Error::Target(ref error) if error.is_connection_error() => Err(FailedClient::Target),
+			Error::Stalled => Err(FailedClient::Both),
+			_ => Ok(()),
+		}
+	}
+}
+
+async fn run_until_connection_lost<P: ParachainsPipeline>(
+	source_client: impl SourceClient<P>,
+	target_client: impl TargetClient<P>,
+	sync_params: ParachainSyncParams,
+	metrics: Option<ParachainsLoopMetrics>,
+	exit_signal: impl Future<Output = ()>,
+) -> Result<(), FailedClient> {
+	let exit_signal = exit_signal.fuse();
+	futures::pin_mut!(exit_signal);
+
+	let mut last_transaction = None;
+
+	loop {
+		let iteration_result = run_loop_iteration::<P, _, _>(
+			&source_client,
+			&target_client,
+			&sync_params,
+			last_transaction.clone(),
+			&metrics,
+		)
+		.await;
+
+		match iteration_result {
+			Ok(updated_last_transaction) => {
+				last_transaction = updated_last_transaction;
+			},
+			Err(error) => {
+				log::error!(
+					target: "bridge",
+					"{} -> {} parachains sync loop iteration has failed with error: {:?}",
+					P::SourceChain::NAME,
+					P::TargetChain::NAME,
+					error,
+				);
+				error.fail_if_connection_error()?;
+			},
+		}
+
+		// wait till exit signal, or next tick
+		select! {
+			_ = async_std::task::sleep(sync_params.tick).fuse() => {},
+			_ = exit_signal => return Ok(()),
+		}
+	}
+}
+
+pub(crate) async fn run_loop_iteration<P, SC, TC>(
+	source_client: &SC,
+	target_client: &TC,
+	sync_params: &ParachainSyncParams,
+	last_transaction: Option<Transaction>,
+	metrics: &Option<ParachainsLoopMetrics>,
+) -> Result<Option<Transaction>, Error<SC::Error, TC::Error>>
+where
+	P: ParachainsPipeline,
+	SC: SourceClient<P>,
+	TC: TargetClient<P>,
+{
+	// we can't read parachain heads from the source relay chain if it is not synced
+	if !source_client.ensure_synced().await.map_err(Error::Source)? {
+		log::warn!(
+			target: "bridge",
+			"{} client is syncing. Won't do anything until it is synced",
+			P::SourceChain::NAME,
+		);
+		return Ok(last_transaction)
+	}
+
+	// read best relay chain block, known to the target chain
+	let best_target_block = target_client.best_block().await.map_err(Error::Target)?;
+	let best_source_block_at_target = target_client
+		.best_finalized_source_block(&best_target_block)
+		.await
+		.map_err(Error::Target)?;
+	if let Some(ref metrics) = *metrics {
+		metrics.update_best_source_block_at_target::<BlockNumberOf<P::SourceChain>>(
+			best_source_block_at_target.0,
+		);
+	}
+
+	// read parachain heads from both clients
+	let mut heads_at_source = BTreeMap::new();
+	let mut heads_at_target = BTreeMap::new();
+	for para_id in &sync_params.parachains {
+		let head_at_source = source_client
+			.parachain_head(best_source_block_at_target, *para_id)
+			.await
+			.map_err(Error::Source)?;
+		let head_at_target = target_client
+			.parachain_head(best_target_block, *para_id)
+			.await
+			.map_err(Error::Target)?;
+		if let Some(ref metrics) = *metrics {
+			metrics.update_parachain_head_synced(*para_id, head_at_source == head_at_target);
+		}
+		heads_at_source.insert(*para_id, head_at_source);
+		heads_at_target.insert(*para_id, head_at_target);
+	}
+
+	// if we have already submitted transaction, then we just need to wait for it
+	// if we're waiting too much, then we believe our transaction has been lost and restart sync
+	if let Some(last_transaction) = last_transaction {
+		if last_transaction.is_completed(&heads_at_target) {
+			// transaction has been mined && we can continue
+		} else if last_transaction.time.elapsed() > sync_params.stall_timeout {
+			log::error!(
+				target: "bridge",
+				"Parachains synchronization from {} to {} has stalled. Going to restart",
+				P::SourceChain::NAME,
+				P::TargetChain::NAME,
+			);
+
+			return Err(Error::Stalled)
+		} else {
+			return Ok(Some(last_transaction))
+		}
+	}
+
+	// select parachains that need to be updated. We can't update parachain to the missing head,
+	// so parachains that are missing at the source are ignored
+	let updated_parachains = select_parachains_to_update(&heads_at_source, &heads_at_target);
+	if updated_parachains.is_empty() {
+		log::trace!(
+			target: "bridge",
+			"All {} parachain heads at {} are synced with {} block {:?}",
+			P::SourceChain::NAME,
+			P::TargetChain::NAME,
+			P::SourceChain::NAME,
+			best_source_block_at_target,
+		);
+		return Ok(None)
+	}
+
+	// prove and submit updated heads
+	log::debug!(
+		target: "bridge",
+		"Going to submit {} heads of parachains {:?} at {} block {:?} to {}",
+		P::SourceChain::NAME,
+		updated_parachains,
+		P::SourceChain::NAME,
+		best_source_block_at_target,
+		P::TargetChain::NAME,
+	);
+	let heads_proof = source_client
+		.prove_parachain_heads(best_source_block_at_target, &updated_parachains)
+		.await
+		.map_err(Error::Source)?;
+	let new_transaction = Transaction {
+		time: Instant::now(),
+		heads_before_submission: updated_parachains
+			.iter()
+			.map(|para_id| (*para_id, heads_at_target.get(para_id).cloned().flatten()))
+			.collect(),
+	};
+	target_client
+		.submit_parachain_heads_proof(best_source_block_at_target, updated_parachains, heads_proof)
+		.await
+		.map_err(Error::Target)?;
+
+	Ok(Some(new_transaction))
+}
+
+/// Select parachains which heads at the target chain need to be updated.
+fn select_parachains_to_update(
+	heads_at_source: &BTreeMap<ParaId, Option<ParaHash>>,
+	heads_at_target: &BTreeMap<ParaId, Option<ParaHash>>,
+) -> Vec<ParaId> {
+	heads_at_source
+		.iter()
+		.filter(|(para_id, head_at_source)| {
+			head_at_source.is_some() &&
+				heads_at_target.get(*para_id).cloned().flatten() != **head_at_source
+		})
+		.map(|(para_id, _)| *para_id)
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use async_std::sync::{Arc, Mutex};
+	use codec::Encode;
+	use relay_rialto_client::Rialto;
+	use relay_utils::HeaderId;
+	use sp_core::H256;
+
+	const PARA_ID: u32 = 100;
+	const PARA_HEAD: ParaHash = H256([1u8; 32]);
+	const OTHER_PARA_HEAD: ParaHash = H256([2u8; 32]);
+
+	#[derive(Clone, Debug)]
+	struct TestParachainsPipeline;
+
+	impl ParachainsPipeline for TestParachainsPipeline {
+		type SourceChain = Rialto;
+		type TargetChain = Rialto;
+	}
+
+	#[derive(Debug)]
+	struct TestError;
+
+	impl MaybeConnectionError for TestError {
+		fn is_connection_error(&self) -> bool {
+			false
+		}
+	}
+
+	#[derive(Debug, Default)]
+	struct TestClientData {
+		source_head: Option<ParaHash>,
+		target_head: Option<ParaHash>,
+		submitted_proofs: Vec<Vec<ParaId>>,
+	}
+
+	#[derive(Clone, Debug, Default)]
+	struct TestClient {
+		data: Arc<Mutex<TestClientData>>,
+	}
+
+	#[async_trait]
+	impl RelayClient for TestClient {
+		type Error = TestError;
+
+		async fn reconnect(&mut self) -> Result<(), TestError> {
+			// test clients have no connection to restore
+			Ok(())
+		}
+	}
+
+	#[async_trait]
+	impl SourceClient<TestParachainsPipeline> for TestClient {
+		async fn ensure_synced(&self) -> Result<bool, TestError> {
+			Ok(true)
+		}
+
+		async fn parachain_head(
+			&self,
+			_at_block: HeaderIdOf<Rialto>,
+			_para_id: ParaId,
+		) -> Result<Option<ParaHash>, TestError> {
+			Ok(self.data.lock().await.source_head)
+		}
+
+		async fn prove_parachain_heads(
+			&self,
+			_at_block: HeaderIdOf<Rialto>,
+			parachains: &[ParaId],
+		) -> Result<ParachainHeadsProof, TestError> {
+			Ok(vec![parachains.encode()])
+		}
+	}
+
+	#[async_trait]
+	impl TargetClient<TestParachainsPipeline> for TestClient {
+		async fn best_block(&self) -> Result<HeaderIdOf<Rialto>, TestError> {
+			Ok(HeaderId(0, Default::default()))
+		}
+
+		async fn best_finalized_source_block(
+			&self,
+			_at_block: &HeaderIdOf<Rialto>,
+		) -> Result<HeaderIdOf<Rialto>, TestError> {
+			Ok(HeaderId(0, Default::default()))
+		}
+
+		async fn parachain_head(
+			&self,
+			_at_block: HeaderIdOf<Rialto>,
+			_para_id: ParaId,
+		) -> Result<Option<ParaHash>, TestError> {
+			Ok(self.data.lock().await.target_head)
+		}
+
+		async fn submit_parachain_heads_proof(
+			&self,
+			_at_source_block: HeaderIdOf<Rialto>,
+			updated_parachains: Vec<ParaId>,
+			_proof: ParachainHeadsProof,
+		) -> Result<(), TestError> {
+			self.data.lock().await.submitted_proofs.push(updated_parachains);
+			Ok(())
+		}
+	}
+
+	fn test_sync_params() -> ParachainSyncParams {
+		ParachainSyncParams {
+			parachains: vec![ParaId(PARA_ID)],
+			tick: Duration::from_secs(1),
+			stall_timeout: Duration::from_secs(60),
+		}
+	}
+
+	fn run_iteration(
+		client: &TestClient,
+		sync_params: &ParachainSyncParams,
+		last_transaction: Option<Transaction>,
+	) -> Result<Option<Transaction>, Error<TestError, TestError>> {
+		async_std::task::block_on(run_loop_iteration::<TestParachainsPipeline, _, _>(
+			client,
+			client,
+			sync_params,
+			last_transaction,
+			&None,
+		))
+	}
+
+	fn client_with_heads(
+		source_head: Option<ParaHash>,
+		target_head: Option<ParaHash>,
+	) -> TestClient {
+		TestClient {
+			data: Arc::new(Mutex::new(TestClientData {
+				source_head,
+				target_head,
+				submitted_proofs: Vec::new(),
+			})),
+		}
+	}
+
+	fn submitted_proofs(client: &TestClient) -> Vec<Vec<ParaId>> {
+		async_std::task::block_on(client.data.lock()).submitted_proofs.clone()
+	}
+
+	#[test]
+	fn submits_head_if_it_is_missing_at_target() {
+		let client = client_with_heads(Some(PARA_HEAD), None);
+		let transaction = run_iteration(&client, &test_sync_params(), None).unwrap();
+		assert!(transaction.is_some());
+		assert_eq!(submitted_proofs(&client), vec![vec![ParaId(PARA_ID)]]);
+	}
+
+	#[test]
+	fn submits_head_if_it_differs_from_head_at_target() {
+		let client = client_with_heads(Some(OTHER_PARA_HEAD), Some(PARA_HEAD));
+		let transaction = run_iteration(&client, &test_sync_params(), None).unwrap();
+		assert!(transaction.is_some());
+		assert_eq!(submitted_proofs(&client), vec![vec![ParaId(PARA_ID)]]);
+	}
+
+	#[test]
+	fn does_nothing_if_heads_are_synced() {
+		let client = client_with_heads(Some(PARA_HEAD), Some(PARA_HEAD));
+		let transaction = run_iteration(&client, &test_sync_params(), None).unwrap();
+		assert!(transaction.is_none());
+		assert!(submitted_proofs(&client).is_empty());
+	}
+
+	#[test]
+	fn does_nothing_if_head_is_missing_at_source() {
+		let client = client_with_heads(None, Some(PARA_HEAD));
+		let transaction = run_iteration(&client, &test_sync_params(), None).unwrap();
+		assert!(transaction.is_none());
+		assert!(submitted_proofs(&client).is_empty());
+	}
+
+	#[test]
+	fn waits_for_submitted_transaction() {
+		let client = client_with_heads(Some(PARA_HEAD), None);
+		let transaction = run_iteration(&client, &test_sync_params(), None).unwrap();
+
+		// transaction is not yet mined => we're not submitting new transaction
+		let same_transaction =
+			run_iteration(&client, &test_sync_params(), transaction.clone()).unwrap();
+		assert_eq!(same_transaction, transaction);
+		assert_eq!(submitted_proofs(&client).len(), 1);
+
+		// transaction is mined => we're not tracking it anymore
+		async_std::task::block_on(client.data.lock()).target_head = Some(PARA_HEAD);
+		assert_eq!(run_iteration(&client, &test_sync_params(), transaction).unwrap(), None);
+		assert_eq!(submitted_proofs(&client).len(), 1);
+	}
+
+	#[test]
+	fn restarts_if_transaction_has_stalled() {
+		let client = client_with_heads(Some(PARA_HEAD), None);
+		let mut sync_params = test_sync_params();
+		sync_params.stall_timeout = Duration::from_secs(0);
+
+		let transaction = run_iteration(&client, &sync_params, None).unwrap();
+		std::thread::sleep(Duration::from_millis(10));
+		assert!(matches!(run_iteration(&client, &sync_params, transaction), Err(Error::Stalled)));
+	}
+}
@@ -0,0 +1,72 @@
+// Copyright 2019-2021 Parity Technologies (UK) Ltd.
+// This file is part of Parity Bridges Common.
+
+// Parity Bridges Common is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Bridges Common is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Bridges Common.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Metrics for parachain heads synchronization loop.
+
+use bp_polkadot_core::parachains::ParaId;
+use relay_utils::metrics::{
+	metric_name, register, GaugeVec, IntGauge, Metric, Opts, PrometheusError, Registry, U64,
+};
+
+/// Parachains sync metrics.
+#[derive(Clone)]
+pub struct ParachainsLoopMetrics {
+	/// Best source relay chain block number, known to the target chain.
+	best_source_block_number_at_target: IntGauge,
+	/// Flag that has `1` value when parachain head at the source relay chain matches the head,
+	/// known to the target chain, and `0` otherwise.
+	is_parachain_head_synced: GaugeVec<U64>,
+}
+
+impl ParachainsLoopMetrics {
+	/// Create and register parachains loop metrics.
+	pub fn new(prefix: Option<&str>) -> Result<Self, PrometheusError> {
+		Ok(ParachainsLoopMetrics {
+			best_source_block_number_at_target: IntGauge::new(
+				metric_name(prefix, "best_source_block_number_at_target"),
+				"Best source relay chain block number, known to the target chain",
+			)?,
+			is_parachain_head_synced: GaugeVec::new(
+				Opts::new(
+					metric_name(prefix, "is_parachain_head_synced"),
+					"Whether the parachain head at the target chain matches the head at the source \
+					relay chain (value 1) or not (value 0)",
+				),
+				&["parachain"],
+			)?,
+		})
+	}
+
+	/// Update best source relay chain block number, known to the target chain.
+	pub fn update_best_source_block_at_target<Number: Into<u64>>(&self, best_number: Number) {
+		self.best_source_block_number_at_target.set(best_number.into());
+	}
+
+	/// Update parachain head synchronization flag.
+	pub fn update_parachain_head_synced(&self, parachain: ParaId, is_synced: bool) {
+		self.is_parachain_head_synced
+			.with_label_values(&[&parachain.0.to_string()])
+			.set(if is_synced { 1 } else { 0 });
+	}
+}
+
+impl Metric for ParachainsLoopMetrics {
+	fn register(&self, registry: &Registry) -> Result<(), PrometheusError> {
+		register(self.best_source_block_number_at_target.clone(), registry)?;
+		register(self.is_parachain_head_synced.clone(), registry)?;
+		Ok(())
+	}
+}